@@ -0,0 +1,126 @@
+//! Compares the `Zip`/iterator-based kernels with the indexed loops they replaced.
+//!
+//! Run with `cargo bench --bench kernels`. The `indexed` functions below reproduce the previous
+//! `for i in 0..x.len()` implementations, which bounds-check every access.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use fast_distances::*;
+use ndarray::{Array1, ArrayView1};
+
+const SIZES: [usize; 3] = [16, 256, 4096];
+
+fn sample(n: usize, phase: f64) -> Array1<f64> {
+    (0..n).map(|i| ((i as f64 + phase) * 0.37).sin()).collect()
+}
+
+fn indexed_euclidean(x: &ArrayView1<f64>, y: &ArrayView1<f64>) -> f64 {
+    let mut result = 0.0;
+    for i in 0..x.len() {
+        let diff = x[i] - y[i];
+        result += diff * diff;
+    }
+    result.sqrt()
+}
+
+fn indexed_manhattan(x: &ArrayView1<f64>, y: &ArrayView1<f64>) -> f64 {
+    let mut result = 0.0;
+    for i in 0..x.len() {
+        result += (x[i] - y[i]).abs();
+    }
+    result
+}
+
+fn indexed_minkowski(x: &ArrayView1<f64>, y: &ArrayView1<f64>, p: f64) -> f64 {
+    let mut result = 0.0;
+    for i in 0..x.len() {
+        result += (x[i] - y[i]).abs().powf(p);
+    }
+    result.powf(1.0 / p)
+}
+
+fn indexed_cosine(x: &ArrayView1<f64>, y: &ArrayView1<f64>) -> f64 {
+    let (mut dot, mut norm_x, mut norm_y) = (0.0, 0.0, 0.0);
+    for i in 0..x.len() {
+        dot += x[i] * y[i];
+        norm_x += x[i] * x[i];
+        norm_y += y[i] * y[i];
+    }
+    1.0 - dot / (norm_x.sqrt() * norm_y.sqrt())
+}
+
+fn indexed_canberra(x: &ArrayView1<f64>, y: &ArrayView1<f64>) -> f64 {
+    let mut result = 0.0;
+    for i in 0..x.len() {
+        let denominator = x[i].abs() + y[i].abs();
+        if denominator > 0.0 {
+            result += (x[i] - y[i]).abs() / denominator;
+        }
+    }
+    result
+}
+
+fn indexed_euclidean_grad(x: &Array1<f64>, y: &Array1<f64>) -> (f64, Vec<f64>) {
+    let mut result = 0.0;
+    for i in 0..x.len() {
+        let diff = x[i] - y[i];
+        result += diff * diff;
+    }
+    let distance = result.sqrt();
+    let mut gradient = Vec::with_capacity(x.len());
+    for i in 0..x.len() {
+        gradient.push((x[i] - y[i]) / (1e-6 + distance));
+    }
+    (distance, gradient)
+}
+
+fn bench_metric(
+    c: &mut Criterion,
+    name: &str,
+    indexed: impl Fn(&ArrayView1<f64>, &ArrayView1<f64>) -> f64,
+    iterator: impl Fn(&ArrayView1<f64>, &ArrayView1<f64>) -> f64,
+) {
+    let mut group = c.benchmark_group(name);
+    for n in SIZES {
+        let (x, y) = (sample(n, 0.0), sample(n, 1.5));
+        let (x, y) = (x.view(), y.view());
+        group.bench_with_input(BenchmarkId::new("indexed", n), &n, |b, _| {
+            b.iter(|| indexed(black_box(&x), black_box(&y)))
+        });
+        group.bench_with_input(BenchmarkId::new("iterator", n), &n, |b, _| {
+            b.iter(|| iterator(black_box(&x), black_box(&y)))
+        });
+    }
+    group.finish();
+}
+
+fn kernels(c: &mut Criterion) {
+    bench_metric(c, "euclidean", indexed_euclidean, euclidean);
+    bench_metric(c, "manhattan", indexed_manhattan, manhattan);
+    bench_metric(
+        c,
+        "minkowski",
+        |x, y| indexed_minkowski(x, y, 3.0),
+        |x, y| minkowski(x, y, 3.0),
+    );
+    bench_metric(c, "cosine", indexed_cosine, cosine);
+    bench_metric(c, "canberra", indexed_canberra, canberra);
+}
+
+fn gradients(c: &mut Criterion) {
+    let mut group = c.benchmark_group("euclidean_grad");
+    for n in SIZES {
+        let (x, y) = (sample(n, 0.0), sample(n, 1.5));
+        group.bench_with_input(BenchmarkId::new("indexed", n), &n, |b, _| {
+            b.iter(|| indexed_euclidean_grad(black_box(&x), black_box(&y)))
+        });
+        group.bench_with_input(BenchmarkId::new("iterator", n), &n, |b, _| {
+            b.iter(|| euclidean_grad(black_box(&x), black_box(&y)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, kernels, gradients);
+criterion_main!(benches);
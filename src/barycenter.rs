@@ -0,0 +1,207 @@
+//! Metric-specific centroids.
+//!
+//! Each function returns the point that plays the role of the "center" of a set of rows for the
+//! corresponding metric, i.e. the update step of a k-means-style algorithm. The [`Barycenter`]
+//! trait exposes them on the built-in metric types so such algorithms can be written generically.
+
+use ndarray::{Array1, ArrayView2, Axis};
+use num::Float;
+
+use crate::{Cosine, Euclidean, Haversine, Manhattan};
+
+/// A metric with a closed-form (or cheap) centroid.
+pub trait Barycenter<T> {
+    /// Computes the centroid of the rows of `data` under this metric.
+    fn barycenter(&self, data: &ArrayView2<T>) -> Array1<T>;
+}
+
+/// Arithmetic mean of the rows of `data`, which minimizes the sum of squared
+/// [`euclidean`](crate::euclidean) distances.
+///
+/// # Panics
+/// Panics if `data` has no rows.
+///
+/// # Example
+/// ```
+/// use ndarray::{arr1, arr2};
+/// use fast_distances::euclidean_barycenter;
+///
+/// let data = arr2(&[[0.0, 0.0], [2.0, 4.0]]);
+/// assert_eq!(euclidean_barycenter(&data.view()), arr1(&[1.0, 2.0]));
+/// ```
+pub fn euclidean_barycenter<T: Float>(data: &ArrayView2<T>) -> Array1<T> {
+    assert!(data.nrows() > 0, "Data must contain at least one row.");
+    let n = T::from(data.nrows()).unwrap();
+    data.sum_axis(Axis(0)).mapv(|v| v / n)
+}
+
+/// Component-wise median of the rows of `data`, which minimizes the sum of
+/// [`manhattan`](crate::manhattan) distances.
+///
+/// For an even number of rows the two middle values are averaged.
+///
+/// # Panics
+/// Panics if `data` has no rows.
+pub fn manhattan_barycenter<T: Float>(data: &ArrayView2<T>) -> Array1<T> {
+    assert!(data.nrows() > 0, "Data must contain at least one row.");
+    let two = T::one() + T::one();
+    data.columns()
+        .into_iter()
+        .map(|column| {
+            let mut values = column.to_vec();
+            values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            let mid = values.len() / 2;
+            if values.len() % 2 == 0 {
+                (values[mid - 1] + values[mid]) / two
+            } else {
+                values[mid]
+            }
+        })
+        .collect()
+}
+
+/// Mean direction of the rows of `data`, the centroid used by spherical k-means with the
+/// [`cosine`](crate::cosine) distance.
+///
+/// Every row is normalized to unit length (zero rows are skipped), the unit vectors are
+/// averaged, and the result is normalized again. If the directions cancel out, the zero
+/// vector is returned.
+///
+/// # Panics
+/// Panics if `data` has no rows.
+pub fn cosine_barycenter<T: Float>(data: &ArrayView2<T>) -> Array1<T> {
+    assert!(data.nrows() > 0, "Data must contain at least one row.");
+    let mut total = Array1::<T>::zeros(data.ncols());
+    for row in data.rows() {
+        let norm = row.iter().fold(T::zero(), |acc, &v| acc + v * v).sqrt();
+        if !norm.is_zero() {
+            total.zip_mut_with(&row, |t, &v| *t = *t + v / norm);
+        }
+    }
+    normalize(total)
+}
+
+/// Spherical mean of the (latitude, longitude) rows of `data`, in radians, for the
+/// [`haversine`](crate::haversine) distance.
+///
+/// The points are mapped to unit vectors in 3D, averaged, projected back onto the sphere and
+/// converted to (latitude, longitude). If the points cancel out (e.g. two antipodal points) the
+/// mean is undefined and `(0, 0)` is returned.
+///
+/// # Panics
+/// Panics if `data` has no rows or does not have exactly two columns.
+///
+/// # Example
+/// ```
+/// use std::f64::consts::FRAC_PI_2;
+/// use ndarray::arr2;
+/// use fast_distances::haversine_barycenter;
+///
+/// let data = arr2(&[[0.0, 0.0], [0.0, FRAC_PI_2]]);
+/// let center = haversine_barycenter(&data.view());
+/// assert!(center[0].abs() < 1e-12);
+/// assert!((center[1] - FRAC_PI_2 / 2.0).abs() < 1e-12);
+/// ```
+pub fn haversine_barycenter<T: Float>(data: &ArrayView2<T>) -> Array1<T> {
+    assert!(data.nrows() > 0, "Data must contain at least one row.");
+    assert_eq!(
+        data.ncols(),
+        2,
+        "Haversine is only defined for 2-dimensional data"
+    );
+
+    let mut total = Array1::<T>::zeros(3);
+    for row in data.rows() {
+        let (lat, lon) = (row[0], row[1]);
+        total[0] = total[0] + lat.cos() * lon.cos();
+        total[1] = total[1] + lat.cos() * lon.sin();
+        total[2] = total[2] + lat.sin();
+    }
+
+    // Treat a resultant that is zero up to rounding as cancelled out.
+    let norm = total.iter().fold(T::zero(), |acc, &x| acc + x * x).sqrt();
+    if norm <= T::epsilon() * T::from(data.nrows()).unwrap() {
+        return Array1::zeros(2);
+    }
+
+    let total = normalize(total);
+    let lat = total[2].atan2(total[0].hypot(total[1]));
+    let lon = total[1].atan2(total[0]);
+    Array1::from_vec(vec![lat, lon])
+}
+
+fn normalize<T: Float>(v: Array1<T>) -> Array1<T> {
+    let norm = v.iter().fold(T::zero(), |acc, &x| acc + x * x).sqrt();
+    if norm.is_zero() {
+        v
+    } else {
+        v.mapv(|x| x / norm)
+    }
+}
+
+impl<T: Float> Barycenter<T> for Euclidean {
+    fn barycenter(&self, data: &ArrayView2<T>) -> Array1<T> {
+        euclidean_barycenter(data)
+    }
+}
+
+impl<T: Float> Barycenter<T> for Manhattan {
+    fn barycenter(&self, data: &ArrayView2<T>) -> Array1<T> {
+        manhattan_barycenter(data)
+    }
+}
+
+impl<T: Float> Barycenter<T> for Cosine {
+    fn barycenter(&self, data: &ArrayView2<T>) -> Array1<T> {
+        cosine_barycenter(data)
+    }
+}
+
+impl<T: Float> Barycenter<T> for Haversine {
+    fn barycenter(&self, data: &ArrayView2<T>) -> Array1<T> {
+        haversine_barycenter(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use ndarray::{arr1, arr2};
+
+    #[test]
+    fn test_manhattan_barycenter_median() {
+        let data = arr2(&[[1.0, 10.0], [3.0, -1.0], [2.0, 0.0]]);
+        assert_eq!(manhattan_barycenter(&data.view()), arr1(&[2.0, 0.0]));
+
+        let data = arr2(&[[1.0], [4.0], [2.0], [3.0]]);
+        assert_eq!(manhattan_barycenter(&data.view()), arr1(&[2.5]));
+    }
+
+    #[test]
+    fn test_cosine_barycenter_ignores_magnitude() {
+        let data = arr2(&[[10.0, 0.0], [0.0, 0.5], [0.0, 0.0]]);
+        let center = cosine_barycenter(&data.view());
+        let expected = 0.5f64.sqrt();
+        assert!((center[0] - expected).abs() < 1e-12);
+        assert!((center[1] - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_haversine_barycenter_antipodal() {
+        let data = arr2(&[[0.0, 0.0], [0.0, std::f64::consts::PI]]);
+        let center = haversine_barycenter(&data.view());
+        assert!(center.iter().all(|c| c.abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_trait_dispatch() {
+        fn center<M: Barycenter<f64>>(metric: &M, data: &ndarray::Array2<f64>) -> Array1<f64> {
+            metric.barycenter(&data.view())
+        }
+
+        let data = arr2(&[[0.0, 0.0], [1.0, 0.0], [5.0, 3.0]]);
+        assert_eq!(center(&Euclidean, &data), arr1(&[2.0, 1.0]));
+        assert_eq!(center(&Manhattan, &data), arr1(&[1.0, 0.0]));
+    }
+}
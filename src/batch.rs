@@ -0,0 +1,470 @@
+//! Chunked distance computations over memory-mapped `.npy` files.
+//!
+//! Only compiled with the `npy` feature. [`NpyMatrix`] maps a two-dimensional, C-ordered `f32`
+//! or `f64` array saved by NumPy (`np.save`) into memory without reading it, so datasets larger
+//! than RAM can be processed: the operating system pages rows in as they are touched and can
+//! evict them again once a chunk is done. [`distances_to_query`] and [`cdist_chunks`] walk the
+//! rows in chunks of a caller-chosen size, so the memory they allocate is bounded by the chunk
+//! size rather than by the number of rows.
+//!
+//! # Example
+//! ```no_run
+//! use ndarray::Array1;
+//! use fast_distances::Cosine;
+//! use fast_distances::batch::{distances_to_query, NpyMatrix};
+//!
+//! let data = NpyMatrix::<f32>::open("embeddings.npy")?;
+//! let query = Array1::<f32>::zeros(data.ncols());
+//! let dists = distances_to_query(&data, &query.view(), &Cosine, 65_536);
+//! assert_eq!(dists.len(), data.nrows());
+//! # Ok::<(), fast_distances::batch::NpyError>(())
+//! ```
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::marker::PhantomData;
+use std::path::Path;
+
+use memmap2::Mmap;
+use ndarray::{s, Array1, Array2, ArrayView1, ArrayView2};
+use num::Float;
+
+use crate::{cdist_into, distances_to_point_into, Distance};
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+/// Errors reported while opening or writing `.npy` files.
+#[derive(Debug)]
+pub enum NpyError {
+    /// Reading, mapping or writing the file failed.
+    Io(io::Error),
+    /// The file is not a valid `.npy` file, or its header could not be parsed.
+    InvalidHeader(String),
+    /// The file is valid but stores an array this module cannot map, e.g. a different dtype,
+    /// Fortran order or a shape that is not two-dimensional.
+    Unsupported(String),
+}
+
+impl fmt::Display for NpyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NpyError::Io(err) => write!(f, "I/O error: {}", err),
+            NpyError::InvalidHeader(reason) => write!(f, "invalid .npy header: {}", reason),
+            NpyError::Unsupported(reason) => write!(f, "unsupported .npy array: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for NpyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            NpyError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for NpyError {
+    fn from(err: io::Error) -> Self {
+        NpyError::Io(err)
+    }
+}
+
+/// Element types that can be mapped from a `.npy` file.
+pub trait NpyElement: Float + bytemuck::Pod {
+    /// The NumPy dtype descriptor of this type in native byte order, e.g. `<f4`.
+    const DESCR: &'static str;
+}
+
+impl NpyElement for f32 {
+    #[cfg(target_endian = "little")]
+    const DESCR: &'static str = "<f4";
+    #[cfg(target_endian = "big")]
+    const DESCR: &'static str = ">f4";
+}
+
+impl NpyElement for f64 {
+    #[cfg(target_endian = "little")]
+    const DESCR: &'static str = "<f8";
+    #[cfg(target_endian = "big")]
+    const DESCR: &'static str = ">f8";
+}
+
+/// A read-only, memory-mapped two-dimensional `.npy` array.
+#[derive(Debug)]
+pub struct NpyMatrix<T> {
+    mmap: Mmap,
+    offset: usize,
+    rows: usize,
+    cols: usize,
+    _element: PhantomData<T>,
+}
+
+impl<T: NpyElement> NpyMatrix<T> {
+    /// Maps the `.npy` file at `path`.
+    ///
+    /// The file must hold a C-ordered, two-dimensional array whose dtype matches `T` in native
+    /// byte order (`<f4` for `f32`, `<f8` for `f64` on little-endian targets).
+    ///
+    /// The file must not be modified or truncated while it is mapped; doing so is undefined
+    /// behavior, as for every memory map.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, NpyError> {
+        let file = File::open(path)?;
+        // SAFETY: the map is read-only, and the documented contract of `open` is that the file
+        // is not modified while the returned matrix is alive.
+        let mmap = unsafe { Mmap::map(&file)? };
+
+        let (offset, rows, cols) = parse_header(&mmap, T::DESCR)?;
+        let bytes = rows
+            .checked_mul(cols)
+            .and_then(|len| len.checked_mul(std::mem::size_of::<T>()))
+            .ok_or_else(|| NpyError::InvalidHeader("shape overflows usize".to_string()))?;
+        if mmap.len() - offset < bytes {
+            return Err(NpyError::InvalidHeader(format!(
+                "expected {} bytes of data, found {}",
+                bytes,
+                mmap.len() - offset
+            )));
+        }
+        if offset % std::mem::align_of::<T>() != 0 {
+            return Err(NpyError::Unsupported(format!(
+                "data offset {} is not aligned for the element type",
+                offset
+            )));
+        }
+
+        Ok(Self {
+            mmap,
+            offset,
+            rows,
+            cols,
+            _element: PhantomData,
+        })
+    }
+
+    /// Returns the number of rows.
+    pub fn nrows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the number of columns.
+    pub fn ncols(&self) -> usize {
+        self.cols
+    }
+
+    /// Returns a view of the whole mapped array. Rows are only read from disk when accessed.
+    pub fn view(&self) -> ArrayView2<'_, T> {
+        let len = self.rows * self.cols * std::mem::size_of::<T>();
+        let data: &[T] = bytemuck::cast_slice(&self.mmap[self.offset..self.offset + len]);
+        ArrayView2::from_shape((self.rows, self.cols), data).unwrap()
+    }
+
+    /// Iterates over consecutive blocks of `chunk_rows` rows (the last one may be shorter),
+    /// yielding the index of the first row of each block together with its view.
+    ///
+    /// # Panics
+    /// Panics if `chunk_rows` is zero.
+    pub fn chunks(&self, chunk_rows: usize) -> impl Iterator<Item = (usize, ArrayView2<'_, T>)> {
+        assert!(chunk_rows > 0, "Chunk size must be positive.");
+        let view = self.view();
+        (0..self.rows).step_by(chunk_rows).map(move |start| {
+            let end = (start + chunk_rows).min(view.nrows());
+            (start, view.slice_move(s![start..end, ..]))
+        })
+    }
+}
+
+/// Computes the distance from every row of `data` to `query`, `chunk_rows` rows at a time.
+///
+/// Only the output vector is allocated; the rows themselves are read through the memory map.
+///
+/// # Panics
+/// Panics if the number of columns of `data` differs from the length of `query`, or if
+/// `chunk_rows` is zero.
+pub fn distances_to_query<T, M>(
+    data: &NpyMatrix<T>,
+    query: &ArrayView1<T>,
+    metric: &M,
+    chunk_rows: usize,
+) -> Array1<T>
+where
+    T: NpyElement,
+    M: Distance<T> + ?Sized,
+{
+    assert_eq!(
+        data.ncols(),
+        query.len(),
+        "Data columns and query must have the same length."
+    );
+
+    let mut out = Array1::zeros(data.nrows());
+    for (start, chunk) in data.chunks(chunk_rows) {
+        let mut block = out.slice_mut(s![start..start + chunk.nrows()]);
+        distances_to_point_into(&chunk, query, metric, &mut block);
+    }
+    out
+}
+
+/// Computes the distances between the rows of `data` and the rows of `queries` block by block.
+///
+/// For every chunk of `chunk_rows` rows of `data`, the corresponding `chunk x queries.nrows()`
+/// block of the distance matrix is computed into a reused buffer and passed to `f` together
+/// with the index of the first row of the chunk. The full matrix is never materialized, so
+/// memory use is bounded by `chunk_rows * queries.nrows()` elements. `queries` can itself be
+/// the [`view`](NpyMatrix::view) of another mapped file.
+///
+/// # Panics
+/// Panics if `data` and `queries` do not have the same number of columns, or if `chunk_rows`
+/// is zero.
+///
+/// # Example
+/// ```no_run
+/// use fast_distances::Euclidean;
+/// use fast_distances::batch::{cdist_chunks, NpyMatrix};
+///
+/// let data = NpyMatrix::<f32>::open("data.npy")?;
+/// let queries = NpyMatrix::<f32>::open("queries.npy")?;
+/// let mut closest = vec![f32::INFINITY; queries.nrows()];
+/// cdist_chunks(&data, &queries.view(), &Euclidean, 4096, |_, block| {
+///     for row in block.rows() {
+///         for (c, &d) in closest.iter_mut().zip(row) {
+///             *c = c.min(d);
+///         }
+///     }
+/// });
+/// # Ok::<(), fast_distances::batch::NpyError>(())
+/// ```
+pub fn cdist_chunks<T, M, F>(
+    data: &NpyMatrix<T>,
+    queries: &ArrayView2<T>,
+    metric: &M,
+    chunk_rows: usize,
+    mut f: F,
+) where
+    T: NpyElement,
+    M: Distance<T> + ?Sized,
+    F: FnMut(usize, ArrayView2<T>),
+{
+    assert_eq!(
+        data.ncols(),
+        queries.ncols(),
+        "Input matrices must have the same number of columns."
+    );
+
+    let mut buffer = Array2::zeros((chunk_rows.min(data.nrows()), queries.nrows()));
+    for (start, chunk) in data.chunks(chunk_rows) {
+        let mut block = buffer.slice_mut(s![..chunk.nrows(), ..]);
+        cdist_into(&chunk, queries, metric, &mut block);
+        f(start, block.view());
+    }
+}
+
+/// Writes `data` to `path` as a C-ordered `.npy` file (format version 1.0) that
+/// [`NpyMatrix::open`] and `np.load` can read.
+pub fn write_npy<T, P>(path: P, data: &ArrayView2<T>) -> Result<(), NpyError>
+where
+    T: NpyElement,
+    P: AsRef<Path>,
+{
+    let mut header = format!(
+        "{{'descr': '{}', 'fortran_order': False, 'shape': ({}, {}), }}",
+        T::DESCR,
+        data.nrows(),
+        data.ncols()
+    );
+    // Pad with spaces so the data starts on a 64-byte boundary; the header ends with '\n'.
+    let unpadded = MAGIC.len() + 2 + 2 + header.len() + 1;
+    header.push_str(&" ".repeat((64 - unpadded % 64) % 64));
+    header.push('\n');
+    let header_len = u16::try_from(header.len())
+        .map_err(|_| NpyError::Unsupported("header is too long".to_string()))?;
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[1, 0])?;
+    writer.write_all(&header_len.to_le_bytes())?;
+    writer.write_all(header.as_bytes())?;
+    for value in data.iter() {
+        writer.write_all(bytemuck::bytes_of(value))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Parses the header of a `.npy` file and returns the data offset and the two dimensions.
+fn parse_header(bytes: &[u8], descr: &str) -> Result<(usize, usize, usize), NpyError> {
+    if bytes.len() < MAGIC.len() + 4 || &bytes[..MAGIC.len()] != MAGIC {
+        return Err(NpyError::InvalidHeader("missing magic string".to_string()));
+    }
+
+    let major = bytes[MAGIC.len()];
+    let (len_start, len_size) = match major {
+        1 => (MAGIC.len() + 2, 2),
+        2 | 3 => (MAGIC.len() + 2, 4),
+        _ => {
+            return Err(NpyError::Unsupported(format!(
+                "format version {} is not supported",
+                major
+            )))
+        }
+    };
+    let header_start = len_start + len_size;
+    if bytes.len() < header_start {
+        return Err(NpyError::InvalidHeader("truncated header".to_string()));
+    }
+    let header_len = bytes[len_start..header_start]
+        .iter()
+        .rev()
+        .fold(0usize, |acc, &b| (acc << 8) | b as usize);
+    let offset = header_start + header_len;
+    if bytes.len() < offset {
+        return Err(NpyError::InvalidHeader("truncated header".to_string()));
+    }
+    let header = std::str::from_utf8(&bytes[header_start..offset])
+        .map_err(|_| NpyError::InvalidHeader("header is not valid text".to_string()))?;
+
+    let found = header_value(header, "descr")?;
+    let found = found.trim_matches(|c| c == '\'' || c == '"');
+    if found != descr {
+        return Err(NpyError::Unsupported(format!(
+            "expected dtype '{}', found '{}'",
+            descr, found
+        )));
+    }
+
+    if header_value(header, "fortran_order")? != "False" {
+        return Err(NpyError::Unsupported(
+            "Fortran-ordered arrays are not supported".to_string(),
+        ));
+    }
+
+    let shape = header_value(header, "shape")?;
+    let dims = shape
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .split(',')
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .map(|d| {
+            d.parse::<usize>()
+                .map_err(|_| NpyError::InvalidHeader(format!("invalid shape {}", shape)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    match dims[..] {
+        [rows, cols] => Ok((offset, rows, cols)),
+        _ => Err(NpyError::Unsupported(format!(
+            "expected a 2-dimensional array, found shape {}",
+            shape
+        ))),
+    }
+}
+
+/// Extracts the raw text of `key`'s value from the Python dict literal of a `.npy` header.
+fn header_value<'a>(header: &'a str, key: &str) -> Result<&'a str, NpyError> {
+    let missing = || NpyError::InvalidHeader(format!("missing key '{}'", key));
+    let start = header
+        .find(&format!("'{}':", key))
+        .or_else(|| header.find(&format!("\"{}\":", key)))
+        .ok_or_else(missing)?
+        + key.len()
+        + 3;
+    let rest = header[start..].trim_start();
+    let end = if rest.starts_with('(') {
+        rest.find(')').map(|i| i + 1)
+    } else {
+        rest.find(',').or_else(|| rest.find('}'))
+    }
+    .ok_or_else(missing)?;
+    Ok(rest[..end].trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use ndarray::arr1;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "fast_distances_{}_{}.npy",
+            name,
+            std::process::id()
+        ))
+    }
+
+    fn sample(rows: usize, cols: usize) -> Array2<f32> {
+        Array2::from_shape_fn((rows, cols), |(i, j)| ((i * 7 + j * 3) % 11) as f32 - 5.0)
+    }
+
+    #[test]
+    fn test_write_and_open_roundtrip() {
+        let path = temp_path("roundtrip");
+        let data = sample(7, 5).mapv(f64::from);
+        write_npy(&path, &data.view()).unwrap();
+
+        let matrix = NpyMatrix::<f64>::open(&path).unwrap();
+        assert_eq!((matrix.nrows(), matrix.ncols()), (7, 5));
+        assert_eq!(matrix.view(), data.view());
+
+        let starts: Vec<_> = matrix
+            .chunks(3)
+            .map(|(start, c)| (start, c.nrows()))
+            .collect();
+        assert_eq!(starts, vec![(0, 3), (3, 3), (6, 1)]);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_chunked_distances_match_in_memory() {
+        let path = temp_path("chunked");
+        let data = sample(10, 4);
+        write_npy(&path, &data.view()).unwrap();
+        let matrix = NpyMatrix::<f32>::open(&path).unwrap();
+
+        let query = arr1(&[1.0f32, -2.0, 0.5, 3.0]);
+        let expected = distances_to_point(&data.view(), &query.view(), &Euclidean);
+        for chunk_rows in [1, 3, 10, 64] {
+            assert_eq!(
+                distances_to_query(&matrix, &query.view(), &Euclidean, chunk_rows),
+                expected
+            );
+        }
+
+        let queries = sample(3, 4).mapv(|v| v * 0.5);
+        let expected = cdist(&data.view(), &queries.view(), &Manhattan);
+        let mut result = Array2::zeros((10, 3));
+        cdist_chunks(&matrix, &queries.view(), &Manhattan, 4, |start, block| {
+            result
+                .slice_mut(s![start..start + block.nrows(), ..])
+                .assign(&block);
+        });
+        assert_eq!(result, expected);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_open_rejects_wrong_dtype() {
+        let path = temp_path("dtype");
+        write_npy(&path, &sample(2, 2).view()).unwrap();
+
+        let err = NpyMatrix::<f64>::open(&path).unwrap_err();
+        assert!(matches!(err, NpyError::Unsupported(_)));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_parse_header() {
+        let header =
+            b"\x93NUMPY\x01\x00\x3a\x00{'descr': '<f4', 'fortran_order': False, 'shape': (3,), }\n";
+        assert!(matches!(
+            parse_header(header, "<f4"),
+            Err(NpyError::Unsupported(_))
+        ));
+        assert!(matches!(
+            parse_header(b"not a npy file", "<f4"),
+            Err(NpyError::InvalidHeader(_))
+        ));
+    }
+}
@@ -0,0 +1,139 @@
+use num::Float;
+
+/// Numerical settings shared by the gradient functions.
+///
+/// Several gradients divide by the distance itself and add a small stabilizing `epsilon` to the
+/// denominator so identical inputs don't produce a division by zero. The default of `1e-6` suits
+/// `f64` data of roughly unit scale; users working in `f32` or with very small-scale data should
+/// pick an `epsilon` that is small relative to their typical distances.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let config = DistanceConfig::default().with_epsilon(1e-12);
+/// let x = arr1(&[1e-4f64, 0.0]);
+/// let y = arr1(&[0.0, 0.0]);
+/// let (_, grad) = euclidean_grad_with_config(&x, &y, &config);
+/// assert!((grad[0] - 1.0).abs() < 1e-6);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DistanceConfig<T> {
+    /// Stabilization term added to denominators that may be zero.
+    pub epsilon: T,
+}
+
+impl<T> DistanceConfig<T>
+where
+    T: Float,
+{
+    /// Creates a configuration with the default epsilon of `1e-6`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a copy of this configuration using the given stabilization epsilon.
+    pub fn with_epsilon(mut self, epsilon: T) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+}
+
+impl<T> Default for DistanceConfig<T>
+where
+    T: Float,
+{
+    fn default() -> Self {
+        Self {
+            epsilon: T::from(1e-6).unwrap(),
+        }
+    }
+}
+
+/// Input preprocessing for the divergences between probability vectors, such as
+/// [`kl_divergence_with_config`](crate::kl_divergence_with_config).
+///
+/// Divergences like Kullback-Leibler are infinite as soon as `q` has a zero where `p` does
+/// not, and assume that both inputs sum to one. `epsilon` is added to every coordinate of both
+/// inputs before anything else, which keeps the result finite for sparse histograms, and
+/// `normalize` then rescales both inputs to sum to one, so raw counts can be passed directly.
+/// An input that sums to zero is left unnormalized. The default applies neither.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let config = DivergenceConfig::default().with_epsilon(1e-9).with_normalize(true);
+/// let p = arr1(&[3.0f64, 1.0, 0.0]);
+/// let q = arr1(&[0.0, 2.0, 2.0]);
+/// assert!(kl_divergence(&p.view(), &q.view()).is_infinite());
+/// assert!(kl_divergence_with_config(&p.view(), &q.view(), &config).is_finite());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DivergenceConfig<T> {
+    /// Smoothing term added to every coordinate of both inputs.
+    pub epsilon: T,
+    /// Whether both inputs are rescaled to sum to one after smoothing.
+    pub normalize: bool,
+}
+
+impl<T> DivergenceConfig<T>
+where
+    T: Float,
+{
+    /// Creates a configuration without smoothing or normalization.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a copy of this configuration using the given smoothing epsilon.
+    pub fn with_epsilon(mut self, epsilon: T) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    /// Returns a copy of this configuration that does or does not normalize the inputs.
+    pub fn with_normalize(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+}
+
+impl<T> Default for DivergenceConfig<T>
+where
+    T: Float,
+{
+    fn default() -> Self {
+        Self {
+            epsilon: T::zero(),
+            normalize: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_epsilon() {
+        assert_eq!(DistanceConfig::<f64>::default().epsilon, 1e-6);
+        assert_eq!(DistanceConfig::<f32>::new().epsilon, 1e-6f32);
+    }
+
+    #[test]
+    fn test_with_epsilon() {
+        let config = DistanceConfig::<f64>::default().with_epsilon(1e-9);
+        assert_eq!(config.epsilon, 1e-9);
+    }
+
+    #[test]
+    fn test_divergence_config() {
+        let config = DivergenceConfig::<f64>::new();
+        assert_eq!((config.epsilon, config.normalize), (0.0, false));
+        let config = config.with_epsilon(1e-3).with_normalize(true);
+        assert_eq!((config.epsilon, config.normalize), (1e-3, true));
+    }
+}
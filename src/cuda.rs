@@ -0,0 +1,335 @@
+//! `f32` distance matrices computed with cuBLAS on NVIDIA GPUs.
+//!
+//! Only compiled with the `cuda` feature. The CUDA driver and cuBLAS are loaded at runtime, so
+//! the crate builds on machines without a CUDA toolkit. As in
+//! [`sqeuclidean_cdist`](crate::sqeuclidean_cdist), the cross terms of every distance come from
+//! one `a · bᵀ` product, which is computed on the device with a single SGEMM; the row norms and
+//! the final combination are computed on the host.
+//!
+//! [`CudaBackend`] gives explicit control over the device and reports errors. The free
+//! functions use a process-wide backend on device 0 and fall back to the CPU implementations
+//! whenever no device is present or the device computation fails, so they can be called
+//! unconditionally.
+//!
+//! The whole problem is processed in one product, so `a`, `b` and the `n x m` output must fit in
+//! device memory together; larger problems fall back to the CPU.
+
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+
+use cudarc::cublas::sys::cublasOperation_t;
+use cudarc::cublas::{CudaBlas, Gemm, GemmConfig};
+use cudarc::driver::{CudaContext, CudaStream};
+use ndarray::{Array1, Array2, ArrayView2, Axis};
+
+/// Errors reported by the CUDA backend.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CudaError {
+    /// The CUDA driver or cuBLAS libraries were not found, or no device is present.
+    NoDevice(String),
+    /// A CUDA driver call failed, e.g. because the device ran out of memory.
+    Driver(String),
+    /// A cuBLAS call failed.
+    Blas(String),
+    /// The matrices are too large for the 32-bit dimensions cuBLAS accepts.
+    InvalidInput(String),
+}
+
+impl fmt::Display for CudaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CudaError::NoDevice(reason) => write!(f, "no CUDA device available: {}", reason),
+            CudaError::Driver(reason) => write!(f, "CUDA driver error: {}", reason),
+            CudaError::Blas(reason) => write!(f, "cuBLAS error: {}", reason),
+            CudaError::InvalidInput(reason) => write!(f, "invalid input: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for CudaError {}
+
+/// A CUDA device with a cuBLAS handle, reusable across many calls.
+#[derive(Debug)]
+pub struct CudaBackend {
+    stream: Arc<CudaStream>,
+    blas: CudaBlas,
+}
+
+impl CudaBackend {
+    /// Opens the device with the given ordinal and creates a cuBLAS handle on its default
+    /// stream.
+    pub fn new(ordinal: usize) -> Result<Self, CudaError> {
+        // SAFETY: these only try to open the shared libraries, without calling into them.
+        let present = unsafe {
+            cudarc::driver::sys::is_culib_present() && cudarc::cublas::sys::is_culib_present()
+        };
+        if !present {
+            return Err(CudaError::NoDevice(
+                "the CUDA driver or cuBLAS library could not be loaded".to_string(),
+            ));
+        }
+
+        let context =
+            CudaContext::new(ordinal).map_err(|err| CudaError::NoDevice(err.to_string()))?;
+        let stream = context.default_stream();
+        let blas = CudaBlas::new(stream.clone()).map_err(|err| CudaError::Blas(err.to_string()))?;
+        Ok(Self { stream, blas })
+    }
+
+    /// Computes the squared Euclidean distance between every row of `a` and every row of `b`,
+    /// clamped at zero like [`sqeuclidean_cdist`](crate::sqeuclidean_cdist).
+    ///
+    /// # Panics
+    /// Panics if `a` and `b` do not have the same number of columns.
+    pub fn sqeuclidean_cdist(
+        &self,
+        a: &ArrayView2<f32>,
+        b: &ArrayView2<f32>,
+    ) -> Result<Array2<f32>, CudaError> {
+        let mut result = self.cross_products(a, b)?;
+        let a_norms = squared_row_norms(a);
+        let b_norms = squared_row_norms(b);
+        for ((i, j), value) in result.indexed_iter_mut() {
+            *value = (a_norms[i] + b_norms[j] - 2.0 * *value).max(0.0);
+        }
+        Ok(result)
+    }
+
+    /// Computes the [`euclidean`](crate::euclidean) distance between every row of `a` and every
+    /// row of `b`.
+    ///
+    /// # Panics
+    /// Panics if `a` and `b` do not have the same number of columns.
+    pub fn euclidean_cdist(
+        &self,
+        a: &ArrayView2<f32>,
+        b: &ArrayView2<f32>,
+    ) -> Result<Array2<f32>, CudaError> {
+        Ok(self.sqeuclidean_cdist(a, b)?.mapv_into(f32::sqrt))
+    }
+
+    /// Computes the [`cosine`](crate::cosine) distance between every row of `a` and every row of
+    /// `b` from the product of the row-normalized matrices.
+    ///
+    /// Zero-norm rows follow the scalar conventions: the distance between two zero rows is `0`,
+    /// and between a zero row and a non-zero row it is `1`.
+    ///
+    /// # Panics
+    /// Panics if `a` and `b` do not have the same number of columns.
+    pub fn cosine_cdist(
+        &self,
+        a: &ArrayView2<f32>,
+        b: &ArrayView2<f32>,
+    ) -> Result<Array2<f32>, CudaError> {
+        let a_norms = squared_row_norms(a).mapv_into(f32::sqrt);
+        let b_norms = squared_row_norms(b).mapv_into(f32::sqrt);
+        let mut result = self.cross_products(
+            &normalize_rows(a, &a_norms).view(),
+            &normalize_rows(b, &b_norms).view(),
+        )?;
+        for ((i, j), value) in result.indexed_iter_mut() {
+            let (na, nb) = (a_norms[i], b_norms[j]);
+            *value = if na == 0.0 && nb == 0.0 {
+                0.0
+            } else if na == 0.0 || nb == 0.0 {
+                1.0
+            } else {
+                1.0 - *value
+            };
+        }
+        Ok(result)
+    }
+
+    /// Computes `a · bᵀ` with one SGEMM on the device.
+    fn cross_products(
+        &self,
+        a: &ArrayView2<f32>,
+        b: &ArrayView2<f32>,
+    ) -> Result<Array2<f32>, CudaError> {
+        assert_eq!(
+            a.ncols(),
+            b.ncols(),
+            "Input matrices must have the same number of columns."
+        );
+
+        let (n, m, d) = (a.nrows(), b.nrows(), a.ncols());
+        if n == 0 || m == 0 || d == 0 {
+            return Ok(Array2::zeros((n, m)));
+        }
+        let dim = |v: usize| {
+            i32::try_from(v).map_err(|_| {
+                CudaError::InvalidInput(format!("dimension {} exceeds the cuBLAS limit", v))
+            })
+        };
+        let (n_i, m_i, d_i) = (dim(n)?, dim(m)?, dim(d)?);
+
+        let driver = |err: cudarc::driver::DriverError| CudaError::Driver(err.to_string());
+        let a_host = a.as_standard_layout();
+        let b_host = b.as_standard_layout();
+        let a_dev = self
+            .stream
+            .clone_htod(a_host.as_slice().unwrap())
+            .map_err(driver)?;
+        let b_dev = self
+            .stream
+            .clone_htod(b_host.as_slice().unwrap())
+            .map_err(driver)?;
+        let mut c_dev = self.stream.alloc_zeros::<f32>(n * m).map_err(driver)?;
+
+        // cuBLAS is column-major: the row-major n x m product A · Bᵀ is the column-major
+        // m x n product B · Aᵀ, where the row-major buffers already hold Bᵀ and Aᵀ.
+        let config = GemmConfig {
+            transa: cublasOperation_t::CUBLAS_OP_T,
+            transb: cublasOperation_t::CUBLAS_OP_N,
+            m: m_i,
+            n: n_i,
+            k: d_i,
+            alpha: 1.0f32,
+            lda: d_i,
+            ldb: d_i,
+            beta: 0.0f32,
+            ldc: m_i,
+        };
+        // SAFETY: the buffers hold m x d, n x d and n x m elements, matching the leading
+        // dimensions and sizes in `config`.
+        unsafe { self.blas.gemm(config, &b_dev, &a_dev, &mut c_dev) }
+            .map_err(|err| CudaError::Blas(err.to_string()))?;
+
+        let values = self.stream.clone_dtoh(&c_dev).map_err(driver)?;
+        Ok(Array2::from_shape_vec((n, m), values).unwrap())
+    }
+}
+
+/// Returns the process-wide backend on device 0, or `None` if CUDA is not available.
+fn shared_backend() -> Option<&'static CudaBackend> {
+    static BACKEND: OnceLock<Option<CudaBackend>> = OnceLock::new();
+    BACKEND.get_or_init(|| CudaBackend::new(0).ok()).as_ref()
+}
+
+/// Squared Euclidean `cdist` on the default CUDA device, falling back to
+/// [`sqeuclidean_cdist`](crate::sqeuclidean_cdist) on the CPU.
+///
+/// # Panics
+/// Panics if `a` and `b` do not have the same number of columns.
+pub fn sqeuclidean_cdist(a: &ArrayView2<f32>, b: &ArrayView2<f32>) -> Array2<f32> {
+    shared_backend()
+        .and_then(|backend| backend.sqeuclidean_cdist(a, b).ok())
+        .unwrap_or_else(|| crate::sqeuclidean_cdist(a, b))
+}
+
+/// Euclidean `cdist` on the default CUDA device, falling back to
+/// [`euclidean_cdist`](crate::euclidean_cdist) on the CPU.
+///
+/// # Panics
+/// Panics if `a` and `b` do not have the same number of columns.
+pub fn euclidean_cdist(a: &ArrayView2<f32>, b: &ArrayView2<f32>) -> Array2<f32> {
+    shared_backend()
+        .and_then(|backend| backend.euclidean_cdist(a, b).ok())
+        .unwrap_or_else(|| crate::euclidean_cdist(a, b))
+}
+
+/// Cosine `cdist` on the default CUDA device, falling back to
+/// [`cosine_cdist`](crate::cosine_cdist) on the CPU.
+///
+/// # Panics
+/// Panics if `a` and `b` do not have the same number of columns.
+pub fn cosine_cdist(a: &ArrayView2<f32>, b: &ArrayView2<f32>) -> Array2<f32> {
+    shared_backend()
+        .and_then(|backend| backend.cosine_cdist(a, b).ok())
+        .unwrap_or_else(|| crate::cosine_cdist(a, b))
+}
+
+/// Euclidean distances between the rows of `data` in condensed form (see
+/// [`pdist`](crate::pdist)), computed from [`euclidean_cdist`].
+pub fn euclidean_pdist(data: &ArrayView2<f32>) -> Array1<f32> {
+    condensed(&euclidean_cdist(data, data))
+}
+
+/// Cosine distances between the rows of `data` in condensed form (see
+/// [`pdist`](crate::pdist)), computed from [`cosine_cdist`].
+pub fn cosine_pdist(data: &ArrayView2<f32>) -> Array1<f32> {
+    condensed(&cosine_cdist(data, data))
+}
+
+fn condensed(square: &Array2<f32>) -> Array1<f32> {
+    let n = square.nrows();
+    let mut result = Vec::with_capacity(crate::condensed_len(n));
+    for i in 0..n {
+        result.extend(square.row(i).iter().skip(i + 1));
+    }
+    Array1::from_vec(result)
+}
+
+fn squared_row_norms(m: &ArrayView2<f32>) -> Array1<f32> {
+    m.map_axis(Axis(1), |row| row.iter().map(|v| v * v).sum())
+}
+
+fn normalize_rows(m: &ArrayView2<f32>, norms: &Array1<f32>) -> Array2<f32> {
+    let mut normalized = m.to_owned();
+    for (mut row, &norm) in normalized.rows_mut().into_iter().zip(norms) {
+        if norm != 0.0 {
+            row.mapv_inplace(|v| v / norm);
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{condensed_len, pdist, Cosine};
+
+    fn sample(rows: usize, cols: usize, seed: usize) -> Array2<f32> {
+        Array2::from_shape_fn((rows, cols), |(i, j)| {
+            (((i + seed) * 31 + j * 17) % 23) as f32 / 7.0 - 1.5
+        })
+    }
+
+    fn assert_close(x: &Array2<f32>, y: &Array2<f32>) {
+        assert_eq!(x.dim(), y.dim());
+        for (a, b) in x.iter().zip(y.iter()) {
+            assert!((a - b).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_cdist_matches_cpu() {
+        // Without a CUDA device these exercise the CPU fallback.
+        let mut a = sample(21, 12, 0);
+        a.row_mut(2).fill(0.0);
+        let b = sample(8, 12, 4);
+        let (a, b) = (a.view(), b.view());
+
+        assert_close(&euclidean_cdist(&a, &b), &crate::euclidean_cdist(&a, &b));
+        assert_close(
+            &sqeuclidean_cdist(&a, &b),
+            &crate::sqeuclidean_cdist(&a, &b),
+        );
+        assert_close(&cosine_cdist(&a, &b), &crate::cosine_cdist(&a, &b));
+    }
+
+    #[test]
+    fn test_pdist_matches_cpu() {
+        let data = sample(9, 5, 1);
+        let result = cosine_pdist(&data.view());
+        let expected = pdist(&data.view(), &Cosine);
+        assert_eq!(result.len(), condensed_len(9));
+        for (r, e) in result.iter().zip(expected.iter()) {
+            assert!((r - e).abs() < 1e-3);
+        }
+        assert_eq!(euclidean_pdist(&data.slice(ndarray::s![..1, ..])).len(), 0);
+    }
+
+    #[test]
+    fn test_backend_when_available() {
+        let Ok(backend) = CudaBackend::new(0) else {
+            return;
+        };
+        let a = sample(5, 3, 0);
+        let b = sample(4, 3, 2);
+        assert_close(
+            &backend.euclidean_cdist(&a.view(), &b.view()).unwrap(),
+            &crate::euclidean_cdist(&a.view(), &b.view()),
+        );
+    }
+}
@@ -0,0 +1,351 @@
+//! Empirical checks of the metric axioms.
+//!
+//! Several dissimilarities in this crate (for example [`yule`](crate::yule),
+//! [`kulsinski`](crate::kulsinski) or [`russell_rao`](crate::russell_rao)) are not metrics. The
+//! functions below evaluate a [`Distance`] on `samples` randomly drawn pairs or triples of rows
+//! of the provided data and report the axioms it violates there. The cost is linear in
+//! `samples` and independent of the number of rows, and the draws are reproducible for a given
+//! `seed`. Pairs and triples are drawn with replacement, so the same violation can be reported
+//! more than once.
+
+use ndarray::ArrayView2;
+use num::Float;
+
+use crate::rng::SplitMix64;
+use crate::Distance;
+
+/// A violation of one of the metric axioms, found on specific rows of the data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AxiomViolation<T> {
+    /// `d(x_i, x_j)` and `d(x_j, x_i)` differ by more than the tolerance.
+    Asymmetric {
+        i: usize,
+        j: usize,
+        d_ij: T,
+        d_ji: T,
+    },
+    /// `d(x_i, x_i)` is not zero.
+    NonZeroSelfDistance { i: usize, d_ii: T },
+    /// `d(x_i, x_j)` is negative.
+    Negative { i: usize, j: usize, d_ij: T },
+    /// Rows `i` and `j` differ but `d(x_i, x_j)` is zero.
+    IndistinctPoints { i: usize, j: usize },
+    /// `d(x_i, x_k) > d(x_i, x_j) + d(x_j, x_k)` by more than the tolerance.
+    TriangleInequality {
+        i: usize,
+        j: usize,
+        k: usize,
+        d_ij: T,
+        d_jk: T,
+        d_ik: T,
+    },
+    /// The distance between rows `i` and `j` is NaN.
+    NotANumber { i: usize, j: usize },
+}
+
+/// The outcome of one of the sampled axiom checks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AxiomReport<T> {
+    /// The number of sampled pairs or triples that were checked.
+    pub checked: usize,
+    /// The total number of violations found, including those beyond `max_violations`.
+    pub violation_count: usize,
+    /// The first `max_violations` violations, in the order they were found.
+    pub violations: Vec<AxiomViolation<T>>,
+}
+
+impl<T> AxiomReport<T> {
+    fn new(max_violations: usize) -> Self {
+        Self {
+            checked: 0,
+            violation_count: 0,
+            violations: Vec::with_capacity(max_violations.min(64)),
+        }
+    }
+
+    /// Returns `true` if no sampled pair or triple violated the axiom.
+    pub fn is_ok(&self) -> bool {
+        self.violation_count == 0
+    }
+
+    fn record(&mut self, violation: AxiomViolation<T>, max_violations: usize) {
+        self.violation_count += 1;
+        if self.violations.len() < max_violations {
+            self.violations.push(violation);
+        }
+    }
+}
+
+/// Draws two distinct indices below `n`, which must be at least 2.
+fn distinct_pair(rng: &mut SplitMix64, n: usize) -> (usize, usize) {
+    let i = rng.next_below(n);
+    let j = rng.next_below(n - 1);
+    (i, if j >= i { j + 1 } else { j })
+}
+
+/// Checks that `d(x_i, x_j) == d(x_j, x_i)` within `tolerance` on `samples` random pairs of
+/// distinct rows.
+///
+/// At most `max_violations` violations are kept in the report; all of them are counted.
+///
+/// # Example
+/// ```
+/// use ndarray::arr2;
+/// use fast_distances::Euclidean;
+/// use fast_distances::diagnostics::check_symmetry;
+///
+/// let data = arr2(&[[0.0, 1.0], [2.0, 3.0], [1.0, -1.0]]);
+/// let report = check_symmetry(&data.view(), &Euclidean, 1e-12, 100, 42, 10);
+/// assert!(report.is_ok());
+/// assert_eq!(report.checked, 100);
+/// ```
+pub fn check_symmetry<T, M>(
+    data: &ArrayView2<T>,
+    metric: &M,
+    tolerance: T,
+    samples: usize,
+    seed: u64,
+    max_violations: usize,
+) -> AxiomReport<T>
+where
+    T: Float,
+    M: Distance<T> + ?Sized,
+{
+    let n = data.nrows();
+    let mut report = AxiomReport::new(max_violations);
+    if n < 2 {
+        return report;
+    }
+
+    let mut rng = SplitMix64::new(seed);
+    for _ in 0..samples {
+        let (a, b) = distinct_pair(&mut rng, n);
+        let (i, j) = (a.min(b), a.max(b));
+        let d_ij = metric.distance(&data.row(i), &data.row(j));
+        let d_ji = metric.distance(&data.row(j), &data.row(i));
+        report.checked += 1;
+        if d_ij.is_nan() || d_ji.is_nan() {
+            report.record(AxiomViolation::NotANumber { i, j }, max_violations);
+        } else if (d_ij - d_ji).abs() > tolerance {
+            report.record(
+                AxiomViolation::Asymmetric { i, j, d_ij, d_ji },
+                max_violations,
+            );
+        }
+    }
+    report
+}
+
+/// Checks the identity axioms on `samples` random rows `i`, each paired with a random other
+/// row `j`: `d(x_i, x_i) == 0`, `d(x_i, x_j) >= 0`, and `d(x_i, x_j) > 0` whenever the two rows
+/// differ, all within `tolerance`.
+///
+/// At most `max_violations` violations are kept in the report; all of them are counted.
+pub fn check_identity<T, M>(
+    data: &ArrayView2<T>,
+    metric: &M,
+    tolerance: T,
+    samples: usize,
+    seed: u64,
+    max_violations: usize,
+) -> AxiomReport<T>
+where
+    T: Float,
+    M: Distance<T> + ?Sized,
+{
+    let n = data.nrows();
+    let mut report = AxiomReport::new(max_violations);
+    if n == 0 {
+        return report;
+    }
+
+    let mut rng = SplitMix64::new(seed);
+    for _ in 0..samples {
+        report.checked += 1;
+        let i = rng.next_below(n);
+        let row_i = data.row(i);
+        let d_ii = metric.distance(&row_i, &row_i);
+        if d_ii.is_nan() {
+            report.record(AxiomViolation::NotANumber { i, j: i }, max_violations);
+        } else if d_ii.abs() > tolerance {
+            report.record(
+                AxiomViolation::NonZeroSelfDistance { i, d_ii },
+                max_violations,
+            );
+        }
+
+        if n < 2 {
+            continue;
+        }
+        let j = rng.next_below(n - 1);
+        let j = if j >= i { j + 1 } else { j };
+        let row_j = data.row(j);
+        let d_ij = metric.distance(&row_i, &row_j);
+        if d_ij.is_nan() {
+            report.record(AxiomViolation::NotANumber { i, j }, max_violations);
+        } else if d_ij < -tolerance {
+            report.record(AxiomViolation::Negative { i, j, d_ij }, max_violations);
+        } else if d_ij.abs() <= tolerance && row_i != row_j {
+            let (i, j) = (i.min(j), i.max(j));
+            report.record(AxiomViolation::IndistinctPoints { i, j }, max_violations);
+        }
+    }
+    report
+}
+
+/// Checks `d(x_i, x_k) <= d(x_i, x_j) + d(x_j, x_k) + tolerance` on `samples` random triples of
+/// rows.
+///
+/// Each triple costs three evaluations of the metric. Triples whose `d(x_i, x_k)` is NaN are
+/// skipped. At most `max_violations` violations are kept in the report; all of them are counted.
+///
+/// # Example
+/// ```
+/// use ndarray::{arr2, ArrayView1};
+/// use fast_distances::*;
+/// use fast_distances::diagnostics::check_triangle_inequality;
+///
+/// let data = arr2(&[[0.0], [1.0], [2.0]]);
+/// assert!(check_triangle_inequality(&data.view(), &Euclidean, 1e-12, 200, 1, 10).is_ok());
+///
+/// let squared = from_fn(|x: &ArrayView1<f64>, y: &ArrayView1<f64>| euclidean(x, y).powi(2));
+/// let report = check_triangle_inequality(&data.view(), &squared, 1e-12, 200, 1, 3);
+/// assert!(report.violation_count > 0);
+/// assert!(report.violations.len() <= 3);
+/// ```
+pub fn check_triangle_inequality<T, M>(
+    data: &ArrayView2<T>,
+    metric: &M,
+    tolerance: T,
+    samples: usize,
+    seed: u64,
+    max_violations: usize,
+) -> AxiomReport<T>
+where
+    T: Float,
+    M: Distance<T> + ?Sized,
+{
+    let n = data.nrows();
+    let mut report = AxiomReport::new(max_violations);
+    if n == 0 {
+        return report;
+    }
+
+    let mut rng = SplitMix64::new(seed);
+    for _ in 0..samples {
+        let (i, j, k) = (rng.next_below(n), rng.next_below(n), rng.next_below(n));
+        report.checked += 1;
+        let d_ik = metric.distance(&data.row(i), &data.row(k));
+        if d_ik.is_nan() {
+            continue;
+        }
+        let d_ij = metric.distance(&data.row(i), &data.row(j));
+        let d_jk = metric.distance(&data.row(j), &data.row(k));
+        if d_ik > d_ij + d_jk + tolerance {
+            report.record(
+                AxiomViolation::TriangleInequality {
+                    i,
+                    j,
+                    k,
+                    d_ij,
+                    d_jk,
+                    d_ik,
+                },
+                max_violations,
+            );
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use ndarray::{arr2, Array2, ArrayView1};
+
+    fn sample_data() -> Array2<f64> {
+        Array2::from_shape_fn((8, 3), |(i, j)| ((i * 5 + j * 3) % 7) as f64 - 2.0)
+    }
+
+    #[test]
+    fn test_metrics_pass() {
+        let data = sample_data();
+        for metric in [
+            Box::new(Euclidean) as Box<dyn Distance<f64>>,
+            Box::new(Manhattan),
+            Box::new(Chebyshev),
+        ] {
+            assert!(check_symmetry(&data.view(), &metric, 1e-12, 200, 1, 10).is_ok());
+            assert!(check_identity(&data.view(), &metric, 1e-12, 200, 2, 10).is_ok());
+            let report = check_triangle_inequality(&data.view(), &metric, 1e-12, 500, 3, 10);
+            assert!(report.is_ok());
+            assert_eq!(report.checked, 500);
+        }
+    }
+
+    #[test]
+    fn test_asymmetric_closure() {
+        let data = arr2(&[[0.0], [1.0]]);
+        let metric = from_fn(|x: &ArrayView1<f64>, y: &ArrayView1<f64>| (x[0] - y[0]).max(0.0));
+
+        assert_eq!(
+            check_symmetry(&data.view(), &metric, 1e-12, 1, 5, 10).violations,
+            vec![AxiomViolation::Asymmetric {
+                i: 0,
+                j: 1,
+                d_ij: 0.0,
+                d_ji: 1.0
+            }]
+        );
+    }
+
+    #[test]
+    fn test_yule_is_not_a_metric() {
+        let data = Array2::from_shape_fn((6, 4), |(i, j)| (((i + 1) * (j + 2)) % 3 % 2) as f64);
+
+        assert!(!check_identity(&data.view(), &Yule, 1e-12, 200, 4, 10).is_ok());
+        let report = check_triangle_inequality(&data.view(), &Yule, 1e-12, 2000, 4, 5);
+        assert!(report.violation_count > 5);
+        assert_eq!(report.violations.len(), 5);
+        assert!(report
+            .violations
+            .iter()
+            .all(|v| matches!(v, AxiomViolation::TriangleInequality { .. })));
+    }
+
+    #[test]
+    fn test_indistinct_points() {
+        let data = arr2(&[[1.0, 2.0], [2.0, 4.0]]);
+        assert_eq!(
+            check_identity(&data.view(), &Cosine, 1e-12, 1, 9, 10).violations,
+            vec![AxiomViolation::IndistinctPoints { i: 0, j: 1 }]
+        );
+    }
+
+    #[test]
+    fn test_reproducible_and_small_inputs() {
+        let data = sample_data();
+        let metric = from_fn(|x: &ArrayView1<f64>, y: &ArrayView1<f64>| euclidean(x, y).powi(2));
+        assert_eq!(
+            check_triangle_inequality(&data.view(), &metric, 1e-12, 300, 11, 4),
+            check_triangle_inequality(&data.view(), &metric, 1e-12, 300, 11, 4)
+        );
+
+        let empty = Array2::<f64>::zeros((0, 3));
+        let single = Array2::<f64>::zeros((1, 3));
+        assert_eq!(
+            check_symmetry(&empty.view(), &Euclidean, 0.0, 10, 0, 1).checked,
+            0
+        );
+        assert_eq!(
+            check_symmetry(&single.view(), &Euclidean, 0.0, 10, 0, 1).checked,
+            0
+        );
+        assert!(check_identity(&single.view(), &Euclidean, 0.0, 10, 0, 1).is_ok());
+        assert_eq!(
+            check_identity(&empty.view(), &Euclidean, 0.0, 10, 0, 1).checked,
+            0
+        );
+    }
+}
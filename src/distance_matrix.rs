@@ -0,0 +1,350 @@
+use ndarray::{Array1, Array2, ArrayView2};
+use num::Float;
+use std::iter::Sum;
+
+use crate::{condensed_index, condensed_len, pdist, Distance, Hellinger};
+
+/// A symmetric distance matrix over `n` points, stored as its condensed upper triangle.
+///
+/// The diagonal is implicitly zero. Entries are laid out in the same order as [`pdist`], so a
+/// `DistanceMatrix` can be built from and converted back to SciPy-style condensed vectors.
+///
+/// # Example
+/// ```
+/// use ndarray::arr2;
+/// use fast_distances::*;
+///
+/// let data = arr2(&[[0.0, 0.0], [3.0, 4.0], [1.0, 0.0]]);
+/// let dm = DistanceMatrix::from_data(&data.view(), &Euclidean);
+///
+/// assert_eq!(dm.get(0, 1), 5.0);
+/// assert_eq!(dm.get(1, 0), 5.0);
+/// assert_eq!(dm.get(2, 2), 0.0);
+/// assert_eq!(dm.row_argmin(0), Some(2));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistanceMatrix<T> {
+    n: usize,
+    condensed: Array1<T>,
+}
+
+impl<T> DistanceMatrix<T>
+where
+    T: Float,
+{
+    /// Computes the distance matrix between the rows of `data` with [`pdist`].
+    pub fn from_data<M>(data: &ArrayView2<T>, metric: &M) -> Self
+    where
+        M: Distance<T> + ?Sized,
+    {
+        Self {
+            n: data.nrows(),
+            condensed: pdist(data, metric),
+        }
+    }
+
+    /// Wraps a condensed distance vector, as returned by [`pdist`].
+    ///
+    /// An empty vector is interpreted as a matrix over zero points.
+    ///
+    /// # Panics
+    /// Panics if the length of `condensed` is not `n * (n - 1) / 2` for any `n`.
+    pub fn from_condensed(condensed: Array1<T>) -> Self {
+        let mut n = 0;
+        while condensed_len(n) < condensed.len() {
+            n += 1;
+        }
+        assert_eq!(
+            condensed_len(n),
+            condensed.len(),
+            "Condensed length must be n * (n - 1) / 2."
+        );
+        Self { n, condensed }
+    }
+
+    /// Builds a distance matrix from the upper triangle of a square matrix.
+    ///
+    /// # Panics
+    /// Panics if `square` is not square.
+    pub fn from_square(square: &ArrayView2<T>) -> Self {
+        assert_eq!(
+            square.nrows(),
+            square.ncols(),
+            "Distance matrix must be square."
+        );
+
+        let n = square.nrows();
+        let mut condensed = Vec::with_capacity(condensed_len(n));
+        for i in 0..n {
+            for j in (i + 1)..n {
+                condensed.push(square[(i, j)]);
+            }
+        }
+
+        Self {
+            n,
+            condensed: Array1::from_vec(condensed),
+        }
+    }
+
+    /// Returns the number of points.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// Returns `true` if the matrix covers no points.
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns the distance between points `i` and `j`; zero when `i == j`.
+    ///
+    /// # Panics
+    /// Panics if `i` or `j` is out of range.
+    pub fn get(&self, i: usize, j: usize) -> T {
+        if i == j {
+            assert!(
+                i < self.n,
+                "Indices must be smaller than the number of points."
+            );
+            T::zero()
+        } else {
+            self.condensed[condensed_index(self.n, i, j)]
+        }
+    }
+
+    /// Returns the condensed upper triangle.
+    pub fn condensed(&self) -> &Array1<T> {
+        &self.condensed
+    }
+
+    /// Consumes the matrix and returns the condensed upper triangle.
+    pub fn into_condensed(self) -> Array1<T> {
+        self.condensed
+    }
+
+    /// Expands the matrix into a full square `Array2`.
+    pub fn to_square(&self) -> Array2<T> {
+        Array2::from_shape_fn((self.n, self.n), |(i, j)| self.get(i, j))
+    }
+
+    /// Returns the distances from point `i` to every point, including itself.
+    pub fn row(&self, i: usize) -> Array1<T> {
+        (0..self.n).map(|j| self.get(i, j)).collect()
+    }
+
+    /// Iterates over all rows of the matrix.
+    pub fn rows(&self) -> impl Iterator<Item = Array1<T>> + '_ {
+        (0..self.n).map(move |i| self.row(i))
+    }
+
+    /// Returns the index and distance of the closest other point to `i`.
+    ///
+    /// The diagonal and NaN entries are ignored; `None` is returned if there is no other point.
+    pub fn row_min(&self, i: usize) -> Option<(usize, T)> {
+        self.row_extremum(i, |candidate, best| candidate < best)
+    }
+
+    /// Returns the index and distance of the farthest other point from `i`.
+    ///
+    /// The diagonal and NaN entries are ignored; `None` is returned if there is no other point.
+    pub fn row_max(&self, i: usize) -> Option<(usize, T)> {
+        self.row_extremum(i, |candidate, best| candidate > best)
+    }
+
+    /// Returns the index of the closest other point to `i`. See [`DistanceMatrix::row_min`].
+    pub fn row_argmin(&self, i: usize) -> Option<usize> {
+        self.row_min(i).map(|(j, _)| j)
+    }
+
+    /// Returns the index of the farthest other point from `i`. See [`DistanceMatrix::row_max`].
+    pub fn row_argmax(&self, i: usize) -> Option<usize> {
+        self.row_max(i).map(|(j, _)| j)
+    }
+
+    fn row_extremum<F>(&self, i: usize, better: F) -> Option<(usize, T)>
+    where
+        F: Fn(T, T) -> bool,
+    {
+        assert!(
+            i < self.n,
+            "Indices must be smaller than the number of points."
+        );
+
+        let mut best: Option<(usize, T)> = None;
+        for j in (0..self.n).filter(|&j| j != i) {
+            let d = self.get(i, j);
+            if d.is_nan() {
+                continue;
+            }
+            match best {
+                Some((_, b)) if !better(d, b) => {}
+                _ => best = Some((j, d)),
+            }
+        }
+        best
+    }
+}
+
+impl<T> From<DistanceMatrix<T>> for Array2<T>
+where
+    T: Float,
+{
+    fn from(matrix: DistanceMatrix<T>) -> Self {
+        matrix.to_square()
+    }
+}
+
+/// Computes the full symmetric distance matrix between the rows of `data`, using the
+/// [`hellinger`](crate::hellinger) distance unless another `metric` is given.
+///
+/// This mirrors UMAP's `pairwise_special_metric`, which is used for discrete and label data
+/// whose rows are (unnormalized) distributions. Each unordered pair is computed once, as in
+/// [`pdist`], and the diagonal is zero.
+///
+/// # Example
+/// ```
+/// use ndarray::arr2;
+/// use fast_distances::*;
+///
+/// let counts = arr2(&[[1.0, 0.0], [0.0, 2.0], [3.0, 0.0]]);
+/// let d = pairwise_special_metric(&counts.view(), None);
+/// assert_eq!(d[(0, 2)], 0.0);
+/// assert_eq!(d[(0, 1)], d[(1, 0)]);
+///
+/// let d = pairwise_special_metric(&counts.view(), Some(&Manhattan));
+/// assert_eq!(d[(0, 2)], 2.0);
+/// ```
+pub fn pairwise_special_metric<T>(
+    data: &ArrayView2<T>,
+    metric: Option<&dyn Distance<T>>,
+) -> Array2<T>
+where
+    T: Float + Sum,
+{
+    let condensed = match metric {
+        Some(metric) => pdist(data, metric),
+        None => pdist(data, &Hellinger),
+    };
+    DistanceMatrix::from_condensed(condensed).to_square()
+}
+
+/// Parallel version of [`pairwise_special_metric`], available with the `parallel` feature.
+///
+/// The upper triangle is computed with [`par_pdist`](crate::par_pdist), so the result is
+/// identical to [`pairwise_special_metric`].
+#[cfg(feature = "parallel")]
+pub fn par_pairwise_special_metric<T>(
+    data: &ArrayView2<T>,
+    metric: Option<&(dyn Distance<T> + Sync)>,
+) -> Array2<T>
+where
+    T: Float + Sum + Send + Sync,
+{
+    par_pairwise_special_metric_with(data, metric, crate::Parallelism::Global)
+}
+
+/// [`par_pairwise_special_metric`] on the thread pool selected by `parallelism`.
+#[cfg(feature = "parallel")]
+pub fn par_pairwise_special_metric_with<T>(
+    data: &ArrayView2<T>,
+    metric: Option<&(dyn Distance<T> + Sync)>,
+    parallelism: crate::Parallelism,
+) -> Array2<T>
+where
+    T: Float + Sum + Send + Sync,
+{
+    let condensed = match metric {
+        Some(metric) => crate::par_pdist_with(data, metric, parallelism),
+        None => crate::par_pdist_with(data, &Hellinger, parallelism),
+    };
+    DistanceMatrix::from_condensed(condensed).to_square()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use ndarray::{arr1, arr2};
+
+    #[test]
+    fn test_from_data_matches_cdist() {
+        let data = arr2(&[[1.0, 2.0], [0.0, 5.0], [3.0, -1.0], [2.0, 2.0]]);
+        let dm = DistanceMatrix::from_data(&data.view(), &Manhattan);
+
+        assert_eq!(dm.len(), 4);
+        assert_eq!(
+            dm.to_square(),
+            cdist(&data.view(), &data.view(), &Manhattan)
+        );
+    }
+
+    #[test]
+    fn test_square_roundtrip() {
+        let square = arr2(&[[0.0, 1.0, 2.0], [1.0, 0.0, 3.0], [2.0, 3.0, 0.0]]);
+        let dm = DistanceMatrix::from_square(&square.view());
+
+        assert_eq!(dm.condensed(), &arr1(&[1.0, 2.0, 3.0]));
+        assert_eq!(Array2::from(dm.clone()), square);
+        assert_eq!(DistanceMatrix::from_condensed(dm.into_condensed()).len(), 3);
+    }
+
+    #[test]
+    fn test_row_accessors() {
+        let dm = DistanceMatrix::from_condensed(arr1(&[4.0, 1.0, 2.0]));
+
+        assert_eq!(dm.row(0), arr1(&[0.0, 4.0, 1.0]));
+        assert_eq!(dm.rows().count(), 3);
+        assert_eq!(dm.row_min(0), Some((2, 1.0)));
+        assert_eq!(dm.row_max(0), Some((1, 4.0)));
+        assert_eq!(dm.row_argmin(1), Some(2));
+        assert_eq!(dm.row_argmax(2), Some(1));
+    }
+
+    #[test]
+    fn test_single_point() {
+        let data = arr2(&[[1.0, 2.0]]);
+        let dm = DistanceMatrix::from_data(&data.view(), &Euclidean);
+
+        assert_eq!(dm.get(0, 0), 0.0);
+        assert_eq!(dm.row_min(0), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Condensed length must be n * (n - 1) / 2.")]
+    fn test_from_condensed_invalid_length() {
+        DistanceMatrix::from_condensed(arr1(&[1.0, 2.0]));
+    }
+
+    #[test]
+    fn test_pairwise_special_metric() {
+        let data = arr2(&[[0.2, 0.8, 0.0], [0.5, 0.25, 0.25], [0.1, 0.1, 0.8]]);
+        let d = pairwise_special_metric(&data.view(), None);
+
+        assert_eq!(d.dim(), (3, 3));
+        assert_eq!(d, d.t());
+        assert_eq!(d[(1, 1)], 0.0);
+        assert_eq!(d[(0, 2)], hellinger(&data.row(0), &data.row(2)));
+
+        let d = pairwise_special_metric(&data.view(), Some(&Euclidean));
+        assert_eq!(d, cdist(&data.view(), &data.view(), &Euclidean.as_fn()));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_par_pairwise_special_metric() {
+        let data = Array2::from_shape_fn((40, 5), |(i, j)| ((i * 3 + j * 7) % 11) as f64 + 0.5);
+        assert_eq!(
+            par_pairwise_special_metric(&data.view(), None),
+            pairwise_special_metric(&data.view(), None)
+        );
+        assert_eq!(
+            par_pairwise_special_metric(&data.view(), Some(&Chebyshev)),
+            pairwise_special_metric(&data.view(), Some(&Chebyshev))
+        );
+        assert_eq!(
+            par_pairwise_special_metric_with(&data.view(), None, Parallelism::Threads(2)),
+            pairwise_special_metric(&data.view(), None)
+        );
+    }
+}
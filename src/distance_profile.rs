@@ -0,0 +1,231 @@
+use ndarray::{Array1, ArrayView1, Zip};
+use num::Float;
+
+use crate::Distance;
+
+/// Computes the distance from `query` to every window of `series` of the same length.
+///
+/// Entry `i` of the result is `metric.distance(query, series[i..i + len(query)])`, so the
+/// profile has `len(series) - len(query) + 1` entries. Its minima locate the best matches of
+/// the query, and the full set of profiles is the matrix profile used for motif and anomaly
+/// detection. For the z-normalized Euclidean distance, [`distance_profile_znorm`] avoids
+/// evaluating each window separately.
+///
+/// # Panics
+/// Panics if `query` is empty or longer than `series`.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let series = arr1(&[0.0, 1.0, 5.0, 6.0, 1.0, 0.0]);
+/// let query = arr1(&[5.0, 6.0]);
+/// let profile = distance_profile(&series.view(), &query.view(), &Manhattan);
+/// assert_eq!(profile, arr1(&[10.0, 5.0, 0.0, 6.0, 10.0]));
+/// ```
+pub fn distance_profile<T, M>(
+    series: &ArrayView1<T>,
+    query: &ArrayView1<T>,
+    metric: &M,
+) -> Array1<T>
+where
+    T: Float,
+    M: Distance<T> + ?Sized,
+{
+    check_query(series, query);
+    series
+        .windows(query.len())
+        .into_iter()
+        .map(|window| metric.distance(query, &window))
+        .collect()
+}
+
+/// Computes the z-normalized Euclidean distance from `query` to every window of `series`.
+///
+/// Each window and the query are shifted to zero mean and scaled to unit standard deviation
+/// before being compared, so matches are found regardless of offset and amplitude. As in
+/// MASS, the distances follow from the sliding dot products `QT_i` and the window means and
+/// standard deviations, which come from cumulative sums in `O(len(series))`:
+///
+/// ..math::
+///    d_i = \sqrt{2 m \left(1 - \frac{QT_i - m \mu_q \mu_i}{m \sigma_q \sigma_i}\right)}
+///
+/// where `m` is the length of the query. The dot products cost `O(len(series) * m)`, or
+/// `O(len(series) log len(series))` with the `fft` feature for queries of at least 64
+/// elements. Constant windows have no shape: their distance to a constant query is `0` and to
+/// any other query `sqrt(m)`.
+///
+/// # Panics
+/// Panics if `query` is empty or longer than `series`.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let series = arr1(&[0.0f64, 3.0, 0.0, 10.0, 20.0, 30.0, 0.0]);
+/// let query = arr1(&[1.0, 2.0, 3.0]);
+/// let profile = distance_profile_znorm(&series.view(), &query.view());
+/// // The window [10, 20, 30] has the same shape as the query.
+/// assert!(profile[3].abs() < 1e-6);
+/// assert!(profile.iter().all(|&d| d >= profile[3]));
+/// ```
+pub fn distance_profile_znorm<T>(series: &ArrayView1<T>, query: &ArrayView1<T>) -> Array1<T>
+where
+    T: Float,
+{
+    check_query(series, query);
+    let (n, m) = (series.len(), query.len());
+    let len = T::from(m).unwrap();
+    let two = T::one() + T::one();
+
+    // Centering on the series mean keeps the cumulative sums from cancelling catastrophically
+    // for series with a large offset.
+    let offset = series.fold(T::zero(), |acc, &v| acc + v) / T::from(n).unwrap();
+    let mut sums = Vec::with_capacity(n + 1);
+    let mut squares = Vec::with_capacity(n + 1);
+    let (mut sum, mut square) = (T::zero(), T::zero());
+    sums.push(sum);
+    squares.push(square);
+    for &v in series {
+        let v = v - offset;
+        sum = sum + v;
+        square = square + v * v;
+        sums.push(sum);
+        squares.push(square);
+    }
+
+    let (mu_q, sigma_q) = mean_std(
+        query.fold(T::zero(), |acc, &v| acc + v),
+        query.fold(T::zero(), |acc, &v| acc + v * v),
+        len,
+    );
+    let dots = sliding_dot_products(series, query);
+    // The dot products use the raw series; subtracting `offset * m * mu_q` turns them into
+    // those of the centered windows.
+    Array1::from_shape_fn(n - m + 1, |i| {
+        let (mu, sigma) = mean_std(sums[i + m] - sums[i], squares[i + m] - squares[i], len);
+        match (sigma_q.is_zero(), sigma.is_zero()) {
+            (true, true) => T::zero(),
+            (true, false) | (false, true) => len.sqrt(),
+            (false, false) => {
+                let dot = dots[i] - offset * len * mu_q;
+                let correlation = (dot - len * mu_q * mu) / (len * sigma_q * sigma);
+                (two * len * (T::one() - correlation)).max(T::zero()).sqrt()
+            }
+        }
+    })
+}
+
+fn check_query<T>(series: &ArrayView1<T>, query: &ArrayView1<T>) {
+    assert!(
+        !query.is_empty() && query.len() <= series.len(),
+        "Query must be non-empty and no longer than the series."
+    );
+}
+
+/// Returns the mean and standard deviation of `len` values from their sum and sum of squares,
+/// with variances at the rounding level of the values treated as zero.
+fn mean_std<T>(sum: T, square: T, len: T) -> (T, T)
+where
+    T: Float,
+{
+    let mean = sum / len;
+    let mean_square = square / len;
+    let variance = mean_square - mean * mean;
+    if variance <= T::epsilon() * mean_square * T::from(16.0).unwrap() {
+        (mean, T::zero())
+    } else {
+        (mean, variance.sqrt())
+    }
+}
+
+/// Returns `sum_k query[k] * series[i + k]` for every window start `i`.
+fn sliding_dot_products<T>(series: &ArrayView1<T>, query: &ArrayView1<T>) -> Vec<T>
+where
+    T: Float,
+{
+    #[cfg(feature = "fft")]
+    if query.len() >= crate::distances::FFT_MIN_LEN {
+        let lags = crate::distances::fft_cross_correlation(series, query);
+        return lags[query.len() - 1..].to_vec();
+    }
+
+    series
+        .windows(query.len())
+        .into_iter()
+        .map(|window| {
+            Zip::from(&window)
+                .and(query)
+                .fold(T::zero(), |acc, &s, &q| acc + s * q)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+    use crate::*;
+    use ndarray::arr1;
+
+    fn znormalize(v: &ArrayView1<f64>) -> Array1<f64> {
+        let mean = v.mean().unwrap();
+        let std = v.std(0.0);
+        v.mapv(|x| (x - mean) / std)
+    }
+
+    #[test]
+    fn test_distance_profile_matches_windows() {
+        let series = arr1(&[1.0, 4.0, 2.0, 8.0, 5.0, 7.0]);
+        let query = arr1(&[2.0, 8.0, 5.0]);
+        let profile = distance_profile(&series.view(), &query.view(), &Euclidean);
+        assert_eq!(profile.len(), 4);
+        for (i, &d) in profile.iter().enumerate() {
+            let window = series.slice(ndarray::s![i..i + 3]);
+            assert_eq!(d, euclidean(&query.view(), &window));
+        }
+        assert_eq!(profile[2], 0.0);
+
+        let whole = distance_profile(&series.view(), &series.view(), &Euclidean);
+        assert_eq!(whole, arr1(&[0.0]));
+    }
+
+    #[test]
+    fn test_znorm_matches_brute_force() {
+        let mut rng = SplitMix64::new(21);
+        for (n, m) in [(10, 2), (50, 7), (200, 64), (300, 100)] {
+            // A large offset exercises the centering of the cumulative sums.
+            let series: Array1<f64> = (0..n).map(|_| 1e4 + rng.next_gaussian()).collect();
+            let query: Array1<f64> = (0..m).map(|_| rng.next_gaussian()).collect();
+            let metric = from_fn(|a: &ArrayView1<f64>, b: &ArrayView1<f64>| {
+                euclidean(&znormalize(a).view(), &znormalize(b).view())
+            });
+            let expected = distance_profile(&series.view(), &query.view(), &metric);
+            let profile = distance_profile_znorm(&series.view(), &query.view());
+            // The square root amplifies rounding near zero, so compare squared distances.
+            for (d, e) in profile.iter().zip(&expected) {
+                assert!((d * d - e * e).abs() < 1e-8);
+            }
+        }
+    }
+
+    #[test]
+    fn test_znorm_constant_windows() {
+        let series = arr1(&[3.0f32, 3.0, 3.0, 1.0, 2.0]);
+        let profile = distance_profile_znorm(&series.view(), &arr1(&[5.0, 5.0]).view());
+        assert_eq!(profile, arr1(&[0.0, 0.0, 2.0f32.sqrt(), 2.0f32.sqrt()]));
+
+        let profile = distance_profile_znorm(&series.view(), &arr1(&[1.0, 2.0]).view());
+        assert_eq!(profile[0], 2.0f32.sqrt());
+        assert!(profile[3].abs() < 1e-3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Query must be non-empty and no longer than the series.")]
+    fn test_query_too_long() {
+        let series = arr1(&[1.0, 2.0]);
+        distance_profile_znorm(&series.view(), &arr1(&[1.0, 2.0, 3.0]).view());
+    }
+}
@@ -0,0 +1,85 @@
+use ndarray::{ArrayView1, Zip};
+use num::Float;
+
+/// Computes the adjusted (mean-centered) cosine distance between `x` and `y`.
+///
+/// Both vectors are centered by the same per-dimension means `μ` before taking the
+/// [`cosine`](crate::cosine) distance:
+///
+/// ..math::
+///     \text{adjusted\_cosine}(x, y) = 1 - \frac{\sum (x_i - \mu_i)(y_i - \mu_i)}{\sqrt{\sum (x_i - \mu_i)^2} \cdot \sqrt{\sum (y_i - \mu_i)^2}}
+///
+/// In item-based collaborative filtering, `x` and `y` are the rating columns of two items and
+/// `μ` holds the mean rating of each user, which removes each user's rating scale. This
+/// differs from [`correlation`](crate::correlation), which centers each vector by its own
+/// mean. [`AdjustedCosine::fit`](crate::AdjustedCosine::fit) computes the means from a data
+/// matrix. The zero-norm conventions of `cosine` apply to the centered vectors.
+///
+/// # Panics
+/// Panics if `x`, `y` and `means` do not all have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[5.0, 3.0, 4.0]);
+/// let y = arr1(&[4.0, 1.0, 5.0]);
+/// let means = arr1(&[4.0, 2.0, 4.0]);
+/// // Centered: (1, 1, 0) and (0, -1, 1).
+/// assert!((adjusted_cosine(&x.view(), &y.view(), &means.view()) - 1.5f64).abs() < 1e-12);
+/// ```
+pub fn adjusted_cosine<T>(x: &ArrayView1<T>, y: &ArrayView1<T>, means: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+    assert_eq!(
+        x.len(),
+        means.len(),
+        "Means must have one entry per coordinate."
+    );
+
+    let (dot, norm_x, norm_y) = Zip::from(x).and(y).and(means).fold(
+        (T::zero(), T::zero(), T::zero()),
+        |(dot, nx, ny), &xi, &yi, &mi| {
+            let (cx, cy) = (xi - mi, yi - mi);
+            (dot + cx * cy, nx + cx * cx, ny + cy * cy)
+        },
+    );
+
+    if norm_x.is_zero() && norm_y.is_zero() {
+        T::zero()
+    } else if norm_x.is_zero() || norm_y.is_zero() {
+        T::one()
+    } else {
+        T::one() - dot / (norm_x.sqrt() * norm_y.sqrt())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cosine;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_adjusted_cosine_matches_centered_cosine() {
+        let x = arr1(&[0.5_f32, -1.25, 3.0, 2.75]);
+        let y = arr1(&[1.5_f32, 0.75, -2.0, 2.5]);
+        let means = arr1(&[0.25_f32, 1.0, -0.5, 2.0]);
+        assert_eq!(
+            adjusted_cosine(&x.view(), &y.view(), &means.view()),
+            cosine(&(&x - &means).view(), &(&y - &means).view())
+        );
+        let zeros = arr1(&[0.0_f32; 4]);
+        assert_eq!(
+            adjusted_cosine(&x.view(), &y.view(), &zeros.view()),
+            cosine(&x.view(), &y.view())
+        );
+        assert_eq!(
+            adjusted_cosine(&means.view(), &y.view(), &means.view()),
+            1.0
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use ndarray::{ArrayView1, Zip};
+use num::Float;
+
+/// Computes the angular distance between two vectors `x` and `y`.
+///
+/// The angle between the vectors is normalized to `[0, 1]`:
+///
+/// ..math::
+///     \text{angular}(x, y) = \frac{1}{\pi} \arccos \frac{\sum x_i \cdot y_i}{\sqrt{\sum x_i^2} \cdot \sqrt{\sum y_i^2}}
+///
+/// This is the geodesic distance between the directions of `x` and `y` on the unit sphere,
+/// scaled by `1 / π`. Unlike [`cosine`](crate::cosine), it satisfies the triangle inequality.
+/// The cosine is clamped to `[-1, 1]` before taking the arccosine, so rounding never produces
+/// NaN. Zero vectors follow the convention of `cosine`: two zero vectors are at distance `0.0`,
+/// and a zero vector is treated as orthogonal to any other vector, at distance `0.5`.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[1.0, 0.0]);
+/// let y = arr1(&[-1.0, 1.0]);
+/// assert!((angular(&x.view(), &y.view()) - 0.75f64).abs() < 1e-12);
+/// ```
+pub fn angular<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+
+    let (dot, norm_x, norm_y) = Zip::from(x).and(y).fold(
+        (T::zero(), T::zero(), T::zero()),
+        |(dot, nx, ny), &xi, &yi| (dot + xi * yi, nx + xi * xi, ny + yi * yi),
+    );
+
+    if norm_x.is_zero() && norm_y.is_zero() {
+        T::zero()
+    } else if norm_x.is_zero() || norm_y.is_zero() {
+        T::from(0.5).unwrap()
+    } else {
+        clamped_cosine(dot, norm_x, norm_y).acos() / T::from(std::f64::consts::PI).unwrap()
+    }
+}
+
+/// Returns the cosine similarity from the dot product and the squared norms, clamped to
+/// `[-1, 1]`.
+pub(crate) fn clamped_cosine<T: Float>(dot: T, norm_x: T, norm_y: T) -> T {
+    (dot / (norm_x.sqrt() * norm_y.sqrt()))
+        .max(-T::one())
+        .min(T::one())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_angular_basic() {
+        let x = arr1(&[1.0_f64, 2.0, 3.0]);
+        let y = arr1(&[4.0_f64, 5.0, 6.0]);
+        let expected =
+            (32.0_f64 / (14.0_f64.sqrt() * 77.0_f64.sqrt())).acos() / std::f64::consts::PI;
+        assert!((angular(&x.view(), &y.view()) - expected).abs() < 1e-15);
+        assert_eq!(angular(&x.view(), &(&x * 3.0).view()), 0.0);
+        assert_eq!(angular(&x.view(), &(&x * -1.0).view()), 1.0);
+    }
+
+    #[test]
+    fn test_angular_zero_norm() {
+        let zero = arr1(&[0.0_f32, 0.0, 0.0]);
+        let y = arr1(&[1.0_f32, 2.0, 3.0]);
+        assert_eq!(angular(&zero.view(), &y.view()), 0.5);
+        assert_eq!(angular(&zero.view(), &zero.view()), 0.0);
+    }
+}
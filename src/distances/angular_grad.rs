@@ -0,0 +1,101 @@
+use ndarray::{Array1, ArrayView1, Zip};
+use num::Float;
+
+use super::angular::clamped_cosine;
+
+/// Computes the angular distance and its gradient between two vectors `x` and `y`.
+///
+/// With `c` the cosine similarity of `x` and `y`, the distance is `arccos(c) / π`, as in
+/// [`angular`](crate::angular), and its gradient with respect to `x` is
+///
+/// ..math::
+///     \frac{\partial}{\partial x_i} = -\frac{1}{\pi \sqrt{1 - c^2}} \left( \frac{y_i}{\lVert x \rVert \lVert y \rVert} - \frac{c \, x_i}{\lVert x \rVert^2} \right)
+///
+/// The arccosine is not differentiable at `c = ±1`, where the vectors are parallel; there and
+/// whenever either vector has a norm of zero, the gradient is zero.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Returns
+/// * A tuple containing:
+///     - The angular distance between `x` and `y`.
+///     - The gradient of the angular distance with respect to `x`.
+pub fn angular_grad<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> (T, Array1<T>)
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+
+    let (dot, norm_x, norm_y) = Zip::from(x).and(y).fold(
+        (T::zero(), T::zero(), T::zero()),
+        |(dot, nx, ny), &xi, &yi| (dot + xi * yi, nx + xi * xi, ny + yi * yi),
+    );
+
+    if norm_x.is_zero() && norm_y.is_zero() {
+        return (T::zero(), Array1::zeros(x.dim()));
+    } else if norm_x.is_zero() || norm_y.is_zero() {
+        return (T::from(0.5).unwrap(), Array1::zeros(x.dim()));
+    }
+
+    let pi = T::from(std::f64::consts::PI).unwrap();
+    let cosine = clamped_cosine(dot, norm_x, norm_y);
+    let dist = cosine.acos() / pi;
+    let sine = (T::one() - cosine * cosine).sqrt();
+    if sine.is_zero() {
+        return (dist, Array1::zeros(x.dim()));
+    }
+
+    let norms = norm_x.sqrt() * norm_y.sqrt();
+    let scale = -T::one() / (pi * sine);
+    let grad = Zip::from(x)
+        .and(y)
+        .map_collect(|&xi, &yi| scale * (yi / norms - cosine * xi / norm_x));
+    (dist, grad)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::angular;
+    use ndarray::arr1;
+
+    fn numeric_grad<F: Fn(&ArrayView1<f64>) -> f64>(f: F, p: &Array1<f64>) -> Array1<f64> {
+        let h = 1e-6;
+        Array1::from_shape_fn(p.len(), |i| {
+            let (mut hi, mut lo) = (p.clone(), p.clone());
+            hi[i] += h;
+            lo[i] -= h;
+            (f(&hi.view()) - f(&lo.view())) / (2.0 * h)
+        })
+    }
+
+    #[test]
+    fn test_angular_grad_matches_finite_differences() {
+        let x = arr1(&[0.5, -1.25, 3.0, 0.0, 2.75, -0.125, 1.0]);
+        let y = arr1(&[1.5, 0.75, -2.0, 0.0, 2.5, 0.375, -4.0]);
+        let (dist, grad) = angular_grad(&x.view(), &y.view());
+        assert_eq!(dist, angular(&x.view(), &y.view()));
+        let expected = numeric_grad(|x| angular(x, &y.view()), &x);
+        assert!(grad
+            .iter()
+            .zip(&expected)
+            .all(|(g, e)| (g - e).abs() < 1e-6));
+        // Scaling `x` does not change the angle.
+        assert!(grad.dot(&x).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_angular_grad_degenerate() {
+        let x = arr1(&[3.0_f32, 4.0, 0.0]);
+        let zero = arr1(&[0.0_f32, 0.0, 0.0]);
+        assert_eq!(
+            angular_grad(&x.view(), &(&x * 2.0).view()),
+            (0.0, arr1(&[0.0, 0.0, 0.0]))
+        );
+        assert_eq!(
+            angular_grad(&zero.view(), &x.view()),
+            (0.5, arr1(&[0.0, 0.0, 0.0]))
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use ndarray::{ArrayView1, Zip};
+use num::Float;
+
+/// Computes the Bhattacharyya coefficient between two vectors of nonnegative weights.
+///
+/// ..math::
+///    BC(x, y) = \frac{\sum_i \sqrt{x_i y_i}}{\sqrt{\sum_i x_i \cdot \sum_i y_i}}
+///
+/// The inputs are normalized by their sums, as in [`hellinger`](crate::hellinger), so the
+/// coefficient is `1` for proportional vectors and `0` for vectors with disjoint supports. With
+/// the same zero-norm semantics as `hellinger`, it is `1` if both vectors are zero and `0` if
+/// only one of them is. It is related to the Hellinger distance by `H^2 = 1 - BC`.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[1.0f64, 0.0, 3.0]);
+/// let y = arr1(&[2.0, 0.0, 6.0]);
+/// assert!((bhattacharyya_coefficient(&x.view(), &y.view()) - 1.0).abs() < 1e-12);
+/// ```
+pub fn bhattacharyya_coefficient<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+
+    let (result, l1_norm_x, l1_norm_y) = Zip::from(x).and(y).fold(
+        (T::zero(), T::zero(), T::zero()),
+        |(result, nx, ny), &xi, &yi| (result + (xi * yi).sqrt(), nx + xi, ny + yi),
+    );
+
+    if l1_norm_x.is_zero() && l1_norm_y.is_zero() {
+        T::one()
+    } else if l1_norm_x.is_zero() || l1_norm_y.is_zero() {
+        T::zero()
+    } else {
+        // Rounding can push the coefficient of proportional vectors slightly above one.
+        (result / (l1_norm_x * l1_norm_y).sqrt()).min(T::one())
+    }
+}
+
+/// Computes the Bhattacharyya distance, the negative logarithm of the
+/// [`bhattacharyya_coefficient`].
+///
+/// ..math::
+///    D_B(x, y) = -\ln BC(x, y)
+///
+/// The distance is `0` for proportional (or both zero) vectors and infinite for vectors with
+/// disjoint supports or when exactly one vector is zero. It does not satisfy the triangle
+/// inequality; [`hellinger`](crate::hellinger) is the related metric.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[0.5, 0.5]);
+/// let y = arr1(&[1.0, 0.0]);
+/// assert!((bhattacharyya(&x.view(), &y.view()) - 0.5_f64.sqrt().ln().abs()).abs() < 1e-12);
+/// assert_eq!(bhattacharyya(&y.view(), &arr1(&[0.0, 1.0]).view()), f64::INFINITY);
+/// ```
+pub fn bhattacharyya<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    -bhattacharyya_coefficient(x, y).ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hellinger;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_known_values() {
+        let x = arr1(&[0.1, 0.2, 0.7]);
+        let y = arr1(&[0.3, 0.3, 0.4]);
+        let bc: f64 = (0.03f64).sqrt() + (0.06f64).sqrt() + (0.28f64).sqrt();
+        assert!((bhattacharyya_coefficient(&x.view(), &y.view()) - bc).abs() < 1e-12);
+        assert!((bhattacharyya(&x.view(), &y.view()) + bc.ln()).abs() < 1e-12);
+        assert_eq!(bhattacharyya(&x.view(), &x.view()), 0.0);
+    }
+
+    #[test]
+    fn test_relation_to_hellinger() {
+        let x = arr1(&[1.0, 4.0, 0.0, 2.0]);
+        let y = arr1(&[3.0, 1.0, 1.0, 0.0]);
+        // `hellinger` returns 1 - BC directly.
+        let h = hellinger(&x.view(), &y.view());
+        assert!((1.0 - bhattacharyya_coefficient(&x.view(), &y.view()) - h).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_zero_norms() {
+        let zeros = arr1(&[0.0f32, 0.0]);
+        let x = arr1(&[1.0f32, 2.0]);
+        assert_eq!(bhattacharyya_coefficient(&zeros.view(), &zeros.view()), 1.0);
+        assert_eq!(bhattacharyya(&zeros.view(), &zeros.view()), 0.0);
+        assert_eq!(bhattacharyya_coefficient(&zeros.view(), &x.view()), 0.0);
+        assert_eq!(bhattacharyya(&x.view(), &zeros.view()), f32::INFINITY);
+    }
+}
@@ -0,0 +1,30 @@
+//! The agreement counts shared by the dissimilarities between binary vectors.
+
+use ndarray::{ArrayView1, Zip};
+use num::Float;
+
+/// Treats non-zero entries as `true` and returns the number of positions where both `x` and
+/// `y` are `true` and the number of positions where exactly one of them is.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+pub(crate) fn binary_counts<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> (T, T)
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+
+    Zip::from(x).and(y).fold(
+        (T::zero(), T::zero()),
+        |(true_true, not_equal), &xi, &yi| {
+            let (x_true, y_true) = (xi != T::zero(), yi != T::zero());
+            if x_true && y_true {
+                (true_true + T::one(), not_equal)
+            } else if x_true != y_true {
+                (true_true, not_equal + T::one())
+            } else {
+                (true_true, not_equal)
+            }
+        },
+    )
+}
@@ -0,0 +1,161 @@
+/// Counts the set bits of `f(x[i], y[i])` over all words.
+///
+/// Four independent accumulators let the compiler keep several `popcnt` instructions (or,
+/// without hardware popcount, several bit-twiddling sequences) in flight at once.
+fn popcount_with(x: &[u64], y: &[u64], f: impl Fn(u64, u64) -> u64) -> usize {
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+
+    let mut counts = [0usize; 4];
+    let (xc, yc) = (x.chunks_exact(4), y.chunks_exact(4));
+    let (xr, yr) = (xc.remainder(), yc.remainder());
+    for (a, b) in xc.zip(yc) {
+        for k in 0..4 {
+            counts[k] += f(a[k], b[k]).count_ones() as usize;
+        }
+    }
+    for (&a, &b) in xr.iter().zip(yr) {
+        counts[0] += f(a, b).count_ones() as usize;
+    }
+    counts.iter().sum()
+}
+
+/// Computes the Hamming distance between two bit-packed vectors, i.e. the number of bit
+/// positions in which they differ.
+///
+/// This is the standard representation for binary fingerprints: bit `k` of the vector is bit
+/// `k % 64` of word `k / 64`. Padding bits beyond the logical length must be zero in both
+/// inputs. Differences are counted with `u64::count_ones`, which compiles to a single `popcnt`
+/// instruction when the target supports it (e.g. with `-C target-cpu=native`).
+///
+/// # Arguments
+/// * `x` - The words of the first bit vector.
+/// * `y` - The words of the second bit vector.
+///
+/// # Returns
+/// The number of differing bits.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same number of words.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// let x = [0b1011u64, u64::MAX];
+/// let y = [0b0110u64, u64::MAX];
+/// assert_eq!(hamming_bits(&x, &y), 3);
+/// ```
+pub fn hamming_bits(x: &[u64], y: &[u64]) -> usize {
+    popcount_with(x, y, |a, b| a ^ b)
+}
+
+/// Computes the Jaccard distance between two bit-packed vectors treated as sets of bit positions.
+///
+/// ..math::
+///    d_J(x, y) = 1 - \frac{|x \wedge y|}{|x \vee y|}
+///
+/// As for [`jaccard`](crate::jaccard), two empty vectors have a distance of `0.0`.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same number of words.
+pub fn jaccard_bits(x: &[u64], y: &[u64]) -> f64 {
+    let union = popcount_with(x, y, |a, b| a | b);
+    if union == 0 {
+        return 0.0;
+    }
+    let intersection = popcount_with(x, y, |a, b| a & b);
+    (union - intersection) as f64 / union as f64
+}
+
+/// Computes the Tanimoto similarity coefficient between two bit-packed fingerprints.
+///
+/// ..math::
+///    T(x, y) = \frac{|x \wedge y|}{|x| + |y| - |x \wedge y|}
+///
+/// For binary data this is the similarity counterpart of [`jaccard_bits`], i.e.
+/// `tanimoto_similarity_bits(x, y) == 1.0 - jaccard_bits(x, y)`, which is also the dense
+/// [`tanimoto`](crate::tanimoto) distance. Two empty fingerprints are considered identical and
+/// have a similarity of `1.0`.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same number of words.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// let x = [0b1110u64];
+/// let y = [0b0111u64];
+/// assert_eq!(tanimoto_similarity_bits(&x, &y), 0.5);
+/// ```
+pub fn tanimoto_similarity_bits(x: &[u64], y: &[u64]) -> f64 {
+    let union = popcount_with(x, y, |a, b| a | b);
+    if union == 0 {
+        return 1.0;
+    }
+    let intersection = popcount_with(x, y, |a, b| a & b);
+    intersection as f64 / union as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hamming, jaccard};
+    use ndarray::Array1;
+
+    fn unpack(words: &[u64], len: usize) -> Array1<f64> {
+        (0..len)
+            .map(|k| ((words[k / 64] >> (k % 64)) & 1) as f64)
+            .collect()
+    }
+
+    fn sample(n: usize, seed: u64) -> Vec<u64> {
+        (0..n as u64)
+            .map(|i| {
+                (i + seed)
+                    .wrapping_mul(0x9E37_79B9_7F4A_7C15)
+                    .rotate_left(17)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_matches_unpacked_metrics() {
+        // Nine words exercise both the unrolled body and the remainder.
+        let (x, y) = (sample(9, 1), sample(9, 2));
+        let (xu, yu) = (unpack(&x, 576), unpack(&y, 576));
+
+        let expected = hamming(&xu.view(), &yu.view());
+        assert_eq!(hamming_bits(&x, &y) as f64 / 576.0, expected);
+        assert!((jaccard_bits(&x, &y) - jaccard(&xu.view(), &yu.view())).abs() < 1e-12);
+        assert!((tanimoto_similarity_bits(&x, &y) - (1.0 - jaccard_bits(&x, &y))).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_identical_and_empty() {
+        let x = sample(5, 3);
+        assert_eq!(hamming_bits(&x, &x), 0);
+        assert_eq!(jaccard_bits(&x, &x), 0.0);
+        assert_eq!(tanimoto_similarity_bits(&x, &x), 1.0);
+
+        let zero = [0u64; 5];
+        assert_eq!(jaccard_bits(&zero, &zero), 0.0);
+        assert_eq!(tanimoto_similarity_bits(&zero, &zero), 1.0);
+        assert_eq!(hamming_bits(&[], &[]), 0);
+    }
+
+    #[test]
+    fn test_disjoint() {
+        let x = [0xFF00u64, 0];
+        let y = [0x00FFu64, 1];
+        assert_eq!(hamming_bits(&x, &y), 17);
+        assert_eq!(jaccard_bits(&x, &y), 1.0);
+        assert_eq!(tanimoto_similarity_bits(&x, &y), 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Input arrays must have the same length.")]
+    fn test_length_mismatch() {
+        hamming_bits(&[1, 2], &[1]);
+    }
+}
@@ -1,6 +1,7 @@
 extern crate ndarray;
 
-use ndarray::ArrayView1;
+use ndarray::{ArrayView1, Zip};
+use num::Float;
 
 /// Computes the Bray-Curtis dissimilarity between two vectors.
 ///
@@ -17,19 +18,22 @@ use ndarray::ArrayView1;
 ///
 /// # Returns
 /// A floating-point value representing the Bray-Curtis dissimilarity between `x` and `y`.
-pub fn bray_curtis(x: &ArrayView1<f64>, y: &ArrayView1<f64>) -> f64 {
-    let mut numerator = 0.0;
-    let mut denominator = 0.0;
+pub fn bray_curtis<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
 
-    for i in 0..x.len() {
-        numerator += (x[i] - y[i]).abs();
-        denominator += (x[i] + y[i]).abs();
-    }
+    let (numerator, denominator) = Zip::from(x)
+        .and(y)
+        .fold((T::zero(), T::zero()), |(num, den), &xi, &yi| {
+            (num + (xi - yi).abs(), den + (xi + yi).abs())
+        });
 
-    if denominator > 0.0 {
+    if denominator > T::zero() {
         numerator / denominator
     } else {
-        0.0
+        T::zero()
     }
 }
 
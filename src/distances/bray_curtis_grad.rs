@@ -1,6 +1,7 @@
 extern crate ndarray;
 
-use ndarray::{Array1, ArrayView1};
+use ndarray::{Array1, ArrayView1, Zip};
+use num::Float;
 
 /// Computes the Bray-Curtis dissimilarity and its gradient between two vectors.
 ///
@@ -22,30 +23,29 @@ use ndarray::{Array1, ArrayView1};
 ///
 /// # Returns
 /// A tuple containing:
-/// - The Bray-Curtis dissimilarity (f64).
-/// - The gradient of the dissimilarity with respect to the first vector `x` (Array1<f64>).
-pub fn bray_curtis_grad(x: &ArrayView1<f64>, y: &ArrayView1<f64>) -> (f64, Array1<f64>) {
-    let mut numerator = 0.0;
-    let mut denominator = 0.0;
-
-    for i in 0..x.len() {
-        numerator += (x[i] - y[i]).abs();
-        denominator += (x[i] + y[i]).abs();
-    }
-
-    let (dist, grad) = if denominator > 0.0 {
+/// - The Bray-Curtis dissimilarity.
+/// - The gradient of the dissimilarity with respect to the first vector `x`.
+pub fn bray_curtis_grad<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> (T, Array1<T>)
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+
+    let (numerator, denominator) = Zip::from(x)
+        .and(y)
+        .fold((T::zero(), T::zero()), |(num, den), &xi, &yi| {
+            (num + (xi - yi).abs(), den + (xi + yi).abs())
+        });
+
+    if denominator > T::zero() {
         let dist = numerator / denominator;
-        let mut grad = Array1::<f64>::zeros(x.len());
-        for i in 0..x.len() {
-            grad[i] = (x[i] - y[i]).signum() - dist;
-            grad[i] /= denominator;
-        }
+        let grad = Zip::from(x)
+            .and(y)
+            .map_collect(|&xi, &yi| ((xi - yi).signum() - dist) / denominator);
         (dist, grad)
     } else {
-        (0.0, Array1::<f64>::zeros(x.len()))
-    };
-
-    (dist, grad)
+        (T::zero(), Array1::zeros(x.len()))
+    }
 }
 
 #[cfg(test)]
@@ -124,4 +124,13 @@ mod tests {
         assert_eq!(dist, 0.0);
         assert_eq!(grad, arr1(&[0.0, 0.0, 0.0]));
     }
+
+    #[test]
+    fn test_bray_curtis_grad_f32() {
+        let x = arr1(&[1.0_f32, 2.0, 3.0]);
+        let y = arr1(&[4.0_f32, 1.0, 6.0]);
+        let (dist, grad) = bray_curtis_grad(&x.view(), &y.view());
+        assert_eq!(dist, 7.0 / 17.0);
+        assert_eq!(grad[1], (1.0 - 7.0 / 17.0) / 17.0);
+    }
 }
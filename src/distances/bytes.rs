@@ -0,0 +1,90 @@
+/// Computes the Hamming distance between two byte vectors, i.e. the number of positions whose
+/// bytes differ.
+///
+/// This is meant for quantized embeddings stored as one `u8` per dimension. Unlike
+/// [`hamming_bits`](crate::hamming_bits), every byte is one symbol rather than eight packed bits,
+/// and the result is a count rather than the fraction returned by [`hamming`](crate::hamming).
+/// With the `simd` feature, [`simd::hamming_u8`](crate::simd::hamming_u8) computes the same
+/// value 32 bytes at a time.
+///
+/// # Arguments
+/// * `x` - The first byte vector.
+/// * `y` - The second byte vector.
+///
+/// # Returns
+/// The number of differing positions.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// assert_eq!(hamming_bytes(&[1, 2, 3, 4], &[1, 0, 3, 0]), 2);
+/// ```
+pub fn hamming_bytes(x: &[u8], y: &[u8]) -> usize {
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+    x.iter().zip(y).filter(|(a, b)| a != b).count()
+}
+
+/// Computes the Manhattan (L1) distance between two byte vectors.
+///
+/// ..math::
+///    d(x, y) = \sum_i |x_i - y_i|
+///
+/// The sum is accumulated in a `u64`, so it is exact for any input length. With the `simd`
+/// feature, [`simd::manhattan_u8`](crate::simd::manhattan_u8) computes the same value 16 bytes
+/// at a time.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// assert_eq!(manhattan_bytes(&[0, 10, 255], &[5, 4, 0]), 266);
+/// ```
+pub fn manhattan_bytes(x: &[u8], y: &[u8]) -> u64 {
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+    x.iter().zip(y).map(|(&a, &b)| a.abs_diff(b) as u64).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hamming, manhattan};
+    use ndarray::Array1;
+
+    #[test]
+    fn test_matches_float_metrics() {
+        let x: Vec<u8> = (0..50).map(|i| (i * 37 % 251) as u8).collect();
+        let y: Vec<u8> = (0..50).map(|i| (i * 53 % 7) as u8).collect();
+        let xf: Array1<f64> = x.iter().map(|&v| v as f64).collect();
+        let yf: Array1<f64> = y.iter().map(|&v| v as f64).collect();
+
+        let expected = hamming(&xf.view(), &yf.view()) * 50.0;
+        assert_eq!(hamming_bytes(&x, &y) as f64, expected.round());
+        assert_eq!(
+            manhattan_bytes(&x, &y) as f64,
+            manhattan(&xf.view(), &yf.view())
+        );
+    }
+
+    #[test]
+    fn test_extremes() {
+        let x = [0u8; 1000];
+        let y = [255u8; 1000];
+        assert_eq!(hamming_bytes(&x, &y), 1000);
+        assert_eq!(manhattan_bytes(&x, &y), 255_000);
+        assert_eq!(hamming_bytes(&x, &x), 0);
+        assert_eq!(manhattan_bytes(&[], &[]), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Input arrays must have the same length.")]
+    fn test_length_mismatch() {
+        manhattan_bytes(&[1, 2], &[1]);
+    }
+}
@@ -1,4 +1,4 @@
-use ndarray::ArrayView1;
+use ndarray::{ArrayView1, Zip};
 
 /// Computes the Canberra distance between two vectors `x` and `y`.
 ///
@@ -35,22 +35,68 @@ where
     // Ensure that the vectors x and y have the same length.
     assert_eq!(x.len(), y.len(), "Vectors must have the same length.");
 
-    // Initialize result
-    let mut result: T = T::zero();
-
-    // Loop through the elements of the vectors
-    for i in 0..x.len() {
-        let denominator = x[i].abs() + y[i].abs();
+    Zip::from(x).and(y).fold(T::zero(), |acc, &xi, &yi| {
+        let denominator = xi.abs() + yi.abs();
         if denominator > T::zero() {
-            result = result + (x[i] - y[i]).abs() / denominator;
+            acc + (xi - yi).abs() / denominator
+        } else {
+            acc
         }
-    }
+    })
+}
 
-    result
+/// Computes the Canberra distance averaged over the coordinates where `|x_i| + |y_i|` is
+/// non-zero.
+///
+/// ..math::
+///     D(x, y) = \frac{1}{|P|} \sum_{i \in P} \frac{|x_i - y_i|}{|x_i| + |y_i|}, \quad P = \{ i : |x_i| + |y_i| > 0 \}
+///
+/// Coordinates where both vectors are zero carry no information, so [`canberra`] skips them,
+/// but they still count towards the length of sparse vectors; averaging over the non-zero
+/// pairs instead keeps the distance in `[0, 1]` regardless of how many shared zeros there
+/// are. R's `dist(method = "canberra")` returns `n` times this value. Two zero vectors are at
+/// distance `0`.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```rust
+/// use ndarray::arr1;
+/// use fast_distances::*;
+/// let x = arr1(&[1.0, 0.0, 0.0, 3.0]);
+/// let y = arr1(&[3.0, 0.0, 0.0, 0.0]);
+/// assert_eq!(canberra(&x.view(), &y.view()), 1.5);
+/// assert_eq!(canberra_normalized(&x.view(), &y.view()), 0.75);
+/// ```
+pub fn canberra_normalized<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: num::Float,
+{
+    assert_eq!(x.len(), y.len(), "Vectors must have the same length.");
+
+    let (sum, pairs) = Zip::from(x)
+        .and(y)
+        .fold((T::zero(), 0usize), |(acc, pairs), &xi, &yi| {
+            let denominator = xi.abs() + yi.abs();
+            if denominator > T::zero() {
+                (acc + (xi - yi).abs() / denominator, pairs + 1)
+            } else {
+                (acc, pairs)
+            }
+        });
+
+    if pairs == 0 {
+        T::zero()
+    } else {
+        sum / T::from(pairs).unwrap()
+    }
 }
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::distances::reference::reference_inputs;
     use ndarray::arr1;
 
     #[test]
@@ -104,4 +150,29 @@ mod tests {
 
         canberra(&x.view(), &y.view());
     }
+
+    #[test]
+    fn test_reference_values() {
+        let (x, y) = reference_inputs();
+        assert_eq!(canberra(&x.view(), &y.view()), 4.5476190476190474);
+    }
+
+    #[test]
+    fn test_canberra_normalized_ignores_shared_zeros() {
+        let x = arr1(&[1.0_f32, 2.0, 0.0]);
+        let y = arr1(&[4.0_f32, 0.0, 0.0]);
+        let padded = (
+            arr1(&[1.0_f32, 2.0, 0.0, 0.0, 0.0]),
+            arr1(&[4.0_f32, 0.0, 0.0, 0.0, 0.0]),
+        );
+        let expected = canberra(&x.view(), &y.view()) / 2.0;
+        assert_eq!(canberra_normalized(&x.view(), &y.view()), expected);
+        assert_eq!(
+            canberra_normalized(&padded.0.view(), &padded.1.view()),
+            expected
+        );
+
+        let zero = arr1(&[0.0_f32, 0.0]);
+        assert_eq!(canberra_normalized(&zero.view(), &zero.view()), 0.0);
+    }
 }
@@ -1,4 +1,4 @@
-use ndarray::ArrayView1;
+use ndarray::{ArrayView1, Zip};
 use num::Float;
 
 /// Computes the Canberra distance and its gradient with respect to the first vector `x`.
@@ -45,16 +45,16 @@ where
     let mut grad = vec![T::zero(); x.len()];
 
     // Loop through the elements of the vectors
-    for i in 0..x.len() {
-        let denominator = x[i].abs() + y[i].abs();
+    Zip::from(&mut grad).and(x).and(y).for_each(|g, &xi, &yi| {
+        let denominator = xi.abs() + yi.abs();
         if denominator > T::zero() {
-            result = result + (x[i] - y[i]).abs() / denominator;
+            result = result + (xi - yi).abs() / denominator;
 
-            let sign_diff = (x[i] - y[i]).signum();
-            let sign_x = x[i].signum();
-            grad[i] = sign_diff / denominator - (x[i] - y[i]).abs() * sign_x / denominator.powi(2);
+            let sign_diff = (xi - yi).signum();
+            let sign_x = xi.signum();
+            *g = sign_diff / denominator - (xi - yi).abs() * sign_x / denominator.powi(2);
         }
-    }
+    });
 
     (result, grad)
 }
@@ -62,6 +62,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::distances::reference::reference_inputs;
     use ndarray::arr1;
 
     #[test]
@@ -125,4 +126,15 @@ mod tests {
 
         canberra_grad(&x.view(), &y.view());
     }
+
+    #[test]
+    fn test_reference_values() {
+        let (x, y) = reference_inputs();
+        let (dist, grad) = canberra_grad(&x.view(), &y.view());
+        assert_eq!(dist, 4.5476190476190474);
+        assert_eq!(
+            grad,
+            vec![-0.75, 0.0, 0.0, 0.0, 0.18140589569160998, 0.0, 0.0]
+        );
+    }
 }
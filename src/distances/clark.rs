@@ -0,0 +1,106 @@
+use ndarray::{ArrayView1, Zip};
+use num::Float;
+
+/// Sums `((x_i - y_i) / (|x_i| + |y_i|))²`, skipping coordinates where the denominator is
+/// zero, as [`canberra`](crate::canberra) does.
+fn squared_relative_differences<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Vectors must have the same length.");
+
+    Zip::from(x).and(y).fold(T::zero(), |acc, &xi, &yi| {
+        let denominator = xi.abs() + yi.abs();
+        if denominator > T::zero() {
+            let ratio = (xi - yi) / denominator;
+            acc + ratio * ratio
+        } else {
+            acc
+        }
+    })
+}
+
+/// Computes the Clark distance between two vectors `x` and `y`.
+///
+/// ..math::
+///     D(x, y) = \sqrt{\sum_i \left(\frac{x_i - y_i}{|x_i| + |y_i|}\right)^2}
+///
+/// This is the Euclidean counterpart of the [`canberra`](crate::canberra) distance: each
+/// coordinate contributes its relative difference, at most `1`, so the distance lies in
+/// `[0, sqrt(n)]`. Coordinates where both vectors are zero are skipped.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```rust
+/// use ndarray::arr1;
+/// use fast_distances::*;
+/// let x = arr1(&[1.0, 0.0, 2.0]);
+/// let y = arr1(&[3.0, 0.0, 0.0]);
+/// // (-2 / 4)² + (2 / 2)²
+/// assert_eq!(clark(&x.view(), &y.view()), 1.25f64.sqrt());
+/// ```
+pub fn clark<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    squared_relative_differences(x, y).sqrt()
+}
+
+/// Computes the divergence distance between two vectors `x` and `y`.
+///
+/// ..math::
+///     D(x, y) = 2 \sum_i \frac{(x_i - y_i)^2}{(|x_i| + |y_i|)^2}
+///
+/// This is twice the square of the [`clark`] distance, and matches the divergence of Cha's
+/// distance survey, written with `(x_i + y_i)²`, on nonnegative data. Coordinates where both
+/// vectors are zero are skipped.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```rust
+/// use ndarray::arr1;
+/// use fast_distances::*;
+/// let x = arr1(&[1.0, 0.0, 2.0]);
+/// let y = arr1(&[3.0, 0.0, 0.0]);
+/// assert_eq!(divergence(&x.view(), &y.view()), 2.5);
+/// ```
+pub fn divergence<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    T::from(2).unwrap() * squared_relative_differences(x, y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_clark_and_divergence() {
+        let x = arr1(&[0.5, -1.25, 3.0, 0.0, 2.75]);
+        let y = arr1(&[1.5, 0.75, -2.0, 0.0, 2.5]);
+        let expected: f64 = [(-1.0 / 2.0), (-2.0 / 2.0), (5.0 / 5.0), (0.25 / 5.25)]
+            .iter()
+            .map(|r: &f64| r * r)
+            .sum();
+        assert!((clark(&x.view(), &y.view()) - expected.sqrt()).abs() < 1e-15);
+        assert!((divergence(&x.view(), &y.view()) - 2.0 * expected).abs() < 1e-15);
+        assert_eq!(clark(&x.view(), &x.view()), 0.0);
+    }
+
+    #[test]
+    fn test_clark_zero_vectors() {
+        let zero = arr1(&[0.0_f32, 0.0]);
+        assert_eq!(clark(&zero.view(), &zero.view()), 0.0);
+        assert_eq!(divergence(&zero.view(), &zero.view()), 0.0);
+        assert_eq!(
+            clark(&zero.view(), &arr1(&[1.0_f32, 2.0]).view()),
+            2f32.sqrt()
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use ndarray::{Array1, ArrayView1, Zip};
+use num::Float;
+
+/// Computes the correlation distance and its gradient between two vectors `x` and `y`.
+///
+/// The correlation distance is the cosine distance between the centered vectors
+/// `x̃ = x - μ_x` and `ỹ = y - μ_y`:
+///
+/// ..math::
+///     \text{correlation}(x, y) = 1 - \frac{\sum \tilde{x}_i \cdot \tilde{y}_i}{\sqrt{\sum \tilde{x}_i^2} \cdot \sqrt{\sum \tilde{y}_i^2}}
+///
+/// The gradient with respect to `x` is
+///
+/// ..math::
+///     \frac{\partial}{\partial x_i} = \frac{\tilde{x}_i (\tilde{x} \cdot \tilde{y}) - \tilde{y}_i \lVert \tilde{x} \rVert^2}{\lVert \tilde{x} \rVert^3 \lVert \tilde{y} \rVert}
+///
+/// which needs no further projection because it already sums to zero. The distance matches
+/// [`correlation`](crate::correlation); if either centered vector has a norm of zero, the
+/// gradient is zero.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Returns
+/// * A tuple containing:
+///     - The correlation distance between `x` and `y`.
+///     - The gradient of the correlation distance with respect to `x`.
+pub fn correlation_grad<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> (T, Array1<T>)
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+
+    let n = T::from(x.len()).unwrap();
+    let mu_x = x.iter().fold(T::zero(), |acc, &xi| acc + xi) / n;
+    let mu_y = y.iter().fold(T::zero(), |acc, &yi| acc + yi) / n;
+    let shifted_x = x.mapv(|xi| xi - mu_x);
+    let shifted_y = y.mapv(|yi| yi - mu_y);
+
+    let (dot, norm_x, norm_y) = Zip::from(&shifted_x).and(&shifted_y).fold(
+        (T::zero(), T::zero(), T::zero()),
+        |(dot, nx, ny), &xi, &yi| (dot + xi * yi, nx + xi * xi, ny + yi * yi),
+    );
+
+    if norm_x.is_zero() && norm_y.is_zero() {
+        (T::zero(), Array1::zeros(x.dim()))
+    } else if norm_x.is_zero() || norm_y.is_zero() {
+        (T::one(), Array1::zeros(x.dim()))
+    } else {
+        let scale = norm_x.powf(T::from(1.5).unwrap()) * norm_y.sqrt();
+        let grad = Zip::from(&shifted_x)
+            .and(&shifted_y)
+            .map_collect(|&xi, &yi| (xi * dot - yi * norm_x) / scale);
+        let dist = T::one() - dot / (norm_x.sqrt() * norm_y.sqrt());
+        (dist, grad)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::correlation;
+    use ndarray::arr1;
+
+    fn numeric_grad<F: Fn(&ArrayView1<f64>) -> f64>(f: F, p: &Array1<f64>) -> Array1<f64> {
+        let h = 1e-6;
+        Array1::from_shape_fn(p.len(), |i| {
+            let (mut hi, mut lo) = (p.clone(), p.clone());
+            hi[i] += h;
+            lo[i] -= h;
+            (f(&hi.view()) - f(&lo.view())) / (2.0 * h)
+        })
+    }
+
+    #[test]
+    fn test_correlation_grad_matches_finite_differences() {
+        let x = arr1(&[0.5, -1.25, 3.0, 0.0, 2.75, -0.125, 1.0]);
+        let y = arr1(&[1.5, 0.75, -2.0, 0.0, 2.5, 0.375, -4.0]);
+        let (dist, grad) = correlation_grad(&x.view(), &y.view());
+        assert!((dist - correlation(&x.view(), &y.view())).abs() < 1e-15);
+        let expected = numeric_grad(|x| correlation(x, &y.view()), &x);
+        assert!(grad
+            .iter()
+            .zip(&expected)
+            .all(|(g, e)| (g - e).abs() < 1e-6));
+        assert!(grad.sum().abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_correlation_grad_constant_input() {
+        let x = arr1(&[2.0_f32, 2.0, 2.0]);
+        let y = arr1(&[1.0_f32, 2.0, 3.0]);
+        let (dist, grad) = correlation_grad(&x.view(), &y.view());
+        assert_eq!(dist, 1.0);
+        assert_eq!(grad, arr1(&[0.0, 0.0, 0.0]));
+
+        let (dist, grad) = correlation_grad(&x.view(), &x.view());
+        assert_eq!(dist, 0.0);
+        assert_eq!(grad, arr1(&[0.0, 0.0, 0.0]));
+    }
+}
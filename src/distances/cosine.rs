@@ -1,4 +1,4 @@
-use ndarray::ArrayView1;
+use ndarray::{ArrayView1, Zip};
 use num::Float;
 
 /// Computes the cosine similarity between two vectors `x` and `y`.
@@ -21,15 +21,12 @@ pub fn cosine<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
 where
     T: Float,
 {
-    let mut result = T::zero();
-    let mut norm_x = T::zero();
-    let mut norm_y = T::zero();
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
 
-    for i in 0..x.len() {
-        result = result + x[i] * y[i];
-        norm_x = norm_x + x[i] * x[i];
-        norm_y = norm_y + y[i] * y[i];
-    }
+    let (result, norm_x, norm_y) = Zip::from(x).and(y).fold(
+        (T::zero(), T::zero(), T::zero()),
+        |(dot, nx, ny), &xi, &yi| (dot + xi * yi, nx + xi * xi, ny + yi * yi),
+    );
 
     if norm_x.is_zero() && norm_y.is_zero() {
         T::zero()
@@ -43,6 +40,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::distances::reference::reference_inputs;
     use ndarray::arr1;
 
     #[test]
@@ -104,4 +102,10 @@ mod tests {
         let result = cosine(&x.view(), &y.view());
         assert_eq!(result, 0.0_f64);
     }
+
+    #[test]
+    fn test_reference_values() {
+        let (x, y) = reference_inputs();
+        assert_eq!(cosine(&x.view(), &y.view()), 1.141171789042315);
+    }
 }
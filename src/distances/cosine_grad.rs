@@ -1,4 +1,4 @@
-use ndarray::{Array1, ArrayView1};
+use ndarray::{Array1, ArrayView1, Zip};
 use num::Float;
 
 /// Computes the cosine similarity and its gradient between two vectors `x` and `y`.
@@ -23,16 +23,13 @@ pub fn cosine_grad<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> (T, Array1<T>)
 where
     T: Float,
 {
-    let mut result = T::zero();
-    let mut norm_x = T::zero();
-    let mut norm_y = T::zero();
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
 
     // Compute the dot product and the squared norms
-    for i in 0..x.len() {
-        result = result + x[i] * y[i];
-        norm_x = norm_x + x[i] * x[i];
-        norm_y = norm_y + y[i] * y[i];
-    }
+    let (result, norm_x, norm_y) = Zip::from(x).and(y).fold(
+        (T::zero(), T::zero(), T::zero()),
+        |(dot, nx, ny), &xi, &yi| (dot + xi * yi, nx + xi * xi, ny + yi * yi),
+    );
 
     // Initialize the gradient and distance (similarity)
     let (dist, grad) = if norm_x.is_zero() && norm_y.is_zero() {
@@ -40,11 +37,10 @@ where
     } else if norm_x.is_zero() || norm_y.is_zero() {
         (T::one(), Array1::<T>::zeros(x.dim()))
     } else {
-        let mut grad = Array1::<T>::zeros(x.dim());
-        for i in 0..x.len() {
-            grad[i] = -(x[i] * result - y[i] * norm_x)
-                / (norm_x.powf(T::from(1.5).unwrap()) * norm_y.sqrt());
-        }
+        let scale = norm_x.powf(T::from(1.5).unwrap()) * norm_y.sqrt();
+        let grad = Zip::from(x)
+            .and(y)
+            .map_collect(|&xi, &yi| -(xi * result - yi * norm_x) / scale);
         let dist = T::one() - (result / (norm_x.sqrt() * norm_y.sqrt()));
         (dist, grad)
     };
@@ -55,6 +51,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::distances::reference::reference_inputs;
     use ndarray::arr1;
 
     #[test]
@@ -128,4 +125,23 @@ mod tests {
         assert_eq!(dist, 0.0_f64);
         assert_eq!(grad, arr1(&[0.0_f64, 0.0, 0.0]));
     }
+
+    #[test]
+    fn test_reference_values() {
+        let (x, y) = reference_inputs();
+        let (dist, grad) = cosine_grad(&x.view(), &y.view());
+        assert_eq!(dist, 1.141171789042315);
+        assert_eq!(
+            grad,
+            arr1(&[
+                0.06667505263180988,
+                0.022416904439948267,
+                -0.06220521670118575,
+                0.0,
+                0.12507921567229444,
+                0.01484865951195969,
+                -0.16081250632222707
+            ])
+        );
+    }
 }
@@ -0,0 +1,233 @@
+use ndarray::ArrayView1;
+use num::Float;
+
+/// Computes the normalized cross-correlation (shape-based) distance between two series.
+///
+/// The series are compared at every relative shift, and the distance is based on the best one:
+///
+/// ..math::
+///    d(x, y) = 1 - \max_k \frac{\sum_i x_{i + k} \, y_i}{\|x\| \, \|y\|}
+///
+/// This is the shape-based distance (SBD) of k-Shape. Unlike
+/// [`correlation`](crate::correlation) and [`cosine`](crate::cosine), it is insensitive to a
+/// phase shift between the series, and unlike [`dtw`](crate::dtw) it does not warp them. The
+/// series may have different lengths; z-normalize them first to also ignore offset and scale.
+///
+/// The direct computation costs `O(len(x) * len(y))`. With the `fft` feature, long series
+/// are correlated with FFTs in `O(L log L)`, where `L` is the padded combined length,
+/// computing in `f64`.
+///
+/// # Arguments
+/// * `x` - The first series.
+/// * `y` - The second series.
+///
+/// # Returns
+/// A distance between `0` and `2`; `0` if both series are zero and `1` if only one of them is.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[0.0, 1.0, 2.0, 1.0, 0.0, 0.0]);
+/// let y = arr1(&[0.0, 0.0, 0.0, 1.0, 2.0, 1.0]);
+/// let d: f64 = cross_correlation_distance(&x.view(), &y.view());
+/// assert!(d.abs() < 1e-12);
+/// assert!(cosine(&x.view(), &y.view()) > 0.5);
+/// ```
+pub fn cross_correlation_distance<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    cross_correlation_distance_with_lag(x, y).0
+}
+
+/// Computes the [`cross_correlation_distance`] along with the best shift.
+///
+/// # Returns
+/// A tuple containing the distance and the lag `k` that aligns `x[i + k]` with `y[i]`, so a
+/// positive lag means that `y` is delayed with respect to `x`. The lag is `0` if either
+/// series is zero.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[0.0, 1.0, 3.0, 0.0, 0.0]);
+/// let y = arr1(&[1.0, 3.0, 0.0, 0.0, 0.0]);
+/// let (_, lag) = cross_correlation_distance_with_lag(&x.view(), &y.view());
+/// assert_eq!(lag, 1);
+/// ```
+pub fn cross_correlation_distance_with_lag<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> (T, isize)
+where
+    T: Float,
+{
+    let norm_x = x.fold(T::zero(), |acc, &v| acc + v * v).sqrt();
+    let norm_y = y.fold(T::zero(), |acc, &v| acc + v * v).sqrt();
+    if norm_x.is_zero() && norm_y.is_zero() {
+        return (T::zero(), 0);
+    } else if norm_x.is_zero() || norm_y.is_zero() {
+        return (T::one(), 0);
+    }
+
+    #[cfg(feature = "fft")]
+    let (best, lag) = if x.len().min(y.len()) >= FFT_MIN_LEN {
+        fft_max_correlation(x, y)
+    } else {
+        max_correlation(x, y)
+    };
+    #[cfg(not(feature = "fft"))]
+    let (best, lag) = max_correlation(x, y);
+
+    (T::one() - best / (norm_x * norm_y), lag)
+}
+
+/// The length from which the FFT path is faster than the direct sums.
+#[cfg(feature = "fft")]
+pub(crate) const FFT_MIN_LEN: usize = 64;
+
+/// Returns the largest raw cross-correlation and its lag, preferring the first lag on ties.
+fn max_correlation<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> (T, isize)
+where
+    T: Float,
+{
+    let (n, m) = (x.len() as isize, y.len() as isize);
+    let mut best = (T::neg_infinity(), 0);
+    for lag in -(m - 1)..n {
+        let start = (-lag).max(0);
+        let end = m.min(n - lag);
+        let value = (start..end).fold(T::zero(), |acc, i| {
+            acc + x[(i + lag) as usize] * y[i as usize]
+        });
+        if value > best.0 {
+            best = (value, lag);
+        }
+    }
+    best
+}
+
+/// Computes [`max_correlation`] from [`fft_cross_correlation`].
+#[cfg(feature = "fft")]
+fn fft_max_correlation<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> (T, isize)
+where
+    T: Float,
+{
+    let first_lag = 1 - y.len() as isize;
+    fft_cross_correlation(x, y)
+        .into_iter()
+        .zip(first_lag..)
+        .fold((T::neg_infinity(), 0), |best, (value, lag)| {
+            if value > best.0 {
+                (value, lag)
+            } else {
+                best
+            }
+        })
+}
+
+/// Returns `sum_i x[i + k] * y[i]` for every lag `k` from `1 - len(y)` to `len(x) - 1`, in
+/// that order, computed in `f64` with a zero-padded FFT.
+#[cfg(feature = "fft")]
+pub(crate) fn fft_cross_correlation<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> Vec<T>
+where
+    T: Float,
+{
+    use rustfft::{num_complex::Complex, FftPlanner};
+
+    let (n, m) = (x.len(), y.len());
+    let len = (n + m - 1).next_power_of_two();
+    let pad = |v: &ArrayView1<T>| -> Vec<Complex<f64>> {
+        let mut buffer: Vec<Complex<f64>> = v
+            .iter()
+            .map(|&value| Complex::new(value.to_f64().unwrap(), 0.0))
+            .collect();
+        buffer.resize(len, Complex::new(0.0, 0.0));
+        buffer
+    };
+    let (mut fx, mut fy) = (pad(x), pad(y));
+
+    let mut planner = FftPlanner::new();
+    let forward = planner.plan_fft_forward(len);
+    forward.process(&mut fx);
+    forward.process(&mut fy);
+    for (a, b) in fx.iter_mut().zip(&fy) {
+        *a *= b.conj();
+    }
+    planner.plan_fft_inverse(len).process(&mut fx);
+
+    // Index `k` holds lag `k` for nonnegative lags and lag `k - len` for negative ones.
+    let scale = (len as f64).recip();
+    (len - (m - 1)..len)
+        .chain(0..n)
+        .map(|k| T::from(fx[k].re * scale).unwrap())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+    use ndarray::{arr1, Array1};
+
+    #[test]
+    fn test_shift_invariance() {
+        let pattern = [1.0, 3.0, -2.0, 0.5];
+        let mut x = Array1::<f64>::zeros(12);
+        let mut y = Array1::<f64>::zeros(12);
+        for (k, &v) in pattern.iter().enumerate() {
+            x[2 + k] = v;
+            y[7 + k] = v;
+        }
+        let (d, lag) = cross_correlation_distance_with_lag(&x.view(), &y.view());
+        assert!(d.abs() < 1e-12);
+        assert_eq!(lag, -5);
+        let (_, lag) = cross_correlation_distance_with_lag(&y.view(), &x.view());
+        assert_eq!(lag, 5);
+
+        // An inverted series is as far as possible at zero lag, but its best match is the
+        // negated autocorrelation at lag one, `-(3 - 6 - 1)`.
+        let z = -&x;
+        let d = cross_correlation_distance(&x.view(), &z.view());
+        assert!((d - (1.0 - 4.0 / 14.25)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_zero_series_and_lengths() {
+        let x = arr1(&[1.0f32, 2.0, 3.0]);
+        let zero = Array1::<f32>::zeros(3);
+        assert_eq!(cross_correlation_distance(&zero.view(), &zero.view()), 0.0);
+        assert_eq!(cross_correlation_distance(&x.view(), &zero.view()), 1.0);
+
+        let y = arr1(&[0.0f32, 0.0, 1.0, 2.0, 3.0, 0.0, 0.0]);
+        let (d, lag) = cross_correlation_distance_with_lag(&x.view(), &y.view());
+        assert!(d.abs() < 1e-6);
+        assert_eq!(lag, -2);
+    }
+
+    #[test]
+    fn test_bounded_by_zero_lag_cosine() {
+        let mut rng = SplitMix64::new(3);
+        for n in [1, 5, 70, 130] {
+            let x: Array1<f64> = (0..n).map(|_| rng.next_gaussian()).collect();
+            let y: Array1<f64> = (0..n).map(|_| rng.next_gaussian()).collect();
+            let d = cross_correlation_distance(&x.view(), &y.view());
+            assert!(d <= crate::cosine(&x.view(), &y.view()) + 1e-12);
+            assert!((0.0..=2.0).contains(&d));
+        }
+    }
+
+    #[cfg(feature = "fft")]
+    #[test]
+    fn test_fft_matches_direct() {
+        let mut rng = SplitMix64::new(11);
+        for (n, m) in [(64, 64), (100, 70), (65, 200), (3, 9)] {
+            let x: Array1<f64> = (0..n).map(|_| rng.next_gaussian()).collect();
+            let y: Array1<f64> = (0..m).map(|_| rng.next_gaussian()).collect();
+            let direct = max_correlation(&x.view(), &y.view());
+            let fft = fft_max_correlation(&x.view(), &y.view());
+            assert!((direct.0 - fft.0).abs() < 1e-9);
+            assert_eq!(direct.1, fft.1);
+        }
+    }
+}
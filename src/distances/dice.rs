@@ -1,6 +1,9 @@
 extern crate ndarray;
 
 use ndarray::ArrayView1;
+use num::Float;
+
+use super::binary::binary_counts;
 
 /// Computes the Dice coefficient between two binary vectors.
 ///
@@ -17,22 +20,17 @@ use ndarray::ArrayView1;
 /// * `y` - A 1D array (view) of values representing the second binary vector.
 ///
 /// # Returns
-/// A f64 value representing the Dice coefficient.
-pub fn dice(x: &ArrayView1<f64>, y: &ArrayView1<f64>) -> f64 {
-    let mut num_true_true = 0.0;
-    let mut num_not_equal = 0.0;
-
-    for i in 0..x.len() {
-        let x_true = x[i] != 0.0;
-        let y_true = y[i] != 0.0;
-        num_true_true += if x_true && y_true { 1.0 } else { 0.0 };
-        num_not_equal += if x_true != y_true { 1.0 } else { 0.0 };
-    }
+/// A value representing the Dice coefficient.
+pub fn dice<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    let (num_true_true, num_not_equal) = binary_counts(x, y);
 
-    if num_not_equal == 0.0 {
-        0.0
+    if num_not_equal.is_zero() {
+        T::zero()
     } else {
-        num_not_equal / (2.0 * num_true_true + num_not_equal)
+        num_not_equal / (T::from(2).unwrap() * num_true_true + num_not_equal)
     }
 }
 
@@ -0,0 +1,550 @@
+use std::collections::VecDeque;
+
+use ndarray::{Array1, ArrayView1};
+use num::Float;
+
+/// Computes the dynamic time warping (DTW) distance between two sequences.
+///
+/// DTW aligns the sequences with a monotonic warping path that may repeat elements of either
+/// one, and returns the cost of the cheapest alignment. The local cost is the squared
+/// difference, and the square root of the accumulated cost is returned, so for sequences of
+/// equal length `dtw(x, y) <= euclidean(x, y)`.
+///
+/// ..math::
+///    D(i, j) = (x_i - y_j)^2 + \min(D(i - 1, j), D(i, j - 1), D(i - 1, j - 1))
+///
+/// The sequences may have different lengths. Only two rows of the cost matrix are kept, so the
+/// memory use is `O(len(y))` and the runtime `O(len(x) * len(y))`.
+///
+/// # Arguments
+/// * `x` - The first sequence.
+/// * `y` - The second sequence.
+///
+/// # Returns
+/// The DTW distance; `0` if both sequences are empty and infinity if only one of them is.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[0.0, 1.0, 2.0, 3.0]);
+/// let y = arr1(&[0.0, 0.0, 1.0, 2.0, 3.0]);
+/// assert_eq!(dtw(&x.view(), &y.view()), 0.0);
+/// ```
+pub fn dtw<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    dtw_bounded(x, y, T::infinity()).unwrap_or(T::infinity())
+}
+
+/// Computes the DTW distance between two sequences, abandoning early once it exceeds `upper`.
+///
+/// Every warping path crosses each row of the cost matrix, so the computation stops as soon as
+/// a whole row exceeds `upper * upper`. Combined with [`lb_kim`] and [`lb_keogh`], this is the
+/// core of UCR-suite style nearest-neighbor search.
+///
+/// # Returns
+/// `Some(distance)` if the distance is at most `upper`, otherwise `None`.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[0.0, 0.0, 0.0]);
+/// let y = arr1(&[3.0, 4.0]);
+/// assert_eq!(dtw_bounded(&x.view(), &y.view(), 10.0), Some(34.0_f64.sqrt()));
+/// assert_eq!(dtw_bounded(&x.view(), &y.view(), 5.0), None);
+/// ```
+pub fn dtw_bounded<T>(x: &ArrayView1<T>, y: &ArrayView1<T>, upper: T) -> Option<T>
+where
+    T: Float,
+{
+    constrained(x, y, DtwWindow::Full, upper, &mut DtwBuffer::new())
+}
+
+/// A global constraint on the warping paths considered by [`dtw_constrained`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum DtwWindow {
+    /// Every monotonic warping path is allowed.
+    #[default]
+    Full,
+    /// The Sakoe-Chiba band: element `i` of `x` may only be aligned with elements `j` of `y`
+    /// where `|i - j| <= band_radius`. The radius is widened to the difference in length if
+    /// needed, so that a path always exists.
+    SakoeChiba { band_radius: usize },
+    /// The Itakura parallelogram: every part of the path must have a slope between
+    /// `1 / max_slope` and `max_slope`, which also forbids long horizontal or vertical runs at
+    /// either end. `max_slope` must be at least `1`.
+    Itakura { max_slope: f64 },
+}
+
+impl DtwWindow {
+    /// Returns the range of columns of `y` allowed in row `i` of an `n` by `m` cost matrix, or
+    /// `None` if the row is empty.
+    fn columns(&self, i: usize, n: usize, m: usize) -> Option<(usize, usize)> {
+        match *self {
+            DtwWindow::Full => Some((0, m - 1)),
+            DtwWindow::SakoeChiba { band_radius } => {
+                let radius = band_radius.max(n.abs_diff(m));
+                Some((i.saturating_sub(radius), (i + radius).min(m - 1)))
+            }
+            DtwWindow::Itakura { max_slope } => {
+                let (i, rest) = (i as f64, (n - 1 - i) as f64);
+                let last = (m - 1) as f64;
+                // Allow for rounding when a boundary passes exactly through a cell.
+                let tolerance = 1e-9;
+                let lower = (i / max_slope).max(last - max_slope * rest);
+                let upper = (i * max_slope).min(last - rest / max_slope);
+                let (lo, hi) = ((lower - tolerance).ceil(), (upper + tolerance).floor());
+                (lo <= hi && hi >= 0.0 && lo <= last)
+                    .then(|| (lo.max(0.0) as usize, hi.min(last) as usize))
+            }
+        }
+    }
+}
+
+/// Reusable rows of the DTW cost matrix.
+///
+/// [`dtw_constrained_with_buffer`] grows the buffer to the length of the longest second
+/// sequence it has seen and then reuses it, so comparing a query against many candidates does
+/// not allocate per call.
+#[derive(Debug, Clone, Default)]
+pub struct DtwBuffer<T> {
+    prev: Vec<T>,
+    curr: Vec<T>,
+}
+
+impl<T> DtwBuffer<T> {
+    /// Creates an empty buffer.
+    pub fn new() -> Self {
+        DtwBuffer {
+            prev: Vec::new(),
+            curr: Vec::new(),
+        }
+    }
+}
+
+/// Computes the DTW distance between two sequences with the warping paths restricted to
+/// `window`.
+///
+/// Only the cells inside the window are visited, so a Sakoe-Chiba band of radius `r` costs
+/// `O(len(x) * r)` instead of `O(len(x) * len(y))`. Restricting the warping also prevents
+/// pathological alignments where one element absorbs a large part of the other sequence.
+/// Constrained distances are never smaller than the unconstrained [`dtw`], and [`lb_keogh`]
+/// with an envelope of the same radius bounds the Sakoe-Chiba form from below.
+///
+/// # Returns
+/// The constrained DTW distance; `0` if both sequences are empty and infinity if only one of
+/// them is or if no warping path fits in the window.
+///
+/// # Panics
+/// Panics if an Itakura `max_slope` is smaller than `1` or NaN.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[0.0, 0.0, 1.0, 2.0]);
+/// let y = arr1(&[0.0, 1.0, 2.0, 2.0]);
+/// assert_eq!(dtw(&x.view(), &y.view()), 0.0);
+/// let band = DtwWindow::SakoeChiba { band_radius: 0 };
+/// assert_eq!(dtw_constrained(&x.view(), &y.view(), band), euclidean(&x.view(), &y.view()));
+/// ```
+pub fn dtw_constrained<T>(x: &ArrayView1<T>, y: &ArrayView1<T>, window: DtwWindow) -> T
+where
+    T: Float,
+{
+    dtw_constrained_with_buffer(x, y, window, &mut DtwBuffer::new())
+}
+
+/// Computes [`dtw_constrained`] using the rows of `buffer` instead of allocating new ones.
+///
+/// # Panics
+/// Panics if an Itakura `max_slope` is smaller than `1` or NaN.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let query = arr1(&[0.0, 1.0, 0.0]);
+/// let window = DtwWindow::Itakura { max_slope: 2.0 };
+/// let mut buffer = DtwBuffer::new();
+/// for candidate in [arr1(&[0.0, 1.0, 0.0]), arr1(&[0.0, 1.0, 1.0, 0.0])] {
+///     let d = dtw_constrained_with_buffer(&query.view(), &candidate.view(), window, &mut buffer);
+///     assert_eq!(d, 0.0);
+/// }
+/// ```
+pub fn dtw_constrained_with_buffer<T>(
+    x: &ArrayView1<T>,
+    y: &ArrayView1<T>,
+    window: DtwWindow,
+    buffer: &mut DtwBuffer<T>,
+) -> T
+where
+    T: Float,
+{
+    if let DtwWindow::Itakura { max_slope } = window {
+        assert!(max_slope >= 1.0, "Itakura slope must be at least 1.");
+    }
+    constrained(x, y, window, T::infinity(), buffer).unwrap_or(T::infinity())
+}
+
+/// The DTW recurrence restricted to `window`, abandoning once a row exceeds `upper`.
+fn constrained<T>(
+    x: &ArrayView1<T>,
+    y: &ArrayView1<T>,
+    window: DtwWindow,
+    upper: T,
+    buffer: &mut DtwBuffer<T>,
+) -> Option<T>
+where
+    T: Float,
+{
+    let (n, m) = (x.len(), y.len());
+    if n == 0 || m == 0 {
+        let result = if n == m { T::zero() } else { T::infinity() };
+        return (result <= upper).then_some(result);
+    }
+
+    let bound = upper * upper;
+    let DtwBuffer { prev, curr } = buffer;
+    prev.resize(m + 1, T::infinity());
+    curr.resize(m + 1, T::infinity());
+    prev[0] = T::zero();
+
+    // Entries of `prev` outside `valid` belong to older rows and are treated as infinite.
+    let mut valid = 0..=0;
+    for i in 0..n {
+        let (lo, hi) = window.columns(i, n, m)?;
+        let mut row_min = T::infinity();
+        for j in lo..=hi {
+            let mut best = T::infinity();
+            if valid.contains(&j) {
+                best = best.min(prev[j]);
+            }
+            if valid.contains(&(j + 1)) {
+                best = best.min(prev[j + 1]);
+            }
+            if j > lo {
+                best = best.min(curr[j]);
+            }
+            let diff = x[i] - y[j];
+            curr[j + 1] = diff * diff + best;
+            row_min = row_min.min(curr[j + 1]);
+        }
+        if row_min > bound {
+            return None;
+        }
+        std::mem::swap(prev, curr);
+        valid = lo + 1..=hi + 1;
+    }
+
+    if !valid.contains(&m) {
+        return None;
+    }
+    let result = prev[m].sqrt();
+    (result <= upper).then_some(result)
+}
+
+/// The running minimum and maximum of a sequence over a window, as used by [`lb_keogh`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Envelope<T> {
+    /// `upper[i]` is the maximum of the sequence over `i - radius..=i + radius`.
+    pub upper: Array1<T>,
+    /// `lower[i]` is the minimum of the sequence over `i - radius..=i + radius`.
+    pub lower: Array1<T>,
+}
+
+/// Builds the envelope of `x` for a warping window of half-width `radius`.
+///
+/// Uses Lemire's streaming min/max with monotonic deques, so the cost is `O(len(x))`
+/// regardless of `radius`. The query envelope only needs to be built once per query and can
+/// then be reused against every candidate.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let env = envelope(&arr1(&[1.0, 3.0, 2.0, 0.0]).view(), 1);
+/// assert_eq!(env.upper, arr1(&[3.0, 3.0, 3.0, 2.0]));
+/// assert_eq!(env.lower, arr1(&[1.0, 1.0, 0.0, 0.0]));
+/// ```
+pub fn envelope<T>(x: &ArrayView1<T>, radius: usize) -> Envelope<T>
+where
+    T: Float,
+{
+    let n = x.len();
+    let radius = radius.min(n);
+    let mut upper = Array1::zeros(n);
+    let mut lower = Array1::zeros(n);
+    let mut max_queue: VecDeque<usize> = VecDeque::new();
+    let mut min_queue: VecDeque<usize> = VecDeque::new();
+
+    for j in 0..n + radius {
+        if j < n {
+            while max_queue.back().is_some_and(|&k| x[k] <= x[j]) {
+                max_queue.pop_back();
+            }
+            max_queue.push_back(j);
+            while min_queue.back().is_some_and(|&k| x[k] >= x[j]) {
+                min_queue.pop_back();
+            }
+            min_queue.push_back(j);
+        }
+
+        // Once `j` is `radius` past position `i`, the window around `i` is complete.
+        if j >= radius {
+            let i = j - radius;
+            while max_queue.front().is_some_and(|&k| k + radius < i) {
+                max_queue.pop_front();
+            }
+            while min_queue.front().is_some_and(|&k| k + radius < i) {
+                min_queue.pop_front();
+            }
+            upper[i] = x[max_queue[0]];
+            lower[i] = x[min_queue[0]];
+        }
+    }
+
+    Envelope { upper, lower }
+}
+
+/// Computes the LB_Keogh lower bound on the DTW distance between a query and a candidate.
+///
+/// ..math::
+///    LB(q, c) = \sqrt{\sum_i \begin{cases} (c_i - U_i)^2 & c_i > U_i \\
+///    (c_i - L_i)^2 & c_i < L_i \\ 0 & \text{otherwise} \end{cases}}
+///
+/// where `U` and `L` form the [`envelope`] of the query. The bound holds for DTW restricted to
+/// a warping window of at most the envelope's `radius`, such as [`dtw_constrained`] with a
+/// Sakoe-Chiba band; with a radius of at least `len(query) - 1` it also bounds the
+/// unconstrained [`dtw`].
+///
+/// # Panics
+/// Panics if the candidate and the envelope do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let query = arr1(&[0.0, 1.0, 0.0, 1.0]);
+/// let candidate = arr1(&[3.0, 0.5, 0.0, -1.0]);
+/// let env = envelope(&query.view(), 4);
+/// assert_eq!(lb_keogh(&env, &candidate.view()), 5.0_f64.sqrt());
+/// assert!(lb_keogh(&env, &candidate.view()) <= dtw(&query.view(), &candidate.view()));
+/// ```
+pub fn lb_keogh<T>(envelope: &Envelope<T>, candidate: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    assert_eq!(
+        envelope.upper.len(),
+        candidate.len(),
+        "Input arrays must have the same length."
+    );
+
+    let mut result = T::zero();
+    for i in 0..candidate.len() {
+        let c = candidate[i];
+        let diff = if c > envelope.upper[i] {
+            c - envelope.upper[i]
+        } else if c < envelope.lower[i] {
+            c - envelope.lower[i]
+        } else {
+            T::zero()
+        };
+        result = result + diff * diff;
+    }
+    result.sqrt()
+}
+
+/// Computes the LB_Kim lower bound on the DTW distance from the first and last elements.
+///
+/// Every warping path starts by aligning the first elements and ends by aligning the last
+/// ones, so their costs alone bound [`dtw`] from below. This is the constant-time "first and
+/// last" form of the bound used as the cheapest pruning step in the UCR suite; the sequences
+/// may have different lengths.
+///
+/// # Returns
+/// The lower bound; `0` if either sequence is empty.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[1.0, 5.0, 2.0]);
+/// let y = arr1(&[4.0, 0.0, 0.0, 6.0]);
+/// assert_eq!(lb_kim(&x.view(), &y.view()), 5.0);
+/// ```
+pub fn lb_kim<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    let (n, m) = (x.len(), y.len());
+    if n == 0 || m == 0 {
+        return T::zero();
+    }
+
+    let first = x[0] - y[0];
+    let mut result = first * first;
+    if n > 1 || m > 1 {
+        let last = x[n - 1] - y[m - 1];
+        result = result + last * last;
+    }
+    result.sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::euclidean;
+    use ndarray::{arr1, Array1};
+
+    fn series(n: usize, phase: f64) -> Array1<f64> {
+        (0..n).map(|i| ((i as f64 + phase) * 0.7).sin()).collect()
+    }
+
+    #[test]
+    fn test_dtw_known_values() {
+        let x = arr1(&[1.0, 2.0, 3.0]);
+        let y = arr1(&[2.0, 2.0, 2.0, 3.0, 4.0]);
+        // Best alignment: (1,2) (2,2) (2,2) (3,3) (3,4).
+        assert_eq!(dtw(&x.view(), &y.view()), 2.0_f64.sqrt());
+        assert_eq!(dtw(&x.view(), &x.view()), 0.0);
+
+        let empty = Array1::<f64>::zeros(0);
+        assert_eq!(dtw(&empty.view(), &empty.view()), 0.0);
+        assert_eq!(dtw(&x.view(), &empty.view()), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_dtw_bounded_by_euclidean() {
+        let x = series(30, 0.0);
+        let y = series(30, 1.3);
+        let d = dtw(&x.view(), &y.view());
+        assert!(d <= euclidean(&x.view(), &y.view()));
+        assert_eq!(dtw(&y.view(), &x.view()), d);
+
+        assert_eq!(dtw_bounded(&x.view(), &y.view(), d), Some(d));
+        assert_eq!(dtw_bounded(&x.view(), &y.view(), d * 0.99), None);
+    }
+
+    #[test]
+    fn test_envelope_matches_brute_force() {
+        let x = series(25, 0.4);
+        for radius in [0, 1, 3, 10, 100] {
+            let env = envelope(&x.view(), radius);
+            for i in 0..x.len() {
+                let window = x.slice(ndarray::s![
+                    i.saturating_sub(radius)..(i + radius + 1).min(x.len())
+                ]);
+                assert_eq!(env.upper[i], window.fold(f64::MIN, |a, &b| a.max(b)));
+                assert_eq!(env.lower[i], window.fold(f64::MAX, |a, &b| a.min(b)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_lower_bounds() {
+        let query = series(40, 0.0);
+        let env = envelope(&query.view(), query.len());
+        for phase in [0.5, 2.0, 7.5] {
+            let candidate = series(40, phase).mapv(|v| v * 1.5 + 0.2);
+            let d = dtw(&query.view(), &candidate.view());
+            assert!(lb_keogh(&env, &candidate.view()) <= d);
+            assert!(lb_kim(&query.view(), &candidate.view()) <= d);
+        }
+
+        let single = arr1(&[2.0]);
+        assert_eq!(lb_kim(&single.view(), &arr1(&[5.0]).view()), 3.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Input arrays must have the same length.")]
+    fn test_lb_keogh_length_mismatch() {
+        let env = envelope(&series(5, 0.0).view(), 1);
+        lb_keogh(&env, &series(4, 0.0).view());
+    }
+
+    #[test]
+    fn test_sakoe_chiba_band() {
+        let x = series(30, 0.0);
+        let y = series(30, 1.3);
+        let (x, y) = (x.view(), y.view());
+        let band = |band_radius| DtwWindow::SakoeChiba { band_radius };
+
+        assert_eq!(dtw_constrained(&x, &y, band(0)), euclidean(&x, &y));
+        assert_eq!(dtw_constrained(&x, &y, band(29)), dtw(&x, &y));
+        assert_eq!(dtw_constrained(&x, &y, DtwWindow::Full), dtw(&x, &y));
+        let mut previous = f64::INFINITY;
+        for radius in [0, 1, 2, 5, 10, 29] {
+            let d = dtw_constrained(&x, &y, band(radius));
+            assert!(d <= previous);
+            assert!(lb_keogh(&envelope(&x, radius), &y) <= d);
+            previous = d;
+        }
+
+        // The band is widened to the difference in length.
+        let z = series(34, 0.0);
+        assert!(dtw_constrained(&x, &z.view(), band(0)).is_finite());
+    }
+
+    #[test]
+    fn test_itakura_parallelogram() {
+        let x = series(20, 0.0);
+        let y = series(20, 0.8);
+        let (x, y) = (x.view(), y.view());
+        let itakura = |max_slope| DtwWindow::Itakura { max_slope };
+
+        // A slope of one only leaves the diagonal.
+        assert!((dtw_constrained(&x, &y, itakura(1.0)) - euclidean(&x, &y)).abs() < 1e-12);
+        let d = dtw_constrained(&x, &y, itakura(2.0));
+        assert!(d >= dtw(&x, &y) && d <= euclidean(&x, &y));
+
+        // y is three times longer than the slope allows.
+        let long = series(61, 0.0);
+        assert_eq!(
+            dtw_constrained(&x, &long.view(), itakura(2.0)),
+            f64::INFINITY
+        );
+        assert!(dtw_constrained(&x, &long.view(), itakura(3.5)).is_finite());
+    }
+
+    #[test]
+    fn test_buffer_reuse() {
+        let query = series(25, 0.0);
+        let mut buffer = DtwBuffer::new();
+        for (len, phase) in [(40, 0.3), (10, 2.0), (25, 1.0), (3, 0.0)] {
+            let candidate = series(len, phase);
+            for window in [
+                DtwWindow::Full,
+                DtwWindow::SakoeChiba { band_radius: 4 },
+                DtwWindow::Itakura { max_slope: 3.0 },
+            ] {
+                let expected = dtw_constrained(&query.view(), &candidate.view(), window);
+                let d = dtw_constrained_with_buffer(
+                    &query.view(),
+                    &candidate.view(),
+                    window,
+                    &mut buffer,
+                );
+                assert_eq!(d.to_bits(), expected.to_bits());
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Itakura slope must be at least 1.")]
+    fn test_itakura_slope_too_small() {
+        let x = series(5, 0.0);
+        dtw_constrained(&x.view(), &x.view(), DtwWindow::Itakura { max_slope: 0.5 });
+    }
+}
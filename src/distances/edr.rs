@@ -0,0 +1,90 @@
+use ndarray::ArrayView1;
+use num::Float;
+
+use super::elastic::align;
+
+/// Computes the edit distance on real sequences (EDR) between two sequences.
+///
+/// EDR (Chen, Özsu & Oria, 2005) counts the insertions, deletions and substitutions needed to
+/// turn one sequence into the other, where two elements match when they differ by at most
+/// `epsilon`:
+///
+/// ..math::
+///    E(i, j) = \min(E(i - 1, j - 1) + s(x_i, y_j), E(i - 1, j) + 1, E(i, j - 1) + 1),
+///    \quad s(a, b) = \begin{cases} 0 & |a - b| \le \epsilon \\ 1 & \text{otherwise}
+///    \end{cases}
+///
+/// Every mismatch costs the same regardless of its size, so a few outliers cannot dominate
+/// the distance the way they dominate [`dtw`](crate::dtw). Divide by the length of the longer
+/// sequence for a value between `0` and `1`.
+///
+/// # Arguments
+/// * `x` - The first sequence.
+/// * `y` - The second sequence.
+/// * `epsilon` - The matching tolerance.
+///
+/// # Returns
+/// The number of edit operations, which is the length of the other sequence if one of them
+/// is empty.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[0.0, 1.0, 2.0, 3.0]);
+/// let y = arr1(&[0.1, 1.1, 50.0, 2.9]);
+/// // Only the outlier needs to be substituted.
+/// assert_eq!(edr(&x.view(), &y.view(), 0.25), 1.0);
+/// ```
+pub fn edr<T>(x: &ArrayView1<T>, y: &ArrayView1<T>, epsilon: T) -> T
+where
+    T: Float,
+{
+    align(
+        x.len(),
+        y.len(),
+        |i, j| T::from(i + j).unwrap(),
+        |i, j, diagonal, up, left| {
+            let substitution = if (x[i] - y[j]).abs() <= epsilon {
+                T::zero()
+            } else {
+                T::one()
+            };
+            (diagonal + substitution).min(up.min(left) + T::one())
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{arr1, Array1};
+
+    #[test]
+    fn test_edr_edit_operations() {
+        let x = arr1(&[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(edr(&x.view(), &x.view(), 0.0), 0.0);
+
+        // One deletion.
+        let y = arr1(&[1.0, 3.0, 4.0]);
+        assert_eq!(edr(&x.view(), &y.view(), 0.0), 1.0);
+        assert_eq!(edr(&y.view(), &x.view(), 0.0), 1.0);
+
+        // Nothing matches with a negative tolerance.
+        assert_eq!(edr(&x.view(), &x.view(), -1.0), 4.0);
+        assert_eq!(edr(&x.view(), &y.view(), -1.0), 4.0);
+    }
+
+    #[test]
+    fn test_edr_tolerance_and_empty() {
+        let x = arr1(&[0.0f32, 0.5, 1.0]);
+        let y = arr1(&[0.2f32, 0.7, 1.2]);
+        assert_eq!(edr(&x.view(), &y.view(), 0.1), 3.0);
+        assert_eq!(edr(&x.view(), &y.view(), 0.3), 0.0);
+
+        let empty = Array1::<f32>::zeros(0);
+        assert_eq!(edr(&empty.view(), &y.view(), 0.3), 3.0);
+        assert_eq!(edr(&empty.view(), &empty.view(), 0.3), 0.0);
+    }
+}
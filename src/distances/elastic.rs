@@ -0,0 +1,26 @@
+//! The dynamic program shared by the elastic sequence distances.
+
+/// Fills the `(n + 1) * (m + 1)` alignment grid of two sequences row by row and returns its
+/// last cell.
+///
+/// `boundary(i, j)` gives the cells of the first row and column, where `i` or `j` is zero.
+/// `step(i, j, diagonal, up, left)` computes the cell that aligns element `i` of the first
+/// sequence with element `j` of the second from its three predecessors. Only two rows are
+/// kept, so the memory use is `O(m)`.
+pub(crate) fn align<T, B, S>(n: usize, m: usize, boundary: B, mut step: S) -> T
+where
+    T: Copy,
+    B: Fn(usize, usize) -> T,
+    S: FnMut(usize, usize, T, T, T) -> T,
+{
+    let mut prev: Vec<T> = (0..=m).map(|j| boundary(0, j)).collect();
+    let mut curr = prev.clone();
+    for i in 0..n {
+        curr[0] = boundary(i + 1, 0);
+        for j in 0..m {
+            curr[j + 1] = step(i, j, prev[j], prev[j + 1], curr[j]);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[m]
+}
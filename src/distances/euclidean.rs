@@ -1,6 +1,8 @@
-use ndarray::ArrayView1;
+use ndarray::{ArrayView1, FoldWhile, Zip};
 use num::{Float, Zero};
 
+use crate::utils::NeumaierSum;
+
 /// Computes the Euclidean distance between two vectors.
 ///
 /// # Arguments
@@ -21,18 +23,153 @@ where
 {
     assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
 
+    let result = Zip::from(x).and(y).fold(T::zero(), |acc, &xi, &yi| {
+        let diff = xi - yi;
+        acc + diff * diff
+    });
+
+    result.sqrt()
+}
+
+/// Computes the Euclidean distance between two vectors, abandoning early once it exceeds `upper`.
+///
+/// The squared partial sum is compared against `upper * upper` after every coordinate, so the
+/// loop stops as soon as the distance is known to be larger than the bound. This is useful in
+/// nearest-neighbor searches where only distances below the current best matter.
+///
+/// # Returns
+///
+/// `Some(distance)` if the distance is at most `upper`, otherwise `None`.
+///
+/// # Panics
+///
+/// This function will panic if the input arrays do not have the same length.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::euclidean_bounded;
+///
+/// let x = arr1(&[0.0, 0.0]);
+/// let y = arr1(&[3.0, 4.0]);
+/// assert_eq!(euclidean_bounded(&x.view(), &y.view(), 6.0), Some(5.0));
+/// assert_eq!(euclidean_bounded(&x.view(), &y.view(), 4.0), None);
+/// ```
+pub fn euclidean_bounded<T>(x: &ArrayView1<T>, y: &ArrayView1<T>, upper: T) -> Option<T>
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+
+    // Squaring would turn a negative bound into a positive one.
+    if upper < T::zero() {
+        return None;
+    }
+    let bound = upper * upper;
     let mut result = T::zero();
-    for i in 0..x.len() {
-        let diff = x[i] - y[i];
-        result = result + diff * diff;
+    let exceeded = Zip::from(x)
+        .and(y)
+        .fold_while((), |(), &xi, &yi| {
+            let diff = xi - yi;
+            result = result + diff * diff;
+            if result > bound {
+                FoldWhile::Done(())
+            } else {
+                FoldWhile::Continue(())
+            }
+        })
+        .is_done();
+    if exceeded {
+        return None;
     }
 
-    result.sqrt()
+    if result <= bound {
+        Some(result.sqrt())
+    } else {
+        None
+    }
+}
+
+/// Computes the Euclidean distance between two vectors using compensated summation.
+///
+/// The squared differences are accumulated with [`NeumaierSum`], which keeps the result
+/// accurate for very high-dimensional inputs (e.g. 100k-dimensional `f32` vectors) where the
+/// plain [`euclidean`] loses precision. It is slower than `euclidean`.
+///
+/// # Panics
+///
+/// This function will panic if the input arrays do not have the same length.
+pub fn euclidean_accurate<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+
+    let mut result = NeumaierSum::new();
+    Zip::from(x).and(y).for_each(|&xi, &yi| {
+        let diff = xi - yi;
+        result.add(diff * diff);
+    });
+
+    result.sum().sqrt()
+}
+
+/// Computes the Euclidean distance between two vectors without intermediate overflow or underflow.
+///
+/// [`euclidean`] squares the differences before summing, which overflows for large magnitudes
+/// (e.g. `f32` values around `1e20`) and underflows to zero for tiny ones. This variant makes two
+/// passes in the style of `hypot`: it finds the largest absolute difference `s`, accumulates
+/// `((x_i - y_i) / s)^2`, and rescales the square root of the sum by `s`.
+///
+/// # Panics
+///
+/// This function will panic if the input arrays do not have the same length.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::{euclidean, euclidean_stable};
+///
+/// let x = arr1(&[3e30f32, 0.0]);
+/// let y = arr1(&[0.0f32, 4e30]);
+/// assert!(euclidean(&x.view(), &y.view()).is_infinite());
+/// assert_eq!(euclidean_stable(&x.view(), &y.view()), 5e30);
+/// ```
+pub fn euclidean_stable<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+
+    let scale = Zip::from(x)
+        .and(y)
+        .fold_while(T::zero(), |scale, &xi, &yi| {
+            let diff = (xi - yi).abs();
+            if diff.is_nan() {
+                FoldWhile::Done(diff)
+            } else {
+                FoldWhile::Continue(scale.max(diff))
+            }
+        })
+        .into_inner();
+    if scale.is_nan() || scale.is_zero() || scale.is_infinite() {
+        return scale;
+    }
+
+    let result = Zip::from(x).and(y).fold(T::zero(), |acc, &xi, &yi| {
+        let diff = (xi - yi) / scale;
+        acc + diff * diff
+    });
+
+    scale * result.sqrt()
 }
 
 #[cfg(test)]
 mod tests {
-    use ndarray::arr1;
+    use crate::distances::reference::reference_inputs;
+    use ndarray::{arr1, Array1};
 
     use super::*; // Import the function to be tested
 
@@ -54,7 +191,7 @@ mod tests {
         let y = arr1(&[4.0f32, 5.0, 6.0]);
 
         let dist = euclidean(&x.view(), &y.view());
-        assert!((dist - 5.1961524).abs() < 1e-6, "Test failed for f32.");
+        assert!((dist - 5.196152).abs() < 1e-6, "Test failed for f32.");
     }
 
     #[test]
@@ -73,4 +210,59 @@ mod tests {
         let y = arr1(&[4.0f64, 5.0, 6.0]);
         euclidean(&x.view(), &y.view()); // This should panic
     }
+
+    #[test]
+    fn test_euclidean_bounded() {
+        let x = arr1(&[1.0f64, 2.0, 3.0]);
+        let y = arr1(&[4.0f64, 5.0, 6.0]);
+        let dist = euclidean(&x.view(), &y.view());
+
+        assert_eq!(euclidean_bounded(&x.view(), &y.view(), 10.0), Some(dist));
+        assert_eq!(euclidean_bounded(&x.view(), &y.view(), 5.0), None);
+        assert_eq!(euclidean_bounded(&x.view(), &y.view(), f64::NAN), None);
+        assert_eq!(euclidean_bounded(&x.view(), &y.view(), -1.0), None);
+        assert_eq!(euclidean_bounded(&x.view(), &x.view(), -1.0), None);
+    }
+
+    #[test]
+    fn test_euclidean_accurate_high_dimensional_f32() {
+        let n = 100_000;
+        let x: Array1<f32> = (0..n).map(|i| ((i as f32) * 0.001).sin()).collect();
+        let y: Array1<f32> = (0..n).map(|i| ((i as f32) * 0.002).cos()).collect();
+
+        let reference = euclidean(&x.mapv(f64::from).view(), &y.mapv(f64::from).view());
+        let accurate = euclidean_accurate(&x.view(), &y.view()) as f64;
+        let naive = euclidean(&x.view(), &y.view()) as f64;
+
+        assert!((accurate - reference).abs() / reference < 1e-6);
+        assert!((accurate - reference).abs() <= (naive - reference).abs());
+    }
+
+    #[test]
+    fn test_euclidean_stable() {
+        let x = arr1(&[1.0f64, 2.0, 3.0]);
+        let y = arr1(&[4.0f64, 5.0, 6.0]);
+        let dist = euclidean_stable(&x.view(), &y.view());
+        assert!((dist - euclidean(&x.view(), &y.view())).abs() < 1e-12);
+
+        // Tiny magnitudes underflow when squared.
+        let x = arr1(&[3e-30f32, 0.0]);
+        let y = arr1(&[0.0f32, 4e-30]);
+        assert_eq!(euclidean(&x.view(), &y.view()), 0.0);
+        assert!((euclidean_stable(&x.view(), &y.view()) - 5e-30).abs() < 1e-36);
+
+        let z = arr1(&[0.0f32, f32::NAN]);
+        assert!(euclidean_stable(&x.view(), &z.view()).is_nan());
+        assert_eq!(euclidean_stable(&x.view(), &x.view()), 0.0);
+    }
+
+    #[test]
+    fn test_reference_values() {
+        let (x, y) = reference_inputs();
+        let (x, y) = (x.view(), y.view());
+        assert_eq!(euclidean(&x, &y), 7.437237390321759);
+        assert_eq!(euclidean_bounded(&x, &y, 10.0), Some(7.437237390321759));
+        assert_eq!(euclidean_accurate(&x, &y), 7.437237390321759);
+        assert_eq!(euclidean_stable(&x, &y), 7.437237390321759);
+    }
 }
@@ -1,6 +1,8 @@
-use ndarray::Array1;
+use ndarray::{Array1, Zip};
 use num::Float;
 
+use crate::DistanceConfig;
+
 /// Computes the Euclidean distance and its gradient between two vectors.
 ///
 /// The function calculates the Euclidean distance between two input vectors `x` and `y`
@@ -28,31 +30,44 @@ use num::Float;
 ///
 /// - If the input arrays do not have the same length, the function will panic with an appropriate error message.
 pub fn euclidean_grad<T>(x: &Array1<T>, y: &Array1<T>) -> (T, Vec<T>)
+where
+    T: Float,
+{
+    euclidean_grad_with_config(x, y, &DistanceConfig::default())
+}
+
+/// Computes the Euclidean distance and its gradient, using the stabilization epsilon from `config`.
+///
+/// See [`euclidean_grad`] for details; this variant lets callers tune the epsilon added to the
+/// distance in the gradient's denominator.
+pub fn euclidean_grad_with_config<T>(
+    x: &Array1<T>,
+    y: &Array1<T>,
+    config: &DistanceConfig<T>,
+) -> (T, Vec<T>)
 where
     T: Float,
 {
     assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
 
-    let mut result = T::zero();
-    for i in 0..x.len() {
-        let diff = x[i] - y[i];
-        result = result + diff * diff;
-    }
+    let result = Zip::from(x).and(y).fold(T::zero(), |acc, &xi, &yi| {
+        let diff = xi - yi;
+        acc + diff * diff
+    });
 
     let distance = result.sqrt();
-    let mut gradient = Vec::with_capacity(x.len());
-
-    // Calculate the gradient
-    for i in 0..x.len() {
-        let grad = (x[i] - y[i]) / (T::from(1e-6).unwrap() + distance);
-        gradient.push(grad);
-    }
+    let gradient = x
+        .iter()
+        .zip(y.iter())
+        .map(|(&xi, &yi)| (xi - yi) / (config.epsilon + distance))
+        .collect();
 
     (distance, gradient)
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::distances::reference::reference_inputs;
     use ndarray::arr1;
 
     use super::*; // Import the function to be tested
@@ -88,7 +103,7 @@ mod tests {
 
         let (dist, grad) = euclidean_grad(&x, &y);
         assert!(
-            (dist - 5.1961524).abs() < 1e-6,
+            (dist - 5.196152).abs() < 1e-6,
             "Distance is incorrect for f32."
         );
         assert!(
@@ -123,6 +138,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_euclidean_grad_with_config() {
+        let x = arr1(&[1e-4f64, 0.0]);
+        let y = arr1(&[0.0f64, 0.0]);
+
+        let (_, default_grad) = euclidean_grad(&x, &y);
+        let config = DistanceConfig::default().with_epsilon(1e-12);
+        let (dist, grad) = euclidean_grad_with_config(&x, &y, &config);
+
+        assert!((dist - 1e-4).abs() < 1e-12);
+        assert!((grad[0] - 1.0).abs() < 1e-6);
+        assert!((default_grad[0] - 1.0).abs() > 1e-3);
+    }
+
     #[test]
     #[should_panic(expected = "Input arrays must have the same length.")]
     fn test_euclidean_grad_different_lengths() {
@@ -130,4 +159,23 @@ mod tests {
         let y = arr1(&[4.0f64, 5.0, 6.0]);
         euclidean_grad(&x, &y); // This should panic
     }
+
+    #[test]
+    fn test_reference_values() {
+        let (x, y) = reference_inputs();
+        let (dist, grad) = euclidean_grad(&x, &y);
+        assert_eq!(dist, 7.437237390321759);
+        assert_eq!(
+            grad,
+            vec![
+                -0.13445851101146925,
+                -0.2689170220229385,
+                0.6722925550573462,
+                0.0,
+                0.03361462775286731,
+                -0.06722925550573462,
+                0.6722925550573462
+            ]
+        );
+    }
 }
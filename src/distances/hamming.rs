@@ -1,24 +1,113 @@
-use ndarray::ArrayView1;
+use ndarray::{Array1, ArrayView1};
 
-/// Computes the Hamming distance between two vectors `x` and `y`.
+/// A sequence whose positions can be compared for the Hamming distance.
+///
+/// This is implemented for ndarray vectors, slices, arrays and `Vec`s of any `PartialEq` type,
+/// including byte strings such as `b"abc"[..]`, and for `str` and `String`, which are compared by
+/// `char`s rather than bytes.
+///
+/// The other sequence defaults to the same type; views are compared with views of any lifetime.
+pub trait HammingSequence<Rhs: ?Sized = Self> {
+    /// Returns the number of positions at which `self` and `other` differ, and their length.
+    ///
+    /// # Panics
+    /// Panics if the sequences do not have the same length.
+    fn mismatches(&self, other: &Rhs) -> (usize, usize);
+}
+
+/// Counts the differing pairs of two iterators, which must have the same length.
+fn count_mismatches<I, J, T>(mut x: I, mut y: J) -> (usize, usize)
+where
+    I: Iterator<Item = T>,
+    J: Iterator<Item = T>,
+    T: PartialEq,
+{
+    let (mut differing, mut len) = (0, 0);
+    loop {
+        match (x.next(), y.next()) {
+            (Some(a), Some(b)) => {
+                differing += usize::from(a != b);
+                len += 1;
+            }
+            (None, None) => return (differing, len),
+            _ => panic!("Vectors must have the same length."),
+        }
+    }
+}
+
+impl<T: PartialEq> HammingSequence for [T] {
+    fn mismatches(&self, other: &Self) -> (usize, usize) {
+        assert_eq!(
+            self.len(),
+            other.len(),
+            "Vectors must have the same length."
+        );
+        count_mismatches(self.iter(), other.iter())
+    }
+}
+
+impl<T: PartialEq, const N: usize> HammingSequence for [T; N] {
+    fn mismatches(&self, other: &Self) -> (usize, usize) {
+        self[..].mismatches(&other[..])
+    }
+}
+
+impl<T: PartialEq> HammingSequence for Vec<T> {
+    fn mismatches(&self, other: &Self) -> (usize, usize) {
+        self[..].mismatches(&other[..])
+    }
+}
+
+impl<'b, T: PartialEq> HammingSequence<ArrayView1<'b, T>> for ArrayView1<'_, T> {
+    fn mismatches(&self, other: &ArrayView1<'b, T>) -> (usize, usize) {
+        assert_eq!(
+            self.len(),
+            other.len(),
+            "Vectors must have the same length."
+        );
+        count_mismatches(self.iter(), other.iter())
+    }
+}
+
+impl<T: PartialEq> HammingSequence for Array1<T> {
+    fn mismatches(&self, other: &Self) -> (usize, usize) {
+        self.view().mismatches(&other.view())
+    }
+}
+
+impl HammingSequence for str {
+    fn mismatches(&self, other: &Self) -> (usize, usize) {
+        count_mismatches(self.chars(), other.chars())
+    }
+}
+
+impl HammingSequence for String {
+    fn mismatches(&self, other: &Self) -> (usize, usize) {
+        self.as_str().mismatches(other.as_str())
+    }
+}
+
+/// Computes the Hamming distance between two sequences `x` and `y`.
 ///
 /// The Hamming distance is a measure of the number of positions at which the corresponding elements of two
-/// sequences of equal length are different. This implementation assumes that `x` and `y` are binary vectors (0 or 1).
+/// sequences of equal length are different. Any [`HammingSequence`] can be compared: ndarray vectors, slices of
+/// `PartialEq` values such as bytes, and strings, which are compared by `char`s.
 ///
 /// The formula for the Hamming distance is:
 ///
 /// ..math::
-///     D(x, y) = \frac{\text{number of different bits}}{\text{length of the vector}}
+///     D(x, y) = \frac{\text{number of different positions}}{\text{length of the sequence}}
 ///
 /// # Parameters:
-/// - `x`: A reference to an `ArrayView1<T>` representing the first vector.
-/// - `y`: A reference to an `ArrayView1<T>` representing the second vector.
+/// - `x`: A reference to the first sequence.
+/// - `y`: A reference to the second sequence.
 ///
 /// # Returns:
-/// The normalized Hamming distance between `x` and `y` as a scalar of type `T`.
+/// The normalized Hamming distance between `x` and `y`, which is NaN for empty sequences. Use [`hamming_count`]
+/// for the number of differing positions.
 ///
 /// # Panics:
-/// - This function may panic if the lengths of `x` and `y` do not match.
+/// - This function panics if the lengths of `x` and `y` do not match.
 ///
 /// # Example:
 /// ```rust
@@ -28,24 +117,42 @@ use ndarray::ArrayView1;
 /// let y = arr1(&[0, 0, 1, 1]);
 /// let dist = hamming(&x.view(), &y.view());
 /// println!("Hamming Distance: {}", dist);
+///
+/// assert_eq!(hamming("karolin", "kathrin"), 3.0 / 7.0);
+/// assert_eq!(hamming(&b"abcd"[..], &b"abce"[..]), 0.25);
 /// ```
-pub fn hamming<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> f64
+pub fn hamming<S, R>(x: &S, y: &R) -> f64
 where
-    T: PartialEq,
+    S: HammingSequence<R> + ?Sized,
+    R: ?Sized,
 {
-    // Ensure that the vectors x and y have the same length.
-    assert_eq!(x.len(), y.len(), "Vectors must have the same length.");
-
-    // Count the number of differing positions
-    let mut result = 0.0;
-    for i in 0..x.len() {
-        if x[i] != y[i] {
-            result += 1.0;
-        }
-    }
+    let (differing, len) = x.mismatches(y);
 
     // Return the normalized Hamming distance (number of differing positions divided by the length of the vector)
-    result / x.len() as f64
+    differing as f64 / len as f64
+}
+
+/// Computes the number of positions at which two sequences differ.
+///
+/// This is the raw count behind the normalized [`hamming`], for any [`HammingSequence`].
+///
+/// # Panics
+/// Panics if the lengths of `x` and `y` do not match.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// assert_eq!(hamming_count("karolin", "kathrin"), 3);
+/// assert_eq!(hamming_count("日本語", "日本人"), 1);
+/// assert_eq!(hamming_count(&[1, 2, 3], &[1, 2, 4]), 1);
+/// ```
+pub fn hamming_count<S, R>(x: &S, y: &R) -> usize
+where
+    S: HammingSequence<R> + ?Sized,
+    R: ?Sized,
+{
+    x.mismatches(y).0
 }
 
 #[cfg(test)]
@@ -103,4 +210,23 @@ mod tests {
 
         hamming(&x.view(), &y.view());
     }
+
+    #[test]
+    fn test_hamming_sequences() {
+        assert_eq!(hamming_count("", ""), 0);
+        assert_eq!(hamming_count("toned", "roses"), 3);
+        assert_eq!(hamming_count(&b"toned"[..], &b"roses"[..]), 3);
+        assert_eq!(hamming_count(&vec!['a', 'b'], &vec!['a', 'c']), 1);
+        assert_eq!(hamming(&String::from("ab"), &String::from("ba")), 1.0);
+        // "é" is two bytes but one char.
+        assert_eq!(hamming_count("café", "cafe"), 1);
+        let x = arr1(&[1.0, 2.0, 3.0]);
+        assert_eq!(hamming_count(&x, &arr1(&[1.0, 0.0, 3.0])), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Vectors must have the same length.")]
+    fn test_hamming_different_length_strings() {
+        hamming("abc", "ab");
+    }
 }
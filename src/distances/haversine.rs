@@ -24,12 +24,85 @@ pub fn haversine<T: Float>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T {
         panic!("Haversine is only defined for 2-dimensional data");
     }
 
-    let sin_lat = (T::from(0.5).unwrap() * (x[0] - y[0])).sin();
-    let sin_long = (T::from(0.5).unwrap() * (x[1] - y[1])).sin();
-    let result = (sin_lat.powi(2) + x[0].cos() * y[0].cos() * sin_long.powi(2)).sqrt();
+    central_angle(x[0], x[1], y[0], y[1])
+}
+
+/// Returns the central angle between two (latitude, longitude) points in radians, by the
+/// haversine formula.
+pub(crate) fn central_angle<T: Float>(lat1: T, lon1: T, lat2: T, lon2: T) -> T {
+    let sin_lat = (T::from(0.5).unwrap() * (lat1 - lat2)).sin();
+    let sin_long = (T::from(0.5).unwrap() * (lon1 - lon2)).sin();
+    let result = (sin_lat.powi(2) + lat1.cos() * lat2.cos() * sin_long.powi(2)).sqrt();
     T::from(2.0).unwrap() * result.asin()
 }
 
+/// The mean radius of the Earth in kilometers, as defined by the IUGG.
+pub const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+/// The mean radius of the Earth in meters, as defined by the IUGG.
+pub const EARTH_RADIUS_M: f64 = EARTH_RADIUS_KM * 1000.0;
+
+/// Computes the great-circle distance between two points on a sphere of the given radius.
+///
+/// This is the [`haversine`] angle multiplied by `radius`, so the result is in the units of the
+/// radius. The points are (latitude, longitude) in radians.
+///
+/// # Arguments
+/// * `x` - The first point (latitude, longitude), in radians.
+/// * `y` - The second point (latitude, longitude), in radians.
+/// * `radius` - The radius of the sphere.
+///
+/// # Panics
+/// Panics if either point does not have two coordinates.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[0.0, 0.0]);
+/// let y = arr1(&[0.0, std::f64::consts::PI]);
+/// assert_eq!(haversine_with_radius(&x.view(), &y.view(), 2.0), 2.0 * std::f64::consts::PI);
+/// ```
+pub fn haversine_with_radius<T: Float>(x: &ArrayView1<T>, y: &ArrayView1<T>, radius: T) -> T {
+    haversine(x, y) * radius
+}
+
+/// Computes the great-circle distance in kilometers between two points on the Earth, modeled as
+/// a sphere of radius [`EARTH_RADIUS_KM`].
+///
+/// The points are (latitude, longitude) in radians. The spherical model is within about 0.5% of
+/// the distance on the WGS84 ellipsoid.
+///
+/// # Panics
+/// Panics if either point does not have two coordinates.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// // Paris and London.
+/// let paris = arr1(&[48.8566f64.to_radians(), 2.3522f64.to_radians()]);
+/// let london = arr1(&[51.5074f64.to_radians(), (-0.1278f64).to_radians()]);
+/// let km = haversine_km(&paris.view(), &london.view());
+/// assert!((km - 343.6).abs() < 0.5);
+/// ```
+pub fn haversine_km<T: Float>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T {
+    haversine_with_radius(x, y, T::from(EARTH_RADIUS_KM).unwrap())
+}
+
+/// Computes the great-circle distance in meters between two points on the Earth, modeled as a
+/// sphere of radius [`EARTH_RADIUS_M`].
+///
+/// The points are (latitude, longitude) in radians.
+///
+/// # Panics
+/// Panics if either point does not have two coordinates.
+pub fn haversine_m<T: Float>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T {
+    haversine_with_radius(x, y, T::from(EARTH_RADIUS_M).unwrap())
+}
+
 #[cfg(test)]
 mod tests {
     use std::f64::consts::PI;
@@ -93,4 +166,19 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_haversine_physical_units() {
+        // A quarter of the equator.
+        let x = arr1(&[0.0, 0.0]);
+        let y = arr1(&[0.0, PI / 2.0]);
+        let km = haversine_km(&x.view(), &y.view());
+        assert!((km - EARTH_RADIUS_KM * PI / 2.0).abs() < 1e-9);
+        assert!((haversine_m(&x.view(), &y.view()) - 1000.0 * km).abs() < 1e-6);
+
+        // One degree of latitude is about 111.2 km.
+        let north = arr1(&[1.0f32.to_radians(), 0.0]);
+        let origin = arr1(&[0.0f32, 0.0]);
+        assert!((haversine_km(&origin.view(), &north.view()) - 111.195).abs() < 1e-2);
+    }
 }
@@ -5,6 +5,8 @@ use ndarray::{Array1, ArrayView1};
 use num::Float;
 use std::f64::consts::PI;
 
+use crate::DistanceConfig;
+
 /// Computes the gradient of the Haversine distance between two points on the Earth's surface.
 ///
 /// The gradient of the Haversine distance is computed with respect to both points.
@@ -22,6 +24,21 @@ use std::f64::consts::PI;
 /// # Panics
 /// Panics if `x` or `y` are not 2-dimensional (latitude and longitude).
 pub fn haversine_grad<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> (T, Array1<T>)
+where
+    T: Float,
+{
+    haversine_grad_with_config(x, y, &DistanceConfig::default())
+}
+
+/// Computes the Haversine distance and its gradient, using the stabilization epsilon from `config`.
+///
+/// See [`haversine_grad`] for details; this variant lets callers tune the epsilon added to the
+/// gradient's denominator.
+pub fn haversine_grad_with_config<T>(
+    x: &ArrayView1<T>,
+    y: &ArrayView1<T>,
+    config: &DistanceConfig<T>,
+) -> (T, Array1<T>)
 where
     T: Float,
 {
@@ -52,13 +69,13 @@ where
         - (x[0] + T::from(PI / 2.0).unwrap()).sin()
             * (y[0] + T::from(PI / 2.0).unwrap()).cos()
             * sin_long.powi(2))
-        / (denom + T::from(1e-6).unwrap());
+        / (denom + config.epsilon);
 
     let grad_y = ((x[0] + T::from(PI / 2.0).unwrap()).cos()
         * (y[0] + T::from(PI / 2.0).unwrap()).cos()
         * sin_long
         * cos_long)
-        / (denom + T::from(1e-6).unwrap());
+        / (denom + config.epsilon);
 
     // Return the distance and gradient as a tuple
     let grad = Array1::from_vec(vec![grad_x, grad_y]);
@@ -124,4 +141,20 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_haversine_grad_with_config() {
+        let x = arr1(&[0.1, 0.2]);
+        let y = arr1(&[0.3, 0.5]);
+
+        let (default_dist, default_grad) = haversine_grad(&x.view(), &y.view());
+        let config = DistanceConfig::default().with_epsilon(1e-6);
+        let (dist, grad) = haversine_grad_with_config(&x.view(), &y.view(), &config);
+        assert_eq!(dist, default_dist);
+        assert_eq!(grad, default_grad);
+
+        let config = DistanceConfig::default().with_epsilon(1.0);
+        let (_, damped) = haversine_grad_with_config(&x.view(), &y.view(), &config);
+        assert!(damped[0].abs() < default_grad[0].abs());
+    }
 }
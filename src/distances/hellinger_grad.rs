@@ -52,7 +52,7 @@ where
         // Use `zip` to perform element-wise operations between `y` and `grad_term`
         grad = grad_term
             .into_iter()
-            .zip(y.into_iter())
+            .zip(y)
             .map(|(grad_term_val, y_val)| grad_numer_const - (*y_val / grad_term_val * dist_denom))
             .map(|val| val / grad_denom)
             .collect::<Array1<T>>();
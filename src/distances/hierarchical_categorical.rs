@@ -0,0 +1,101 @@
+use ndarray::{s, ArrayView1};
+use num::Float;
+
+/// Computes the hierarchical categorical distance between two paths in a taxonomy.
+///
+/// `x` and `y` hold integer category codes, one per level of the hierarchy from the root
+/// down, so `x[l]` is the category of the first item at level `l`. If the paths first differ
+/// at level `k`, the distance is the total weight of the levels from `k` down:
+///
+/// ..math::
+///    d(x, y) = \sum_{l \ge k} w_l
+///
+/// Items that share a deeper ancestor are therefore closer, and identical paths are at
+/// distance `0`. With `n` levels and uniform weights `1 / n` this is UMAP's
+/// `hierarchical_categorical`, which returns `(n - k) / n`.
+///
+/// # Arguments
+/// * `x` - The category codes of the first item, one per level.
+/// * `y` - The category codes of the second item, one per level.
+/// * `level_weights` - The nonnegative weight of each level.
+///
+/// # Panics
+/// Panics if `x`, `y` and `level_weights` do not all have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// // Kingdom, family, species.
+/// let wolf = arr1(&[0.0, 3.0, 7.0]);
+/// let dog = arr1(&[0.0, 3.0, 8.0]);
+/// let cat = arr1(&[0.0, 4.0, 1.0]);
+/// let weights = arr1(&[1.0, 1.0, 1.0]) / 3.0;
+/// assert_eq!(hierarchical_categorical(&wolf.view(), &dog.view(), &weights.view()), 1.0 / 3.0);
+/// assert_eq!(hierarchical_categorical(&wolf.view(), &cat.view(), &weights.view()), 2.0 / 3.0);
+/// ```
+pub fn hierarchical_categorical<T>(
+    x: &ArrayView1<T>,
+    y: &ArrayView1<T>,
+    level_weights: &ArrayView1<T>,
+) -> T
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+    assert_eq!(
+        x.len(),
+        level_weights.len(),
+        "Level weights must have one entry per level."
+    );
+
+    match x.iter().zip(y).position(|(a, b)| a != b) {
+        Some(level) => level_weights
+            .slice(s![level..])
+            .fold(T::zero(), |acc, &w| acc + w),
+        None => T::zero(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_hierarchical_categorical_umap_parity() {
+        let paths = [
+            arr1(&[0.0_f32, 0.0, 0.0, 0.0]),
+            arr1(&[0.0_f32, 0.0, 0.0, 1.0]),
+            arr1(&[0.0_f32, 0.0, 2.0, 1.0]),
+            arr1(&[0.0_f32, 1.0, 0.0, 0.0]),
+            arr1(&[5.0_f32, 0.0, 0.0, 0.0]),
+        ];
+        let weights = arr1(&[0.25_f32; 4]);
+        let expected = [0.0, 0.25, 0.5, 0.75, 1.0];
+        for (path, &e) in paths.iter().zip(&expected) {
+            let d = hierarchical_categorical(&paths[0].view(), &path.view(), &weights.view());
+            assert_eq!(d, e);
+        }
+    }
+
+    #[test]
+    fn test_hierarchical_categorical_weights() {
+        let x = arr1(&[1.0, 2.0, 3.0]);
+        let y = arr1(&[1.0, 5.0, 3.0]);
+        let w = arr1(&[4.0, 2.0, 1.0]);
+        // Paths that diverge stay apart below the split, even if later codes agree.
+        assert_eq!(
+            hierarchical_categorical(&x.view(), &y.view(), &w.view()),
+            3.0
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Level weights must have one entry per level.")]
+    fn test_hierarchical_categorical_weight_length() {
+        let x = arr1(&[1.0, 2.0]);
+        hierarchical_categorical(&x.view(), &x.view(), &arr1(&[1.0]).view());
+    }
+}
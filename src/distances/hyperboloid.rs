@@ -0,0 +1,201 @@
+use ndarray::{Array1, ArrayView1, Zip};
+use num::Float;
+
+/// Computes the Lorentzian (Minkowski) inner product of two vectors in ambient coordinates.
+///
+/// ..math::
+///    \langle x, y \rangle_L = -x_0 y_0 + \sum_{i \geq 1} x_i y_i
+///
+/// The first coordinate is the time-like one. Points `x` of the hyperboloid model satisfy
+/// `<x, x>_L = -1` with `x_0 > 0`, and their distance is `arcosh(-<x, y>_L)`.
+///
+/// # Panics
+/// Panics if `x` and `y` have different lengths or are empty.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[2.0, 1.0, 1.0]);
+/// let y = arr1(&[1.0, 0.0, 0.0]);
+/// assert_eq!(lorentzian_inner(&x.view(), &y.view()), -2.0);
+/// ```
+pub fn lorentzian_inner<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+    assert!(
+        !x.is_empty(),
+        "Ambient coordinates must include the time coordinate."
+    );
+
+    let spatial = Zip::from(x.slice(ndarray::s![1..]))
+        .and(y.slice(ndarray::s![1..]))
+        .fold(T::zero(), |acc, &a, &b| acc + a * b);
+    spatial - x[0] * y[0]
+}
+
+/// Lifts spatial coordinates `x` onto the hyperboloid, returning the ambient coordinates
+/// `(sqrt(1 + ‖x‖²), x)`.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let lifted = hyperboloid_lift(&arr1(&[0.0, 0.0]).view());
+/// assert_eq!(lifted, arr1(&[1.0, 0.0, 0.0]));
+/// assert_eq!(lorentzian_inner(&lifted.view(), &lifted.view()), -1.0);
+/// ```
+pub fn hyperboloid_lift<T>(x: &ArrayView1<T>) -> Array1<T>
+where
+    T: Float,
+{
+    hyperboloid_lift_with_curvature(x, T::one())
+}
+
+/// Lifts spatial coordinates `x` onto the hyperboloid of curvature `-c`, where
+/// `<x, x>_L = -1 / c`, returning `(sqrt(1 / c + ‖x‖²), x)`.
+///
+/// # Panics
+/// Panics if `c` is not positive.
+pub fn hyperboloid_lift_with_curvature<T>(x: &ArrayView1<T>, c: T) -> Array1<T>
+where
+    T: Float,
+{
+    assert!(c > T::zero(), "Curvature must be positive.");
+    let time = (c.recip() + x.fold(T::zero(), |acc, &v| acc + v * v)).sqrt();
+    std::iter::once(time).chain(x.iter().copied()).collect()
+}
+
+/// Computes the hyperbolic distance between two points of the hyperboloid model given by their
+/// spatial coordinates.
+///
+/// As for [`hyperboloid_grad`](crate::hyperboloid_grad), the time coordinate is implied, so any
+/// vectors can be compared. The distance is the arccosh of the Minkowski bilinear form of the
+/// [lifted](hyperboloid_lift) points:
+///
+/// ..math::
+///    D(x, y) = \operatorname{arcosh}\left(\sqrt{1 + \lVert x \rVert^2} \sqrt{1 + \lVert y \rVert^2} - x \cdot y\right)
+///
+/// Rounding can push the argument slightly below one for nearby points; it is clamped, so
+/// identical points are at distance zero.
+///
+/// # Panics
+/// Panics if `x` and `y` have different lengths.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[0.5f64, 0.3, 0.2]);
+/// let y = arr1(&[0.1, 0.4, 0.5]);
+/// let (expected, _) = hyperboloid_grad(&x, &y);
+/// assert!((hyperboloid(&x.view(), &y.view()) - expected).abs() < 1e-12);
+/// ```
+pub fn hyperboloid<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    hyperboloid_with_curvature(x, y, T::one())
+}
+
+/// Computes the hyperbolic distance on the hyperboloid of curvature `-c`, from spatial
+/// coordinates.
+///
+/// The points are [lifted](hyperboloid_lift_with_curvature) to `<x, x>_L = -1 / c`, and
+///
+/// ..math::
+///    D_c(x, y) = \frac{1}{\sqrt{c}} \operatorname{arcosh}\left(-c \langle x, y \rangle_L\right)
+///
+/// which is [`hyperboloid`] for `c = 1` and agrees with
+/// [`poincare_with_curvature`](crate::poincare_with_curvature) for the same `c`.
+///
+/// # Panics
+/// Panics if `x` and `y` have different lengths or if `c` is not positive.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[0.5, 0.3, 0.2]);
+/// let y = arr1(&[0.1, 0.4, 0.5]);
+/// assert_eq!(
+///     hyperboloid_with_curvature(&x.view(), &y.view(), 1.0),
+///     hyperboloid(&x.view(), &y.view())
+/// );
+/// ```
+pub fn hyperboloid_with_curvature<T>(x: &ArrayView1<T>, y: &ArrayView1<T>, c: T) -> T
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+    assert!(c > T::zero(), "Curvature must be positive.");
+
+    let (dot, norm_x, norm_y) = Zip::from(x).and(y).fold(
+        (T::zero(), T::zero(), T::zero()),
+        |(dot, nx, ny), &a, &b| (dot + a * b, nx + a * a, ny + b * b),
+    );
+    let radius = c.recip();
+    let b = c * ((radius + norm_x).sqrt() * (radius + norm_y).sqrt() - dot);
+    b.max(T::one()).acosh() / c.sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{poincare, poincare_with_curvature};
+    use ndarray::arr1;
+
+    #[test]
+    fn test_hyperboloid_matches_lorentzian_form() {
+        let x = arr1(&[0.5, -1.3, 2.0]);
+        let y = arr1(&[0.1, 0.4, -0.5]);
+        let (lx, ly) = (hyperboloid_lift(&x.view()), hyperboloid_lift(&y.view()));
+        assert!((lorentzian_inner(&lx.view(), &lx.view()) + 1.0).abs() < 1e-12);
+        let expected = (-lorentzian_inner(&lx.view(), &ly.view())).acosh();
+        assert!((hyperboloid(&x.view(), &y.view()) - expected).abs() < 1e-12);
+        assert_eq!(hyperboloid(&x.view(), &x.view()), 0.0);
+    }
+
+    #[test]
+    fn test_hyperboloid_matches_poincare() {
+        // The ball point u corresponds to the spatial coordinates 2u / (1 - |u|^2).
+        let to_hyperboloid = |u: &Array1<f64>| u.mapv(|v| 2.0 * v / (1.0 - u.dot(u)));
+        let u = arr1(&[0.5, 0.3, 0.2]);
+        let v = arr1(&[0.1, 0.4, 0.5]);
+        let d = hyperboloid(&to_hyperboloid(&u).view(), &to_hyperboloid(&v).view());
+        assert!((d - poincare(&u, &v)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_curvature_models_agree() {
+        let c = 0.7;
+        let u = arr1(&[0.5, -0.3, 0.6]);
+        let v = arr1(&[0.1, 0.4, -0.5]);
+        // The ball point u maps to the spatial coordinates 2u / (1 - c |u|^2).
+        let to_hyperboloid = |u: &Array1<f64>| u.mapv(|v| 2.0 * v / (1.0 - c * u.dot(u)));
+        let (x, y) = (to_hyperboloid(&u), to_hyperboloid(&v));
+        let d = hyperboloid_with_curvature(&x.view(), &y.view(), c);
+        assert!((d - poincare_with_curvature(&u, &v, c)).abs() < 1e-12);
+
+        let (lx, ly) = (
+            hyperboloid_lift_with_curvature(&x.view(), c),
+            hyperboloid_lift_with_curvature(&y.view(), c),
+        );
+        assert!((lorentzian_inner(&lx.view(), &lx.view()) + 1.0 / c).abs() < 1e-12);
+        let expected = (-c * lorentzian_inner(&lx.view(), &ly.view())).acosh() / c.sqrt();
+        assert!((d - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    #[should_panic(expected = "Ambient coordinates must include the time coordinate.")]
+    fn test_lorentzian_inner_empty() {
+        let empty = Array1::<f64>::zeros(0);
+        lorentzian_inner(&empty.view(), &empty.view());
+    }
+}
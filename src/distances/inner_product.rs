@@ -0,0 +1,84 @@
+use ndarray::{ArrayView1, Zip};
+use num::Float;
+
+/// Computes the negated inner product of two vectors `x` and `y`.
+///
+/// ..math::
+///     \text{neg\_inner\_product}(x, y) = -\sum x_i \cdot y_i
+///
+/// This is not a metric: it can be negative and a vector is not closest to itself. It turns
+/// maximum-inner-product search into a nearest-neighbor search, so the bulk and k-NN APIs of
+/// this crate return the rows with the largest inner products first.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[1.0, 2.0, 3.0]);
+/// let y = arr1(&[4.0, -5.0, 6.0]);
+/// assert_eq!(neg_inner_product(&x.view(), &y.view()), -12.0);
+/// ```
+pub fn neg_inner_product<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+
+    -Zip::from(x)
+        .and(y)
+        .fold(T::zero(), |dot, &xi, &yi| dot + xi * yi)
+}
+
+/// Computes the inner product of `x` and `y` subtracted from `shift`.
+///
+/// ..math::
+///     \text{shifted\_inner\_product}(x, y) = \text{shift} - \sum x_i \cdot y_i
+///
+/// It orders pairs exactly like [`neg_inner_product`]. With `shift` at least the largest
+/// possible inner product the values are non-negative, and with `shift = 1` on unit vectors
+/// it equals the [`cosine`](crate::cosine) distance (pynndescent's `dot`).
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[0.6f64, 0.8]);
+/// let y = arr1(&[1.0, 0.0]);
+/// let d = shifted_inner_product(&x.view(), &y.view(), 1.0);
+/// assert!((d - cosine(&x.view(), &y.view())).abs() < 1e-12);
+/// ```
+pub fn shifted_inner_product<T>(x: &ArrayView1<T>, y: &ArrayView1<T>, shift: T) -> T
+where
+    T: Float,
+{
+    shift + neg_inner_product(x, y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_neg_inner_product() {
+        let x = arr1(&[0.5_f32, -1.0, 2.0]);
+        let y = arr1(&[2.0_f32, 3.0, 0.25]);
+        assert_eq!(neg_inner_product(&x.view(), &y.view()), 1.5);
+        assert_eq!(neg_inner_product(&x.view(), &x.view()), -5.25);
+        assert_eq!(shifted_inner_product(&x.view(), &y.view(), 10.0), 11.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "Input arrays must have the same length.")]
+    fn test_neg_inner_product_length_mismatch() {
+        neg_inner_product(&arr1(&[1.0]).view(), &arr1(&[1.0, 2.0]).view());
+    }
+}
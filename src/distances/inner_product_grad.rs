@@ -0,0 +1,38 @@
+use ndarray::{Array1, ArrayView1};
+use num::Float;
+
+use crate::neg_inner_product;
+
+/// Computes the negated inner product of `x` and `y` and its gradient with respect to `x`.
+///
+/// The gradient of `-x · y` with respect to `x` is `-y`. It is also the gradient of
+/// [`shifted_inner_product`](crate::shifted_inner_product), whose shift is a constant.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Returns
+/// * A tuple containing:
+///     - The negated inner product of `x` and `y`.
+///     - The gradient with respect to `x`.
+pub fn neg_inner_product_grad<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> (T, Array1<T>)
+where
+    T: Float,
+{
+    (neg_inner_product(x, y), y.mapv(|yi| -yi))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_neg_inner_product_grad() {
+        let x = arr1(&[0.5, -1.0, 2.0]);
+        let y = arr1(&[2.0, 3.0, 0.25]);
+        let (d, grad) = neg_inner_product_grad(&x.view(), &y.view());
+        assert_eq!(d, 1.5);
+        assert_eq!(grad, arr1(&[-2.0, -3.0, -0.25]));
+    }
+}
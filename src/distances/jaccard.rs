@@ -1,6 +1,9 @@
 extern crate ndarray;
 
 use ndarray::ArrayView1;
+use num::Float;
+
+use super::binary::binary_counts;
 
 /// Computes the Jaccard similarity between two binary vectors.
 ///
@@ -23,22 +26,18 @@ use ndarray::ArrayView1;
 /// * `y` - A 1D array (view) of values representing the second vector.
 ///
 /// # Returns
-/// A f64 value representing the Jaccard similarity.
-pub fn jaccard(x: &ArrayView1<f64>, y: &ArrayView1<f64>) -> f64 {
-    let mut num_non_zero = 0.0;
-    let mut num_equal = 0.0;
-
-    for i in 0..x.len() {
-        let x_true = x[i] != 0.0;
-        let y_true = y[i] != 0.0;
-        num_non_zero += if x_true || y_true { 1.0 } else { 0.0 };
-        num_equal += if x_true && y_true { 1.0 } else { 0.0 };
-    }
+/// A value representing the Jaccard similarity.
+pub fn jaccard<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    let (num_true_true, num_not_equal) = binary_counts(x, y);
+    let num_non_zero = num_true_true + num_not_equal;
 
-    if num_non_zero == 0.0 {
-        0.0
+    if num_non_zero.is_zero() {
+        T::zero()
     } else {
-        (num_non_zero - num_equal) / num_non_zero
+        num_not_equal / num_non_zero
     }
 }
 
@@ -92,4 +91,11 @@ mod tests {
         let similarity = jaccard(&x.view(), &y.view());
         assert_eq!(similarity, 1.0); // x is zero vector, y is all ones
     }
+
+    #[test]
+    fn test_jaccard_f32() {
+        let x = arr1(&[1.0_f32, 0.0, 2.5, 0.0, 1.0]);
+        let y = arr1(&[1.0_f32, 1.0, 0.0, 0.0, 3.0]);
+        assert_eq!(jaccard(&x.view(), &y.view()), 0.5_f32);
+    }
 }
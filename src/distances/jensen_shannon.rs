@@ -0,0 +1,108 @@
+use ndarray::{ArrayView1, Zip};
+use num::Float;
+
+use super::kl_divergence::{kl_term, prepare};
+use crate::DivergenceConfig;
+
+/// Computes the Jensen-Shannon distance between two probability vectors.
+///
+/// ..math::
+///    d(p, q) = \sqrt{\frac{1}{2} D_{KL}(p \| m) + \frac{1}{2} D_{KL}(q \| m)},
+///    \quad m = \frac{p + q}{2}
+///
+/// Unlike the Kullback-Leibler divergence it is symmetric, always finite and, as the square
+/// root of the Jensen-Shannon divergence, a true metric. Natural logarithms are used, so the
+/// distance is at most `sqrt(ln 2)` for probability vectors. The inputs are used as given; see
+/// [`jensen_shannon_with_config`] to smooth or normalize them.
+///
+/// # Arguments
+/// * `p` - The first distribution.
+/// * `q` - The second distribution.
+///
+/// # Panics
+/// Panics if `p` and `q` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let p = arr1(&[1.0, 0.0]);
+/// let q = arr1(&[0.0, 1.0]);
+/// assert!((jensen_shannon(&p.view(), &q.view()) - 2.0_f64.ln().sqrt()).abs() < 1e-12);
+/// ```
+pub fn jensen_shannon<T>(p: &ArrayView1<T>, q: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    assert_eq!(p.len(), q.len(), "Input arrays must have the same length.");
+    let half = T::from(0.5).unwrap();
+    let divergence = Zip::from(p)
+        .and(q)
+        .fold(T::zero(), |acc, &pi, &qi| acc + js_term(pi, qi));
+    // Rounding can leave a tiny negative divergence for identical inputs.
+    (half * divergence).max(T::zero()).sqrt()
+}
+
+/// Computes the Jensen-Shannon distance after smoothing and normalizing the inputs as described
+/// by `config`.
+///
+/// # Panics
+/// Panics if `p` and `q` do not have the same length.
+pub fn jensen_shannon_with_config<T>(
+    p: &ArrayView1<T>,
+    q: &ArrayView1<T>,
+    config: &DivergenceConfig<T>,
+) -> T
+where
+    T: Float,
+{
+    let (p, q) = (prepare(p, config).0, prepare(q, config).0);
+    jensen_shannon(&p.view(), &q.view())
+}
+
+/// Twice the contribution of one coordinate to the Jensen-Shannon divergence.
+pub(crate) fn js_term<T: Float>(p: T, q: T) -> T {
+    let m = (p + q) * T::from(0.5).unwrap();
+    kl_term(p, m) + kl_term(q, m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kl_divergence;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_matches_kl_definition() {
+        let p = arr1(&[0.1, 0.6, 0.3]);
+        let q = arr1(&[0.5, 0.2, 0.3]);
+        let m = (&p + &q) / 2.0;
+        let expected = (0.5 * kl_divergence(&p.view(), &m.view())
+            + 0.5 * kl_divergence(&q.view(), &m.view()))
+        .sqrt();
+        let d = jensen_shannon(&p.view(), &q.view());
+        assert!((d - expected).abs() < 1e-12);
+        assert_eq!(d, jensen_shannon(&q.view(), &p.view()));
+    }
+
+    #[test]
+    fn test_identical_and_disjoint() {
+        let p = arr1(&[0.2f32, 0.8]);
+        assert_eq!(jensen_shannon(&p.view(), &p.view()), 0.0);
+
+        let p = arr1(&[0.5, 0.5, 0.0, 0.0]);
+        let q = arr1(&[0.0, 0.0, 0.25, 0.75]);
+        assert!((jensen_shannon(&p.view(), &q.view()) - 2f64.ln().sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_normalized_counts() {
+        let config = DivergenceConfig::default().with_normalize(true);
+        let p = arr1(&[1.0, 3.0]);
+        let q = arr1(&[2.0, 2.0]);
+        let d = jensen_shannon_with_config(&p.view(), &q.view(), &config);
+        let expected = jensen_shannon(&arr1(&[0.25, 0.75]).view(), &arr1(&[0.5, 0.5]).view());
+        assert!((d - expected).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,112 @@
+use ndarray::{Array1, ArrayView1};
+use num::Float;
+
+use super::jensen_shannon::js_term;
+use super::kl_divergence_grad::divergence_grad;
+use crate::DivergenceConfig;
+
+/// Computes the Jensen-Shannon distance and its gradient with respect to `p`.
+///
+/// ..math::
+///    \frac{\partial d}{\partial p_i} = \frac{1}{4 d} \ln \frac{p_i}{m_i},
+///    \quad m = \frac{p + q}{2}
+///
+/// The gradient is zero when the distance is zero, and `-inf` where `p_i = 0 < q_i`; use
+/// [`jensen_shannon_grad_with_config`] with a positive epsilon to keep it finite. This makes
+/// [`jensen_shannon`](crate::jensen_shannon) usable as a UMAP output metric on histograms.
+///
+/// # Returns
+/// A tuple containing the distance and the gradient.
+///
+/// # Panics
+/// Panics if `p` and `q` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let p = arr1(&[0.4, 0.6]);
+/// let q = arr1(&[0.6, 0.4]);
+/// let (d, grad) = jensen_shannon_grad(&p.view(), &q.view());
+/// assert_eq!(d, jensen_shannon(&p.view(), &q.view()));
+/// assert!(grad[0] < 0.0 && grad[1] > 0.0);
+/// ```
+pub fn jensen_shannon_grad<T>(p: &ArrayView1<T>, q: &ArrayView1<T>) -> (T, Array1<T>)
+where
+    T: Float,
+{
+    jensen_shannon_grad_with_config(p, q, &DivergenceConfig::default())
+}
+
+/// Computes the Jensen-Shannon distance and its gradient with respect to the raw `p`, after
+/// smoothing and normalizing the inputs as described by `config`.
+///
+/// # Panics
+/// Panics if `p` and `q` do not have the same length.
+pub fn jensen_shannon_grad_with_config<T>(
+    p: &ArrayView1<T>,
+    q: &ArrayView1<T>,
+    config: &DivergenceConfig<T>,
+) -> (T, Array1<T>)
+where
+    T: Float,
+{
+    let half = T::from(0.5).unwrap();
+    let (divergence, mut grad) = divergence_grad(p, q, config, |pi, qi| {
+        let derivative = if pi.is_zero() && qi.is_zero() {
+            T::zero()
+        } else {
+            (pi / ((pi + qi) * half)).ln()
+        };
+        (half * js_term(pi, qi), half * derivative)
+    });
+
+    let distance = divergence.max(T::zero()).sqrt();
+    if distance.is_zero() {
+        grad.fill(T::zero());
+    } else {
+        let scale = T::from(2.0).unwrap() * distance;
+        grad.mapv_inplace(|g| g / scale);
+    }
+    (distance, grad)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{jensen_shannon, jensen_shannon_with_config};
+    use ndarray::arr1;
+
+    #[test]
+    fn test_matches_finite_differences() {
+        let p = arr1(&[0.2, 0.5, 0.3]);
+        let q = arr1(&[0.3, 0.1, 0.6]);
+        let h = 1e-7;
+        for config in [
+            DivergenceConfig::default(),
+            DivergenceConfig::default().with_normalize(true),
+        ] {
+            let (d, grad) = jensen_shannon_grad_with_config(&p.view(), &q.view(), &config);
+            assert!((d - jensen_shannon_with_config(&p.view(), &q.view(), &config)).abs() < 1e-12);
+            for i in 0..3 {
+                let (mut hi, mut lo) = (p.clone(), p.clone());
+                hi[i] += h;
+                lo[i] -= h;
+                let numeric = (jensen_shannon_with_config(&hi.view(), &q.view(), &config)
+                    - jensen_shannon_with_config(&lo.view(), &q.view(), &config))
+                    / (2.0 * h);
+                assert!((grad[i] - numeric).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_identical_inputs() {
+        let p = arr1(&[0.3, 0.7]);
+        let (d, grad) = jensen_shannon_grad(&p.view(), &p.view());
+        assert_eq!(d, 0.0);
+        assert_eq!(grad, arr1(&[0.0, 0.0]));
+        assert_eq!(d, jensen_shannon(&p.view(), &p.view()));
+    }
+}
@@ -0,0 +1,182 @@
+use ndarray::{Array1, ArrayView1, Zip};
+use num::Float;
+
+use crate::DivergenceConfig;
+
+/// Computes the Kullback-Leibler divergence of `q` from `p`.
+///
+/// ..math::
+///    D_{KL}(p \| q) = \sum_i p_i \ln \frac{p_i}{q_i}
+///
+/// Terms with `p_i = 0` contribute zero, and the divergence is infinite if `q_i = 0` for some
+/// `p_i > 0`. The inputs are used as given; see [`kl_divergence_with_config`] to smooth or
+/// normalize them. The divergence is not symmetric, see [`symmetric_kl`].
+///
+/// # Arguments
+/// * `p` - The reference distribution.
+/// * `q` - The approximating distribution.
+///
+/// # Panics
+/// Panics if `p` and `q` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let p = arr1(&[0.5, 0.5]);
+/// let q = arr1(&[0.25, 0.75]);
+/// let expected = 0.5 * 2.0_f64.ln() + 0.5 * (2.0_f64 / 3.0).ln();
+/// assert!((kl_divergence(&p.view(), &q.view()) - expected).abs() < 1e-12);
+/// ```
+pub fn kl_divergence<T>(p: &ArrayView1<T>, q: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    assert_eq!(p.len(), q.len(), "Input arrays must have the same length.");
+    Zip::from(p)
+        .and(q)
+        .fold(T::zero(), |acc, &pi, &qi| acc + kl_term(pi, qi))
+}
+
+/// Computes the Kullback-Leibler divergence after smoothing and normalizing the inputs as
+/// described by `config`.
+///
+/// # Panics
+/// Panics if `p` and `q` do not have the same length.
+pub fn kl_divergence_with_config<T>(
+    p: &ArrayView1<T>,
+    q: &ArrayView1<T>,
+    config: &DivergenceConfig<T>,
+) -> T
+where
+    T: Float,
+{
+    let (p, q) = (prepare(p, config).0, prepare(q, config).0);
+    kl_divergence(&p.view(), &q.view())
+}
+
+/// Computes the symmetric Kullback-Leibler divergence, also known as the Jeffreys divergence.
+///
+/// ..math::
+///    D_J(p, q) = D_{KL}(p \| q) + D_{KL}(q \| p) = \sum_i (p_i - q_i) \ln \frac{p_i}{q_i}
+///
+/// It is infinite as soon as exactly one of `p_i` and `q_i` is zero.
+///
+/// # Panics
+/// Panics if `p` and `q` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let p = arr1(&[0.5, 0.5]);
+/// let q = arr1(&[0.25, 0.75]);
+/// let d = symmetric_kl(&p.view(), &q.view());
+/// assert_eq!(d, symmetric_kl(&q.view(), &p.view()));
+/// ```
+pub fn symmetric_kl<T>(p: &ArrayView1<T>, q: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    assert_eq!(p.len(), q.len(), "Input arrays must have the same length.");
+    Zip::from(p).and(q).fold(T::zero(), |acc, &pi, &qi| {
+        acc + kl_term(pi, qi) + kl_term(qi, pi)
+    })
+}
+
+/// Computes the symmetric Kullback-Leibler divergence after smoothing and normalizing the
+/// inputs as described by `config`.
+///
+/// # Panics
+/// Panics if `p` and `q` do not have the same length.
+pub fn symmetric_kl_with_config<T>(
+    p: &ArrayView1<T>,
+    q: &ArrayView1<T>,
+    config: &DivergenceConfig<T>,
+) -> T
+where
+    T: Float,
+{
+    let (p, q) = (prepare(p, config).0, prepare(q, config).0);
+    symmetric_kl(&p.view(), &q.view())
+}
+
+/// The contribution `p ln(p / q)` of one coordinate, with `0 ln 0 = 0`.
+pub(crate) fn kl_term<T: Float>(p: T, q: T) -> T {
+    if p.is_zero() {
+        T::zero()
+    } else {
+        p * (p / q).ln()
+    }
+}
+
+/// Applies the smoothing and normalization of `config` to `x`.
+///
+/// Returns the prepared vector and, if it was normalized, the sum it was divided by.
+pub(crate) fn prepare<T: Float>(
+    x: &ArrayView1<T>,
+    config: &DivergenceConfig<T>,
+) -> (Array1<T>, Option<T>) {
+    let mut x = x.mapv(|v| v + config.epsilon);
+    let total = x.sum();
+    if config.normalize && !total.is_zero() {
+        x.mapv_inplace(|v| v / total);
+        (x, Some(total))
+    } else {
+        (x, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_known_values() {
+        let p = arr1(&[0.1, 0.4, 0.5]);
+        let q = arr1(&[0.8, 0.15, 0.05]);
+        let expected: f64 =
+            0.1 * (0.1f64 / 0.8).ln() + 0.4 * (0.4f64 / 0.15).ln() + 0.5 * 10f64.ln();
+        assert!((kl_divergence(&p.view(), &q.view()) - expected).abs() < 1e-12);
+        assert_eq!(kl_divergence(&p.view(), &p.view()), 0.0);
+
+        let reverse = kl_divergence(&q.view(), &p.view());
+        let d = symmetric_kl(&p.view(), &q.view());
+        assert!((d - expected - reverse).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_zero_coordinates() {
+        let p = arr1(&[0.0, 1.0]);
+        let q = arr1(&[0.5, 0.5]);
+        assert!((kl_divergence(&p.view(), &q.view()) - 2f64.ln()).abs() < 1e-12);
+        assert_eq!(kl_divergence(&q.view(), &p.view()), f64::INFINITY);
+        assert_eq!(symmetric_kl(&p.view(), &q.view()), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_config() {
+        let counts_p = arr1(&[2.0, 6.0, 2.0]);
+        let counts_q = arr1(&[5.0, 3.0, 2.0]);
+        let (p, q) = (counts_p.mapv(|v| v / 10.0), counts_q.mapv(|v| v / 10.0));
+        let config = DivergenceConfig::default().with_normalize(true);
+
+        let d = kl_divergence_with_config(&counts_p.view(), &counts_q.view(), &config);
+        assert!((d - kl_divergence(&p.view(), &q.view())).abs() < 1e-12);
+        let d = symmetric_kl_with_config(&counts_p.view(), &counts_q.view(), &config);
+        assert!((d - symmetric_kl(&p.view(), &q.view())).abs() < 1e-12);
+
+        let config = config.with_epsilon(1e-6);
+        let sparse = arr1(&[0.0, 10.0, 0.0]);
+        assert!(kl_divergence_with_config(&counts_p.view(), &sparse.view(), &config).is_finite());
+    }
+
+    #[test]
+    #[should_panic(expected = "Input arrays must have the same length.")]
+    fn test_length_mismatch() {
+        kl_divergence(&arr1(&[1.0]).view(), &arr1(&[0.5, 0.5]).view());
+    }
+}
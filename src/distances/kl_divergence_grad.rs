@@ -0,0 +1,190 @@
+use ndarray::{Array1, ArrayView1, Zip};
+use num::Float;
+
+use super::kl_divergence::{kl_term, prepare};
+use crate::DivergenceConfig;
+
+/// Computes the Kullback-Leibler divergence of `q` from `p` and its gradient with respect to
+/// `p`.
+///
+/// ..math::
+///    \frac{\partial D_{KL}(p \| q)}{\partial p_i} = \ln \frac{p_i}{q_i} + 1
+///
+/// The gradient is `-inf` where `p_i = 0`; use [`kl_divergence_grad_with_config`] with a
+/// positive epsilon to keep it finite.
+///
+/// # Returns
+/// A tuple containing the divergence and the gradient.
+///
+/// # Panics
+/// Panics if `p` and `q` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let p = arr1(&[0.5, 0.5]);
+/// let (d, grad) = kl_divergence_grad(&p.view(), &p.view());
+/// assert_eq!(d, 0.0);
+/// assert_eq!(grad, arr1(&[1.0, 1.0]));
+/// ```
+pub fn kl_divergence_grad<T>(p: &ArrayView1<T>, q: &ArrayView1<T>) -> (T, Array1<T>)
+where
+    T: Float,
+{
+    kl_divergence_grad_with_config(p, q, &DivergenceConfig::default())
+}
+
+/// Computes the Kullback-Leibler divergence and its gradient with respect to the raw `p`,
+/// after smoothing and normalizing the inputs as described by `config`.
+///
+/// When the inputs are normalized, the gradient accounts for the normalization: rescaling `p`
+/// does not change the divergence, so the gradient is orthogonal to the smoothed `p`.
+///
+/// # Panics
+/// Panics if `p` and `q` do not have the same length.
+pub fn kl_divergence_grad_with_config<T>(
+    p: &ArrayView1<T>,
+    q: &ArrayView1<T>,
+    config: &DivergenceConfig<T>,
+) -> (T, Array1<T>)
+where
+    T: Float,
+{
+    divergence_grad(p, q, config, |pi, qi| {
+        (kl_term(pi, qi), (pi / qi).ln() + T::one())
+    })
+}
+
+/// Computes the symmetric Kullback-Leibler divergence and its gradient with respect to `p`.
+///
+/// ..math::
+///    \frac{\partial D_J(p, q)}{\partial p_i} = \ln \frac{p_i}{q_i} + 1 - \frac{q_i}{p_i}
+///
+/// # Returns
+/// A tuple containing the divergence and the gradient.
+///
+/// # Panics
+/// Panics if `p` and `q` do not have the same length.
+pub fn symmetric_kl_grad<T>(p: &ArrayView1<T>, q: &ArrayView1<T>) -> (T, Array1<T>)
+where
+    T: Float,
+{
+    symmetric_kl_grad_with_config(p, q, &DivergenceConfig::default())
+}
+
+/// Computes the symmetric Kullback-Leibler divergence and its gradient with respect to the raw
+/// `p`, after smoothing and normalizing the inputs as described by `config`.
+///
+/// # Panics
+/// Panics if `p` and `q` do not have the same length.
+pub fn symmetric_kl_grad_with_config<T>(
+    p: &ArrayView1<T>,
+    q: &ArrayView1<T>,
+    config: &DivergenceConfig<T>,
+) -> (T, Array1<T>)
+where
+    T: Float,
+{
+    divergence_grad(p, q, config, |pi, qi| {
+        let value = kl_term(pi, qi) + kl_term(qi, pi);
+        (value, (pi / qi).ln() + T::one() - qi / pi)
+    })
+}
+
+/// Sums the per-coordinate `(value, derivative)` pairs returned by `term` over the prepared
+/// inputs and maps the derivatives back through the normalization of `p`.
+pub(crate) fn divergence_grad<T, F>(
+    p: &ArrayView1<T>,
+    q: &ArrayView1<T>,
+    config: &DivergenceConfig<T>,
+    term: F,
+) -> (T, Array1<T>)
+where
+    T: Float,
+    F: Fn(T, T) -> (T, T),
+{
+    assert_eq!(p.len(), q.len(), "Input arrays must have the same length.");
+
+    let ((p, total), (q, _)) = (prepare(p, config), prepare(q, config));
+    let mut value = T::zero();
+    let mut grad = Zip::from(&p).and(&q).map_collect(|&pi, &qi| {
+        let (v, g) = term(pi, qi);
+        value = value + v;
+        g
+    });
+
+    // For p = x / sum(x), d/dx_i = (g_i - sum_j p_j g_j) / sum(x).
+    if let Some(total) = total {
+        let mean = Zip::from(&p)
+            .and(&grad)
+            .fold(T::zero(), |acc, &pi, &gi| acc + pi * gi);
+        grad.mapv_inplace(|g| (g - mean) / total);
+    }
+
+    (value, grad)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{kl_divergence_with_config, symmetric_kl_with_config};
+    use ndarray::arr1;
+
+    fn numeric_grad<F: Fn(&ArrayView1<f64>) -> f64>(f: F, p: &Array1<f64>) -> Array1<f64> {
+        let h = 1e-6;
+        Array1::from_shape_fn(p.len(), |i| {
+            let (mut hi, mut lo) = (p.clone(), p.clone());
+            hi[i] += h;
+            lo[i] -= h;
+            (f(&hi.view()) - f(&lo.view())) / (2.0 * h)
+        })
+    }
+
+    #[test]
+    fn test_gradients_match_finite_differences() {
+        let p = arr1(&[0.2, 0.3, 0.5]);
+        let q = arr1(&[0.4, 0.4, 0.2]);
+        let configs = [
+            DivergenceConfig::default(),
+            DivergenceConfig::default().with_epsilon(1e-3),
+            DivergenceConfig::default().with_normalize(true),
+        ];
+        for config in configs {
+            let (d, grad) = kl_divergence_grad_with_config(&p.view(), &q.view(), &config);
+            assert_eq!(d, kl_divergence_with_config(&p.view(), &q.view(), &config));
+            let expected = numeric_grad(|x| kl_divergence_with_config(x, &q.view(), &config), &p);
+            assert!(grad
+                .iter()
+                .zip(&expected)
+                .all(|(g, e)| (g - e).abs() < 1e-6));
+
+            let (d, grad) = symmetric_kl_grad_with_config(&p.view(), &q.view(), &config);
+            assert!((d - symmetric_kl_with_config(&p.view(), &q.view(), &config)).abs() < 1e-12);
+            let expected = numeric_grad(|x| symmetric_kl_with_config(x, &q.view(), &config), &p);
+            assert!(grad
+                .iter()
+                .zip(&expected)
+                .all(|(g, e)| (g - e).abs() < 1e-6));
+        }
+    }
+
+    #[test]
+    fn test_normalized_counts() {
+        let p = arr1(&[4.0, 1.0, 5.0]);
+        let q = arr1(&[2.0, 2.0, 6.0]);
+        let config = DivergenceConfig::default().with_normalize(true);
+        let (_, grad) = kl_divergence_grad_with_config(&p.view(), &q.view(), &config);
+        // Scaling p does not change the normalized divergence.
+        assert!(grad.dot(&p).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_identical_inputs() {
+        let p = arr1(&[0.25, 0.75]);
+        let (d, grad) = symmetric_kl_grad(&p.view(), &p.view());
+        assert_eq!(d, 0.0);
+        assert!(grad.iter().all(|g| g.abs() < 1e-15));
+    }
+}
@@ -0,0 +1,92 @@
+use ndarray::ArrayView1;
+use num::Float;
+
+use super::elastic::align;
+
+/// Computes the longest common subsequence (LCSS) distance between two sequences.
+///
+/// Two elements match when they differ by at most `epsilon` and their positions by at most
+/// `delta`. The length `L` of the longest common subsequence of matching elements (Vlachos,
+/// Kollios & Gunopulos, 2002) is found with the same alignment recurrence as
+/// [`edr`](crate::edr), and the distance is
+///
+/// ..math::
+///    d(x, y) = 1 - \frac{L}{\min(n, m)}
+///
+/// Unmatched elements are simply skipped, so outliers and gaps do not add to the distance.
+/// The time window `delta` bounds how far elements may be shifted against each other.
+///
+/// # Arguments
+/// * `x` - The first sequence.
+/// * `y` - The second sequence.
+/// * `epsilon` - The matching tolerance on values.
+/// * `delta` - The matching tolerance on positions.
+///
+/// # Returns
+/// A distance between `0` and `1`; `0` if both sequences are empty and `1` if only one of
+/// them is.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[0.0, 1.0, 2.0, 3.0]);
+/// let y = arr1(&[0.0, 9.0, 1.0, 2.0, 3.0]);
+/// assert_eq!(lcss(&x.view(), &y.view(), 0.1, 1), 0.0);
+/// assert_eq!(lcss(&x.view(), &y.view(), 0.1, 0), 0.75);
+/// ```
+pub fn lcss<T>(x: &ArrayView1<T>, y: &ArrayView1<T>, epsilon: T, delta: usize) -> T
+where
+    T: Float,
+{
+    let (n, m) = (x.len(), y.len());
+    if n == 0 || m == 0 {
+        return if n == m { T::zero() } else { T::one() };
+    }
+
+    let common = align(
+        n,
+        m,
+        |_, _| T::zero(),
+        |i, j, diagonal, up, left| {
+            if i.abs_diff(j) <= delta && (x[i] - y[j]).abs() <= epsilon {
+                diagonal + T::one()
+            } else {
+                up.max(left)
+            }
+        },
+    );
+    T::one() - common / T::from(n.min(m)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{arr1, Array1};
+
+    #[test]
+    fn test_lcss_known_values() {
+        let x = arr1(&[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(lcss(&x.view(), &x.view(), 0.0, 0), 0.0);
+
+        // The subsequence [1, 3, 4] is shared.
+        let y = arr1(&[1.0, 7.0, 3.0, 4.0, 8.0]);
+        assert_eq!(lcss(&x.view(), &y.view(), 0.0, 5), 0.25);
+        assert_eq!(lcss(&y.view(), &x.view(), 0.0, 5), 0.25);
+        assert_eq!(lcss(&x.view(), &y.view(), 10.0, 5), 0.0);
+    }
+
+    #[test]
+    fn test_lcss_time_window() {
+        // The same pattern shifted by three steps only matches with a wide enough window.
+        let x = arr1(&[5.0f32, 6.0, 7.0, 0.0, 0.0, 0.0]);
+        let y = arr1(&[1.0f32, 1.0, 1.0, 5.0, 6.0, 7.0]);
+        assert_eq!(lcss(&x.view(), &y.view(), 0.5, 2), 1.0);
+        assert_eq!(lcss(&x.view(), &y.view(), 0.5, 3), 0.5);
+
+        let empty = Array1::<f32>::zeros(0);
+        assert_eq!(lcss(&empty.view(), &x.view(), 0.5, 3), 1.0);
+        assert_eq!(lcss(&empty.view(), &empty.view(), 0.5, 3), 0.0);
+    }
+}
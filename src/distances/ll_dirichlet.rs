@@ -8,7 +8,7 @@ fn log_single_beta<T: Float>(x: T) -> T {
         + T::from(0.125).unwrap() / x
 }
 
-fn log_beta<T: Float>(x: T, y: T) -> T
+fn log_beta<T>(x: T, y: T) -> T
 where
     T: Float,
 {
@@ -58,39 +58,138 @@ where
 /// let result = ll_dirichlet(&data1, &data2);
 /// println!("Log Dirichlet likelihood: {}", result);
 /// ```
-pub fn ll_dirichlet<T: Float>(data1: &[T], data2: &[T]) -> T
+pub fn ll_dirichlet<T>(data1: &[T], data2: &[T]) -> T
 where
     T: Float + Sum,
 {
-    let n1: T = data1.iter().copied().sum();
-    let n2: T = data2.iter().copied().sum();
+    ll_dirichlet_with_terms(
+        data1,
+        &DirichletTerms::new(data1),
+        data2,
+        &DirichletTerms::new(data2),
+    )
+}
+
+/// The terms of [`ll_dirichlet`] that depend on a single sample.
+///
+/// Computing them once per sample with [`DirichletTerms::new`] and passing them to
+/// [`ll_dirichlet_with_terms`] avoids recomputing them when one sample is compared against
+/// many others.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DirichletTerms<T> {
+    /// The total count `n` of the sample.
+    pub total: T,
+    /// `log_single_beta(n)`.
+    pub log_single_beta_total: T,
+    /// The sum of `log_single_beta(x_i)` over the counts greater than `0.9`.
+    pub self_denom: T,
+}
 
+impl<T> DirichletTerms<T>
+where
+    T: Float + Sum,
+{
+    /// Computes the terms of `data`.
+    pub fn new(data: &[T]) -> Self {
+        let threshold = T::from(0.9).unwrap();
+        let total: T = data.iter().copied().sum();
+        let self_denom = data
+            .iter()
+            .filter(|&&x| x > threshold)
+            .fold(T::zero(), |acc, &x| acc + log_single_beta(x));
+        DirichletTerms {
+            total,
+            log_single_beta_total: log_single_beta(total),
+            self_denom,
+        }
+    }
+}
+
+/// Calculates [`ll_dirichlet`] from precomputed [`DirichletTerms`] of both samples.
+///
+/// # Panics
+/// Panics if `data1` and `data2` do not have the same length. The terms are not checked
+/// against the data they were computed from.
+///
+/// # Examples
+///
+/// ```
+/// use fast_distances::*;
+/// let query: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0];
+/// let query_terms = DirichletTerms::new(&query);
+/// for sample in [vec![5.0, 6.0, 7.0, 8.0], vec![8.0, 6.0, 7.0, 5.0]] {
+///     let terms = DirichletTerms::new(&sample);
+///     let result = ll_dirichlet_with_terms(&query, &query_terms, &sample, &terms);
+///     assert_eq!(result, ll_dirichlet(&query, &sample));
+/// }
+/// ```
+pub fn ll_dirichlet_with_terms<T>(
+    data1: &[T],
+    terms1: &DirichletTerms<T>,
+    data2: &[T],
+    terms2: &DirichletTerms<T>,
+) -> T
+where
+    T: Float,
+{
+    let (a1, a2) = likelihood_terms(data1, terms1, data2, terms2);
+    T::sqrt(T::from(1.0).unwrap() / terms2.total * a2 + T::from(1.0).unwrap() / terms1.total * a1)
+}
+
+/// Returns the bracketed terms of [`ll_dirichlet`] that are divided by `n1` and `n2`.
+pub(crate) fn likelihood_terms<T>(
+    data1: &[T],
+    terms1: &DirichletTerms<T>,
+    data2: &[T],
+    terms2: &DirichletTerms<T>,
+) -> (T, T)
+where
+    T: Float,
+{
+    let (log_b, self_denom1, self_denom2) = pair_terms(data1, terms1, data2, terms2);
+    let (n1, n2) = (terms1.total, terms2.total);
+    (
+        log_b - log_beta(n2, n1) - (self_denom1 - terms1.log_single_beta_total),
+        log_b - log_beta(n1, n2) - (self_denom2 - terms2.log_single_beta_total),
+    )
+}
+
+/// Returns the shared `log_b` sum and both `self_denom` sums of a pair of samples.
+///
+/// Counts that are at most `0.9` but whose product with the matching count exceeds `0.9` also
+/// enter the `self_denom` sums, so they are added to the per-sample terms here.
+fn pair_terms<T>(
+    data1: &[T],
+    terms1: &DirichletTerms<T>,
+    data2: &[T],
+    terms2: &DirichletTerms<T>,
+) -> (T, T, T)
+where
+    T: Float,
+{
+    assert_eq!(
+        data1.len(),
+        data2.len(),
+        "Input arrays must have the same length."
+    );
+    let threshold = T::from(0.9).unwrap();
     let mut log_b = T::from(0.0).unwrap();
-    let mut self_denom1 = T::from(0.0).unwrap();
-    let mut self_denom2 = T::from(0.0).unwrap();
-
-    for i in 0..data1.len() {
-        if data1[i] * data2[i] > T::from(0.9).unwrap() {
-            log_b = log_b + log_beta(data1[i], data2[i]);
-            self_denom1 = self_denom1 + log_single_beta(data1[i]);
-            self_denom2 = self_denom2 + log_single_beta(data2[i]);
-        } else {
-            if data1[i] > T::from(0.9).unwrap() {
-                self_denom1 = self_denom1 + log_single_beta(data1[i]);
-            }
+    let mut self_denom1 = terms1.self_denom;
+    let mut self_denom2 = terms2.self_denom;
 
-            if data2[i] > T::from(0.9).unwrap() {
-                self_denom2 = self_denom2 + log_single_beta(data2[i]);
+    for (&x, &y) in data1.iter().zip(data2) {
+        if x * y > threshold {
+            log_b = log_b + log_beta(x, y);
+            if x <= threshold {
+                self_denom1 = self_denom1 + log_single_beta(x);
+            }
+            if y <= threshold {
+                self_denom2 = self_denom2 + log_single_beta(y);
             }
         }
     }
 
-    T::sqrt(
-        T::from(1.0).unwrap() / n2
-            * (log_b - log_beta(n1, n2) - (self_denom2 - log_single_beta(n2)))
-            + T::from(1.0).unwrap() / n1
-                * (log_b - log_beta(n2, n1) - (self_denom1 - log_single_beta(n1))),
-    )
+    (log_b, self_denom1, self_denom2)
 }
 
 #[cfg(test)]
@@ -114,4 +213,25 @@ mod tests {
         let result = ll_dirichlet(&data1, &data2);
         assert_eq!(result, 0.36789301898248805, "ll_dirichlet with f64");
     }
+
+    #[test]
+    fn test_ll_dirichlet_fractional_counts() {
+        // 0.5 * 5.0 > 0.9, so the 0.5 enters the first sample's denominator through the pair.
+        let data1: Vec<f64> = vec![0.5, 2.0, 3.0, 4.0];
+        let data2: Vec<f64> = vec![5.0, 0.0, 7.0, 8.0];
+        let terms1 = DirichletTerms::new(&data1);
+        let terms2 = DirichletTerms::new(&data2);
+        let (log_b, self_denom1, self_denom2) = pair_terms(&data1, &terms1, &data2, &terms2);
+
+        assert_eq!(terms1.total, 9.5);
+        let expected = log_beta(0.5, 5.0) + log_beta(3.0, 7.0) + log_beta(4.0, 8.0);
+        assert!((log_b - expected).abs() < 1e-12);
+        let expected = (log_single_beta(2.0) + log_single_beta(3.0) + log_single_beta(4.0))
+            + log_single_beta(0.5);
+        assert_eq!(self_denom1, expected);
+        let expected = log_single_beta(5.0) + log_single_beta(7.0) + log_single_beta(8.0);
+        assert_eq!(self_denom2, expected);
+        assert!(ll_dirichlet(&data1, &data2).is_finite());
+        assert_eq!(ll_dirichlet(&data1, &data2), ll_dirichlet(&data2, &data1));
+    }
 }
@@ -0,0 +1,135 @@
+use std::iter::Sum;
+
+use num::Float;
+
+use super::ll_dirichlet::{likelihood_terms, DirichletTerms};
+
+/// Computes [`ll_dirichlet`](crate::ll_dirichlet) and its gradient with respect to `data1`.
+///
+/// Writing the distance as `sqrt(S)`, the gradient is `dS / (2 sqrt(S))`, where `dS` collects
+/// the derivatives of the pairwise `log_beta` terms, of the first sample's `self_denom` sum and
+/// of every term that depends on the total `n1`, which moves with each count. The thresholds
+/// at `0.9` and the integer loop inside `log_beta` for small arguments make the distance
+/// piecewise smooth; the gradient is that of the piece containing `data1`.
+///
+/// # Returns
+/// A tuple containing the distance and the gradient; the gradient is zero when the distance
+/// is zero or not finite.
+///
+/// # Panics
+/// Panics if `data1` and `data2` do not have the same length.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// let data1: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0];
+/// let data2: Vec<f64> = vec![5.0, 6.0, 7.0, 8.0];
+/// let (distance, gradient) = ll_dirichlet_grad(&data1, &data2);
+/// assert_eq!(distance, ll_dirichlet(&data1, &data2));
+/// assert_eq!(gradient.len(), 4);
+/// ```
+pub fn ll_dirichlet_grad<T>(data1: &[T], data2: &[T]) -> (T, Vec<T>)
+where
+    T: Float + Sum,
+{
+    let terms1 = DirichletTerms::new(data1);
+    let terms2 = DirichletTerms::new(data2);
+    let (a1, a2) = likelihood_terms(data1, &terms1, data2, &terms2);
+    let (n1, n2) = (terms1.total, terms2.total);
+    let (inv1, inv2) = (n1.recip(), n2.recip());
+    let distance = (inv2 * a2 + inv1 * a1).sqrt();
+    if distance.is_zero() || !distance.is_finite() {
+        return (distance, vec![T::zero(); data1.len()]);
+    }
+
+    // Every count moves `n1`, which appears in both `log_beta(n1, n2)`, in the `1 / n1` factor
+    // and in `log_single_beta(n1)`.
+    let shared =
+        -(inv1 + inv2) * log_beta_grad(n1, n2) - a1 * inv1 * inv1 + inv1 * log_single_beta_grad(n1);
+
+    let threshold = T::from(0.9).unwrap();
+    let two = T::one() + T::one();
+    let gradient = data1
+        .iter()
+        .zip(data2)
+        .map(|(&x, &y)| {
+            let mut ds = shared;
+            if x * y > threshold {
+                ds = ds + (inv1 + inv2) * log_beta_grad(x, y);
+            }
+            if x * y > threshold || x > threshold {
+                ds = ds - inv1 * log_single_beta_grad(x);
+            }
+            ds / (two * distance)
+        })
+        .collect();
+
+    (distance, gradient)
+}
+
+/// The derivative of `log_single_beta(x)`.
+fn log_single_beta_grad<T: Float>(x: T) -> T {
+    let two = T::one() + T::one();
+    -two * two.ln() - T::from(0.5).unwrap() / x - T::from(0.125).unwrap() / (x * x)
+}
+
+/// The derivative of `log_beta(x, y)` with respect to `x`.
+fn log_beta_grad<T: Float>(x: T, y: T) -> T {
+    let (a, b) = (x.min(y), x.max(y));
+    if b < T::from(5.0).unwrap() {
+        // Only `b` enters continuously; the loop bound over `a` is piecewise constant.
+        if x < y {
+            return T::zero();
+        }
+        let mut value = -b.recip();
+        for i in 1..a.to_i64().unwrap() {
+            value = value - (b + T::from(i).unwrap()).recip();
+        }
+        value
+    } else {
+        log_single_beta_grad(x) - log_single_beta_grad(x + y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ll_dirichlet;
+
+    #[test]
+    fn test_ll_dirichlet_grad_finite_difference() {
+        let cases: [(Vec<f64>, Vec<f64>); 3] = [
+            // A count below the threshold whose product with its pair is above it.
+            (vec![0.5, 2.5, 6.2, 4.0], vec![5.0, 6.0, 7.0, 8.0]),
+            // Only the small-argument branch of `log_beta`.
+            (vec![3.0, 1.0], vec![2.0, 1.0]),
+            (
+                vec![1.0, 2.0, 3.0, 4.0, 0.0, 3.0],
+                vec![50.0, 60.0, 70.0, 80.0, 2.0, 20.0],
+            ),
+        ];
+        for (data1, data2) in cases {
+            let (distance, gradient) = ll_dirichlet_grad(&data1, &data2);
+            assert_eq!(distance, ll_dirichlet(&data1, &data2));
+            assert!(distance.is_finite());
+
+            let h = 1e-7;
+            for i in 0..data1.len() {
+                let mut shifted = data1.clone();
+                shifted[i] += h;
+                let numeric = (ll_dirichlet(&shifted, &data2) - distance) / h;
+                assert!((numeric - gradient[i]).abs() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ll_dirichlet_grad_f32() {
+        let data1: Vec<f32> = vec![1.0, 2.0, 3.0, 4.0];
+        let data2: Vec<f32> = vec![5.0, 6.0, 7.0, 8.0];
+        let (distance, gradient) = ll_dirichlet_grad(&data1, &data2);
+        assert_eq!(distance, ll_dirichlet(&data1, &data2));
+        assert!(gradient.iter().all(|g| g.is_finite()));
+    }
+}
@@ -25,7 +25,7 @@ pub fn log_beta<T: Float>(x: T, y: T) -> T {
             value = value + (i.ln() - (b + i).ln());
             i = i + T::one();
         }
-        return value;
+        value
     } else {
         approx_log_gamma(x) + approx_log_gamma(y) - approx_log_gamma(x + y)
     }
@@ -39,7 +39,11 @@ mod tests {
     fn test_log_beta_f32() {
         // Test for x = 1.0 and y = 2.0 (Beta(1, 2))
         let result = log_beta(1.0f32, 2.0f32);
-        assert_eq!(result, -0.6931472f32, "log(Beta(1, 2)) ≈ -0.6931472");
+        assert_eq!(
+            result,
+            -std::f32::consts::LN_2,
+            "log(Beta(1, 2)) ≈ -0.6931472"
+        );
 
         // Test for x = 3.0 and y = 5.0 (Beta(3, 5))
         let result = log_beta(3.0f32, 5.0f32);
@@ -54,7 +58,11 @@ mod tests {
     fn test_log_beta_f64() {
         // Test for x = 1.0 and y = 2.0 (Beta(1, 2))
         let result = log_beta(1.0f64, 2.0f64);
-        assert_eq!(result, -0.6931471805599453, "log(Beta(1, 2)) ≈ -0.6931472");
+        assert_eq!(
+            result,
+            -std::f64::consts::LN_2,
+            "log(Beta(1, 2)) ≈ -0.6931472"
+        );
 
         // Test for x = 3.0 and y = 5.0 (Beta(3, 5))
         let result = log_beta(3.0f64, 5.0f64);
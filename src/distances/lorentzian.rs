@@ -0,0 +1,57 @@
+use ndarray::{ArrayView1, Zip};
+use num::Float;
+
+/// Computes the Lorentzian distance between two vectors `x` and `y`.
+///
+/// ..math::
+///    d(x, y) = \sum_i \ln(1 + |x_i - y_i|)
+///
+/// The logarithm damps large coordinate differences, so a few outlying coordinates weigh
+/// less than under [`manhattan`](crate::manhattan), which it matches to first order for small
+/// differences.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[1.0, 2.0, 3.0]);
+/// let y = arr1(&[2.0, 2.0, 0.0]);
+/// assert_eq!(lorentzian(&x.view(), &y.view()), 2f64.ln() + 4f64.ln());
+/// ```
+pub fn lorentzian<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+
+    Zip::from(x)
+        .and(y)
+        .fold(T::zero(), |acc, &xi, &yi| acc + (xi - yi).abs().ln_1p())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manhattan;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_lorentzian_basic() {
+        let x = arr1(&[0.5_f32, -1.0, 3.0]);
+        let y = arr1(&[0.5_f32, 2.0, -1.0]);
+        assert_eq!(lorentzian(&x.view(), &y.view()), 4f32.ln() + 5f32.ln());
+        assert_eq!(lorentzian(&x.view(), &x.view()), 0.0);
+    }
+
+    #[test]
+    fn test_lorentzian_small_differences() {
+        let x = arr1(&[1.0, 2.0, 3.0]);
+        let y = &x + 1e-9;
+        let d = lorentzian(&x.view(), &y.view());
+        assert!((d - manhattan(&x.view(), &y.view())).abs() < 1e-15);
+    }
+}
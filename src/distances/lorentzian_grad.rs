@@ -0,0 +1,63 @@
+use ndarray::{Array1, ArrayView1, Zip};
+use num::Float;
+
+/// Computes the Lorentzian distance and its gradient between two vectors `x` and `y`.
+///
+/// The gradient of `Σ ln(1 + |x_i - y_i|)` with respect to `x` is
+///
+/// ..math::
+///    \frac{\partial d}{\partial x_i} = \frac{\operatorname{sign}(x_i - y_i)}{1 + |x_i - y_i|}
+///
+/// with the subgradient `0` where `x_i = y_i`.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Returns
+/// A tuple containing:
+/// * The [`lorentzian`](crate::lorentzian) distance between `x` and `y`.
+/// * The gradient of the distance with respect to `x`.
+pub fn lorentzian_grad<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> (T, Array1<T>)
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+
+    let mut result = T::zero();
+    let grad = Zip::from(x).and(y).map_collect(|&xi, &yi| {
+        let diff = xi - yi;
+        let abs = diff.abs();
+        result = result + abs.ln_1p();
+        if diff.is_zero() {
+            T::zero()
+        } else {
+            diff.signum() / (T::one() + abs)
+        }
+    });
+
+    (result, grad)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lorentzian;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_lorentzian_grad() {
+        let x = arr1(&[0.5, -1.0, 3.0]);
+        let y = arr1(&[0.5, 2.0, -1.0]);
+        let (d, grad) = lorentzian_grad(&x.view(), &y.view());
+        assert_eq!(d, lorentzian(&x.view(), &y.view()));
+        assert_eq!(grad, arr1(&[0.0, -0.25, 0.2]));
+
+        let h = 1e-6;
+        let (mut hi, mut lo) = (x.clone(), x.clone());
+        hi[2] += h;
+        lo[2] -= h;
+        let numeric =
+            (lorentzian(&hi.view(), &y.view()) - lorentzian(&lo.view(), &y.view())) / (2.0 * h);
+        assert!((numeric - grad[2]).abs() < 1e-8);
+    }
+}
@@ -1,4 +1,4 @@
-use ndarray::{Array2, ArrayView1};
+use ndarray::{Array2, ArrayView1, ArrayView2};
 use num::Float;
 
 use crate::utils::identity_matrix;
@@ -20,7 +20,7 @@ use crate::utils::identity_matrix;
 /// - `x`: A reference to an `ArrayView1<T>` representing the first vector.
 /// - `y`: A reference to an `ArrayView1<T>` representing the second vector.
 /// - `vinv`: An optional reference to an `ArrayView2<T>` representing the inverse covariance matrix.
-///           If `None`, it defaults to the identity matrix, effectively reducing the distance to Euclidean distance.
+///   If `None`, it defaults to the identity matrix, effectively reducing the distance to Euclidean distance.
 ///
 /// # Returns:
 /// The Mahalanobis distance between `x` and `y` as a scalar of type `T`.
@@ -51,6 +51,19 @@ where
         identity_matrix(x.len()) // Use the identity matrix if vinv is None
     });
 
+    mahalanobis_with_vinv(x, y, &vinv.view())
+}
+
+/// Computes the Mahalanobis distance for a borrowed inverse covariance matrix, so callers
+/// comparing many pairs against the same `vinv` don't need to clone it for every call.
+pub(crate) fn mahalanobis_with_vinv<T>(
+    x: &ArrayView1<T>,
+    y: &ArrayView1<T>,
+    vinv: &ArrayView2<T>,
+) -> T
+where
+    T: Float,
+{
     // Compute the difference (x - y)
     let mut diff = vec![T::zero(); x.len()];
     for i in 0..x.len() {
@@ -70,6 +83,135 @@ where
     result.sqrt()
 }
 
+/// Computes the Mahalanobis distance from the lower-triangular Cholesky factor `l` of the
+/// covariance matrix, where `V = L · Lᵀ`.
+///
+/// Since `(x - y)ᵀ V⁻¹ (x - y) = ‖L⁻¹ (x - y)‖²`, the difference is whitened by forward
+/// substitution and the Euclidean norm of the result is returned. This avoids forming `V⁻¹`
+/// explicitly, which is both cheaper to set up and better conditioned than [`mahalanobis`].
+/// Only the lower triangle of `l` is read.
+///
+/// # Panics
+/// Panics if the lengths of `x` and `y` do not match, or if `l` is smaller than `x.len()` in
+/// either dimension.
+///
+/// # Example
+/// ```
+/// use ndarray::{arr1, arr2};
+/// use fast_distances::*;
+///
+/// // V = L · Lᵀ = [[4, 2], [2, 5]]
+/// let l = arr2(&[[2.0, 0.0], [1.0, 2.0]]);
+/// let x = arr1(&[2.0, 3.0]);
+/// let y = arr1(&[0.0, 0.0]);
+/// assert_eq!(mahalanobis_cholesky(&x.view(), &y.view(), &l.view()), 2.0f64.sqrt());
+/// ```
+pub fn mahalanobis_cholesky<T>(x: &ArrayView1<T>, y: &ArrayView1<T>, l: &ArrayView2<T>) -> T
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+
+    // Solve L z = (x - y) by forward substitution, accumulating ‖z‖² as we go.
+    let mut z = vec![T::zero(); x.len()];
+    let mut result = T::zero();
+    for i in 0..x.len() {
+        let mut acc = x[i] - y[i];
+        for j in 0..i {
+            acc = acc - l[(i, j)] * z[j];
+        }
+        z[i] = acc / l[(i, i)];
+        result = result + z[i] * z[i];
+    }
+
+    result.sqrt()
+}
+
+/// Computes the Mahalanobis distance from the covariance matrix `cov` itself.
+///
+/// The covariance is factorized as `V = L · Lᵀ` and the distance is computed by
+/// [`mahalanobis_cholesky`], so no inverse is formed. Factorizing costs `O(n³)` per call;
+/// to compare many pairs against the same covariance, build a
+/// [`MahalanobisCholesky`](crate::MahalanobisCholesky) once instead. Only the lower triangle
+/// of `cov` is read.
+///
+/// # Panics
+/// Panics if the lengths of `x` and `y` do not match, if `cov` is not `n x n`, or if it is not
+/// positive definite.
+///
+/// # Example
+/// ```
+/// use ndarray::{arr1, arr2};
+/// use fast_distances::*;
+///
+/// let cov = arr2(&[[4.0, 2.0], [2.0, 5.0]]);
+/// let x = arr1(&[2.0, 3.0]);
+/// let y = arr1(&[0.0, 0.0]);
+/// assert!((mahalanobis_from_cov(&x.view(), &y.view(), &cov.view()) - 2.0f64.sqrt()).abs() < 1e-12);
+/// ```
+pub fn mahalanobis_from_cov<T>(x: &ArrayView1<T>, y: &ArrayView1<T>, cov: &ArrayView2<T>) -> T
+where
+    T: Float,
+{
+    assert_eq!(
+        cov.dim(),
+        (x.len(), x.len()),
+        "Covariance matrix must be square with one row per coordinate."
+    );
+    let l = cholesky(cov).expect("Covariance matrix must be positive definite.");
+    mahalanobis_cholesky(x, y, &l.view())
+}
+
+/// Returns the lower-triangular Cholesky factor `L` of the symmetric matrix `a = L · Lᵀ`, or
+/// `None` if `a` is not positive definite. Only the lower triangle of `a` is read.
+pub(crate) fn cholesky<T>(a: &ArrayView2<T>) -> Option<Array2<T>>
+where
+    T: Float,
+{
+    let n = a.nrows();
+    let mut l = Array2::zeros((n, n));
+    for i in 0..n {
+        for j in 0..=i {
+            let mut acc = a[(i, j)];
+            for k in 0..j {
+                acc = acc - l[(i, k)] * l[(j, k)];
+            }
+            if i == j {
+                if acc.is_nan() || acc <= T::zero() {
+                    return None;
+                }
+                l[(i, i)] = acc.sqrt();
+            } else {
+                l[(i, j)] = acc / l[(j, j)];
+            }
+        }
+    }
+    Some(l)
+}
+
+/// Estimates the covariance of the rows of `data`, with the `n - 1` denominator, and shrinks
+/// it towards a multiple of the identity: `(1 - shrinkage) · S + shrinkage · (tr(S) / d) · I`.
+pub(crate) fn shrunk_covariance<T>(data: &ArrayView2<T>, shrinkage: T) -> Array2<T>
+where
+    T: Float + 'static,
+{
+    let (n, d) = data.dim();
+    let rows = T::from(n).unwrap();
+    let mean = data.map_axis(ndarray::Axis(0), |column| {
+        column.fold(T::zero(), |acc, &v| acc + v) / rows
+    });
+    let centered = data - &mean;
+    let denominator = T::from(n - 1).unwrap();
+    let mut cov = centered.t().dot(&centered).mapv_into(|v| v / denominator);
+
+    let target = cov.diag().fold(T::zero(), |acc, &v| acc + v) / T::from(d).unwrap();
+    cov.mapv_inplace(|v| v * (T::one() - shrinkage));
+    for i in 0..d {
+        cov[(i, i)] = cov[(i, i)] + shrinkage * target;
+    }
+    cov
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -83,7 +225,7 @@ mod tests {
         let y = arr1(&[4.0, 5.0, 6.0]);
 
         let result = mahalanobis(&x.view(), &y.view(), None);
-        let expected = ((3.0_f64.powi(2) + 3.0_f64.powi(2) + 3.0_f64.powi(2)) as f64).sqrt();
+        let expected = (3.0_f64.powi(2) + 3.0_f64.powi(2) + 3.0_f64.powi(2)).sqrt();
 
         assert!((result - expected).abs() < 1e-6);
     }
@@ -110,4 +252,43 @@ mod tests {
         let result = mahalanobis(&x.view(), &y.view(), None);
         assert_eq!(result, 0.0);
     }
+
+    #[test]
+    fn test_mahalanobis_cholesky_matches_vinv() {
+        // V = L · Lᵀ = [[4, 2, 0], [2, 10, 3], [0, 3, 2]], V⁻¹ computed by hand.
+        let l = arr2(&[[2.0, 0.0, 0.0], [1.0, 3.0, 0.0], [0.0, 1.0, 1.0]]);
+        let vinv = arr2(&[
+            [11.0 / 36.0, -1.0 / 9.0, 1.0 / 6.0],
+            [-1.0 / 9.0, 2.0 / 9.0, -1.0 / 3.0],
+            [1.0 / 6.0, -1.0 / 3.0, 1.0],
+        ]);
+        let x = arr1(&[1.0, -2.0, 0.5]);
+        let y = arr1(&[3.0, 1.0, -1.0]);
+
+        let expected = mahalanobis(&x.view(), &y.view(), Some(vinv));
+        let result = mahalanobis_cholesky(&x.view(), &y.view(), &l.view());
+        assert!((result - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_mahalanobis_from_cov_matches_cholesky() {
+        let l = arr2(&[[2.0, 0.0, 0.0], [1.0, 3.0, 0.0], [0.0, 1.0, 1.0]]);
+        let cov = l.dot(&l.t());
+        let x = arr1(&[1.0, -2.0, 0.5]);
+        let y = arr1(&[3.0, 1.0, -1.0]);
+
+        let factor = cholesky(&cov.view()).unwrap();
+        assert!(factor.iter().zip(&l).all(|(a, b)| (a - b).abs() < 1e-12));
+        let expected = mahalanobis_cholesky(&x.view(), &y.view(), &l.view());
+        let result = mahalanobis_from_cov(&x.view(), &y.view(), &cov.view());
+        assert!((result - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    #[should_panic(expected = "Covariance matrix must be positive definite.")]
+    fn test_mahalanobis_from_cov_singular() {
+        let cov = arr2(&[[1.0, 1.0], [1.0, 1.0]]);
+        let x = arr1(&[1.0, 0.0]);
+        mahalanobis_from_cov(&x.view(), &x.view(), &cov.view());
+    }
 }
@@ -2,6 +2,7 @@ use ndarray::{Array1, Array2, ArrayView1};
 use num::Float;
 
 use crate::utils::identity_matrix;
+use crate::DistanceConfig;
 
 /// Computes the Mahalanobis distance and its gradient with respect to `x`
 /// using the inverse covariance matrix `vinv`.
@@ -26,7 +27,7 @@ use crate::utils::identity_matrix;
 /// - `x`: A reference to an `ArrayView1<T>` representing the first vector.
 /// - `y`: A reference to an `ArrayView1<T>` representing the second vector.
 /// - `vinv`: An optional reference to an `ArrayView2<T>` representing the inverse covariance matrix.
-///           If `None`, it defaults to the identity matrix, effectively reducing the distance to Euclidean distance.
+///   If `None`, it defaults to the identity matrix, effectively reducing the distance to Euclidean distance.
 ///
 /// # Returns:
 /// The Mahalanobis distance between `x` and `y`, and the gradient of the distance with respect to `x`
@@ -55,6 +56,22 @@ pub fn mahalanobis_grad<T>(
     y: &ArrayView1<T>,
     vinv: Option<Array2<T>>,
 ) -> (T, Array1<T>)
+where
+    T: Float,
+{
+    mahalanobis_grad_with_config(x, y, vinv, &DistanceConfig::default())
+}
+
+/// Computes the Mahalanobis distance and its gradient, using the stabilization epsilon from `config`.
+///
+/// See [`mahalanobis_grad`] for details; this variant lets callers tune the epsilon added to the
+/// distance in the gradient's denominator.
+pub fn mahalanobis_grad_with_config<T>(
+    x: &ArrayView1<T>,
+    y: &ArrayView1<T>,
+    vinv: Option<Array2<T>>,
+    config: &DistanceConfig<T>,
+) -> (T, Array1<T>)
 where
     T: Float,
 {
@@ -84,7 +101,7 @@ where
     let dist = result.sqrt();
     let grad: Array1<T> = grad_tmp
         .iter()
-        .map(|&g| g / (T::from(1e-6).unwrap() + dist))
+        .map(|&g| g / (config.epsilon + dist))
         .collect();
 
     (dist, Array1::from(grad))
@@ -103,7 +120,7 @@ mod tests {
         let y = arr1(&[4.0, 5.0, 6.0]);
 
         let (dist, grad) = mahalanobis_grad(&x.view(), &y.view(), None);
-        let expected_dist = ((3.0_f64.powi(2) + 3.0_f64.powi(2) + 3.0_f64.powi(2)) as f64).sqrt();
+        let expected_dist = (3.0_f64.powi(2) + 3.0_f64.powi(2) + 3.0_f64.powi(2)).sqrt();
         let expected_grad = arr1(&[
             1.0 * (x[0] - y[0]) / (expected_dist + 1e-6),
             1.0 * (x[1] - y[1]) / (expected_dist + 1e-6),
@@ -143,4 +160,16 @@ mod tests {
         assert_eq!(dist, 0.0);
         assert_eq!(grad.sum(), 0.0);
     }
+
+    #[test]
+    fn test_mahalanobis_grad_with_config() {
+        let x = arr1(&[1.0, 2.0, 3.0]);
+        let y = arr1(&[4.0, 5.0, 6.0]);
+        let config = DistanceConfig::default().with_epsilon(0.0);
+
+        let (dist, grad) = mahalanobis_grad_with_config(&x.view(), &y.view(), None, &config);
+        for i in 0..3 {
+            assert!((grad[i] - (x[i] - y[i]) / dist).abs() < 1e-12);
+        }
+    }
 }
@@ -1,6 +1,8 @@
-use ndarray::ArrayView1;
+use ndarray::{ArrayView1, Zip};
 use num::Float;
 
+use crate::utils::NeumaierSum;
+
 /// Computes the Manhattan, taxicab, or L1 distance between two vectors.
 ///
 /// # Arguments
@@ -27,16 +29,70 @@ use num::Float;
 ///
 /// ..math::
 ///     D(x, y) = \sum_i |x_i - y_i|
-pub fn manhattan<T: Float>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+pub fn manhattan<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
 where
     T: Float + std::iter::Sum,
 {
     assert_eq!(x.len(), y.len(), "Input vectors must have the same length");
 
-    x.iter()
-        .zip(y.iter())
-        .map(|(&xi, &yi)| (xi - yi).abs())
-        .sum()
+    Zip::from(x)
+        .and(y)
+        .fold(T::zero(), |acc, &xi, &yi| acc + (xi - yi).abs())
+}
+
+/// Computes the Manhattan distance between two vectors, abandoning early once it exceeds `upper`.
+///
+/// # Returns
+///
+/// `Some(distance)` if the distance is at most `upper`, otherwise `None`.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::manhattan_bounded;
+///
+/// let x = arr1(&[1.0, 2.0, 3.0]);
+/// let y = arr1(&[4.0, 5.0, 6.0]);
+/// assert_eq!(manhattan_bounded(&x.view(), &y.view(), 9.0), Some(9.0));
+/// assert_eq!(manhattan_bounded(&x.view(), &y.view(), 5.0), None);
+/// ```
+pub fn manhattan_bounded<T>(x: &ArrayView1<T>, y: &ArrayView1<T>, upper: T) -> Option<T>
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input vectors must have the same length");
+
+    let mut result = T::zero();
+    for (&xi, &yi) in x.iter().zip(y.iter()) {
+        result = result + (xi - yi).abs();
+        if result > upper {
+            return None;
+        }
+    }
+
+    if result <= upper {
+        Some(result)
+    } else {
+        None
+    }
+}
+
+/// Computes the Manhattan distance between two vectors using compensated summation.
+///
+/// See [`euclidean_accurate`](crate::euclidean_accurate); the absolute differences are
+/// accumulated with [`NeumaierSum`].
+pub fn manhattan_accurate<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input vectors must have the same length");
+
+    let mut result = NeumaierSum::new();
+    for (&xi, &yi) in x.iter().zip(y.iter()) {
+        result.add((xi - yi).abs());
+    }
+    result.sum()
 }
 
 #[cfg(test)]
@@ -66,4 +122,27 @@ mod tests {
         let y = arr1(&[3.0, 4.0, 5.0]);
         manhattan(&x.view(), &y.view());
     }
+
+    #[test]
+    fn test_manhattan_bounded() {
+        let x = arr1(&[1.0f32, -2.0, 3.0]);
+        let y = arr1(&[4.0f32, 5.0, 6.0]);
+
+        assert_eq!(manhattan_bounded(&x.view(), &y.view(), 13.0), Some(13.0));
+        assert_eq!(manhattan_bounded(&x.view(), &y.view(), 12.5), None);
+    }
+
+    #[test]
+    fn test_manhattan_accurate_high_dimensional_f32() {
+        let n = 100_000;
+        let x: ndarray::Array1<f32> = (0..n).map(|i| ((i as f32) * 0.003).sin()).collect();
+        let y: ndarray::Array1<f32> = (0..n).map(|i| 0.1 + (i % 7) as f32 * 0.01).collect();
+
+        let reference = manhattan(&x.mapv(f64::from).view(), &y.mapv(f64::from).view());
+        let accurate = manhattan_accurate(&x.view(), &y.view()) as f64;
+        let naive = manhattan(&x.view(), &y.view()) as f64;
+
+        assert!((accurate - reference).abs() / reference < 1e-6);
+        assert!((accurate - reference).abs() <= (naive - reference).abs());
+    }
 }
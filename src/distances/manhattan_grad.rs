@@ -1,4 +1,4 @@
-use ndarray::{Array1, ArrayView1};
+use ndarray::{Array1, ArrayView1, Zip};
 use num::Float;
 
 /// Manhattan, taxicab, or l1 distance with gradient.
@@ -41,13 +41,11 @@ pub fn manhattan_grad<T: Float + num::Signed>(
     );
 
     let mut result = T::zero();
-    let mut grad = Array1::<T>::zeros(x.dim());
-
-    for i in 0..x.len() {
-        let diff = x[i] - y[i];
+    let grad = Zip::from(x).and(y).map_collect(|&xi, &yi| {
+        let diff = xi - yi;
         result = result + diff.abs();
-        grad[i] = diff.signum();
-    }
+        diff.signum()
+    });
 
     (result, grad)
 }
@@ -55,6 +53,7 @@ pub fn manhattan_grad<T: Float + num::Signed>(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::distances::reference::reference_inputs;
     use ndarray::arr1;
 
     #[test]
@@ -86,4 +85,12 @@ mod tests {
         assert_eq!(distance, 12.0);
         assert_eq!(grad, arr1(&[-1.0, 1.0, -1.0]));
     }
+
+    #[test]
+    fn test_reference_values() {
+        let (x, y) = reference_inputs();
+        let (dist, grad) = manhattan_grad(&x.view(), &y.view());
+        assert_eq!(dist, 13.75);
+        assert_eq!(grad, arr1(&[-1.0, -1.0, 1.0, 1.0, 1.0, -1.0, 1.0]));
+    }
 }
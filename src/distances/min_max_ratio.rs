@@ -0,0 +1,180 @@
+//! The Soergel, Motyka, Wave Hedges and Kulczynski dissimilarities between nonnegative vectors.
+//!
+//! Each compares the coordinate-wise minima and maxima of the two vectors, as catalogued in
+//! Cha's survey of probability density function distances. They share one zero rule: a ratio
+//! whose numerator and denominator both vanish counts as `0`, so two zero vectors (or two zero
+//! coordinates) are at distance `0`, and a positive numerator over a zero denominator gives
+//! infinity.
+
+use ndarray::{ArrayView1, Zip};
+use num::Float;
+
+/// Returns `Σ |x_i - y_i|`, `Σ min(x_i, y_i)` and `Σ max(x_i, y_i)`.
+fn sums<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> (T, T, T)
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+    Zip::from(x).and(y).fold(
+        (T::zero(), T::zero(), T::zero()),
+        |(diff, lo, hi), &a, &b| (diff + (a - b).abs(), lo + a.min(b), hi + a.max(b)),
+    )
+}
+
+/// Divides `numerator` by `denominator`, with `0 / 0 = 0`.
+fn ratio<T: Float>(numerator: T, denominator: T) -> T {
+    if numerator.is_zero() {
+        T::zero()
+    } else {
+        numerator / denominator
+    }
+}
+
+/// Computes the Soergel distance between two nonnegative vectors.
+///
+/// ..math::
+///    d(x, y) = \frac{\sum_i |x_i - y_i|}{\sum_i \max(x_i, y_i)}
+///
+/// For nonnegative inputs this equals the [`weighted_jaccard`](crate::weighted_jaccard)
+/// distance and lies in `[0, 1]`.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[1.0, 3.0, 0.0]);
+/// let y = arr1(&[2.0, 1.0, 1.0]);
+/// assert_eq!(soergel(&x.view(), &y.view()), 4.0 / 6.0);
+/// ```
+pub fn soergel<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    let (diff, _, max_sum) = sums(x, y);
+    ratio(diff, max_sum)
+}
+
+/// Computes the Motyka dissimilarity between two nonnegative vectors.
+///
+/// ..math::
+///    d(x, y) = \frac{\sum_i \max(x_i, y_i)}{\sum_i (x_i + y_i)}
+///
+/// This is one minus the Motyka similarity `Σ min / Σ (x + y)`. It lies in `[1/2, 1]` for
+/// nonzero nonnegative inputs, reaching `1/2` for identical vectors and `1` for vectors with
+/// disjoint supports; two zero vectors are at distance `0`.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[1.0, 3.0, 0.0]);
+/// let y = arr1(&[2.0, 1.0, 1.0]);
+/// assert_eq!(motyka(&x.view(), &y.view()), 6.0 / 8.0);
+/// assert_eq!(motyka(&x.view(), &x.view()), 0.5);
+/// ```
+pub fn motyka<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    // sum(x) + sum(y) = sum(min) + sum(max)
+    let (_, min_sum, max_sum) = sums(x, y);
+    ratio(max_sum, min_sum + max_sum)
+}
+
+/// Computes the Wave Hedges distance between two nonnegative vectors.
+///
+/// ..math::
+///    d(x, y) = \sum_i \frac{|x_i - y_i|}{\max(x_i, y_i)} = \sum_i \left(1 - \frac{\min(x_i, y_i)}{\max(x_i, y_i)}\right)
+///
+/// Coordinates where both vectors are zero contribute nothing, so the distance lies in
+/// `[0, n]`.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[1.0, 3.0, 0.0, 0.0]);
+/// let y = arr1(&[2.0, 1.0, 1.0, 0.0]);
+/// assert_eq!(wave_hedges(&x.view(), &y.view()), 0.5 + 2.0 / 3.0 + 1.0);
+/// ```
+pub fn wave_hedges<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+    Zip::from(x).and(y).fold(T::zero(), |acc, &a, &b| {
+        acc + ratio((a - b).abs(), a.max(b))
+    })
+}
+
+/// Computes the Kulczynski distance between two nonnegative vectors.
+///
+/// ..math::
+///    d(x, y) = \frac{\sum_i |x_i - y_i|}{\sum_i \min(x_i, y_i)}
+///
+/// The distance is unbounded: it is infinite when the supports of `x` and `y` are disjoint and
+/// the vectors differ.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[1.0, 3.0, 0.0]);
+/// let y = arr1(&[2.0, 1.0, 1.0]);
+/// assert_eq!(kulczynski(&x.view(), &y.view()), 4.0 / 2.0);
+/// assert_eq!(kulczynski(&arr1(&[1.0, 0.0]).view(), &arr1(&[0.0, 1.0]).view()), f64::INFINITY);
+/// ```
+pub fn kulczynski<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    let (diff, min_sum, _) = sums(x, y);
+    ratio(diff, min_sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weighted_jaccard;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_soergel_matches_weighted_jaccard() {
+        let x = arr1(&[0.5, 3.0, 1.2, 0.0, 4.0]);
+        let y = arr1(&[2.0, 1.0, 0.7, 0.4, 4.0]);
+        let d = soergel(&x.view(), &y.view());
+        assert!((d - weighted_jaccard(&x.view(), &y.view())).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_shared_zero_rule() {
+        let zero = arr1(&[0.0_f32, 0.0]);
+        for d in [soergel, motyka, wave_hedges, kulczynski] {
+            assert_eq!(d(&zero.view(), &zero.view()), 0.0);
+        }
+        let x = arr1(&[2.0_f32, 0.0]);
+        assert_eq!(soergel(&x.view(), &x.view()), 0.0);
+        assert_eq!(wave_hedges(&x.view(), &x.view()), 0.0);
+        assert_eq!(kulczynski(&x.view(), &x.view()), 0.0);
+        assert_eq!(soergel(&x.view(), &zero.view()), 1.0);
+        assert_eq!(motyka(&x.view(), &zero.view()), 1.0);
+        assert_eq!(wave_hedges(&x.view(), &zero.view()), 1.0);
+        assert_eq!(kulczynski(&x.view(), &zero.view()), f32::INFINITY);
+    }
+}
@@ -1,6 +1,8 @@
-use ndarray::ArrayView1;
+use ndarray::{ArrayView1, FoldWhile, Zip};
 use num::Float;
 
+use crate::utils::NeumaierSum;
+
 /// Minkowski distance.
 ///
 /// Computes the Minkowski distance of order `p` between two vectors `x` and `y`.
@@ -10,7 +12,7 @@ use num::Float;
 /// * `x` - A 1D array view representing the first vector.
 /// * `y` - A 1D array view representing the second vector.
 /// * `p` - The order of the Minkowski distance. For p=1, it is equivalent to Manhattan distance;
-///         for p=2, it is Euclidean distance; and for p=infinity, it is Chebyshev distance.
+///   for p=2, it is Euclidean distance; and for p=infinity, it is Chebyshev distance.
 ///
 /// # Returns
 ///
@@ -33,18 +35,97 @@ pub fn minkowski<T: Float>(x: &ArrayView1<T>, y: &ArrayView1<T>, p: T) -> T {
         "Vectors x and y must have the same length"
     );
 
+    let result = Zip::from(x)
+        .and(y)
+        .fold(T::zero(), |acc, &xi, &yi| acc + (xi - yi).abs().powf(p));
+
+    result.powf(T::one() / p)
+}
+
+/// Minkowski distance with early abandoning.
+///
+/// Accumulates `|x_i - y_i|^p` and stops as soon as the partial sum exceeds `upper^p`, i.e. once
+/// the distance is known to be larger than `upper`.
+///
+/// # Returns
+///
+/// `Some(distance)` if the distance is at most `upper`, otherwise `None`.
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::minkowski_bounded;
+/// let x = arr1(&[1.0, 2.0, 3.0]);
+/// let y = arr1(&[4.0, 5.0, 6.0]);
+/// assert_eq!(minkowski_bounded(&x.view(), &y.view(), 1.0, 10.0), Some(9.0));
+/// assert_eq!(minkowski_bounded(&x.view(), &y.view(), 1.0, 8.0), None);
+/// ```
+pub fn minkowski_bounded<T: Float>(
+    x: &ArrayView1<T>,
+    y: &ArrayView1<T>,
+    p: T,
+    upper: T,
+) -> Option<T> {
+    assert_eq!(
+        x.len(),
+        y.len(),
+        "Vectors x and y must have the same length"
+    );
+
+    // An even power would turn a negative bound into a positive one.
+    if upper < T::zero() {
+        return None;
+    }
+    let bound = upper.powf(p);
     let mut result = T::zero();
 
-    for i in 0..x.len() {
-        result = result + (x[i] - y[i]).abs().powf(p);
+    let exceeded = Zip::from(x)
+        .and(y)
+        .fold_while((), |(), &xi, &yi| {
+            result = result + (xi - yi).abs().powf(p);
+            if result > bound {
+                FoldWhile::Done(())
+            } else {
+                FoldWhile::Continue(())
+            }
+        })
+        .is_done();
+    if exceeded {
+        return None;
     }
 
-    result.powf(T::one() / p)
+    if result <= bound {
+        Some(result.powf(T::one() / p))
+    } else {
+        None
+    }
+}
+
+/// Minkowski distance using compensated summation.
+///
+/// See [`euclidean_accurate`](crate::euclidean_accurate); the terms `|x_i - y_i|^p` are
+/// accumulated with [`NeumaierSum`].
+pub fn minkowski_accurate<T: Float>(x: &ArrayView1<T>, y: &ArrayView1<T>, p: T) -> T {
+    assert_eq!(
+        x.len(),
+        y.len(),
+        "Vectors x and y must have the same length"
+    );
+
+    let mut result = NeumaierSum::new();
+
+    Zip::from(x)
+        .and(y)
+        .for_each(|&xi, &yi| result.add((xi - yi).abs().powf(p)));
+
+    result.sum().powf(T::one() / p)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::distances::reference::reference_inputs;
     use approx::assert_abs_diff_eq;
     use ndarray::arr1;
 
@@ -79,4 +160,43 @@ mod tests {
         let distance = minkowski(&x.view(), &y.view(), 3.0);
         assert_abs_diff_eq!(distance, (3_f64.powi(3) * 3.0).cbrt(), epsilon = 1.0e-12);
     }
+
+    #[test]
+    fn test_minkowski_bounded() {
+        let x = arr1(&[1.0, 2.0, 3.0]);
+        let y = arr1(&[4.0, 5.0, 6.0]);
+        let distance = minkowski(&x.view(), &y.view(), 3.0);
+
+        assert_eq!(
+            minkowski_bounded(&x.view(), &y.view(), 3.0, distance + 1e-9),
+            Some(distance)
+        );
+        assert_eq!(minkowski_bounded(&x.view(), &y.view(), 3.0, 4.0), None);
+        assert_eq!(minkowski_bounded(&x.view(), &y.view(), 2.0, -10.0), None);
+        assert_eq!(minkowski_bounded(&x.view(), &x.view(), 2.0, -1.0), None);
+    }
+
+    #[test]
+    fn test_minkowski_accurate_high_dimensional_f32() {
+        let n = 100_000;
+        let x: ndarray::Array1<f32> = (0..n).map(|i| ((i as f32) * 0.001).sin()).collect();
+        let y: ndarray::Array1<f32> = (0..n).map(|i| ((i as f32) * 0.002).cos()).collect();
+
+        let reference = minkowski(&x.mapv(f64::from).view(), &y.mapv(f64::from).view(), 3.0);
+        let accurate = minkowski_accurate(&x.view(), &y.view(), 3.0) as f64;
+
+        assert!((accurate - reference).abs() / reference < 1e-5);
+    }
+
+    #[test]
+    fn test_reference_values() {
+        let (x, y) = reference_inputs();
+        let (x, y) = (x.view(), y.view());
+        assert_eq!(minkowski(&x, &y, 3.0), 6.375464531060837);
+        assert_eq!(
+            minkowski_bounded(&x, &y, 3.0, 10.0),
+            Some(6.375464531060837)
+        );
+        assert_eq!(minkowski_accurate(&x, &y, 3.0), 6.375464531060837);
+    }
 }
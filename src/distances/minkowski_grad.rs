@@ -1,4 +1,4 @@
-use ndarray::{Array1, ArrayView1};
+use ndarray::{Array1, ArrayView1, Zip};
 use num::Float;
 
 /// Minkowski distance with gradient.
@@ -11,7 +11,7 @@ use num::Float;
 /// * `x` - A 1D array view representing the first vector.
 /// * `y` - A 1D array view representing the second vector.
 /// * `p` - The order of the Minkowski distance. For p=1, it is equivalent to Manhattan distance;
-///         for p=2, it is Euclidean distance; and for p=infinity, it is Chebyshev distance.
+///   for p=2, it is Euclidean distance; and for p=infinity, it is Chebyshev distance.
 ///
 /// # Returns
 ///
@@ -34,30 +34,24 @@ pub fn minkowski_grad<T: Float>(x: &ArrayView1<T>, y: &ArrayView1<T>, p: T) -> (
         "Vectors x and y must have the same length"
     );
 
-    let mut result = T::zero();
-
-    for i in 0..x.len() {
-        result = result + (x[i] - y[i]).abs().powf(p);
-    }
+    let result = Zip::from(x)
+        .and(y)
+        .fold(T::zero(), |acc, &xi, &yi| acc + (xi - yi).abs().powf(p));
 
     let distance = result.powf(T::one() / p);
 
-    let mut grad = Array1::<T>::zeros(x.len());
-
-    if p != T::one() {
-        for i in 0..x.len() {
-            let diff = x[i] - y[i];
-            grad[i] = diff
-                .abs()
-                .powf(p - T::one() * diff.signum() * distance.powf(T::one() / (p - T::one())));
-        }
+    let grad = if p != T::one() {
+        Zip::from(x).and(y).map_collect(|&xi, &yi| {
+            let diff = xi - yi;
+            diff.abs()
+                .powf(p - T::one() * diff.signum() * distance.powf(T::one() / (p - T::one())))
+        })
     } else {
         // Special case for p=1
-        for i in 0..x.len() {
-            let diff = x[i] - y[i];
-            grad[i] = diff.signum();
-        }
-    }
+        Zip::from(x)
+            .and(y)
+            .map_collect(|&xi, &yi| (xi - yi).signum())
+    };
 
     (distance, grad)
 }
@@ -65,6 +59,7 @@ pub fn minkowski_grad<T: Float>(x: &ArrayView1<T>, y: &ArrayView1<T>, p: T) -> (
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::distances::reference::reference_inputs;
     use ndarray::arr1;
 
     /// Check if all elements in two arrays are close to each other within a specified tolerance.
@@ -123,10 +118,33 @@ mod tests {
     fn test_minkowski_grad_chebyshev() {
         let x = arr1(&[1.0, 2.0, 3.0]);
         let y = arr1(&[4.0, 5.0, 6.0]);
-        let (distance, grad) = minkowski_grad(&x.view(), &y.view(), std::f64::INFINITY);
+        let (distance, grad) = minkowski_grad(&x.view(), &y.view(), f64::INFINITY);
         assert_eq!(distance, 1.0);
 
         let expected_grad = arr1(&[f64::INFINITY, f64::INFINITY, f64::INFINITY]);
         assert_eq!(grad, expected_grad);
     }
+
+    #[test]
+    fn test_reference_values() {
+        let (x, y) = reference_inputs();
+        let (dist, grad) = minkowski_grad(&x.view(), &y.view(), 3.0);
+        assert_eq!(dist, 6.375464531060837);
+        assert_eq!(
+            grad,
+            arr1(&[
+                1.0,
+                46.044860383385256,
+                2.147993499355987,
+                0.0,
+                0.517609660089219,
+                0.021717950530714132,
+                2.147993499355987
+            ])
+        );
+
+        let (dist, grad) = minkowski_grad(&x.view(), &y.view(), 1.0);
+        assert_eq!(dist, 13.75);
+        assert_eq!(grad, arr1(&[-1.0, -1.0, 1.0, 1.0, 1.0, -1.0, 1.0]));
+    }
 }
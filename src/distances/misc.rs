@@ -0,0 +1,128 @@
+//! Further entries of Cha's survey of distance measures that do not belong to a larger
+//! family: the average of the city block and Chebyshev distances, and the minimum symmetric
+//! chi-square divergence.
+
+use ndarray::{ArrayView1, Zip};
+use num::Float;
+
+/// Computes the average of the mean absolute difference and the Chebyshev distance.
+///
+/// ..math::
+///     D(x, y) = \frac{1}{2} \left( \frac{1}{n} \sum_i |x_i - y_i| + \max_i |x_i - y_i| \right)
+///
+/// Both terms are on the scale of a single coordinate, so the distance blends the typical
+/// and the worst coordinate difference. Two empty vectors are at distance `0`.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[1.0, 2.0, 3.0, 4.0]);
+/// let y = arr1(&[1.0, 0.0, 4.0, 0.0]);
+/// // Mean absolute difference 7 / 4, maximum 4.
+/// assert_eq!(average_distance(&x.view(), &y.view()), (1.75 + 4.0) / 2.0);
+/// ```
+pub fn average_distance<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+    if x.is_empty() {
+        return T::zero();
+    }
+
+    let (sum, max) = Zip::from(x)
+        .and(y)
+        .fold((T::zero(), T::zero()), |(sum, max), &xi, &yi| {
+            let diff = (xi - yi).abs();
+            (sum + diff, max.max(diff))
+        });
+    (sum / T::from(x.len()).unwrap() + max) / T::from(2).unwrap()
+}
+
+/// Computes the minimum symmetric chi-square divergence between two nonnegative vectors.
+///
+/// ..math::
+///     D(x, y) = \min\left( \sum_i \frac{(x_i - y_i)^2}{x_i}, \sum_i \frac{(x_i - y_i)^2}{y_i} \right)
+///
+/// The two sums are Neyman's and Pearson's chi-square divergences, and taking the smaller
+/// makes the result symmetric. Coordinates where both vectors are zero are skipped; a sum
+/// with a zero denominator under a positive difference is infinite, so the result is only
+/// infinite if both are.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[1.0, 2.0, 0.0]);
+/// let y = arr1(&[2.0, 2.0, 0.0]);
+/// // Neyman: 1 / 1, Pearson: 1 / 2.
+/// assert_eq!(min_symmetric_chi_square(&x.view(), &y.view()), 0.5);
+/// ```
+pub fn min_symmetric_chi_square<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+
+    let (neyman, pearson) =
+        Zip::from(x)
+            .and(y)
+            .fold((T::zero(), T::zero()), |(neyman, pearson), &xi, &yi| {
+                let diff = xi - yi;
+                if diff.is_zero() {
+                    (neyman, pearson)
+                } else {
+                    let squared = diff * diff;
+                    (neyman + squared / xi, pearson + squared / yi)
+                }
+            });
+    neyman.min(pearson)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{chebyshev, manhattan};
+    use ndarray::arr1;
+
+    #[test]
+    fn test_average_distance() {
+        let x = arr1(&[0.5, -1.25, 3.0, 0.0, 2.75]);
+        let y = arr1(&[1.5, 0.75, -2.0, 0.0, 2.5]);
+        let expected =
+            (manhattan(&x.view(), &y.view()) / 5.0 + chebyshev(&x.view(), &y.view())) / 2.0;
+        assert!((average_distance(&x.view(), &y.view()) - expected).abs() < 1e-15);
+        assert_eq!(
+            average_distance::<f32>(&arr1(&[]).view(), &arr1(&[]).view()),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_min_symmetric_chi_square_zeros() {
+        let x = arr1(&[0.0_f32, 1.0, 3.0]);
+        let y = arr1(&[2.0_f32, 1.0, 1.0]);
+        // Neyman is infinite because of the first coordinate; Pearson is 4 / 2 + 4 / 1.
+        assert_eq!(min_symmetric_chi_square(&x.view(), &y.view()), 6.0);
+        assert_eq!(min_symmetric_chi_square(&y.view(), &x.view()), 6.0);
+
+        let z = arr1(&[0.0_f32, 1.0, 0.0]);
+        let w = arr1(&[1.0_f32, 1.0, 1.0]);
+        let v = arr1(&[1.0_f32, 1.0, 0.0]);
+        assert!(min_symmetric_chi_square(&z.view(), &w.view()).is_finite());
+        assert_eq!(
+            min_symmetric_chi_square(&arr1(&[0.0_f32, 1.0]).view(), &arr1(&[1.0_f32, 0.0]).view()),
+            f32::INFINITY
+        );
+        assert_eq!(min_symmetric_chi_square(&v.view(), &v.view()), 0.0);
+    }
+}
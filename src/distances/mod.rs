@@ -1,83 +1,185 @@
+mod adjusted_cosine;
+mod angular;
+mod angular_grad;
 mod approx_log_gamma;
+mod bhattacharyya;
+mod binary;
+mod bits;
 mod bray_curtis;
 mod bray_curtis_grad;
+mod bytes;
 mod canberra;
 mod canberra_grad;
 mod chebyshev;
 mod chebyshev_grad;
+mod circular_kantorovich;
+mod clark;
 mod correlation;
+mod correlation_grad;
 mod cosine;
 mod cosine_grad;
+mod cross_correlation;
 mod dice;
+mod dtw;
+mod edr;
+mod elastic;
 mod euclidean;
 mod euclidean_grad;
+mod gaussian_energy_grad;
 mod hamming;
 mod haversine;
 mod haversine_grad;
 mod hellinger;
 mod hellinger_grad;
+mod hierarchical_categorical;
+mod hyperboloid;
 mod hyperboloid_grad;
+mod inner_product;
+mod inner_product_grad;
 mod jaccard;
+mod jensen_shannon;
+mod jensen_shannon_grad;
+mod kendall_tau;
+mod kl_divergence;
+mod kl_divergence_grad;
 mod kulsinski;
+mod lcss;
 mod ll_dirichlet;
+mod ll_dirichlet_grad;
 mod log_beta;
 mod log_single_beta;
+mod lorentzian;
+mod lorentzian_grad;
 mod mahalanobis;
 mod mahalanobis_grad;
 mod manhattan;
 mod manhattan_grad;
 mod matching;
+mod min_max_ratio;
 mod minkowski;
 mod minkowski_grad;
+mod misc;
+mod nan_euclidean;
 mod poincare;
+mod poincare_grad;
+mod quantitative_sorensen;
+mod rdist;
+mod rdist_grad;
+#[cfg(test)]
+mod reference;
+mod renyi_divergence;
 mod rogers_tanimoto;
 mod russellrao;
+mod sinkhorn;
+mod soft_dice;
+mod soft_dice_grad;
+mod soft_dtw;
+mod soft_dtw_grad;
 mod sokal_michener;
 mod sokal_sneath;
+mod spherical_gaussian_energy_grad;
+mod squared_chord;
+mod squared_chord_grad;
 mod standardised_euclidean;
 mod standardised_euclidean_grad;
+mod tanimoto;
+mod true_angular;
+mod tsss;
+mod wasserstein_1d;
+mod weighted_jaccard;
+mod weighted_jaccard_grad;
 mod weighted_minkowski;
 mod weighted_minkowski_grad;
 mod yule;
 
+pub use adjusted_cosine::*;
+pub use angular::*;
+pub use angular_grad::*;
 pub use approx_log_gamma::*;
+pub use bhattacharyya::*;
+pub use bits::*;
 pub use bray_curtis::*;
 pub use bray_curtis_grad::*;
+pub use bytes::*;
 pub use canberra::*;
 pub use canberra_grad::*;
 pub use chebyshev::*;
 pub use chebyshev_grad::*;
+pub use circular_kantorovich::*;
+pub use clark::*;
 pub use correlation::*;
+pub use correlation_grad::*;
 pub use cosine::*;
 pub use cosine_grad::*;
+pub use cross_correlation::*;
 pub use dice::*;
+pub use dtw::*;
+pub use edr::*;
 pub use euclidean::*;
 pub use euclidean_grad::*;
+pub use gaussian_energy_grad::*;
 pub use hamming::*;
 pub use haversine::*;
 pub use haversine_grad::*;
 pub use hellinger::*;
 pub use hellinger_grad::*;
+pub use hierarchical_categorical::*;
+pub use hyperboloid::*;
 pub use hyperboloid_grad::*;
+pub use inner_product::*;
+pub use inner_product_grad::*;
 pub use jaccard::*;
+pub use jensen_shannon::*;
+pub use jensen_shannon_grad::*;
+pub use kendall_tau::*;
+pub use kl_divergence::*;
+pub use kl_divergence_grad::*;
 pub use kulsinski::*;
+pub use lcss::*;
 pub use ll_dirichlet::*;
+pub use ll_dirichlet_grad::*;
 pub use log_beta::*;
 pub use log_single_beta::*;
+pub use lorentzian::*;
+pub use lorentzian_grad::*;
 pub use mahalanobis::*;
 pub use mahalanobis_grad::*;
 pub use manhattan::*;
 pub use manhattan_grad::*;
 pub use matching::*;
+pub use min_max_ratio::*;
 pub use minkowski::*;
 pub use minkowski_grad::*;
+pub use misc::*;
+pub use nan_euclidean::*;
 pub use poincare::*;
+pub use poincare_grad::*;
+pub use quantitative_sorensen::*;
+pub use rdist::*;
+pub use rdist_grad::*;
+pub use renyi_divergence::*;
 pub use rogers_tanimoto::*;
 pub use russellrao::*;
+pub use sinkhorn::*;
+pub use soft_dice::*;
+pub use soft_dice_grad::*;
+pub use soft_dtw::*;
+pub use soft_dtw_grad::*;
 pub use sokal_michener::*;
 pub use sokal_sneath::*;
+pub use spherical_gaussian_energy_grad::*;
+pub use squared_chord::*;
+pub use squared_chord_grad::*;
 pub use standardised_euclidean::*;
 pub use standardised_euclidean_grad::*;
+pub use tanimoto::*;
+pub use true_angular::*;
+pub use tsss::*;
+pub use wasserstein_1d::*;
+pub use weighted_jaccard::*;
+pub use weighted_jaccard_grad::*;
 pub use weighted_minkowski::*;
 pub use weighted_minkowski_grad::*;
 pub use yule::*;
+
+pub(crate) use elastic::align;
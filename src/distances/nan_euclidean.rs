@@ -0,0 +1,165 @@
+use ndarray::{ArrayView1, Zip};
+use num::Float;
+
+/// Folds `f(x_i, y_i)` over the coordinates where neither `x` nor `y` is NaN and returns the
+/// sum together with the number of such coordinates.
+fn present_fold<T, A, F>(x: &ArrayView1<T>, y: &ArrayView1<T>, init: A, mut f: F) -> (A, usize)
+where
+    T: Float,
+    F: FnMut(A, T, T) -> A,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+
+    Zip::from(x)
+        .and(y)
+        .fold((init, 0), |(acc, present), &xi, &yi| {
+            if xi.is_nan() || yi.is_nan() {
+                (acc, present)
+            } else {
+                (f(acc, xi, yi), present + 1)
+            }
+        })
+}
+
+/// Scales a sum over the present coordinates up to all `n` coordinates.
+fn rescale<T: Float>(sum: T, present: usize, n: usize) -> T {
+    if present == 0 {
+        T::nan()
+    } else {
+        sum * T::from(n).unwrap() / T::from(present).unwrap()
+    }
+}
+
+/// Computes the Euclidean distance between `x` and `y` ignoring missing (NaN) coordinates.
+///
+/// Coordinates where either vector is NaN are skipped and the squared distance over the
+/// remaining ones is scaled up by the fraction of present coordinates, as in scikit-learn's
+/// `nan_euclidean_distances`:
+///
+/// ..math::
+///     \text{nan\_euclidean}(x, y) = \sqrt{\frac{n}{|P|} \sum_{i \in P} (x_i - y_i)^2}
+///
+/// where `P` is the set of coordinates present in both vectors. Without missing values this is
+/// the [`euclidean`](crate::euclidean) distance.
+///
+/// # Returns
+/// The distance, or NaN if no coordinate is present in both vectors.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[3.0, f64::NAN, 0.0, 1.0]);
+/// let y = arr1(&[0.0, 2.0, 4.0, f64::NAN]);
+/// // Two of the four coordinates are present: sqrt(4 / 2 * (9 + 16)).
+/// assert_eq!(nan_euclidean(&x.view(), &y.view()), 50f64.sqrt());
+/// ```
+pub fn nan_euclidean<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    let (sum, present) = present_fold(x, y, T::zero(), |acc, xi, yi| {
+        let diff = xi - yi;
+        acc + diff * diff
+    });
+    rescale(sum, present, x.len()).sqrt()
+}
+
+/// Computes the Manhattan distance between `x` and `y` ignoring missing (NaN) coordinates.
+///
+/// The sum of absolute differences over the coordinates present in both vectors is scaled up
+/// by `n / |P|`, like [`nan_euclidean`].
+///
+/// # Returns
+/// The distance, or NaN if no coordinate is present in both vectors.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+pub fn nan_manhattan<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    let (sum, present) = present_fold(x, y, T::zero(), |acc, xi, yi| acc + (xi - yi).abs());
+    rescale(sum, present, x.len())
+}
+
+/// Computes the cosine distance between `x` and `y` over the coordinates present in both.
+///
+/// Coordinates where either vector is NaN are dropped from the dot product and from both
+/// norms. No rescaling is needed, since the ratio does not depend on the number of
+/// coordinates. The zero-norm conventions of [`cosine`](crate::cosine) apply to the remaining
+/// coordinates.
+///
+/// # Returns
+/// The distance, or NaN if no coordinate is present in both vectors.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+pub fn nan_cosine<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    let ((dot, norm_x, norm_y), present) = present_fold(
+        x,
+        y,
+        (T::zero(), T::zero(), T::zero()),
+        |(dot, nx, ny), xi, yi| (dot + xi * yi, nx + xi * xi, ny + yi * yi),
+    );
+
+    if present == 0 {
+        T::nan()
+    } else if norm_x.is_zero() && norm_y.is_zero() {
+        T::zero()
+    } else if norm_x.is_zero() || norm_y.is_zero() {
+        T::one()
+    } else {
+        T::one() - dot / (norm_x.sqrt() * norm_y.sqrt())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cosine, euclidean, manhattan};
+    use ndarray::arr1;
+
+    #[test]
+    fn test_without_missing_values() {
+        let x = arr1(&[0.5, -1.25, 3.0, 0.0, 2.75]);
+        let y = arr1(&[1.5, 0.75, -2.0, 0.0, 2.5]);
+        assert!(
+            (nan_euclidean(&x.view(), &y.view()) - euclidean(&x.view(), &y.view())).abs() < 1e-12
+        );
+        assert!(
+            (nan_manhattan(&x.view(), &y.view()) - manhattan(&x.view(), &y.view())).abs() < 1e-12
+        );
+        assert!((nan_cosine(&x.view(), &y.view()) - cosine(&x.view(), &y.view())).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_sklearn_reference() {
+        // sklearn.metrics.pairwise.nan_euclidean_distances([[3, nan, nan, 6]], [[1, nan, 4, 5]])
+        let x = arr1(&[3.0_f32, f32::NAN, f32::NAN, 6.0]);
+        let y = arr1(&[1.0_f32, f32::NAN, 4.0, 5.0]);
+        assert!((nan_euclidean(&x.view(), &y.view()) - 3.1622777).abs() < 1e-6);
+        assert_eq!(nan_manhattan(&x.view(), &y.view()), 6.0);
+        let present = (arr1(&[3.0_f32, 6.0]), arr1(&[1.0_f32, 5.0]));
+        assert_eq!(
+            nan_cosine(&x.view(), &y.view()),
+            cosine(&present.0.view(), &present.1.view())
+        );
+    }
+
+    #[test]
+    fn test_all_missing() {
+        let x = arr1(&[f64::NAN, 1.0]);
+        let y = arr1(&[2.0, f64::NAN]);
+        assert!(nan_euclidean(&x.view(), &y.view()).is_nan());
+        assert!(nan_manhattan(&x.view(), &y.view()).is_nan());
+        assert!(nan_cosine(&x.view(), &y.view()).is_nan());
+    }
+}
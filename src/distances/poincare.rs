@@ -1,6 +1,6 @@
 use std::iter::Sum;
 
-use ndarray::Array1;
+use ndarray::{Array1, ArrayView1};
 use num::traits::{NumCast, ToPrimitive};
 use num::{Float, Num};
 
@@ -36,6 +36,49 @@ pub fn poincare<T>(u: &Array1<T>, v: &Array1<T>) -> T
 where
     T: Num + Float + NumCast + ToPrimitive + Sum,
 {
+    poincare_with_curvature(u, v, T::one())
+}
+
+/// Computes the Poincaré distance in the ball of curvature `-c`.
+///
+/// The ball has radius `1 / sqrt(c)`, and the distance generalizes [`poincare`], which is the
+/// case `c = 1`:
+///
+/// .. math::
+///     \delta_c(u, v) = 2 c \frac{\lVert u - v \rVert^2}{(1 - c \lVert u \rVert^2)(1 - c \lVert v \rVert^2)}
+///     D_c(u, v) = \frac{1}{\sqrt{c}} \operatorname{arcosh}(1 + \delta_c(u, v))
+///
+/// This is the hyperbolic branch of the κ-stereographic model with `κ = -c`, as used by models
+/// trained with a learned or non-unit curvature.
+///
+/// # Parameters:
+/// - `u`: A reference to a vector `u` with `c ‖u‖² < 1`.
+/// - `v`: A reference to a vector `v` with `c ‖v‖² < 1`.
+/// - `c`: The magnitude of the (negative) curvature.
+///
+/// # Panics:
+/// Panics if `c` is not positive.
+///
+/// # Example:
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let u = arr1(&[0.5, 0.3, 0.2]);
+/// let v = arr1(&[0.1, 0.4, 0.5]);
+/// assert_eq!(poincare_with_curvature(&u, &v, 1.0), poincare(&u, &v));
+///
+/// // Scaling the points by 1 / sqrt(c) maps the unit ball onto the ball of curvature -c.
+/// let c: f64 = 4.0;
+/// let d = poincare_with_curvature(&(&u / c.sqrt()), &(&v / c.sqrt()), c);
+/// assert!((d - poincare(&u, &v) / c.sqrt()).abs() < 1e-12);
+/// ```
+pub fn poincare_with_curvature<T>(u: &Array1<T>, v: &Array1<T>, c: T) -> T
+where
+    T: Num + Float + NumCast + ToPrimitive + Sum,
+{
+    assert!(c > T::zero(), "Curvature must be positive.");
+
     // Special case: If both vectors are zero, return zero distance immediately
     if u.iter().all(|&x| x.is_zero()) && v.iter().all(|&x| x.is_zero()) {
         return T::zero();
@@ -55,12 +98,32 @@ where
     // Calculate the Poincare distance using the formula
     let one = T::one();
     let two = T::from(2u8).unwrap();
-    let delta = two * sq_dist / ((one - sq_u_norm) * (one - sq_v_norm));
+    let delta = two * c * sq_dist / ((one - c * sq_u_norm) * (one - c * sq_v_norm));
 
-    // Return the Poincare distance, which is the inverse hyperbolic cosine of (1 + delta)
-    let result = (one + delta).ln_1p(); // ln_1p(x) is used for better precision with small x
+    // Return the Poincare distance, which is the inverse hyperbolic cosine of (1 + delta):
+    // arcosh(1 + delta) = ln(1 + delta + sqrt(delta * (delta + 2))), where ln_1p keeps the
+    // precision for nearby points with a small delta
+    (delta + (delta * (delta + two)).sqrt()).ln_1p() / c.sqrt()
+}
 
-    result
+/// Möbius addition `x ⊕ y` in the unit Poincaré ball, the translation along which
+/// [`poincare`] is invariant: `poincare(x ⊕ u, x ⊕ v) == poincare(u, v)`.
+pub(crate) fn mobius_add<T: Float>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> Array1<T> {
+    let dot = |a: &ArrayView1<T>, b: &ArrayView1<T>| {
+        a.iter()
+            .zip(b.iter())
+            .fold(T::zero(), |acc, (&p, &q)| acc + p * q)
+    };
+    let one = T::one();
+    let two = one + one;
+    let (xy, xx, yy) = (dot(x, y), dot(x, x), dot(y, y));
+    let denominator = one + two * xy + xx * yy;
+    let cx = (one + two * xy + yy) / denominator;
+    let cy = (one - xx) / denominator;
+    x.iter()
+        .zip(y.iter())
+        .map(|(&a, &b)| cx * a + cy * b)
+        .collect()
 }
 
 #[cfg(test)]
@@ -77,9 +140,9 @@ mod tests {
         let dist = poincare(&u, &v);
 
         // Known value for the Poincaré distance of these vectors
-        let expected_dist = 1.2372289865051938;
+        let expected_dist = 1.5429458782040535;
 
-        assert_eq!(dist, expected_dist, "Test failed for f64");
+        assert!((dist - expected_dist).abs() < 1e-12, "Test failed for f64");
     }
 
     /// Test the Poincaré distance function with `f32` values.
@@ -91,9 +154,9 @@ mod tests {
         let dist = poincare(&u, &v);
 
         // Known value for the Poincaré distance of these vectors
-        let expected_dist = 1.237229;
+        let expected_dist = 1.5429459;
 
-        assert_eq!(dist, expected_dist, "Test failed for f32");
+        assert!((dist - expected_dist).abs() < 1e-6, "Test failed for f32");
     }
 
     /// Test the Poincaré distance function with zero vectors.
@@ -117,10 +180,38 @@ mod tests {
         let dist = poincare(&u, &v);
 
         // The distance between identical vectors should be 0.
-        assert_eq!(
-            dist.abs(),
-            0.6931471805599453,
-            "Test failed for identical vectors"
+        assert_eq!(dist.abs(), 0.0, "Test failed for identical vectors");
+    }
+
+    /// Test the Poincaré distance against the closed form along a diameter,
+    /// `d(0, r e_1) = 2 artanh(r)`.
+    #[test]
+    fn test_poincare_from_origin() {
+        for r in [1e-6, 0.1, 0.5, 0.9, 0.999] {
+            let u = arr1(&[0.0, 0.0]);
+            let v = arr1(&[r, 0.0]);
+            let expected = 2.0 * f64::atanh(r);
+            assert!((poincare(&u, &v) - expected).abs() < 1e-9 * expected.max(1.0));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Curvature must be positive.")]
+    fn test_poincare_invalid_curvature() {
+        let u = arr1(&[0.1, 0.2]);
+        poincare_with_curvature(&u, &u, 0.0);
+    }
+
+    #[test]
+    fn test_mobius_add_is_an_isometry() {
+        let x = arr1(&[0.3, -0.4]);
+        let u = arr1(&[0.1, 0.6]);
+        let v = arr1(&[-0.5, 0.2]);
+        let (xu, xv) = (
+            mobius_add(&x.view(), &u.view()),
+            mobius_add(&x.view(), &v.view()),
         );
+        assert!((poincare(&xu, &xv) - poincare(&u, &v)).abs() < 1e-12);
+        assert_eq!(mobius_add(&x.view(), &arr1(&[0.0, 0.0]).view()), x);
     }
 }
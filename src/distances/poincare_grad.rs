@@ -0,0 +1,170 @@
+use std::iter::Sum;
+
+use ndarray::Array1;
+use num::traits::{NumCast, ToPrimitive};
+use num::{Float, Num};
+
+use super::poincare::poincare_with_curvature;
+
+/// Computes the Poincaré distance and its gradient with respect to `u`.
+///
+/// With `α = 1 - ‖u‖²`, `β = 1 - ‖v‖²` and `γ = 1 + 2 ‖u - v‖² / (α β)`, the distance is
+/// `arcosh(γ)` and
+///
+/// .. math::
+///     \nabla_u D(u, v) = \frac{4}{\alpha \beta \sqrt{\gamma^2 - 1}} \left( (u - v) + \frac{\lVert u - v \rVert^2}{\alpha} u \right)
+///
+/// This is the Euclidean gradient, as for the other `*_grad` functions, so it can be fed to any
+/// optimizer on the ambient coordinates. Riemannian SGD in the ball instead uses
+/// `(1 - ‖u‖²)² / 4` times this gradient, the inverse of the Poincaré metric tensor, and then
+/// retracts the update into the ball.
+///
+/// # Parameters:
+/// - `u`: A reference to a vector `u` strictly inside the unit ball.
+/// - `v`: A reference to a vector `v` strictly inside the unit ball.
+///
+/// # Returns:
+/// The [`poincare`](crate::poincare) distance between `u` and `v`, and its gradient with
+/// respect to `u`. The distance is not differentiable at `u = v`, where the gradient is zero.
+///
+/// # Panics:
+/// Panics if `u` and `v` do not have the same length.
+///
+/// # Example:
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let u = arr1(&[0.5f64, 0.3, 0.2]);
+/// let v = arr1(&[0.1, 0.4, 0.5]);
+/// let (distance, gradient) = poincare_grad(&u, &v);
+/// assert_eq!(distance, poincare(&u, &v));
+///
+/// // Riemannian gradient for an update of `u` in the ball.
+/// let scale = (1.0 - u.dot(&u)).powi(2) / 4.0;
+/// let riemannian = gradient * scale;
+/// assert_eq!(riemannian.len(), 3);
+/// ```
+pub fn poincare_grad<T>(u: &Array1<T>, v: &Array1<T>) -> (T, Array1<T>)
+where
+    T: Num + Float + NumCast + ToPrimitive + Sum,
+{
+    poincare_grad_with_curvature(u, v, T::one())
+}
+
+/// Computes the [`poincare_with_curvature`](crate::poincare_with_curvature) distance in the
+/// ball of curvature `-c` and its Euclidean gradient with respect to `u`.
+///
+/// With `α = 1 - c ‖u‖²`, `β = 1 - c ‖v‖²` and `δ = 2 c ‖u - v‖² / (α β)`,
+///
+/// .. math::
+///     \nabla_u D_c(u, v) = \frac{4 c}{\sqrt{c} \, \alpha \beta \sqrt{\delta (\delta + 2)}} \left( (u - v) + \frac{c \lVert u - v \rVert^2}{\alpha} u \right)
+///
+/// and the Riemannian gradient is `(1 - c ‖u‖²)² / 4` times this one.
+///
+/// # Panics:
+/// Panics if `u` and `v` do not have the same length or if `c` is not positive.
+pub fn poincare_grad_with_curvature<T>(u: &Array1<T>, v: &Array1<T>, c: T) -> (T, Array1<T>)
+where
+    T: Num + Float + NumCast + ToPrimitive + Sum,
+{
+    assert_eq!(u.len(), v.len(), "Input arrays must have the same length.");
+
+    let distance = poincare_with_curvature(u, v, c);
+
+    let sq_dist = u
+        .iter()
+        .zip(v.iter())
+        .map(|(&a, &b)| (a - b).powi(2))
+        .sum::<T>();
+    if sq_dist.is_zero() {
+        return (distance, Array1::zeros(u.len()));
+    }
+
+    let one = T::one();
+    let two = T::from(2u8).unwrap();
+    let alpha = one - c * u.iter().map(|&x| x * x).sum::<T>();
+    let beta = one - c * v.iter().map(|&x| x * x).sum::<T>();
+    let delta = two * c * sq_dist / (alpha * beta);
+
+    // sqrt(gamma^2 - 1) with gamma = 1 + delta, written to avoid cancellation
+    let root = (delta * (delta + two)).sqrt();
+    let scale = two * two * c / (c.sqrt() * alpha * beta * root);
+    let grad = u
+        .iter()
+        .zip(v.iter())
+        .map(|(&a, &b)| scale * ((a - b) + c * sq_dist / alpha * a))
+        .collect();
+
+    (distance, grad)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poincare;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_poincare_grad_finite_differences() {
+        let u = arr1(&[0.5, 0.3, 0.2]);
+        let v = arr1(&[0.1, -0.4, 0.5]);
+        let (distance, grad) = poincare_grad(&u, &v);
+        assert_eq!(distance, poincare(&u, &v));
+
+        let h = 1e-6;
+        for i in 0..u.len() {
+            let (mut up, mut down) = (u.clone(), u.clone());
+            up[i] += h;
+            down[i] -= h;
+            let numeric = (poincare(&up, &v) - poincare(&down, &v)) / (2.0 * h);
+            assert!(
+                (grad[i] - numeric).abs() < 1e-6,
+                "{i}: {} vs {numeric}",
+                grad[i]
+            );
+        }
+    }
+
+    #[test]
+    fn test_poincare_grad_identical_points() {
+        let u = arr1(&[0.2f32, -0.1]);
+        let (distance, grad) = poincare_grad(&u, &u);
+        assert_eq!(distance, 0.0);
+        assert_eq!(grad, arr1(&[0.0, 0.0]));
+    }
+
+    #[test]
+    fn test_poincare_grad_points_away_from_v() {
+        // Moving u away from v increases the distance, so the gradient points from v to u.
+        let u = arr1(&[0.3, 0.0]);
+        let v = arr1(&[-0.3, 0.0]);
+        let (_, grad) = poincare_grad(&u, &v);
+        assert!(grad[0] > 0.0);
+        assert_eq!(grad[1], 0.0);
+    }
+
+    #[test]
+    fn test_poincare_grad_with_curvature_finite_differences() {
+        let c = 2.5;
+        let u = arr1(&[0.3, 0.2, -0.1]);
+        let v = arr1(&[-0.1, 0.4, 0.2]);
+        let (distance, grad) = poincare_grad_with_curvature(&u, &v, c);
+        assert_eq!(distance, poincare_with_curvature(&u, &v, c));
+
+        let h = 1e-6;
+        for i in 0..u.len() {
+            let (mut up, mut down) = (u.clone(), u.clone());
+            up[i] += h;
+            down[i] -= h;
+            let numeric = (poincare_with_curvature(&up, &v, c)
+                - poincare_with_curvature(&down, &v, c))
+                / (2.0 * h);
+            assert!(
+                (grad[i] - numeric).abs() < 1e-6,
+                "{i}: {} vs {numeric}",
+                grad[i]
+            );
+        }
+    }
+}
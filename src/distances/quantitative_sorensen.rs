@@ -0,0 +1,77 @@
+use ndarray::ArrayView1;
+use num::Float;
+
+use super::weighted_jaccard::min_max_sums;
+
+/// Computes the quantitative Sørensen (Sørensen–Dice on counts) dissimilarity between two
+/// nonnegative vectors.
+///
+/// ..math::
+///    d(x, y) = 1 - \frac{2 \sum_i \min(x_i, y_i)}{\sum_i x_i + \sum_i y_i}
+///
+/// This is the abundance-weighted counterpart of the binary [`dice`](crate::dice), to which it
+/// reduces on 0/1 vectors. For nonnegative data it equals [`bray_curtis`](crate::bray_curtis),
+/// and its similarity `S = 1 - d` relates to the weighted Jaccard similarity `J` by
+/// `S = 2J / (1 + J)`. Two zero vectors are at distance `0`.
+///
+/// # Arguments
+/// * `x` - A 1D array (view) of nonnegative values.
+/// * `y` - A 1D array (view) of nonnegative values.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[1.0, 3.0, 0.0]);
+/// let y = arr1(&[2.0, 1.0, 1.0]);
+/// // 1 - 2 * 2 / (4 + 4)
+/// assert_eq!(quantitative_sorensen(&x.view(), &y.view()), 0.5);
+/// assert_eq!(bray_curtis(&x.view(), &y.view()), 0.5);
+/// ```
+pub fn quantitative_sorensen<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    // sum(x) + sum(y) = sum(min) + sum(max)
+    let (min_sum, max_sum) = min_max_sums(x, y);
+    let total = min_sum + max_sum;
+    if total.is_zero() {
+        T::zero()
+    } else {
+        (max_sum - min_sum) / total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bray_curtis, dice, weighted_jaccard};
+    use ndarray::arr1;
+
+    #[test]
+    fn test_quantitative_sorensen_relations() {
+        let x = arr1(&[0.5, 3.0, 1.2, 0.0, 4.0]);
+        let y = arr1(&[2.0, 1.0, 0.7, 0.4, 4.0]);
+        let d = quantitative_sorensen(&x.view(), &y.view());
+        assert!((d - bray_curtis(&x.view(), &y.view())).abs() < 1e-15);
+
+        let j = 1.0 - weighted_jaccard(&x.view(), &y.view());
+        assert!((1.0 - d - 2.0 * j / (1.0 + j)).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_quantitative_sorensen_binary_and_zero() {
+        let x = arr1(&[1.0, 0.0, 1.0, 1.0]);
+        let y = arr1(&[0.0, 1.0, 1.0, 1.0]);
+        assert_eq!(
+            quantitative_sorensen(&x.view(), &y.view()),
+            dice(&x.view(), &y.view())
+        );
+        let zero = arr1(&[0.0f32, 0.0]);
+        assert_eq!(quantitative_sorensen(&zero.view(), &zero.view()), 0.0);
+    }
+}
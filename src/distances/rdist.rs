@@ -0,0 +1,83 @@
+use ndarray::ArrayView1;
+use num::Float;
+
+/// Computes the "reduced" Euclidean distance, i.e. the squared Euclidean distance, between two
+/// vectors.
+///
+/// ..math::
+///    D(x, y) = \sum_i (x_i - y_i)^2
+///
+/// `rdist` is a monotonic transformation of [`euclidean`](crate::euclidean), so it orders
+/// neighbors identically while skipping the square root. UMAP uses it for its embedding
+/// optimization for this reason.
+///
+/// # Arguments
+///
+/// * `x` - A 1-dimensional array view of type `T`.
+/// * `y` - Another 1-dimensional array view of type `T`.
+///
+/// # Returns
+///
+/// The squared Euclidean distance between `x` and `y`, of type `T`.
+///
+/// # Panics
+///
+/// This function will panic if the input arrays do not have the same length.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::rdist;
+///
+/// let x = arr1(&[0.0, 0.0]);
+/// let y = arr1(&[3.0, 4.0]);
+/// assert_eq!(rdist(&x.view(), &y.view()), 25.0);
+/// ```
+pub fn rdist<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+
+    let mut result = T::zero();
+    for i in 0..x.len() {
+        let diff = x[i] - y[i];
+        result = result + diff * diff;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::euclidean;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_rdist_is_squared_euclidean() {
+        let x = arr1(&[1.0, -2.0, 3.5]);
+        let y = arr1(&[4.0, 5.0, -6.0]);
+
+        let expected = euclidean(&x.view(), &y.view()).powi(2);
+        assert!((rdist(&x.view(), &y.view()) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rdist_identical_and_empty() {
+        let x = arr1(&[1.0f32, 2.0]);
+        assert_eq!(rdist(&x.view(), &x.view()), 0.0);
+
+        let empty = arr1::<f64>(&[]);
+        assert_eq!(rdist(&empty.view(), &empty.view()), 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Input arrays must have the same length.")]
+    fn test_rdist_length_mismatch() {
+        let x = arr1(&[1.0, 2.0]);
+        let y = arr1(&[1.0]);
+        rdist(&x.view(), &y.view());
+    }
+}
@@ -0,0 +1,84 @@
+use ndarray::Array1;
+use num::Float;
+
+/// Computes the squared Euclidean distance ([`rdist`](crate::rdist)) and its gradient with
+/// respect to `x`.
+///
+/// ..math::
+///    \nabla_x D(x, y) = 2 (x - y)
+///
+/// Unlike [`euclidean_grad`](crate::euclidean_grad), the gradient needs neither a square root
+/// nor a division by the distance, so it is exact for identical inputs and needs no
+/// stabilizing epsilon.
+///
+/// # Parameters
+///
+/// - **`x`:** An `Array1<T>` representing the first vector.
+/// - **`y`:** An `Array1<T>` representing the second vector.
+///
+/// # Returns
+///
+/// A tuple containing:
+/// 1. The squared Euclidean distance between the two input vectors, of type `T`.
+/// 2. The gradient `2 (x - y)`.
+///
+/// # Panics
+///
+/// - If the input arrays do not have the same length, the function will panic with an appropriate error message.
+pub fn rdist_grad<T>(x: &Array1<T>, y: &Array1<T>) -> (T, Vec<T>)
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+
+    let two = T::one() + T::one();
+    let mut result = T::zero();
+    let mut gradient = Vec::with_capacity(x.len());
+    for i in 0..x.len() {
+        let diff = x[i] - y[i];
+        result = result + diff * diff;
+        gradient.push(two * diff);
+    }
+
+    (result, gradient)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rdist;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_rdist_grad_f64() {
+        let x = arr1(&[1.0f64, 2.0, 3.0]);
+        let y = arr1(&[4.0f64, 5.0, 7.0]);
+
+        let (distance, gradient) = rdist_grad(&x, &y);
+        assert_eq!(distance, rdist(&x.view(), &y.view()));
+        assert_eq!(gradient, vec![-6.0, -6.0, -8.0]);
+    }
+
+    #[test]
+    fn test_rdist_grad_identical() {
+        let x = arr1(&[0.5f32, -1.5]);
+        let (distance, gradient) = rdist_grad(&x, &x);
+        assert_eq!(distance, 0.0);
+        assert_eq!(gradient, vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_rdist_grad_finite_difference() {
+        let x = arr1(&[0.3f64, -1.2, 2.0]);
+        let y = arr1(&[1.0f64, 0.5, -0.5]);
+        let (_, gradient) = rdist_grad(&x, &y);
+
+        let h = 1e-6;
+        for i in 0..x.len() {
+            let mut xp = x.clone();
+            xp[i] += h;
+            let numeric = (rdist(&xp.view(), &y.view()) - rdist(&x.view(), &y.view())) / h;
+            assert!((numeric - gradient[i]).abs() < 1e-4);
+        }
+    }
+}
@@ -0,0 +1,14 @@
+//! Inputs shared by the `test_reference_values` tests of the hot kernels.
+//!
+//! Those tests lock in the exact results of the original indexed loops, so that the
+//! iterator-based implementations cannot drift from them. Each module asserts its own values.
+
+use ndarray::{arr1, Array1};
+
+/// A pair of vectors with mixed signs, a shared zero and unequal magnitudes.
+pub(crate) fn reference_inputs() -> (Array1<f64>, Array1<f64>) {
+    (
+        arr1(&[0.5, -1.25, 3.0, 0.0, 2.75, -0.125, 1.0]),
+        arr1(&[1.5, 0.75, -2.0, 0.0, 2.5, 0.375, -4.0]),
+    )
+}
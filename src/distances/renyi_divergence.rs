@@ -0,0 +1,167 @@
+use ndarray::{ArrayView1, Zip};
+use num::Float;
+
+use super::kl_divergence::{kl_divergence, prepare};
+use crate::DivergenceConfig;
+
+/// Computes the Rényi divergence of order `alpha` of `q` from `p`.
+///
+/// ..math::
+///    D_\alpha(p \| q) = \frac{1}{\alpha - 1} \ln \sum_i p_i^\alpha q_i^{1 - \alpha}
+///
+/// The orders without a finite formula are evaluated as limits:
+///
+/// * `alpha = 0`: `-ln sum(q_i for p_i > 0)`;
+/// * `alpha = 1`: the [`kl_divergence`](crate::kl_divergence);
+/// * `alpha = inf`: `ln max(p_i / q_i)`.
+///
+/// `alpha = 0.5` gives twice the [`bhattacharyya`](crate::bhattacharyya) distance and
+/// `alpha = 2` the logarithm of one plus the chi-square divergence. Coordinates with `p_i = 0`
+/// contribute nothing for positive orders. The inputs are used as given; see
+/// [`renyi_divergence_with_config`] to smooth or normalize them.
+///
+/// # Arguments
+/// * `p` - The reference distribution.
+/// * `q` - The approximating distribution.
+/// * `alpha` - The order, between `0` and infinity.
+///
+/// # Returns
+/// The divergence, or NaN if `alpha` is negative or NaN. With the `validate` feature,
+/// [`Renyi`](crate::Renyi) reports such orders as a
+/// [`DistanceError::InvalidParameter`](crate::DistanceError::InvalidParameter) instead.
+///
+/// # Panics
+/// Panics if `p` and `q` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let p = arr1(&[0.5, 0.5]);
+/// let q = arr1(&[0.25, 0.75]);
+/// let d2 = renyi_divergence(&p.view(), &q.view(), 2.0);
+/// assert!((d2 - (0.25 / 0.25 + 0.25 / 0.75_f64).ln()).abs() < 1e-12);
+/// assert!(renyi_divergence(&p.view(), &q.view(), 0.5) <= kl_divergence(&p.view(), &q.view()));
+/// assert!(renyi_divergence(&p.view(), &q.view(), -1.0).is_nan());
+/// ```
+pub fn renyi_divergence<T>(p: &ArrayView1<T>, q: &ArrayView1<T>, alpha: T) -> T
+where
+    T: Float,
+{
+    assert_eq!(p.len(), q.len(), "Input arrays must have the same length.");
+
+    if alpha.is_nan() || alpha < T::zero() {
+        return T::nan();
+    }
+    if alpha.is_zero() {
+        let mass = Zip::from(p).and(q).fold(
+            T::zero(),
+            |acc, &pi, &qi| {
+                if pi > T::zero() {
+                    acc + qi
+                } else {
+                    acc
+                }
+            },
+        );
+        return -mass.ln();
+    }
+    if alpha == T::one() {
+        return kl_divergence(p, q);
+    }
+    if alpha.is_infinite() {
+        let ratio = Zip::from(p).and(q).fold(T::zero(), |acc, &pi, &qi| {
+            if pi > T::zero() {
+                acc.max(pi / qi)
+            } else {
+                acc
+            }
+        });
+        return ratio.ln();
+    }
+
+    let exponent = T::one() - alpha;
+    let total = Zip::from(p).and(q).fold(T::zero(), |acc, &pi, &qi| {
+        if pi > T::zero() {
+            acc + pi.powf(alpha) * qi.powf(exponent)
+        } else {
+            acc
+        }
+    });
+    total.ln() / (alpha - T::one())
+}
+
+/// Computes the Rényi divergence of order `alpha` after smoothing and normalizing the inputs
+/// as described by `config`.
+///
+/// # Panics
+/// Panics if `p` and `q` do not have the same length.
+pub fn renyi_divergence_with_config<T>(
+    p: &ArrayView1<T>,
+    q: &ArrayView1<T>,
+    alpha: T,
+    config: &DivergenceConfig<T>,
+) -> T
+where
+    T: Float,
+{
+    let (p, q) = (prepare(p, config).0, prepare(q, config).0);
+    renyi_divergence(&p.view(), &q.view(), alpha)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bhattacharyya;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_special_orders() {
+        let p = arr1(&[0.2, 0.5, 0.3, 0.0]);
+        let q = arr1(&[0.1, 0.4, 0.4, 0.1]);
+        let (p, q) = (p.view(), q.view());
+
+        assert!((renyi_divergence(&p, &q, 0.0) + 0.9f64.ln()).abs() < 1e-12);
+        assert!((renyi_divergence(&p, &q, 0.5) - 2.0 * bhattacharyya(&p, &q)).abs() < 1e-12);
+        assert_eq!(renyi_divergence(&p, &q, 1.0), kl_divergence(&p, &q));
+        let chi2: f64 = 0.04 / 0.1 + 0.25 / 0.4 + 0.09 / 0.4;
+        assert!((renyi_divergence(&p, &q, 2.0) - chi2.ln()).abs() < 1e-12);
+        assert!((renyi_divergence(&p, &q, f64::INFINITY) - 2f64.ln()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_continuity_and_monotonicity() {
+        let p = arr1(&[0.6, 0.3, 0.1]);
+        let q = arr1(&[0.2, 0.5, 0.3]);
+        let (p, q) = (p.view(), q.view());
+
+        let kl = kl_divergence(&p, &q);
+        assert!((renyi_divergence(&p, &q, 1.0 - 1e-7) - kl).abs() < 1e-6);
+        assert!((renyi_divergence(&p, &q, 1.0 + 1e-7) - kl).abs() < 1e-6);
+
+        // The divergence is nondecreasing in alpha.
+        let orders = [0.0, 0.25, 0.5, 0.9, 1.0, 1.5, 2.0, 10.0, f64::INFINITY];
+        let values: Vec<f64> = orders
+            .iter()
+            .map(|&a| renyi_divergence(&p, &q, a))
+            .collect();
+        assert!(values.windows(2).all(|w| w[0] <= w[1] + 1e-12));
+    }
+
+    #[test]
+    fn test_unsupported_orders_and_zeros() {
+        let p = arr1(&[1.0, 0.0]);
+        let q = arr1(&[0.0, 1.0]);
+        assert!(renyi_divergence(&p.view(), &q.view(), -0.5).is_nan());
+        assert!(renyi_divergence(&p.view(), &q.view(), f64::NAN).is_nan());
+        assert_eq!(renyi_divergence(&p.view(), &q.view(), 2.0), f64::INFINITY);
+        assert_eq!(renyi_divergence(&p.view(), &p.view(), 3.0), 0.0);
+
+        let config = DivergenceConfig::default()
+            .with_epsilon(0.5)
+            .with_normalize(true);
+        let d = renyi_divergence_with_config(&p.view(), &q.view(), 2.0, &config);
+        assert!(d.is_finite() && d > 0.0);
+    }
+}
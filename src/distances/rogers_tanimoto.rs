@@ -1,6 +1,9 @@
 extern crate ndarray;
 
 use ndarray::ArrayView1;
+use num::Float;
+
+use super::binary::binary_counts;
 
 /// Computes the Rogers-Tanimoto similarity between two binary vectors.
 ///
@@ -20,17 +23,13 @@ use ndarray::ArrayView1;
 /// * `y` - A 1D array (view) of values representing the second binary vector.
 ///
 /// # Returns
-/// A f64 value representing the Rogers-Tanimoto similarity.
-pub fn rogers_tanimoto(x: &ArrayView1<f64>, y: &ArrayView1<f64>) -> f64 {
-    let mut num_not_equal = 0.0;
-
-    for i in 0..x.len() {
-        let x_true = x[i] != 0.0;
-        let y_true = y[i] != 0.0;
-        num_not_equal += if x_true != y_true { 1.0 } else { 0.0 };
-    }
-
-    (2.0 * num_not_equal) / (x.len() as f64 + num_not_equal)
+/// A value representing the Rogers-Tanimoto similarity.
+pub fn rogers_tanimoto<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    let (_, num_not_equal) = binary_counts(x, y);
+    (T::from(2).unwrap() * num_not_equal) / (T::from(x.len()).unwrap() + num_not_equal)
 }
 
 #[cfg(test)]
@@ -1,6 +1,9 @@
 extern crate ndarray;
 
 use ndarray::ArrayView1;
+use num::Float;
+
+use super::binary::binary_counts;
 
 /// Computes the Russell-Rao similarity between two binary vectors.
 ///
@@ -20,24 +23,22 @@ use ndarray::ArrayView1;
 /// * `y` - A 1D array (view) of values representing the second binary vector.
 ///
 /// # Returns
-/// A f64 value representing the Russell-Rao similarity.
-pub fn russell_rao(x: &ArrayView1<f64>, y: &ArrayView1<f64>) -> f64 {
-    let mut num_true_true = 0.0;
-
-    for i in 0..x.len() {
-        let x_true = x[i] != 0.0;
-        let y_true = y[i] != 0.0;
-        num_true_true += if x_true && y_true { 1.0 } else { 0.0 };
-    }
+/// A value representing the Russell-Rao similarity.
+pub fn russell_rao<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    let (num_true_true, _) = binary_counts(x, y);
 
     // Check if both vectors are entirely non-zero
-    let sum_x = x.iter().filter(|&&val| val != 0.0).count() as f64;
-    let sum_y = y.iter().filter(|&&val| val != 0.0).count() as f64;
+    let sum_x = T::from(x.iter().filter(|&&val| val != T::zero()).count()).unwrap();
+    let sum_y = T::from(y.iter().filter(|&&val| val != T::zero()).count()).unwrap();
 
     if num_true_true == sum_x && num_true_true == sum_y {
-        0.0 // If all non-zero elements match, return 0 similarity
+        T::zero() // If all non-zero elements match, return 0 similarity
     } else {
-        (x.len() as f64 - num_true_true) / x.len() as f64
+        let n = T::from(x.len()).unwrap();
+        (n - num_true_true) / n
     }
 }
 
@@ -0,0 +1,271 @@
+use ndarray::{Array1, Array2, ArrayView1, ArrayView2, Zip};
+use num::Float;
+
+/// The outcome of a [`sinkhorn_plan`] solve.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sinkhorn<T> {
+    /// The transport cost `<plan, cost>` of the regularized plan.
+    pub distance: T,
+    /// The regularized transport plan, with one row per entry of `p` and one column per entry
+    /// of `q`.
+    pub plan: Array2<T>,
+    /// The number of iterations performed.
+    pub iterations: usize,
+    /// The L1 distance between the row sums of `plan` and the normalized `p`. The column sums
+    /// match the normalized `q` up to rounding after every iteration.
+    pub marginal_error: T,
+    /// Whether `marginal_error` fell below the square root of the machine epsilon before
+    /// `max_iter` was reached.
+    pub converged: bool,
+}
+
+/// Computes the entropy-regularized optimal transport (Sinkhorn) distance between two
+/// histograms.
+///
+/// ..math::
+///    P^* = \arg\min_{P \in U(p, q)} \langle P, C \rangle - \epsilon H(P),
+///    \quad d(p, q) = \langle P^*, C \rangle
+///
+/// where `U(p, q)` is the set of plans whose rows sum to `p` and columns sum to `q`. As
+/// `epsilon` goes to zero the distance approaches the unregularized earth mover's distance;
+/// larger values converge faster and give smoother plans. With
+/// [`utils::cost_matrix`](crate::utils::cost_matrix) as the cost, the limit is the total
+/// variation distance.
+///
+/// See [`sinkhorn_plan`] for the transport plan and convergence diagnostics.
+///
+/// # Arguments
+/// * `p` - The source histogram, with nonnegative weights.
+/// * `q` - The target histogram, with nonnegative weights.
+/// * `cost` - The cost of moving a unit of mass from each bin of `p` to each bin of `q`.
+/// * `epsilon` - The strength of the entropic regularization.
+/// * `max_iter` - The maximum number of Sinkhorn iterations.
+///
+/// # Panics
+/// Panics if `cost` does not have shape `(len(p), len(q))`, if `epsilon` is not positive, or
+/// if either histogram has zero mass.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let p = arr1(&[0.5, 0.5, 0.0]);
+/// let q = arr1(&[0.0, 0.5, 0.5]);
+/// let cost = utils::cost_matrix::<f64>(3);
+/// let d = sinkhorn(&p.view(), &q.view(), &cost.view(), 0.01, 1000);
+/// assert!((d - 0.5).abs() < 1e-6);
+/// ```
+pub fn sinkhorn<T>(
+    p: &ArrayView1<T>,
+    q: &ArrayView1<T>,
+    cost: &ArrayView2<T>,
+    epsilon: T,
+    max_iter: usize,
+) -> T
+where
+    T: Float,
+{
+    sinkhorn_plan(p, q, cost, epsilon, max_iter).distance
+}
+
+/// Solves the entropy-regularized optimal transport problem between two histograms and returns
+/// the plan along with convergence diagnostics.
+///
+/// Both histograms are normalized to unit mass. The iterations alternately rescale the plan to
+/// match the row and column marginals, and run on the dual potentials in the log domain, so
+/// small values of `epsilon` do not underflow. They stop once the row marginals are within the
+/// square root of the machine epsilon of `p`, or after `max_iter` iterations.
+///
+/// # Panics
+/// Panics if `cost` does not have shape `(len(p), len(q))`, if `epsilon` is not positive, or
+/// if either histogram has zero mass.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let p = arr1(&[1.0, 3.0]);
+/// let q = arr1(&[2.0, 2.0]);
+/// let cost = utils::cost_matrix::<f64>(2);
+/// let result = sinkhorn_plan(&p.view(), &q.view(), &cost.view(), 0.01, 1000);
+/// assert!(result.converged);
+/// assert!((result.plan[[1, 0]] - 0.25).abs() < 1e-6);
+/// assert!((result.distance - 0.25).abs() < 1e-6);
+/// ```
+pub fn sinkhorn_plan<T>(
+    p: &ArrayView1<T>,
+    q: &ArrayView1<T>,
+    cost: &ArrayView2<T>,
+    epsilon: T,
+    max_iter: usize,
+) -> Sinkhorn<T>
+where
+    T: Float,
+{
+    assert_eq!(
+        cost.dim(),
+        (p.len(), q.len()),
+        "Cost matrix must have one row per entry of p and one column per entry of q."
+    );
+    assert!(epsilon > T::zero(), "Epsilon must be positive.");
+    let log_p = log_weights(p);
+    let log_q = log_weights(q);
+
+    let tolerance = T::epsilon().sqrt();
+    let mut f = Array1::<T>::zeros(p.len());
+    let mut g = Array1::<T>::zeros(q.len());
+    let mut iterations = 0;
+    let mut marginal_error = T::infinity();
+    while iterations < max_iter && marginal_error > tolerance {
+        for (i, fi) in f.iter_mut().enumerate() {
+            *fi = epsilon * (log_p[i] - log_sum_exp(&g.view(), &cost.row(i), epsilon));
+        }
+        for (j, gj) in g.iter_mut().enumerate() {
+            *gj = epsilon * (log_q[j] - log_sum_exp(&f.view(), &cost.column(j), epsilon));
+        }
+        iterations += 1;
+        marginal_error = row_error(&f, &g, &log_p, cost, epsilon);
+    }
+
+    let plan = Array2::from_shape_fn(cost.dim(), |(i, j)| {
+        transport(f[i], g[j], cost[[i, j]], epsilon)
+    });
+    let distance = Zip::from(&plan)
+        .and(cost)
+        .fold(T::zero(), |acc, &pij, &cij| {
+            if pij > T::zero() {
+                acc + pij * cij
+            } else {
+                acc
+            }
+        });
+    Sinkhorn {
+        distance,
+        plan,
+        iterations,
+        marginal_error,
+        converged: marginal_error <= tolerance,
+    }
+}
+
+/// Returns the logarithms of `weights` normalized to unit mass.
+fn log_weights<T>(weights: &ArrayView1<T>) -> Array1<T>
+where
+    T: Float,
+{
+    let total = weights.fold(T::zero(), |acc, &w| acc + w);
+    assert!(total > T::zero(), "Histograms must have positive mass.");
+    weights.mapv(|w| (w / total).ln())
+}
+
+/// Computes `ln sum_k exp((potential_k - cost_k) / epsilon)` without overflow.
+fn log_sum_exp<T>(potential: &ArrayView1<T>, cost: &ArrayView1<T>, epsilon: T) -> T
+where
+    T: Float,
+{
+    let max = Zip::from(potential)
+        .and(cost)
+        .fold(T::neg_infinity(), |acc, &v, &c| acc.max((v - c) / epsilon));
+    if max == T::neg_infinity() {
+        return max;
+    }
+    let sum = Zip::from(potential)
+        .and(cost)
+        .fold(T::zero(), |acc, &v, &c| {
+            acc + ((v - c) / epsilon - max).exp()
+        });
+    max + sum.ln()
+}
+
+fn transport<T>(f: T, g: T, cost: T, epsilon: T) -> T
+where
+    T: Float,
+{
+    ((f + g - cost) / epsilon).exp()
+}
+
+fn row_error<T>(
+    f: &Array1<T>,
+    g: &Array1<T>,
+    log_p: &Array1<T>,
+    cost: &ArrayView2<T>,
+    epsilon: T,
+) -> T
+where
+    T: Float,
+{
+    (0..f.len()).fold(T::zero(), |acc, i| {
+        let row = Zip::from(g)
+            .and(cost.row(i))
+            .fold(T::zero(), |sum, &gj, &cij| {
+                sum + transport(f[i], gj, cij, epsilon)
+            });
+        acc + (row - log_p[i].exp()).abs()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::cost_matrix;
+    use crate::wasserstein_1d;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_total_variation_limit() {
+        let p = arr1(&[0.1, 0.4, 0.2, 0.3]);
+        let q = arr1(&[0.3, 0.3, 0.0, 0.4]);
+        let cost = cost_matrix::<f64>(4);
+        let d = sinkhorn(&p.view(), &q.view(), &cost.view(), 0.005, 5000);
+        // Half the L1 distance between the histograms.
+        assert!((d - 0.3).abs() < 1e-4);
+
+        let result = sinkhorn_plan(&p.view(), &p.view(), &cost.view(), 0.005, 5000);
+        assert!(result.converged);
+        assert!(result.distance < 1e-6);
+    }
+
+    #[test]
+    fn test_plan_marginals_and_wasserstein_limit() {
+        let p = arr1(&[3.0, 1.0, 0.0, 2.0, 4.0]);
+        let q = arr1(&[1.0, 1.0, 5.0, 2.0, 1.0]);
+        let cost = Array2::from_shape_fn((5, 5), |(i, j)| (i as f64 - j as f64).abs());
+        let result = sinkhorn_plan(&p.view(), &q.view(), &cost.view(), 0.02, 10_000);
+        assert!(result.converged);
+        for (sum, expected) in result.plan.sum_axis(ndarray::Axis(0)).iter().zip(&q) {
+            assert!((sum - expected / 10.0).abs() < 1e-8);
+        }
+        for (sum, expected) in result.plan.sum_axis(ndarray::Axis(1)).iter().zip(&p) {
+            assert!((sum - expected / 10.0).abs() < 1e-6);
+        }
+        assert!(result.plan.row(2).iter().all(|&v| v == 0.0));
+
+        let emd = wasserstein_1d(&p.view(), &q.view());
+        assert!(result.distance >= emd - 1e-9);
+        assert!((result.distance - emd).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_iteration_budget() {
+        let p = arr1(&[0.9, 0.1]);
+        let q = arr1(&[0.1, 0.9]);
+        let cost = cost_matrix::<f64>(2);
+        let result = sinkhorn_plan(&p.view(), &q.view(), &cost.view(), 1e-3, 1);
+        assert_eq!(result.iterations, 1);
+        assert!(!result.converged);
+        assert!(result.marginal_error > f64::EPSILON.sqrt());
+
+        let result = sinkhorn_plan(&p.view(), &q.view(), &cost.view(), 1e-3, 0);
+        assert_eq!(result.iterations, 0);
+        assert!(result.marginal_error.is_infinite());
+    }
+
+    #[test]
+    #[should_panic(expected = "Cost matrix must have one row per entry of p")]
+    fn test_cost_shape_mismatch() {
+        let p = arr1(&[0.5, 0.5]);
+        sinkhorn(&p.view(), &p.view(), &cost_matrix::<f64>(3).view(), 0.1, 10);
+    }
+}
@@ -0,0 +1,79 @@
+use ndarray::{ArrayView1, Zip};
+use num::Float;
+
+/// Computes the soft Dice distance, a continuous relaxation of the [`dice`](crate::dice)
+/// dissimilarity.
+///
+/// ..math::
+///    d(x, y) = 1 - \frac{2 \sum_i x_i y_i}{\sum_i x_i^2 + \sum_i y_i^2}
+///
+/// On 0/1 vectors this is exactly the binary Dice dissimilarity, but it is smooth in the
+/// inputs, so set-style overlap can drive gradient-based embeddings through
+/// [`soft_dice_grad`](crate::soft_dice_grad). For nonnegative inputs it lies in `[0, 1]`.
+/// The `Σ min / Σ max` relaxation of Jaccard is
+/// [`weighted_jaccard`](crate::weighted_jaccard), with its subgradient in
+/// [`weighted_jaccard_grad`](crate::weighted_jaccard_grad). Two zero vectors are at
+/// distance `0`.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[1.0, 1.0, 0.0, 1.0]);
+/// let y = arr1(&[1.0, 0.0, 1.0, 1.0]);
+/// assert_eq!(soft_dice(&x.view(), &y.view()), dice(&x.view(), &y.view()));
+/// ```
+pub fn soft_dice<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    let (dot, total) = dot_and_total(x, y);
+    if total.is_zero() {
+        T::zero()
+    } else {
+        (total - T::from(2).unwrap() * dot) / total
+    }
+}
+
+/// Returns `Σ x_i y_i` and `Σ x_i² + Σ y_i²`.
+pub(crate) fn dot_and_total<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> (T, T)
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+    Zip::from(x)
+        .and(y)
+        .fold((T::zero(), T::zero()), |(dot, total), &a, &b| {
+            (dot + a * b, total + a * a + b * b)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dice;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_soft_dice_binary_matches_dice() {
+        let x = arr1(&[1.0_f32, 0.0, 1.0, 1.0, 0.0]);
+        let y = arr1(&[0.0_f32, 0.0, 1.0, 1.0, 1.0]);
+        assert_eq!(soft_dice(&x.view(), &y.view()), dice(&x.view(), &y.view()));
+    }
+
+    #[test]
+    fn test_soft_dice_values() {
+        let x = arr1(&[0.5, 2.0, 0.0]);
+        let y = arr1(&[1.0, 1.0, 3.0]);
+        // 1 - 2 * 2.5 / (4.25 + 11)
+        assert!((soft_dice(&x.view(), &y.view()) - (1.0 - 5.0 / 15.25)).abs() < 1e-15);
+        assert_eq!(soft_dice(&x.view(), &x.view()), 0.0);
+        let zero = arr1(&[0.0, 0.0, 0.0]);
+        assert_eq!(soft_dice(&zero.view(), &zero.view()), 0.0);
+        assert_eq!(soft_dice(&zero.view(), &y.view()), 1.0);
+    }
+}
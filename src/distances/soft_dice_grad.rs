@@ -0,0 +1,73 @@
+use ndarray::{Array1, ArrayView1, Zip};
+use num::Float;
+
+use super::soft_dice::dot_and_total;
+
+/// Computes the soft Dice distance and its gradient with respect to `x`.
+///
+/// With `s = Σ x_i y_i` and `t = Σ x_i² + Σ y_i²`, the distance `1 - 2s / t` has the partial
+/// derivatives
+///
+/// ..math::
+///    \frac{\partial d}{\partial x_i} = \frac{4 s x_i - 2 t y_i}{t^2}
+///
+/// When both vectors are zero, the distance and the gradient are zero.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Returns
+/// A tuple containing:
+/// - The [`soft_dice`](crate::soft_dice) distance.
+/// - The gradient with respect to `x`.
+pub fn soft_dice_grad<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> (T, Array1<T>)
+where
+    T: Float,
+{
+    let (dot, total) = dot_and_total(x, y);
+    if total.is_zero() {
+        return (T::zero(), Array1::zeros(x.len()));
+    }
+
+    let two = T::from(2).unwrap();
+    let dist = (total - two * dot) / total;
+    let scale = total * total;
+    let grad = Zip::from(x)
+        .and(y)
+        .map_collect(|&xi, &yi| (two * two * dot * xi - two * total * yi) / scale);
+    (dist, grad)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::soft_dice;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_soft_dice_grad_matches_finite_differences() {
+        let x = arr1(&[0.5, 2.0, 0.0, 1.5]);
+        let y = arr1(&[1.0, 1.0, 3.0, 0.25]);
+        let (dist, grad) = soft_dice_grad(&x.view(), &y.view());
+        assert_eq!(dist, soft_dice(&x.view(), &y.view()));
+
+        let h = 1e-6;
+        for i in 0..x.len() {
+            let (mut hi, mut lo) = (x.clone(), x.clone());
+            hi[i] += h;
+            lo[i] -= h;
+            let numeric =
+                (soft_dice(&hi.view(), &y.view()) - soft_dice(&lo.view(), &y.view())) / (2.0 * h);
+            assert!((numeric - grad[i]).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_soft_dice_grad_zero_vectors() {
+        let zero = arr1(&[0.0_f32, 0.0]);
+        assert_eq!(
+            soft_dice_grad(&zero.view(), &zero.view()),
+            (0.0, arr1(&[0.0, 0.0]))
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use ndarray::ArrayView1;
+use num::Float;
+
+use super::elastic::align;
+
+/// Computes the soft dynamic time warping (soft-DTW) discrepancy between two sequences.
+///
+/// Soft-DTW (Cuturi & Blondel, 2017) replaces the minimum in the [`dtw`](crate::dtw)
+/// recurrence with the smooth soft-minimum
+///
+/// ..math::
+///    R(i, j) = (x_i - y_j)^2 + \operatorname{min}^\gamma(R(i - 1, j - 1), R(i - 1, j),
+///    R(i, j - 1)), \quad \operatorname{min}^\gamma(a) = -\gamma \ln \sum_k e^{-a_k / \gamma}
+///
+/// and returns `R(len(x), len(y))`. This makes the result differentiable in both sequences
+/// (see [`soft_dtw_grad`](crate::soft_dtw_grad)), so it can be used as a training loss or
+/// as an output metric on sequences. As `gamma` goes to zero it approaches the squared
+/// [`dtw`](crate::dtw) distance. Because the soft-minimum lies below the minimum, the value
+/// can be negative and is not zero for identical sequences.
+///
+/// Only two rows of the cost matrix are kept, so the memory use is `O(len(y))`.
+///
+/// # Arguments
+/// * `x` - The first sequence.
+/// * `y` - The second sequence.
+/// * `gamma` - The smoothing parameter.
+///
+/// # Returns
+/// The soft-DTW value; `0` if both sequences are empty and infinity if only one of them is.
+///
+/// # Panics
+/// Panics if `gamma` is not positive.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[0.0, 1.0, 2.0]);
+/// let y = arr1(&[0.0, 2.0, 2.0, 3.0]);
+/// let d: f64 = dtw(&x.view(), &y.view());
+/// assert!((soft_dtw(&x.view(), &y.view(), 1e-3) - d * d).abs() < 1e-2);
+/// assert!(soft_dtw(&x.view(), &y.view(), 1.0) < d * d);
+/// ```
+pub fn soft_dtw<T>(x: &ArrayView1<T>, y: &ArrayView1<T>, gamma: T) -> T
+where
+    T: Float,
+{
+    assert!(gamma > T::zero(), "Gamma must be positive.");
+    align(
+        x.len(),
+        y.len(),
+        |i, j| {
+            if i == 0 && j == 0 {
+                T::zero()
+            } else {
+                T::infinity()
+            }
+        },
+        |i, j, diagonal, up, left| {
+            let diff = x[i] - y[j];
+            diff * diff + soft_min(diagonal, up, left, gamma)
+        },
+    )
+}
+
+/// Computes `-gamma * ln(exp(-a / gamma) + exp(-b / gamma) + exp(-c / gamma))` without
+/// overflow, treating infinite arguments as absent.
+pub(crate) fn soft_min<T>(a: T, b: T, c: T, gamma: T) -> T
+where
+    T: Float,
+{
+    let min = a.min(b).min(c);
+    if min.is_infinite() {
+        return min;
+    }
+    let sum = [a, b, c]
+        .iter()
+        .fold(T::zero(), |acc, &v| acc + (-(v - min) / gamma).exp());
+    min - gamma * sum.ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dtw;
+    use ndarray::{arr1, Array1};
+
+    fn series(n: usize, phase: f64) -> Array1<f64> {
+        (0..n).map(|i| ((i as f64 + phase) * 0.7).sin()).collect()
+    }
+
+    #[test]
+    fn test_soft_dtw_approaches_dtw() {
+        let x = series(15, 0.0);
+        let y = series(12, 1.1);
+        let d = dtw(&x.view(), &y.view());
+        let value = soft_dtw(&x.view(), &y.view(), 1e-4);
+        assert!((value - d * d).abs() < 1e-2);
+        assert!(value <= d * d);
+
+        // Larger gammas smooth more and lower the value.
+        assert!(soft_dtw(&x.view(), &y.view(), 1.0) < value);
+        assert_eq!(
+            soft_dtw(&x.view(), &y.view(), 0.5),
+            soft_dtw(&y.view(), &x.view(), 0.5)
+        );
+    }
+
+    #[test]
+    fn test_soft_dtw_known_value() {
+        // A single cell: no soft-minimum is taken beyond the start.
+        let x = arr1(&[1.0]);
+        let y = arr1(&[3.0]);
+        assert_eq!(soft_dtw(&x.view(), &y.view(), 0.1), 4.0);
+
+        // Two cells, whose only predecessors are the start and the previous cell.
+        let y = arr1(&[1.0, 2.0]);
+        assert_eq!(soft_dtw(&x.view(), &y.view(), 1.0), 1.0);
+
+        let empty = Array1::<f64>::zeros(0);
+        assert_eq!(soft_dtw(&empty.view(), &empty.view(), 1.0), 0.0);
+        assert_eq!(soft_dtw(&x.view(), &empty.view(), 1.0), f64::INFINITY);
+    }
+
+    #[test]
+    #[should_panic(expected = "Gamma must be positive.")]
+    fn test_soft_dtw_zero_gamma() {
+        let x = arr1(&[1.0, 2.0]);
+        soft_dtw(&x.view(), &x.view(), 0.0);
+    }
+}
@@ -0,0 +1,132 @@
+use ndarray::{Array1, Array2, ArrayView1};
+use num::Float;
+
+use super::soft_dtw::soft_min;
+
+/// Computes the [`soft_dtw`](crate::soft_dtw) value and its gradient with respect to `x`.
+///
+/// Follows the backward recursion of Cuturi & Blondel (2017): the expected alignment matrix
+///
+/// ..math::
+///    E(i, j) = \frac{\partial R(n, m)}{\partial R(i, j)}
+///    = \sum_{(k, l)} E(k, l) \, e^{(R(k, l) - R(i, j) - \delta(k, l)) / \gamma}
+///
+/// over the successors `(k, l)` of `(i, j)`, is accumulated from the end of both sequences,
+/// and the gradient is `sum_j 2 E(i, j) (x_i - y_j)`. The full `(len(x) + 2) * (len(y) + 2)`
+/// matrices are kept, so the memory use is `O(len(x) * len(y))`.
+///
+/// # Returns
+/// A tuple containing the soft-DTW value and the gradient; the gradient is zero if either
+/// sequence is empty.
+///
+/// # Panics
+/// Panics if `gamma` is not positive.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[0.0, 1.0, 2.0]);
+/// let y = arr1(&[0.0, 2.0, 2.0, 3.0]);
+/// let (value, grad) = soft_dtw_grad(&x.view(), &y.view(), 0.1);
+/// assert_eq!(value, soft_dtw(&x.view(), &y.view(), 0.1));
+/// // Moving the middle element towards 2 shortens the alignment.
+/// assert!(grad[1] < 0.0);
+/// ```
+pub fn soft_dtw_grad<T>(x: &ArrayView1<T>, y: &ArrayView1<T>, gamma: T) -> (T, Array1<T>)
+where
+    T: Float,
+{
+    assert!(gamma > T::zero(), "Gamma must be positive.");
+    let (n, m) = (x.len(), y.len());
+    if n == 0 || m == 0 {
+        let value = if n == m { T::zero() } else { T::infinity() };
+        return (value, Array1::zeros(n));
+    }
+
+    // Both matrices are padded by one cell on each side so the recursions need no bounds
+    // checks; `delta[(i, j)]` is the cost of aligning `x[i - 1]` with `y[j - 1]`.
+    let mut delta = Array2::<T>::zeros((n + 2, m + 2));
+    let mut r = Array2::from_elem((n + 2, m + 2), T::infinity());
+    r[(0, 0)] = T::zero();
+    for i in 1..=n {
+        for j in 1..=m {
+            let diff = x[i - 1] - y[j - 1];
+            delta[(i, j)] = diff * diff;
+            r[(i, j)] =
+                delta[(i, j)] + soft_min(r[(i - 1, j - 1)], r[(i - 1, j)], r[(i, j - 1)], gamma);
+        }
+    }
+    let value = r[(n, m)];
+
+    for i in 1..=n {
+        r[(i, m + 1)] = T::neg_infinity();
+    }
+    for j in 1..=m {
+        r[(n + 1, j)] = T::neg_infinity();
+    }
+    r[(n + 1, m + 1)] = value;
+
+    let mut e = Array2::<T>::zeros((n + 2, m + 2));
+    e[(n + 1, m + 1)] = T::one();
+    for i in (1..=n).rev() {
+        for j in (1..=m).rev() {
+            let weight =
+                |k: usize, l: usize| ((r[(k, l)] - r[(i, j)] - delta[(k, l)]) / gamma).exp();
+            e[(i, j)] = e[(i + 1, j)] * weight(i + 1, j)
+                + e[(i, j + 1)] * weight(i, j + 1)
+                + e[(i + 1, j + 1)] * weight(i + 1, j + 1);
+        }
+    }
+
+    let two = T::one() + T::one();
+    let grad = Array1::from_shape_fn(n, |i| {
+        (0..m).fold(T::zero(), |acc, j| {
+            acc + two * e[(i + 1, j + 1)] * (x[i] - y[j])
+        })
+    });
+    (value, grad)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::soft_dtw;
+    use ndarray::{arr1, Array1};
+
+    #[test]
+    fn test_soft_dtw_grad_finite_difference() {
+        let x: Array1<f64> = (0..7).map(|i| (i as f64 * 0.9).sin()).collect();
+        let y: Array1<f64> = (0..5).map(|i| (i as f64 * 1.3 + 0.4).cos()).collect();
+        for gamma in [0.01, 0.5, 2.0] {
+            let (value, grad) = soft_dtw_grad(&x.view(), &y.view(), gamma);
+            assert!((value - soft_dtw(&x.view(), &y.view(), gamma)).abs() < 1e-12);
+
+            let h = 1e-6;
+            for i in 0..x.len() {
+                let (mut xp, mut xm) = (x.clone(), x.clone());
+                xp[i] += h;
+                xm[i] -= h;
+                let numeric = (soft_dtw(&xp.view(), &y.view(), gamma)
+                    - soft_dtw(&xm.view(), &y.view(), gamma))
+                    / (2.0 * h);
+                assert!((numeric - grad[i]).abs() < 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn test_soft_dtw_grad_single_cell() {
+        let x = arr1(&[1.0f32]);
+        let y = arr1(&[3.0f32]);
+        let (value, grad) = soft_dtw_grad(&x.view(), &y.view(), 1.0);
+        assert_eq!(value, 4.0);
+        assert_eq!(grad, arr1(&[-4.0]));
+
+        let empty = Array1::<f32>::zeros(0);
+        let (value, grad) = soft_dtw_grad(&empty.view(), &y.view(), 1.0);
+        assert_eq!(value, f32::INFINITY);
+        assert_eq!(grad.len(), 0);
+    }
+}
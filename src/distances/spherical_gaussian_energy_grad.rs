@@ -0,0 +1,122 @@
+use ndarray::{Array1, ArrayView1};
+use num::Float;
+use std::f64::consts::PI;
+
+/// Computes the spherical Gaussian energy distance and its gradient between two 2D Gaussians.
+///
+/// Each input vector encodes an isotropic 2D Gaussian as `[mu_1, mu_2, sigma]`. The distance
+/// is the negative log expected likelihood of the two Gaussians:
+///
+/// ..math::
+///     \sigma = |\sigma_x| + |\sigma_y|, \quad \mu = \mu_x - \mu_y
+///     D(x, y) = \frac{\lVert \mu \rVert^2}{2 \sigma} + \log \sigma + \log 2\pi
+///
+/// This mirrors UMAP's `spherical_gaussian_energy_grad` output metric.
+///
+/// # Arguments
+/// * `x` - A 1D array (view) of 3 values describing the first Gaussian.
+/// * `y` - A 1D array (view) of 3 values describing the second Gaussian.
+///
+/// # Returns
+/// A tuple of the spherical Gaussian energy distance and its gradient with respect to `x`.
+///
+/// # Panics
+/// Panics if `x` or `y` do not have exactly 3 components.
+pub fn spherical_gaussian_energy_grad<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> (T, Array1<T>)
+where
+    T: Float,
+{
+    if x.len() != 3 || y.len() != 3 {
+        panic!("Spherical Gaussian energy is only defined for 3-dimensional data");
+    }
+
+    let two = T::from(2.0).unwrap();
+
+    let mu_1 = x[0] - y[0];
+    let mu_2 = x[1] - y[1];
+
+    let sigma = x[2].abs() + y[2].abs();
+    let sign_sigma = if x[2] > T::zero() {
+        T::one()
+    } else if x[2] < T::zero() {
+        -T::one()
+    } else {
+        T::zero()
+    };
+
+    let sq_mu = mu_1.powi(2) + mu_2.powi(2);
+    let dist = sq_mu / (two * sigma) + sigma.ln() + (two * T::from(PI).unwrap()).ln();
+
+    let grad = Array1::from_vec(vec![
+        mu_1 / sigma,
+        mu_2 / sigma,
+        sign_sigma * (T::one() / sigma - sq_mu / (two * sigma.powi(2))),
+    ]);
+
+    (dist, grad)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_spherical_gaussian_energy_grad_f64() {
+        let x = arr1(&[0.5, -0.2, 1.2]);
+        let y = arr1(&[-0.1, 0.4, 0.8]);
+
+        let (dist, grad) = spherical_gaussian_energy_grad(&x.view(), &y.view());
+        assert!((dist - 2.7110242469692905).abs() < 1e-12);
+        assert!((grad[0] - 0.3).abs() < 1e-12);
+        assert!((grad[1] + 0.3).abs() < 1e-12);
+        assert!((grad[2] - 0.41).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_spherical_gaussian_energy_grad_f32() {
+        let x = arr1(&[0.5f32, -0.2, 1.2]);
+        let y = arr1(&[-0.1f32, 0.4, 0.8]);
+
+        let (dist, grad) = spherical_gaussian_energy_grad(&x.view(), &y.view());
+        assert!((dist - 2.711_024).abs() < 1e-5);
+        assert!((grad[2] - 0.41).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_spherical_gaussian_energy_grad_matches_finite_difference() {
+        let x = arr1(&[0.5, -0.2, 1.2]);
+        let y = arr1(&[-0.1, 0.4, 0.8]);
+        let (_, grad) = spherical_gaussian_energy_grad(&x.view(), &y.view());
+
+        let h = 1e-6;
+        for i in 0..3 {
+            let mut xp = x.clone();
+            let mut xm = x.clone();
+            xp[i] += h;
+            xm[i] -= h;
+            let numeric = (spherical_gaussian_energy_grad(&xp.view(), &y.view()).0
+                - spherical_gaussian_energy_grad(&xm.view(), &y.view()).0)
+                / (2.0 * h);
+            assert!((grad[i] - numeric).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_spherical_gaussian_energy_grad_negative_sigma() {
+        let x = arr1(&[0.5, -0.2, -1.2]);
+        let y = arr1(&[-0.1, 0.4, 0.8]);
+
+        let (dist, grad) = spherical_gaussian_energy_grad(&x.view(), &y.view());
+        assert!((dist - 2.7110242469692905).abs() < 1e-12);
+        assert!((grad[2] + 0.41).abs() < 1e-12);
+    }
+
+    #[test]
+    #[should_panic(expected = "Spherical Gaussian energy is only defined for 3-dimensional data")]
+    fn test_spherical_gaussian_energy_grad_wrong_dimension() {
+        let x = arr1(&[1.0, 2.0]);
+        let y = arr1(&[4.0, 5.0]);
+        spherical_gaussian_energy_grad(&x.view(), &y.view());
+    }
+}
@@ -0,0 +1,69 @@
+use ndarray::{ArrayView1, Zip};
+use num::Float;
+
+/// Computes the squared-chord distance between two nonnegative vectors.
+///
+/// ..math::
+///    D(x, y) = \sum_i (\sqrt{x_i} - \sqrt{y_i})^2
+///
+/// It is the squared Euclidean distance between the square roots of the inputs, and therefore
+/// the unnormalized counterpart of the [`hellinger`](crate::hellinger) distance: for
+/// probability vectors it equals `2 * hellinger(x, y)`. Unlike Hellinger, it depends on the
+/// total mass of each input.
+///
+/// # Arguments
+/// * `x` - The first vector, with nonnegative entries.
+/// * `y` - The second vector, with nonnegative entries.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[1.0, 4.0, 0.0]);
+/// let y = arr1(&[4.0, 4.0, 9.0]);
+/// assert_eq!(squared_chord(&x.view(), &y.view()), 10.0);
+/// ```
+pub fn squared_chord<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+    Zip::from(x).and(y).fold(T::zero(), |acc, &xi, &yi| {
+        let diff = xi.sqrt() - yi.sqrt();
+        acc + diff * diff
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hellinger;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_squared_chord_basic() {
+        let x = arr1(&[0.0f32, 1.0, 9.0]);
+        let y = arr1(&[1.0f32, 1.0, 4.0]);
+        assert_eq!(squared_chord(&x.view(), &y.view()), 2.0);
+        assert_eq!(squared_chord(&x.view(), &x.view()), 0.0);
+        assert_eq!(squared_chord(&y.view(), &x.view()), 2.0);
+    }
+
+    #[test]
+    fn test_squared_chord_matches_hellinger() {
+        let x = arr1(&[0.1, 0.2, 0.3, 0.4]);
+        let y = arr1(&[0.25, 0.25, 0.0, 0.5]);
+        let expected = 2.0 * hellinger(&x.view(), &y.view());
+        assert!((squared_chord(&x.view(), &y.view()) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    #[should_panic(expected = "Input arrays must have the same length.")]
+    fn test_squared_chord_length_mismatch() {
+        squared_chord(&arr1(&[1.0]).view(), &arr1(&[1.0, 2.0]).view());
+    }
+}
@@ -0,0 +1,82 @@
+use ndarray::{Array1, ArrayView1, Zip};
+use num::Float;
+
+/// Computes the squared-chord distance and its gradient with respect to `x`.
+///
+/// ..math::
+///    \frac{\partial D}{\partial x_i} = 1 - \sqrt{\frac{y_i}{x_i}}
+///
+/// Coordinates where both inputs are zero get a zero gradient, and those where only `x_i` is
+/// zero get `-inf`, the limit as `x_i` approaches zero from above.
+///
+/// # Returns
+/// A tuple containing the [`squared_chord`](crate::squared_chord) distance and the gradient.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[1.0, 4.0]);
+/// let y = arr1(&[4.0, 1.0]);
+/// let (d, grad) = squared_chord_grad(&x.view(), &y.view());
+/// assert_eq!(d, 2.0);
+/// assert_eq!(grad, arr1(&[-1.0, 0.5]));
+/// ```
+pub fn squared_chord_grad<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> (T, Array1<T>)
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+    let mut result = T::zero();
+    let grad = Zip::from(x).and(y).map_collect(|&xi, &yi| {
+        let (sx, sy) = (xi.sqrt(), yi.sqrt());
+        result = result + (sx - sy) * (sx - sy);
+        if xi.is_zero() {
+            if yi.is_zero() {
+                T::zero()
+            } else {
+                T::neg_infinity()
+            }
+        } else {
+            T::one() - sy / sx
+        }
+    });
+    (result, grad)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::squared_chord;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_squared_chord_grad_finite_difference() {
+        let x = arr1(&[0.3f64, 1.2, 2.0, 0.05]);
+        let y = arr1(&[1.0f64, 0.5, 2.0, 0.7]);
+        let (d, grad) = squared_chord_grad(&x.view(), &y.view());
+        assert_eq!(d, squared_chord(&x.view(), &y.view()));
+        assert_eq!(grad[2], 0.0);
+
+        let h = 1e-7;
+        for i in 0..x.len() {
+            let mut xp = x.clone();
+            xp[i] += h;
+            let numeric = (squared_chord(&xp.view(), &y.view()) - d) / h;
+            assert!((numeric - grad[i]).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_squared_chord_grad_zeros() {
+        let x = arr1(&[0.0f32, 0.0, 1.0]);
+        let y = arr1(&[0.0f32, 1.0, 0.0]);
+        let (d, grad) = squared_chord_grad(&x.view(), &y.view());
+        assert_eq!(d, 2.0);
+        assert_eq!(grad, arr1(&[0.0, f32::NEG_INFINITY, 1.0]));
+    }
+}
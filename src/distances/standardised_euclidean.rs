@@ -13,7 +13,7 @@ use num_traits::{Float, One, Zero};
 /// * `x` - A reference to a 1-dimensional array view of type `T`.
 /// * `y` - Another reference to a 1-dimensional array view of type `T`.
 /// * `sigma` - An optional 1-dimensional array of type `T` representing the standard deviations
-///             or variances for each feature. If not provided, defaults to a vector of ones.
+///   or variances for each feature. If not provided, defaults to a vector of ones.
 ///
 /// # Returns
 ///
@@ -40,9 +40,22 @@ where
         None => arr1(&vec![T::one(); len]),
     };
 
+    standardised_euclidean_with_sigma(x, y, &sigma_view.view())
+}
+
+/// Computes the standardised Euclidean distance for a borrowed `sigma`, so callers comparing
+/// many pairs against the same standard deviations don't need to clone them for every call.
+pub(crate) fn standardised_euclidean_with_sigma<T>(
+    x: &ArrayView1<T>,
+    y: &ArrayView1<T>,
+    sigma: &ArrayView1<T>,
+) -> T
+where
+    T: Float,
+{
     let mut result = T::zero();
-    for i in 0..len {
-        result = result + ((x[i] - y[i]) * (x[i] - y[i])) / sigma_view[i];
+    for i in 0..x.len() {
+        result = result + ((x[i] - y[i]) * (x[i] - y[i])) / sigma[i];
     }
 
     result.sqrt()
@@ -1,6 +1,8 @@
 use ndarray::{Array1, ArrayView1};
 use num_traits::{Float, FromPrimitive};
 
+use crate::DistanceConfig;
+
 /// Euclidean distance standardised against a vector of standard deviations per coordinate with gradient.
 ///
 /// # Arguments
@@ -17,6 +19,22 @@ pub fn standardised_euclidean_grad<T>(
     y: &ArrayView1<T>,
     sigma: Option<Array1<T>>,
 ) -> (T, Array1<T>)
+where
+    T: Float + FromPrimitive,
+{
+    standardised_euclidean_grad_with_config(x, y, sigma, &DistanceConfig::default())
+}
+
+/// Standardised Euclidean distance with gradient, using the stabilization epsilon from `config`.
+///
+/// See [`standardised_euclidean_grad`] for details; this variant lets callers tune the epsilon
+/// added to the gradient's denominator.
+pub fn standardised_euclidean_grad_with_config<T>(
+    x: &ArrayView1<T>,
+    y: &ArrayView1<T>,
+    sigma: Option<Array1<T>>,
+    config: &DistanceConfig<T>,
+) -> (T, Array1<T>)
 where
     T: Float + FromPrimitive,
 {
@@ -39,7 +57,7 @@ where
     let d: T = result.sqrt();
 
     let mut grad = Array1::<T>::zeros(x.len());
-    let epsilon = T::from_f64(1e-6).unwrap();
+    let epsilon = config.epsilon;
     for i in 0..x.len() {
         let diff: T = x[i] - y[i];
         let s: T = sigma[i];
@@ -98,4 +116,16 @@ mod tests {
         assert!((grad[1] - expected_grad_x1).abs() < 1e-9);
         assert!((grad[2] - expected_grad_x2).abs() < 1e-9);
     }
+
+    #[test]
+    fn test_standardised_euclidean_grad_with_config() {
+        let x = arr1(&[1.0f32, 2.0, 3.0]);
+        let y = arr1(&[1.0f32, 2.0, 3.001]);
+        let config = DistanceConfig::default().with_epsilon(0.0);
+
+        let (d, grad) =
+            standardised_euclidean_grad_with_config(&x.view(), &y.view(), None, &config);
+        assert!((grad[2] - (x[2] - y[2]) / d).abs() < 1e-4);
+        assert!((grad[2] + 1.0).abs() < 1e-3);
+    }
 }
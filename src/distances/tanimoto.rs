@@ -0,0 +1,170 @@
+use ndarray::{Array2, ArrayView1, ArrayView2, Zip};
+use num::Float;
+
+/// Computes the generalized Tanimoto similarity between two real-valued vectors, such as count
+/// fingerprints.
+///
+/// ..math::
+///    T(x, y) = \frac{x \cdot y}{\lVert x \rVert^2 + \lVert y \rVert^2 - x \cdot y}
+///
+/// On 0/1 vectors this equals [`tanimoto_similarity_bits`](crate::tanimoto_similarity_bits)
+/// and one minus [`jaccard`](crate::jaccard), but on weighted data it uses the magnitudes,
+/// whereas `jaccard` only looks at which entries are nonzero. Two zero vectors are considered
+/// identical and have a similarity of `1`.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[1.0, 2.0, 0.0]);
+/// let y = arr1(&[1.0, 1.0, 1.0]);
+/// assert_eq!(tanimoto_similarity(&x.view(), &y.view()), 0.6);
+/// // The Jaccard distance ignores the count of 2.
+/// assert!((tanimoto(&x.view(), &y.view()) - 0.4f64).abs() < 1e-15);
+/// assert_eq!(jaccard(&x.view(), &y.view()), 1.0 / 3.0);
+/// ```
+pub fn tanimoto_similarity<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+
+    let (dot, norm_x, norm_y) = Zip::from(x).and(y).fold(
+        (T::zero(), T::zero(), T::zero()),
+        |(dot, nx, ny), &a, &b| (dot + a * b, nx + a * a, ny + b * b),
+    );
+    let denominator = norm_x + norm_y - dot;
+    if denominator.is_zero() {
+        T::one()
+    } else {
+        dot / denominator
+    }
+}
+
+/// Computes the Tanimoto distance, one minus the [`tanimoto_similarity`].
+///
+/// For nonnegative vectors the distance lies in `[0, 1]`; for binary fingerprints it equals
+/// [`jaccard_bits`](crate::jaccard_bits).
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[3.0, 0.0, 1.0]);
+/// let y = arr1(&[3.0, 0.0, 1.0]);
+/// assert_eq!(tanimoto(&x.view(), &y.view()), 0.0);
+/// ```
+pub fn tanimoto<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    T::one() - tanimoto_similarity(x, y)
+}
+
+/// Computes the Tanimoto similarities between every pair of bit-packed fingerprints from `a`
+/// and `b`, as used to screen a set of queries against a compound library.
+///
+/// Each row is one fingerprint in the layout of
+/// [`tanimoto_similarity_bits`](crate::tanimoto_similarity_bits). The popcount of every
+/// fingerprint is computed once, so each pair only needs the popcount of the intersection. Entry `(i, j)` is the similarity between row `i` of `a` and row `j` of
+/// `b`; use one minus the entries, or [`jaccard_bits`](crate::jaccard_bits), for distances.
+///
+/// # Panics
+/// Panics if `a` and `b` do not have the same number of words per row.
+///
+/// # Example
+/// ```
+/// use ndarray::arr2;
+/// use fast_distances::*;
+///
+/// let queries = arr2(&[[0b1110u64], [0b0001]]);
+/// let library = arr2(&[[0b0111u64], [0b1110], [0]]);
+/// let similarities = tanimoto_similarity_matrix(&queries.view(), &library.view());
+/// assert_eq!(similarities.row(0).to_vec(), [0.5, 1.0, 0.0]);
+/// assert_eq!(similarities[(1, 0)], tanimoto_similarity_bits(&[0b0001], &[0b0111]));
+/// ```
+pub fn tanimoto_similarity_matrix(a: &ArrayView2<u64>, b: &ArrayView2<u64>) -> Array2<f64> {
+    assert_eq!(
+        a.ncols(),
+        b.ncols(),
+        "Fingerprints must have the same number of words."
+    );
+
+    let popcount = |row: ArrayView1<u64>| row.iter().map(|w| w.count_ones()).sum::<u32>();
+    let counts_a: Vec<u32> = a.rows().into_iter().map(popcount).collect();
+    let counts_b: Vec<u32> = b.rows().into_iter().map(popcount).collect();
+
+    let mut result = Array2::zeros((a.nrows(), b.nrows()));
+    for (i, x) in a.rows().into_iter().enumerate() {
+        for (j, y) in b.rows().into_iter().enumerate() {
+            let intersection: u32 = Zip::from(&x)
+                .and(&y)
+                .fold(0, |acc, &p, &q| acc + (p & q).count_ones());
+            let union = counts_a[i] + counts_b[j] - intersection;
+            result[(i, j)] = if union == 0 {
+                1.0
+            } else {
+                intersection as f64 / union as f64
+            };
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{jaccard, jaccard_bits, tanimoto_similarity_bits};
+    use ndarray::{arr1, Array2};
+
+    #[test]
+    fn test_tanimoto_matches_jaccard_on_binary() {
+        let x = arr1(&[1.0, 0.0, 1.0, 1.0, 0.0]);
+        let y = arr1(&[1.0, 1.0, 0.0, 1.0, 0.0]);
+        assert_eq!(
+            tanimoto(&x.view(), &y.view()),
+            jaccard(&x.view(), &y.view())
+        );
+
+        // The packed variants report the similarity, and jaccard_bits the distance.
+        let packed = [0b01101u64];
+        let other = [0b01011u64];
+        assert_eq!(
+            tanimoto_similarity(&x.view(), &y.view()),
+            tanimoto_similarity_bits(&packed, &other)
+        );
+        assert_eq!(
+            tanimoto(&x.view(), &y.view()),
+            jaccard_bits(&packed, &other)
+        );
+
+        let zero = arr1(&[0.0f32, 0.0]);
+        assert_eq!(tanimoto_similarity(&zero.view(), &zero.view()), 1.0);
+        assert_eq!(tanimoto(&zero.view(), &zero.view()), 0.0);
+    }
+
+    #[test]
+    fn test_tanimoto_similarity_matrix_matches_pairwise() {
+        let a = Array2::from_shape_fn((4, 2), |(i, j)| {
+            (i as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15) >> (j * 7)
+        });
+        let b = Array2::from_shape_fn((3, 2), |(i, j)| (0xF0F0_u64 << (i * 3)) ^ j as u64);
+        let matrix = tanimoto_similarity_matrix(&a.view(), &b.view());
+        assert_eq!(matrix.dim(), (4, 3));
+        for i in 0..4 {
+            for j in 0..3 {
+                let (x, y) = (a.row(i).to_vec(), b.row(j).to_vec());
+                assert_eq!(matrix[(i, j)], tanimoto_similarity_bits(&x, &y));
+                assert!((1.0 - matrix[(i, j)] - jaccard_bits(&x, &y)).abs() < 1e-15);
+            }
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use ndarray::ArrayView1;
+use num::Float;
+
+use crate::angular;
+
+/// Computes the angular distance between two vectors `x` and `y` under pynndescent's name.
+///
+/// This is the same quantity as [`angular`]: the angle between the vectors divided by `π`, a
+/// proper metric on directions unlike [`cosine`](crate::cosine), so it can back metric-tree
+/// indexes.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[1.0, 0.0]);
+/// let y = arr1(&[-1.0, 1.0]);
+/// assert!((true_angular(&x.view(), &y.view()) - 0.75f64).abs() < 1e-12);
+/// ```
+pub fn true_angular<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    angular(x, y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+    use ndarray::{arr1, Array1};
+
+    #[test]
+    fn test_true_angular_basic() {
+        let x = arr1(&[1.0_f64, 2.0, 3.0]);
+        let y = arr1(&[4.0_f64, 5.0, 6.0]);
+        let expected =
+            (32.0_f64 / (14.0_f64.sqrt() * 77.0_f64.sqrt())).acos() / std::f64::consts::PI;
+        assert!((true_angular(&x.view(), &y.view()) - expected).abs() < 1e-15);
+        assert_eq!(true_angular(&x.view(), &(&x * 3.0).view()), 0.0);
+        assert_eq!(true_angular(&x.view(), &(&x * -1.0).view()), 1.0);
+    }
+
+    #[test]
+    fn test_true_angular_zero_norm() {
+        let zero = arr1(&[0.0_f32, 0.0, 0.0]);
+        let y = arr1(&[1.0_f32, 2.0, 3.0]);
+        assert_eq!(true_angular(&zero.view(), &y.view()), 0.5);
+        assert_eq!(true_angular(&zero.view(), &zero.view()), 0.0);
+    }
+
+    #[test]
+    fn test_true_angular_triangle_inequality() {
+        let mut rng = SplitMix64::new(17);
+        let mut sample = || Array1::from_shape_fn(4, |_| rng.next_gaussian());
+        for _ in 0..200 {
+            let (a, b, c) = (sample(), sample(), sample());
+            let ab = true_angular(&a.view(), &b.view());
+            let bc = true_angular(&b.view(), &c.view());
+            let ac = true_angular(&a.view(), &c.view());
+            assert!(ac <= ab + bc + 1e-12);
+        }
+    }
+}
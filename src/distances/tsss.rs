@@ -0,0 +1,86 @@
+use ndarray::{ArrayView1, Zip};
+use num::Float;
+
+/// Computes the TSSS (triangle area similarity times sector area similarity) dissimilarity.
+///
+/// TSSS combines the magnitudes and the angle of two vectors. With `θ` the angle between them
+/// widened by a padding of 10 degrees, so that parallel vectors of different lengths are still
+/// told apart,
+///
+/// ..math::
+///    TS(x, y) = \frac{\lVert x \rVert \lVert y \rVert \sin \theta}{2}, \quad
+///    SS(x, y) = \left(\lVert x - y \rVert + \bigl|\lVert x \rVert - \lVert y \rVert\bigr|\right)^2 \theta
+///
+/// and the dissimilarity is `TS · SS`. This matches pynndescent's `tsss`, with the cosine
+/// clamped to `[-1, 1]` so rounding cannot produce NaN. The triangle vanishes with either
+/// norm, so the dissimilarity involving a zero vector is `0`.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[1.0, 0.0]);
+/// let y = arr1(&[0.0, 1.0]);
+/// let theta = std::f64::consts::FRAC_PI_2 + 10f64.to_radians();
+/// let expected = theta.sin() / 2.0 * 2.0 * theta;
+/// assert!((tsss(&x.view(), &y.view()) - expected).abs() < 1e-12);
+/// ```
+pub fn tsss<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+
+    let (sq_euclidean, dot, norm_x, norm_y) = Zip::from(x).and(y).fold(
+        (T::zero(), T::zero(), T::zero(), T::zero()),
+        |(d2, dot, nx, ny), &a, &b| {
+            let diff = a - b;
+            (d2 + diff * diff, dot + a * b, nx + a * a, ny + b * b)
+        },
+    );
+    let (norm_x, norm_y) = (norm_x.sqrt(), norm_y.sqrt());
+    if norm_x.is_zero() || norm_y.is_zero() {
+        return T::zero();
+    }
+
+    let cosine = (dot / (norm_x * norm_y)).max(-T::one()).min(T::one());
+    let theta = cosine.acos() + T::from(10.0f64.to_radians()).unwrap();
+    let magnitude_difference = (norm_x - norm_y).abs();
+    let sector = (sq_euclidean.sqrt() + magnitude_difference).powi(2) * theta;
+    let triangle = norm_x * norm_y * theta.sin() / T::from(2).unwrap();
+    triangle * sector
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_tsss_reference_value() {
+        // pynndescent.distances.tsss([1, 2, 3], [2, 0, 1])
+        let x = arr1(&[1.0, 2.0, 3.0]);
+        let y = arr1(&[2.0, 0.0, 1.0]);
+        let (nx, ny) = (14f64.sqrt(), 5f64.sqrt());
+        let theta = (5.0 / (nx * ny)).acos() + 10f64.to_radians();
+        let expected = nx * ny * theta.sin() / 2.0 * (9f64.sqrt() + nx - ny).powi(2) * theta;
+        assert!((tsss(&x.view(), &y.view()) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_tsss_properties() {
+        let x = arr1(&[0.3f32, -1.2, 2.0]);
+        assert_eq!(tsss(&x.view(), &x.view()), 0.0);
+        assert_eq!(tsss(&x.view(), &arr1(&[0.0, 0.0, 0.0]).view()), 0.0);
+        assert_eq!(
+            tsss(&x.view(), &(&x * 2.0).view()),
+            tsss(&(&x * 2.0).view(), &x.view())
+        );
+        // The padding separates parallel vectors of different lengths.
+        assert!(tsss(&x.view(), &(&x * 2.0).view()) > 0.0);
+    }
+}
@@ -0,0 +1,84 @@
+use ndarray::{ArrayView1, Zip};
+use num::Float;
+
+/// Computes the weighted Jaccard (Ružička) distance between two nonnegative vectors.
+///
+/// ..math::
+///    d(x, y) = 1 - \frac{\sum_i \min(x_i, y_i)}{\sum_i \max(x_i, y_i)}
+///
+/// Unlike [`jaccard`](crate::jaccard), which only compares which entries are nonzero, this
+/// keeps the magnitudes, so it suits abundance or count profiles; on 0/1 vectors the two
+/// agree. The distance lies in `[0, 1]` for nonnegative inputs, and two zero vectors are at
+/// distance `0`.
+///
+/// # Arguments
+/// * `x` - A 1D array (view) of nonnegative values.
+/// * `y` - A 1D array (view) of nonnegative values.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[1.0, 3.0, 0.0]);
+/// let y = arr1(&[2.0, 1.0, 1.0]);
+/// // min sum 2, max sum 6
+/// assert!((weighted_jaccard(&x.view(), &y.view()) - 2.0f64 / 3.0).abs() < 1e-15);
+/// ```
+pub fn weighted_jaccard<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    let (min_sum, max_sum) = min_max_sums(x, y);
+    if max_sum.is_zero() {
+        T::zero()
+    } else {
+        (max_sum - min_sum) / max_sum
+    }
+}
+
+/// Returns `Σ min(x_i, y_i)` and `Σ max(x_i, y_i)`.
+pub(crate) fn min_max_sums<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> (T, T)
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+    Zip::from(x)
+        .and(y)
+        .fold((T::zero(), T::zero()), |(lo, hi), &a, &b| {
+            (lo + a.min(b), hi + a.max(b))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jaccard;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_weighted_jaccard_binary_matches_jaccard() {
+        let x = arr1(&[1.0, 0.0, 1.0, 1.0]);
+        let y = arr1(&[0.0, 0.0, 1.0, 1.0]);
+        assert_eq!(
+            weighted_jaccard(&x.view(), &y.view()),
+            jaccard(&x.view(), &y.view())
+        );
+    }
+
+    #[test]
+    fn test_weighted_jaccard_edge_cases() {
+        let zero = arr1(&[0.0f32, 0.0]);
+        assert_eq!(weighted_jaccard(&zero.view(), &zero.view()), 0.0);
+        let x = arr1(&[2.0f32, 5.0]);
+        assert_eq!(weighted_jaccard(&x.view(), &x.view()), 0.0);
+        assert_eq!(weighted_jaccard(&x.view(), &zero.view()), 1.0);
+        // Scaling both profiles leaves the distance unchanged.
+        let y = arr1(&[1.0f32, 7.0]);
+        let scaled = weighted_jaccard(&(&x * 3.0).view(), &(&y * 3.0).view());
+        assert!((scaled - weighted_jaccard(&x.view(), &y.view())).abs() < 1e-6);
+    }
+}
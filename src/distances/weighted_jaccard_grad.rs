@@ -0,0 +1,110 @@
+use ndarray::{Array1, ArrayView1, Zip};
+use num::Float;
+
+use super::weighted_jaccard::min_max_sums;
+
+/// Computes the weighted Jaccard (Ružička) distance and a subgradient with respect to `x`.
+///
+/// With `m = Σ min(x_i, y_i)` and `M = Σ max(x_i, y_i)`, the distance `1 - m / M` has the
+/// partial derivatives
+///
+/// ..math::
+///    \frac{\partial d}{\partial x_i} = \begin{cases} -1 / M & x_i < y_i \\ m / M^2 & x_i > y_i \end{cases}
+///
+/// It is not differentiable where `x_i = y_i`; there the average of the two one-sided
+/// derivatives is returned, which is a valid subgradient. When both vectors are zero, the
+/// distance and the gradient are zero.
+///
+/// # Arguments
+/// * `x` - A 1D array (view) of nonnegative values.
+/// * `y` - A 1D array (view) of nonnegative values.
+///
+/// # Returns
+/// A tuple containing:
+/// - The [`weighted_jaccard`](crate::weighted_jaccard) distance.
+/// - The subgradient with respect to `x`.
+///
+/// # Panics
+/// Panics if `x` and `y` do not have the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[1.0, 3.0]);
+/// let y = arr1(&[2.0, 1.0]);
+/// let (distance, grad) = weighted_jaccard_grad(&x.view(), &y.view());
+/// assert_eq!(distance, weighted_jaccard(&x.view(), &y.view()));
+/// // Raising x_0 towards y_0 shrinks the distance, raising x_1 further grows it.
+/// assert!(grad[0] < 0.0 && grad[1] > 0.0);
+/// ```
+pub fn weighted_jaccard_grad<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> (T, Array1<T>)
+where
+    T: Float,
+{
+    let (min_sum, max_sum) = min_max_sums(x, y);
+    if max_sum.is_zero() {
+        return (T::zero(), Array1::zeros(x.len()));
+    }
+
+    let below = -max_sum.recip();
+    let above = min_sum / (max_sum * max_sum);
+    let tie = (below + above) / T::from(2).unwrap();
+    let grad = Zip::from(x).and(y).map_collect(|&a, &b| {
+        if a < b {
+            below
+        } else if a > b {
+            above
+        } else {
+            tie
+        }
+    });
+
+    ((max_sum - min_sum) / max_sum, grad)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weighted_jaccard;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_weighted_jaccard_grad_finite_differences() {
+        let x = arr1(&[0.5, 3.0, 1.2, 0.0]);
+        let y = arr1(&[2.0, 1.0, 0.7, 0.4]);
+        let (distance, grad) = weighted_jaccard_grad(&x.view(), &y.view());
+        assert_eq!(distance, weighted_jaccard(&x.view(), &y.view()));
+
+        let h = 1e-7;
+        for i in 0..x.len() {
+            let (mut up, mut down) = (x.clone(), x.clone());
+            up[i] += h;
+            down[i] -= h;
+            let numeric = (weighted_jaccard(&up.view(), &y.view())
+                - weighted_jaccard(&down.view(), &y.view()))
+                / (2.0 * h);
+            assert!(
+                (grad[i] - numeric).abs() < 1e-6,
+                "{i}: {} vs {numeric}",
+                grad[i]
+            );
+        }
+    }
+
+    #[test]
+    fn test_weighted_jaccard_grad_ties_and_zero() {
+        let x = arr1(&[1.0, 2.0]);
+        let (distance, grad) = weighted_jaccard_grad(&x.view(), &x.view());
+        assert_eq!(distance, 0.0);
+        // At a tie the one-sided derivatives are -1/3 and 1/3.
+        assert!(grad.iter().all(|g: &f64| g.abs() < 1e-15));
+
+        let zero = arr1(&[0.0, 0.0]);
+        assert_eq!(
+            weighted_jaccard_grad(&zero.view(), &zero.view()),
+            (0.0, arr1(&[0.0, 0.0]))
+        );
+    }
+}
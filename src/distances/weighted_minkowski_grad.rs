@@ -29,7 +29,7 @@ use num::Float;
 /// - `x`: A reference to an `ArrayView1<T>` representing the first vector.
 /// - `y`: A reference to an `ArrayView1<T>` representing the second vector.
 /// - `w`: An optional `Array<T>` representing the weights.
-///          If `None`, the weights are assumed to be all ones (i.e., unweighted distance).
+///   If `None`, the weights are assumed to be all ones (i.e., unweighted distance).
 /// - `p`: A floating-point value `T` representing the exponent for the Minkowski distance.
 ///
 /// # Returns:
@@ -1,25 +1,32 @@
 extern crate ndarray;
 extern crate num;
 
-use ndarray::ArrayView1;
+use ndarray::{ArrayView1, Zip};
 use num::Float;
 
-/// Computes the Yule's Q statistic (a measure of association between two binary variables).
+/// Computes the Yule dissimilarity between two binary vectors.
 ///
-/// The Yule's Q statistic is a measure of the association between two binary variables based on the counts
-/// of true-positive, false-positive, true-negative, and false-negative outcomes. It is defined as:
+/// With `TT`, `TF`, `FT` and `FF` the number of positions where `x` and `y` are respectively
+/// both true, true and false, false and true, and both false (non-zero entries count as true),
+/// the dissimilarity is
 ///
 /// .. math::
-///     Q = \frac{2 \cdot (True\_Positive \cdot False\_Negative)}{(True\_Positive \cdot False\_Positive) + (True\_Negative \cdot False\_Negative)}
+///     D(x, y) = \frac{2 \cdot TF \cdot FT}{TT \cdot FF + TF \cdot FT}
 ///
-/// If either the number of false positives or false negatives is zero, the function returns `0.0`.
+/// that is `1 - Q` for Yule's coefficient of association `Q`, so it lies in `[0, 2]`. If either
+/// `TF` or `FT` is zero, the function returns `0.0`.
+///
+/// For 0/1 input this matches SciPy's `scipy.spatial.distance.yule`, including the zero case.
+/// Other values are counted as true when non-zero, as SciPy's `cdist` and `pdist` do with
+/// `metric="yule"` (they cast to bool first); the standalone SciPy function instead does real
+/// arithmetic on them, which [`yule_scipy`] reproduces.
 ///
 /// # Arguments
 /// * `x` - A 1D array (view) of values representing the first binary variable.
 /// * `y` - A 1D array (view) of values representing the second binary variable.
 ///
 /// # Returns
-/// A floating-point value representing the Yule's Q statistic.
+/// A floating-point value representing the Yule dissimilarity.
 ///
 /// # Panics
 /// Panics if the input arrays are not of the same length.
@@ -59,6 +66,55 @@ where
         / (num_true_true * num_false_false + num_true_false * num_false_true)
 }
 
+/// Computes the Yule dissimilarity exactly as SciPy's `scipy.spatial.distance.yule` does.
+///
+/// The counts are real-valued sums rather than boolean tallies: with `x' = 1 - x` and
+/// `y' = 1 - y`, `TT = Σ x·y`, `TF = Σ x·y'`, `FT = Σ x'·y` and `FF = Σ x'·y'`. The result is
+/// `0.0` if `TF · FT` is zero and `2 · TF · FT / (TT · FF + TF · FT)` otherwise.
+///
+/// On 0/1 input this equals [`yule`]. On other values it follows SciPy, for example
+/// `[1, 0, 0, 1, 2.5]` against `[1, -3, 0, 1, 0]` gives `30` where `yule` gives `2/3`, and the
+/// result may fall outside `[0, 2]` or be infinite when the denominator vanishes.
+///
+/// # Panics
+/// Panics if the input arrays are not of the same length.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[1.0, 0.0, 0.0, 1.0, 2.5]);
+/// let y = arr1(&[1.0, -3.0, 0.0, 1.0, 0.0]);
+/// assert!((yule_scipy(&x.view(), &y.view()) - 30.0f64).abs() < 1e-12);
+/// ```
+pub fn yule_scipy<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T
+where
+    T: Float,
+{
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+
+    let one = T::one();
+    let (ntt, ntf, nft, nff) = Zip::from(x).and(y).fold(
+        (T::zero(), T::zero(), T::zero(), T::zero()),
+        |(tt, tf, ft, ff), &xi, &yi| {
+            let (not_x, not_y) = (one - xi, one - yi);
+            (
+                tt + xi * yi,
+                tf + xi * not_y,
+                ft + not_x * yi,
+                ff + not_x * not_y,
+            )
+        },
+    );
+
+    let half_r = ntf * nft;
+    if half_r == T::zero() {
+        return T::zero();
+    }
+    (half_r + half_r) / (ntt * nff + half_r)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -157,4 +213,57 @@ mod tests {
         });
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_yule_matches_scipy_on_binary_input() {
+        // scipy.spatial.distance.yule(u, v) for each 0/1 pair.
+        let cases: [(&[f64], &[f64], f64); 3] = [
+            (
+                &[1.0, 0.0, 1.0, 1.0, 0.0, 1.0, 0.0, 0.0],
+                &[1.0, 1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0],
+                1.0,
+            ),
+            (
+                &[1.0, 1.0, 1.0, 0.0, 0.0, 1.0],
+                &[1.0, 1.0, 0.0, 0.0, 1.0, 1.0],
+                0.5,
+            ),
+            (&[1.0, 0.0, 1.0], &[1.0, 0.0, 0.0], 0.0),
+        ];
+        for (u, v, expected) in cases {
+            let (u, v) = (arr1(u), arr1(v));
+            assert!((yule(&u.view(), &v.view()) - expected).abs() < 1e-15);
+            assert!((yule_scipy(&u.view(), &v.view()) - expected).abs() < 1e-15);
+        }
+    }
+
+    #[test]
+    fn test_yule_counts_non_zero_as_true() {
+        // This crate's convention, shared with SciPy's cdist/pdist(metric="yule") after their
+        // cast to bool, but not with the standalone scipy.spatial.distance.yule.
+        let x = arr1(&[1.0, 0.0, 0.0, 1.0, 2.5]);
+        let y = arr1(&[1.0, -3.0, 0.0, 1.0, 0.0]);
+        assert!((yule(&x.view(), &y.view()) - 2.0 / 3.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_yule_scipy_non_binary_input() {
+        // scipy.spatial.distance.yule(u, v), which computes 1 - u and 1 - v arithmetically.
+        let x = arr1(&[1.0, 0.0, 0.0, 1.0, 2.5]);
+        let y = arr1(&[1.0, -3.0, 0.0, 1.0, 0.0]);
+        assert!((yule_scipy(&x.view(), &y.view()) - 30.0).abs() < 1e-12);
+
+        let x = arr1(&[2.0f32, 0.0]);
+        let y = arr1(&[3.0f32, 0.0]);
+        assert!((yule_scipy(&x.view(), &y.view()) - 0.8).abs() < 1e-6);
+        assert_eq!(yule(&x.view(), &y.view()), 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Input arrays must have the same length.")]
+    fn test_yule_scipy_different_lengths() {
+        let x = arr1(&[1.0, 0.0]);
+        let y = arr1(&[1.0]);
+        yule_scipy(&x.view(), &y.view());
+    }
 }
@@ -0,0 +1,74 @@
+use std::fmt;
+
+/// Errors reported by the fallible APIs of this crate.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DistanceError {
+    /// Two inputs that must agree in size do not.
+    DimensionMismatch { expected: usize, found: usize },
+    /// An input contains a NaN or infinite value.
+    NonFinite {
+        argument: &'static str,
+        index: usize,
+    },
+    /// An input that must be non-negative contains a negative value.
+    Negative {
+        argument: &'static str,
+        index: usize,
+    },
+    /// An input lies outside the domain of the metric.
+    InvalidInput(String),
+    /// A metric parameter has an unsupported value.
+    InvalidParameter(String),
+}
+
+impl fmt::Display for DistanceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DistanceError::DimensionMismatch { expected, found } => write!(
+                f,
+                "dimension mismatch: expected length {}, found {}",
+                expected, found
+            ),
+            DistanceError::NonFinite { argument, index } => write!(
+                f,
+                "`{}` contains a non-finite value at index {}",
+                argument, index
+            ),
+            DistanceError::Negative { argument, index } => write!(
+                f,
+                "`{}` must be non-negative, found a negative value at index {}",
+                argument, index
+            ),
+            DistanceError::InvalidInput(reason) => write!(f, "invalid input: {}", reason),
+            DistanceError::InvalidParameter(reason) => write!(f, "invalid parameter: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for DistanceError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let err = DistanceError::DimensionMismatch {
+            expected: 3,
+            found: 2,
+        };
+        assert_eq!(
+            err.to_string(),
+            "dimension mismatch: expected length 3, found 2"
+        );
+
+        let err = DistanceError::NonFinite {
+            argument: "x",
+            index: 1,
+        };
+        assert_eq!(
+            err.to_string(),
+            "`x` contains a non-finite value at index 1"
+        );
+    }
+}
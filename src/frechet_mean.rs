@@ -0,0 +1,226 @@
+use std::iter::Sum;
+
+use ndarray::{Array1, ArrayView1, ArrayView2};
+use num::Float;
+
+use crate::distances::{mobius_add, poincare, poincare_grad};
+
+/// The result of [`poincare_frechet_mean`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrechetMean<T> {
+    /// The estimated mean, a point strictly inside the unit ball.
+    pub mean: Array1<T>,
+    /// The mean squared hyperbolic distance from `mean` to the input points.
+    pub objective: T,
+    /// The Riemannian norm of the gradient of the objective at `mean`.
+    pub gradient_norm: T,
+    /// The number of iterations performed.
+    pub iterations: usize,
+    /// Whether `gradient_norm` dropped below the tolerance within the iteration budget.
+    pub converged: bool,
+}
+
+/// Computes the Fréchet (Karcher) mean of the rows of `points` in the Poincaré ball.
+///
+/// The mean minimizes the mean squared [`poincare`] distance to the points. Starting from the
+/// Euclidean mean, each iteration averages the logarithmic maps of the points at the current
+/// estimate, which is the negative Riemannian gradient, and moves along it with the exponential
+/// map. The logarithmic maps are obtained from [`poincare_grad`] as
+/// `log_m(x) = -d(m, x) ∇_m d(m, x) / λ_m²`, with the conformal factor `λ_m = 2 / (1 - ‖m‖²)`:
+///
+/// ..math::
+///     m_{t+1} = \exp_{m_t}\left(\frac{1}{N} \sum_i \log_{m_t}(x_i)\right)
+///
+/// The step is halved until it decreases the objective, since a full step can overshoot when the
+/// points are spread far apart. Because the Poincaré ball has negative curvature the objective is
+/// geodesically convex, so the iteration converges to the unique minimizer.
+///
+/// # Arguments
+/// * `points` - A 2D array view whose rows lie strictly inside the unit ball.
+/// * `max_iterations` - The maximum number of iterations.
+/// * `tolerance` - Stop once the Riemannian gradient norm falls below this value.
+///
+/// # Returns
+/// The mean together with convergence diagnostics, see [`FrechetMean`]. The iteration also stops
+/// early, unconverged, if no step decreases the objective any more; points close to the boundary
+/// of the ball limit the attainable precision.
+///
+/// # Panics
+/// Panics if `points` has no rows.
+///
+/// # Example
+/// ```
+/// use ndarray::arr2;
+/// use fast_distances::*;
+///
+/// let points = arr2(&[[0.5, 0.0], [-0.5, 0.0]]);
+/// let result = poincare_frechet_mean(&points.view(), 100, 1e-10);
+/// assert!(result.converged);
+/// assert!(result.mean.iter().all(|m: &f64| m.abs() < 1e-10));
+/// ```
+pub fn poincare_frechet_mean<T>(
+    points: &ArrayView2<T>,
+    max_iterations: usize,
+    tolerance: T,
+) -> FrechetMean<T>
+where
+    T: Float + Sum,
+{
+    assert!(points.nrows() > 0, "Points must contain at least one row.");
+
+    let n = T::from(points.nrows()).unwrap();
+    let mut mean = points.sum_axis(ndarray::Axis(0)).mapv(|v| v / n);
+    let mut objective = mean_squared_distance(&mean, points);
+    let mut iterations = 0;
+
+    let mut direction = mean_log_map(&mean, points);
+    let mut gradient_norm = riemannian_norm(&mean.view(), &direction.view());
+    while gradient_norm >= tolerance && iterations < max_iterations {
+        iterations += 1;
+
+        // Backtrack until the step decreases the objective.
+        let half = T::from(0.5).unwrap();
+        let mut step = T::one();
+        let mut candidate = exp_map(&mean, &direction.view());
+        let mut candidate_objective = mean_squared_distance(&candidate, points);
+        while candidate_objective > objective {
+            step = step * half;
+            if step < T::epsilon() {
+                break;
+            }
+            candidate = exp_map(&mean, &direction.mapv(|v| v * step).view());
+            candidate_objective = mean_squared_distance(&candidate, points);
+        }
+        if step < T::epsilon() {
+            // No further progress is possible at this precision.
+            break;
+        }
+
+        mean = candidate;
+        objective = candidate_objective;
+        direction = mean_log_map(&mean, points);
+        gradient_norm = riemannian_norm(&mean.view(), &direction.view());
+    }
+
+    FrechetMean {
+        mean,
+        objective,
+        gradient_norm,
+        iterations,
+        converged: gradient_norm < tolerance,
+    }
+}
+
+fn mean_squared_distance<T: Float + Sum>(m: &Array1<T>, points: &ArrayView2<T>) -> T {
+    points
+        .rows()
+        .into_iter()
+        .map(|p| {
+            let d = poincare(m, &p.to_owned());
+            d * d
+        })
+        .fold(T::zero(), |acc, d2| acc + d2)
+        / T::from(points.nrows()).unwrap()
+}
+
+fn dot<T: Float>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T {
+    x.iter()
+        .zip(y.iter())
+        .fold(T::zero(), |acc, (&a, &b)| acc + a * b)
+}
+
+/// The conformal factor `2 / (1 - |x|^2)`.
+fn conformal_factor<T: Float>(x: &ArrayView1<T>) -> T {
+    let two = T::one() + T::one();
+    two / (T::one() - dot(x, x))
+}
+
+fn riemannian_norm<T: Float>(x: &ArrayView1<T>, v: &ArrayView1<T>) -> T {
+    conformal_factor(x) * dot(v, v).sqrt()
+}
+
+/// Average of `log_m(x_i)` over the rows of `points`.
+fn mean_log_map<T: Float + Sum>(m: &Array1<T>, points: &ArrayView2<T>) -> Array1<T> {
+    let lambda = conformal_factor(&m.view());
+    let mut total = Array1::<T>::zeros(m.len());
+    for p in points.rows() {
+        let (distance, gradient) = poincare_grad(m, &p.to_owned());
+        total.zip_mut_with(&gradient, |t, &g| *t = *t + distance * g);
+    }
+    let scale = -T::one() / (lambda * lambda * T::from(points.nrows()).unwrap());
+    total.mapv_into(|t| t * scale)
+}
+
+/// The exponential map `exp_m(v)`.
+fn exp_map<T: Float>(m: &Array1<T>, v: &ArrayView1<T>) -> Array1<T> {
+    let two = T::one() + T::one();
+    let norm = dot(v, v).sqrt();
+    if norm.is_zero() {
+        return m.clone();
+    }
+    let scale = (conformal_factor(&m.view()) * norm / two).tanh() / norm;
+    mobius_add(&m.view(), &v.mapv(|x| scale * x).view())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{arr1, arr2};
+
+    #[test]
+    fn test_single_point() {
+        let points = arr2(&[[0.3, -0.2, 0.1]]);
+        let result = poincare_frechet_mean(&points.view(), 50, 1e-12);
+
+        assert!(result.converged);
+        assert_eq!(result.iterations, 0);
+        assert_eq!(result.mean, arr1(&[0.3, -0.2, 0.1]));
+        assert!(result.objective.abs() < 1e-20);
+    }
+
+    #[test]
+    fn test_two_points_geodesic_midpoint() {
+        let points = arr2(&[[0.1, 0.2], [0.7, -0.1]]);
+        let result = poincare_frechet_mean(&points.view(), 200, 1e-9);
+        assert!(result.converged);
+
+        let (p0, p1) = (points.row(0).to_owned(), points.row(1).to_owned());
+        let d0 = poincare(&result.mean, &p0);
+        let d1 = poincare(&result.mean, &p1);
+        let d01 = poincare(&p0, &p1);
+        assert!((d0 - d1).abs() < 1e-9);
+        assert!((d0 + d1 - d01).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_mean_differs_from_euclidean_mean() {
+        let points = arr2(&[[0.0, 0.0], [0.9, 0.0], [0.0, 0.9]]);
+        let result = poincare_frechet_mean(&points.view(), 500, 1e-8);
+        assert!(result.converged);
+        assert!(result.gradient_norm < 1e-8);
+
+        // Moving away from the mean in any direction increases the objective.
+        let f = |m: &Array1<f64>| -> f64 {
+            points
+                .rows()
+                .into_iter()
+                .map(|p| poincare(m, &p.to_owned()).powi(2))
+                .sum::<f64>()
+                / 3.0
+        };
+        assert!((f(&result.mean) - result.objective).abs() < 1e-12);
+        for delta in [arr1(&[1e-3, 0.0]), arr1(&[0.0, -1e-3])] {
+            assert!(f(&(&result.mean + &delta)) > result.objective);
+        }
+        let euclidean_mean = arr1(&[0.3, 0.3]);
+        assert!(f(&euclidean_mean) > result.objective);
+    }
+
+    #[test]
+    fn test_iteration_budget() {
+        let points = arr2(&[[0.0, 0.0], [0.9, 0.0], [0.0, 0.9]]);
+        let result = poincare_frechet_mean(&points.view(), 0, 1e-10);
+        assert!(!result.converged);
+        assert_eq!(result.iterations, 0);
+    }
+}
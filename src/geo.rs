@@ -0,0 +1,312 @@
+//! Geographic coordinates for the great-circle metrics.
+//!
+//! [`haversine`](crate::haversine) and the metrics built on it take (latitude, longitude) in
+//! radians. [`LatLon`] names the two coordinates and checks their ranges, and the `*_degrees`
+//! wrappers accept degrees, so neither swapped coordinates nor unconverted degrees go unnoticed.
+//! [`initial_bearing`] and [`cross_track_distance`] use the same spherical model for route
+//! matching.
+
+use ndarray::{arr1, Array1, ArrayView1};
+use num::Float;
+
+use crate::{central_angle, DistanceError, EARTH_RADIUS_KM, EARTH_RADIUS_M};
+
+/// A point on the sphere, stored as latitude and longitude in radians.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// let paris = LatLon::from_degrees(48.8566, 2.3522).unwrap();
+/// let london = LatLon::from_degrees(51.5074, -0.1278).unwrap();
+/// assert!((paris.distance_km(&london) - 343.6f64).abs() < 0.5);
+/// assert_eq!(
+///     paris.haversine(&london),
+///     haversine(&paris.to_array().view(), &london.to_array().view())
+/// );
+///
+/// // Latitude comes first; a longitude of 151 degrees is not a valid latitude.
+/// assert!(LatLon::from_degrees(151.2093, -33.8688).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatLon<T> {
+    lat: T,
+    lon: T,
+}
+
+impl<T: Float> LatLon<T> {
+    /// Creates a point from its latitude and longitude in degrees.
+    ///
+    /// # Errors
+    /// Returns [`DistanceError::InvalidInput`] if the latitude is not in `[-90, 90]` or the
+    /// longitude is not in `[-180, 180]`, including when either is NaN.
+    pub fn from_degrees(lat: T, lon: T) -> Result<Self, DistanceError> {
+        let (quarter, half) = (T::from(90.0).unwrap(), T::from(180.0).unwrap());
+        check_range(lat, quarter, "latitude must be in [-90, 90] degrees")?;
+        check_range(lon, half, "longitude must be in [-180, 180] degrees")?;
+        Ok(Self {
+            lat: lat.to_radians(),
+            lon: lon.to_radians(),
+        })
+    }
+
+    /// Creates a point from its latitude and longitude in radians.
+    ///
+    /// # Errors
+    /// Returns [`DistanceError::InvalidInput`] if the latitude is not in `[-π/2, π/2]` or the
+    /// longitude is not in `[-π, π]`, including when either is NaN.
+    pub fn from_radians(lat: T, lon: T) -> Result<Self, DistanceError> {
+        let half = T::from(std::f64::consts::PI).unwrap();
+        let quarter = T::from(std::f64::consts::FRAC_PI_2).unwrap();
+        check_range(lat, quarter, "latitude must be in [-π/2, π/2] radians")?;
+        check_range(lon, half, "longitude must be in [-π, π] radians")?;
+        Ok(Self { lat, lon })
+    }
+
+    /// Returns the latitude in radians.
+    pub fn lat(&self) -> T {
+        self.lat
+    }
+
+    /// Returns the longitude in radians.
+    pub fn lon(&self) -> T {
+        self.lon
+    }
+
+    /// Returns the latitude and longitude in degrees.
+    pub fn to_degrees(&self) -> (T, T) {
+        (self.lat.to_degrees(), self.lon.to_degrees())
+    }
+
+    /// Returns `[lat, lon]` in radians, the layout expected by
+    /// [`haversine`](crate::haversine) and the [`Haversine`](crate::Haversine) metric.
+    pub fn to_array(&self) -> Array1<T> {
+        arr1(&[self.lat, self.lon])
+    }
+
+    /// Returns the central angle to `other` in radians, as computed by
+    /// [`haversine`](crate::haversine).
+    pub fn haversine(&self, other: &Self) -> T {
+        central_angle(self.lat, self.lon, other.lat, other.lon)
+    }
+
+    /// Returns the great-circle distance to `other` in kilometers on the mean Earth sphere.
+    pub fn distance_km(&self, other: &Self) -> T {
+        self.haversine(other) * T::from(EARTH_RADIUS_KM).unwrap()
+    }
+
+    /// Returns the great-circle distance to `other` in meters on the mean Earth sphere.
+    pub fn distance_m(&self, other: &Self) -> T {
+        self.haversine(other) * T::from(EARTH_RADIUS_M).unwrap()
+    }
+}
+
+/// Checks that `|value| <= bound`, which also rejects NaN.
+fn check_range<T: Float>(value: T, bound: T, reason: &str) -> Result<(), DistanceError> {
+    if value.is_nan() || value.abs() > bound {
+        Err(DistanceError::InvalidInput(reason.to_string()))
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns the (latitude, longitude) of a point given in degrees, converted to radians.
+fn radians<T: Float>(x: &ArrayView1<T>) -> (T, T) {
+    if x.len() != 2 {
+        panic!("Haversine is only defined for 2-dimensional data");
+    }
+    (x[0].to_radians(), x[1].to_radians())
+}
+
+/// Computes the [`haversine`](crate::haversine) central angle, in radians, between two
+/// (latitude, longitude) points given in degrees.
+///
+/// The coordinates are converted but not range-checked; use [`LatLon::from_degrees`] to
+/// validate them.
+///
+/// # Panics
+/// Panics if either point does not have two coordinates.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let d = haversine_degrees(&arr1(&[0.0, 0.0]).view(), &arr1(&[0.0, 90.0]).view());
+/// assert!((d - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+/// ```
+pub fn haversine_degrees<T: Float>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T {
+    let ((lat1, lon1), (lat2, lon2)) = (radians(x), radians(y));
+    central_angle(lat1, lon1, lat2, lon2)
+}
+
+/// Computes the [`haversine_km`](crate::haversine_km) distance between two (latitude,
+/// longitude) points given in degrees.
+///
+/// # Panics
+/// Panics if either point does not have two coordinates.
+pub fn haversine_km_degrees<T: Float>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T {
+    haversine_degrees(x, y) * T::from(EARTH_RADIUS_KM).unwrap()
+}
+
+/// Computes the [`haversine_m`](crate::haversine_m) distance between two (latitude, longitude)
+/// points given in degrees.
+///
+/// # Panics
+/// Panics if either point does not have two coordinates.
+pub fn haversine_m_degrees<T: Float>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> T {
+    haversine_degrees(x, y) * T::from(EARTH_RADIUS_M).unwrap()
+}
+
+/// Computes the initial bearing (forward azimuth) of the great circle from `a` to `b`.
+///
+/// ..math::
+///    \theta = \operatorname{atan2}\left(\sin \Delta\lambda \cos \varphi_b,
+///        \cos \varphi_a \sin \varphi_b - \sin \varphi_a \cos \varphi_b \cos \Delta\lambda\right)
+///
+/// The bearing changes along the path unless it follows a meridian or the equator.
+///
+/// # Returns
+/// The bearing in radians clockwise from north, in `[0, 2π)`; `0` if the points coincide.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// let a = LatLon::from_degrees(0.0, 0.0).unwrap();
+/// let b = LatLon::from_degrees(0.0, 10.0).unwrap();
+/// let bearing: f64 = initial_bearing(&a, &b);
+/// assert!((bearing.to_degrees() - 90.0).abs() < 1e-12);
+/// ```
+pub fn initial_bearing<T: Float>(a: &LatLon<T>, b: &LatLon<T>) -> T {
+    let delta = b.lon - a.lon;
+    let y = delta.sin() * b.lat.cos();
+    let x = a.lat.cos() * b.lat.sin() - a.lat.sin() * b.lat.cos() * delta.cos();
+    let bearing = y.atan2(x);
+    if bearing < T::zero() {
+        bearing + T::from(std::f64::consts::TAU).unwrap()
+    } else {
+        bearing
+    }
+}
+
+/// Computes the signed angular distance from `point` to the great circle through `path_start`
+/// and `path_end`.
+///
+/// ..math::
+///    d_{xt} = \arcsin\left(\sin \delta_{13} \sin(\theta_{13} - \theta_{12})\right)
+///
+/// where `δ13` is the [`haversine`](crate::haversine) angle from the start to the point and
+/// `θ13`, `θ12` are the [`initial_bearing`]s from the start to the point and to the end. The
+/// distance is to the whole great circle, not only the segment between the two ends.
+///
+/// # Returns
+/// The central angle in radians, positive when the point lies to the right of the path and
+/// negative to its left. Multiply by [`EARTH_RADIUS_KM`] for kilometers on the Earth.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// // A point 1 degree north of an eastbound path along the equator lies to its left.
+/// let start = LatLon::from_degrees(0.0, 0.0).unwrap();
+/// let end = LatLon::from_degrees(0.0, 20.0).unwrap();
+/// let point = LatLon::from_degrees(1.0, 5.0).unwrap();
+/// let d: f64 = cross_track_distance(&point, &start, &end);
+/// assert!((d.to_degrees() + 1.0).abs() < 1e-12);
+/// assert!((d * EARTH_RADIUS_KM + 111.195).abs() < 1e-2);
+/// ```
+pub fn cross_track_distance<T: Float>(
+    point: &LatLon<T>,
+    path_start: &LatLon<T>,
+    path_end: &LatLon<T>,
+) -> T {
+    let delta13 = path_start.haversine(point);
+    let theta13 = initial_bearing(path_start, point);
+    let theta12 = initial_bearing(path_start, path_end);
+    (delta13.sin() * (theta13 - theta12).sin()).asin()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{haversine, haversine_km};
+
+    #[test]
+    fn test_from_degrees_validation() {
+        assert!(LatLon::from_degrees(90.0, 180.0).is_ok());
+        assert!(LatLon::from_degrees(-90.0, -180.0).is_ok());
+        assert_eq!(
+            LatLon::from_degrees(90.5, 0.0),
+            Err(DistanceError::InvalidInput(
+                "latitude must be in [-90, 90] degrees".to_string()
+            ))
+        );
+        assert!(LatLon::from_degrees(0.0, 180.5).is_err());
+        assert!(LatLon::from_degrees(f64::NAN, 0.0).is_err());
+        assert!(LatLon::from_degrees(0.0, f64::INFINITY).is_err());
+        assert!(LatLon::from_radians(2.0, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_conversions() {
+        let p = LatLon::from_degrees(45.0f64, -90.0).unwrap();
+        assert!((p.lat() - std::f64::consts::FRAC_PI_4).abs() < 1e-15);
+        assert!((p.lon() + std::f64::consts::FRAC_PI_2).abs() < 1e-15);
+        let (lat, lon) = p.to_degrees();
+        assert!((lat - 45.0).abs() < 1e-12 && (lon + 90.0).abs() < 1e-12);
+        assert_eq!(LatLon::from_radians(p.lat(), p.lon()), Ok(p));
+    }
+
+    #[test]
+    fn test_degree_wrappers_match_radians() {
+        let (a, b) = (arr1(&[40.7128, -74.006]), arr1(&[34.0522, -118.2437]));
+        let (ra, rb) = (a.mapv(f64::to_radians), b.mapv(f64::to_radians));
+        assert_eq!(
+            haversine_degrees(&a.view(), &b.view()),
+            haversine(&ra.view(), &rb.view())
+        );
+        assert_eq!(
+            haversine_km_degrees(&a.view(), &b.view()),
+            haversine_km(&ra.view(), &rb.view())
+        );
+        let km = haversine_km_degrees(&a.view(), &b.view());
+        assert!((haversine_m_degrees(&a.view(), &b.view()) - 1000.0 * km).abs() < 1e-6);
+        // New York to Los Angeles.
+        assert!((km - 3935.7).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_initial_bearing_cardinal_directions() {
+        let origin = LatLon::from_degrees(0.0f64, 0.0).unwrap();
+        let bearing = |lat, lon| {
+            initial_bearing(&origin, &LatLon::from_degrees(lat, lon).unwrap()).to_degrees()
+        };
+        assert!(bearing(10.0, 0.0).abs() < 1e-12);
+        assert!((bearing(0.0, 10.0) - 90.0).abs() < 1e-12);
+        assert!((bearing(-10.0, 0.0) - 180.0).abs() < 1e-12);
+        assert!((bearing(0.0, -10.0) - 270.0).abs() < 1e-12);
+        assert_eq!(initial_bearing(&origin, &origin), 0.0);
+
+        // London to Paris heads south-east, at about 148 degrees.
+        let london = LatLon::from_degrees(51.5074, -0.1278).unwrap();
+        let paris = LatLon::from_degrees(48.8566, 2.3522).unwrap();
+        assert!((initial_bearing(&london, &paris).to_degrees() - 148.1).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_cross_track_distance() {
+        let start = LatLon::from_degrees(0.0f64, 0.0).unwrap();
+        let end = LatLon::from_degrees(0.0, 90.0).unwrap();
+        let south = LatLon::from_degrees(-10.0, 45.0).unwrap();
+        let d = cross_track_distance(&south, &start, &end);
+        assert!((d.to_degrees() - 10.0).abs() < 1e-12);
+        // Points on the path, including beyond its end, are at distance zero.
+        let beyond = LatLon::from_degrees(0.0, 120.0).unwrap();
+        assert!(cross_track_distance(&beyond, &start, &end).abs() < 1e-12);
+        assert!(cross_track_distance(&start, &start, &end).abs() < 1e-12);
+        // Reversing the path flips the side.
+        let reversed = cross_track_distance(&south, &end, &start);
+        assert!((reversed + d).abs() < 1e-12);
+    }
+}
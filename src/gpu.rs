@@ -0,0 +1,422 @@
+//! Pairwise distances on the GPU through wgpu compute shaders.
+//!
+//! Only compiled with the `gpu` feature. A [`GpuContext`] owns a wgpu device and a compiled
+//! WGSL pipeline; [`GpuContext::cdist`] uploads blocks of both matrices, evaluates one output
+//! element per shader invocation and reads the block back. Blocks are sized so every buffer
+//! fits the device's storage buffer limit, which lets matrices far larger than a single binding
+//! be processed.
+//!
+//! Only `f32` is supported, since WGSL has no portable `f64`. The shader accumulates in the same
+//! order as the scalar loops but GPUs may fuse multiplications and additions, so results agree
+//! with [`cdist`](crate::cdist) up to rounding.
+//!
+//! # Example
+//! ```no_run
+//! use ndarray::Array2;
+//! use fast_distances::gpu::{GpuContext, GpuMetric};
+//!
+//! let gpu = GpuContext::new()?;
+//! let a = Array2::<f32>::ones((1000, 64));
+//! let b = Array2::<f32>::zeros((500, 64));
+//! let d = gpu.cdist(&a.view(), &b.view(), GpuMetric::Euclidean)?;
+//! assert_eq!(d.dim(), (1000, 500));
+//! # Ok::<(), fast_distances::gpu::GpuError>(())
+//! ```
+
+use std::fmt;
+use std::sync::mpsc;
+
+use ndarray::{s, Array2, ArrayView2};
+use wgpu::util::DeviceExt;
+
+const WORKGROUP_SIZE: usize = 16;
+
+/// Upper bound on the rows of either operand in one block, so the dispatch stays well within
+/// the per-dimension workgroup limit.
+const MAX_BLOCK_ROWS: usize = 4096;
+
+const SHADER: &str = r#"
+struct Params {
+    n: u32,
+    m: u32,
+    d: u32,
+    metric: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> a: array<f32>;
+@group(0) @binding(2) var<storage, read> b: array<f32>;
+@group(0) @binding(3) var<storage, read_write> out: array<f32>;
+
+@compute @workgroup_size(16, 16)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.y;
+    let j = id.x;
+    if (i >= params.n || j >= params.m) {
+        return;
+    }
+
+    let a_start = i * params.d;
+    let b_start = j * params.d;
+    var result = 0.0;
+    if (params.metric == 0u) {
+        for (var k = 0u; k < params.d; k++) {
+            let diff = a[a_start + k] - b[b_start + k];
+            result += diff * diff;
+        }
+        result = sqrt(result);
+    } else if (params.metric == 1u) {
+        for (var k = 0u; k < params.d; k++) {
+            result += abs(a[a_start + k] - b[b_start + k]);
+        }
+    } else {
+        var dot = 0.0;
+        var norm_x = 0.0;
+        var norm_y = 0.0;
+        for (var k = 0u; k < params.d; k++) {
+            let x = a[a_start + k];
+            let y = b[b_start + k];
+            dot += x * y;
+            norm_x += x * x;
+            norm_y += y * y;
+        }
+        if (norm_x == 0.0 && norm_y == 0.0) {
+            result = 0.0;
+        } else if (norm_x == 0.0 || norm_y == 0.0) {
+            result = 1.0;
+        } else {
+            result = 1.0 - dot / (sqrt(norm_x) * sqrt(norm_y));
+        }
+    }
+    out[i * params.m + j] = result;
+}
+"#;
+
+/// Errors reported by the GPU backend.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GpuError {
+    /// No compatible GPU adapter is available.
+    NoAdapter(String),
+    /// The adapter was found but a device could not be created from it.
+    Device(String),
+    /// Reading the results back from the device failed.
+    Readback(String),
+    /// The input matrices cannot be processed, e.g. because a single row exceeds the device's
+    /// storage buffer limit.
+    InvalidInput(String),
+}
+
+impl fmt::Display for GpuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GpuError::NoAdapter(reason) => write!(f, "no GPU adapter available: {}", reason),
+            GpuError::Device(reason) => write!(f, "failed to create GPU device: {}", reason),
+            GpuError::Readback(reason) => write!(f, "failed to read GPU results: {}", reason),
+            GpuError::InvalidInput(reason) => write!(f, "invalid input: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for GpuError {}
+
+/// The metrics implemented by the GPU shader.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpuMetric {
+    /// [`euclidean`](crate::euclidean)
+    Euclidean,
+    /// [`manhattan`](crate::manhattan)
+    Manhattan,
+    /// [`cosine`](crate::cosine), with the same conventions for zero-norm rows.
+    Cosine,
+}
+
+impl GpuMetric {
+    fn code(self) -> u32 {
+        match self {
+            GpuMetric::Euclidean => 0,
+            GpuMetric::Manhattan => 1,
+            GpuMetric::Cosine => 2,
+        }
+    }
+}
+
+/// A wgpu device with the distance pipeline compiled, reusable across many calls.
+#[derive(Debug)]
+pub struct GpuContext {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+}
+
+impl GpuContext {
+    /// Requests the default high-performance adapter and compiles the distance shader.
+    ///
+    /// Returns [`GpuError::NoAdapter`] on machines without a usable GPU, so callers can fall
+    /// back to [`cdist`](crate::cdist).
+    pub fn new() -> Result<Self, GpuError> {
+        pollster::block_on(Self::new_async())
+    }
+
+    async fn new_async() -> Result<Self, GpuError> {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::new_without_display_handle());
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::HighPerformance,
+                ..Default::default()
+            })
+            .await
+            .map_err(|err| GpuError::NoAdapter(err.to_string()))?;
+
+        let (device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor {
+                label: Some("fast-distances"),
+                required_limits: adapter.limits(),
+                ..Default::default()
+            })
+            .await
+            .map_err(|err| GpuError::Device(err.to_string()))?;
+
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("fast-distances cdist"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("fast-distances cdist"),
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        Ok(Self {
+            device,
+            queue,
+            pipeline,
+        })
+    }
+
+    /// Computes the distance between every row of `a` and every row of `b` on the GPU.
+    ///
+    /// The output is produced in blocks whose operands and results each fit in one storage
+    /// buffer; every block is uploaded, computed and read back before the next one starts.
+    ///
+    /// # Panics
+    /// Panics if `a` and `b` do not have the same number of columns.
+    pub fn cdist(
+        &self,
+        a: &ArrayView2<f32>,
+        b: &ArrayView2<f32>,
+        metric: GpuMetric,
+    ) -> Result<Array2<f32>, GpuError> {
+        assert_eq!(
+            a.ncols(),
+            b.ncols(),
+            "Input matrices must have the same number of columns."
+        );
+
+        let (n, m, d) = (a.nrows(), b.nrows(), a.ncols());
+        let mut out = Array2::zeros((n, m));
+        // Rows without columns are all at distance zero, as on the CPU, and wgpu rejects the
+        // zero-sized operand buffers they would need.
+        if n == 0 || m == 0 || d == 0 {
+            return Ok(out);
+        }
+
+        let max_elements = self.device.limits().max_storage_buffer_binding_size as usize / 4;
+        let rows = block_rows(max_elements, d)?;
+        for j0 in (0..m).step_by(rows) {
+            let j1 = (j0 + rows).min(m);
+            let b_block = upload(&self.device, &b.slice(s![j0..j1, ..]), "b");
+            for i0 in (0..n).step_by(rows) {
+                let i1 = (i0 + rows).min(n);
+                let a_block = upload(&self.device, &a.slice(s![i0..i1, ..]), "a");
+                let block = self.run_block(&a_block, &b_block, i1 - i0, j1 - j0, d, metric)?;
+                out.slice_mut(s![i0..i1, j0..j1]).assign(&block);
+            }
+        }
+        Ok(out)
+    }
+
+    fn run_block(
+        &self,
+        a: &wgpu::Buffer,
+        b: &wgpu::Buffer,
+        n: usize,
+        m: usize,
+        d: usize,
+        metric: GpuMetric,
+    ) -> Result<Array2<f32>, GpuError> {
+        let params = [n as u32, m as u32, d as u32, metric.code()];
+        let params = self
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("params"),
+                contents: bytemuck::cast_slice(&params),
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+
+        let size = (n * m * 4) as wgpu::BufferAddress;
+        let output = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("out"),
+            size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let staging = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("staging"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &self.pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: params.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: a.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: b.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: output.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: None,
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(
+                m.div_ceil(WORKGROUP_SIZE) as u32,
+                n.div_ceil(WORKGROUP_SIZE) as u32,
+                1,
+            );
+        }
+        encoder.copy_buffer_to_buffer(&output, 0, &staging, 0, size);
+        self.queue.submit(Some(encoder.finish()));
+
+        let (sender, receiver) = mpsc::channel();
+        staging.map_async(wgpu::MapMode::Read, .., move |result| {
+            let _ = sender.send(result);
+        });
+        self.device
+            .poll(wgpu::PollType::wait_indefinitely())
+            .map_err(|err| GpuError::Readback(err.to_string()))?;
+        receiver
+            .recv()
+            .map_err(|err| GpuError::Readback(err.to_string()))?
+            .map_err(|err| GpuError::Readback(err.to_string()))?;
+
+        let mapped = staging
+            .get_mapped_range(..)
+            .map_err(|err| GpuError::Readback(err.to_string()))?;
+        let values = bytemuck::cast_slice::<u8, f32>(&mapped).to_vec();
+        drop(mapped);
+        staging.unmap();
+        Ok(Array2::from_shape_vec((n, m), values).unwrap())
+    }
+}
+
+/// Copies `block` into a new storage buffer in row-major order.
+fn upload(device: &wgpu::Device, block: &ArrayView2<f32>, label: &str) -> wgpu::Buffer {
+    let data = block.as_standard_layout();
+    device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some(label),
+        contents: bytemuck::cast_slice(data.as_slice().unwrap()),
+        usage: wgpu::BufferUsages::STORAGE,
+    })
+}
+
+/// Returns the number of rows per operand block such that a block of rows with `d` columns and
+/// the square output block both fit in `max_elements` floats.
+fn block_rows(max_elements: usize, d: usize) -> Result<usize, GpuError> {
+    let by_operand = max_elements / d;
+    let by_output = (max_elements as f64).sqrt() as usize;
+    let rows = by_operand.min(by_output).min(MAX_BLOCK_ROWS);
+    if rows == 0 {
+        return Err(GpuError::InvalidInput(format!(
+            "rows of {} columns exceed the storage buffer limit",
+            d
+        )));
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    fn sample(rows: usize, cols: usize, seed: usize) -> Array2<f32> {
+        Array2::from_shape_fn((rows, cols), |(i, j)| {
+            (((i + seed) * 31 + j * 17) % 23) as f32 / 7.0 - 1.5
+        })
+    }
+
+    #[test]
+    fn test_block_rows() {
+        assert_eq!(block_rows(1 << 25, 768).unwrap(), 4096);
+        assert_eq!(block_rows(1 << 20, 768).unwrap(), 1024);
+        assert_eq!(block_rows(1 << 20, 4096).unwrap(), 256);
+        assert!(block_rows(100, 768).is_err());
+    }
+
+    #[test]
+    fn test_gpu_cdist_matches_cpu() {
+        // Machines without a GPU (such as most CI runners) skip the comparison.
+        let Ok(gpu) = GpuContext::new() else {
+            return;
+        };
+
+        let mut a = sample(37, 20, 0);
+        a.row_mut(5).fill(0.0);
+        let b = sample(19, 20, 3);
+        // The plain kernels are compared, since the CPU block kernels lose precision near zero.
+        for (metric, gpu_metric) in [
+            (Euclidean.as_fn(), GpuMetric::Euclidean),
+            (Manhattan.as_fn(), GpuMetric::Manhattan),
+            (Cosine.as_fn(), GpuMetric::Cosine),
+        ] {
+            let expected = cdist(&a.view(), &b.view(), &metric);
+            let result = gpu.cdist(&a.view(), &b.view(), gpu_metric).unwrap();
+            for (r, e) in result.iter().zip(expected.iter()) {
+                assert!((r - e).abs() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_gpu_cdist_zero_columns() {
+        let Ok(gpu) = GpuContext::new() else {
+            return;
+        };
+
+        let a = Array2::<f32>::zeros((4, 0));
+        let b = Array2::<f32>::zeros((3, 0));
+        for metric in [
+            GpuMetric::Euclidean,
+            GpuMetric::Manhattan,
+            GpuMetric::Cosine,
+        ] {
+            let result = gpu.cdist(&a.view(), &b.view(), metric).unwrap();
+            assert_eq!(result, Array2::<f32>::zeros((4, 3)));
+        }
+    }
+}
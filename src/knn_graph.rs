@@ -0,0 +1,256 @@
+use std::collections::BinaryHeap;
+
+use ndarray::{Array2, ArrayView2};
+use num::Float;
+
+use crate::neighbors::Candidate;
+use crate::Distance;
+
+/// Computes the exact k-nearest-neighbor graph of the rows of `data`.
+///
+/// Every unordered pair of rows is evaluated once and offered to the neighbor heaps of both
+/// rows, so the brute-force cost is `n * (n - 1) / 2` distance evaluations. Once both heaps are
+/// full, pairs are evaluated with [`Distance::distance_bounded`] against the larger of the two
+/// current worst distances. The point itself is never reported as one of its neighbors.
+///
+/// # Arguments
+/// * `data` - A 2D array view whose rows are the points.
+/// * `k` - The number of neighbors per point.
+/// * `metric` - Any [`Distance`]. It is assumed to be symmetric.
+///
+/// # Returns
+/// A pair `(indices, distances)` of `(n, k)` arrays. Row `i` lists the neighbors of point `i`
+/// by increasing distance, with ties resolved in favour of the lowest index. If fewer than `k`
+/// neighbors have a non-NaN distance, the remaining slots hold `usize::MAX` and infinity.
+///
+/// # Panics
+/// Panics if `k` is not smaller than the number of rows.
+///
+/// # Example
+/// ```
+/// use ndarray::arr2;
+/// use fast_distances::*;
+///
+/// let data = arr2(&[[0.0], [1.0], [3.0]]);
+/// let (indices, distances) = knn_graph(&data.view(), 1, &Euclidean);
+/// assert_eq!(indices, arr2(&[[1], [0], [1]]));
+/// assert_eq!(distances, arr2(&[[1.0], [1.0], [2.0]]));
+/// ```
+pub fn knn_graph<T, M>(data: &ArrayView2<T>, k: usize, metric: &M) -> (Array2<usize>, Array2<T>)
+where
+    T: Float,
+    M: Distance<T> + ?Sized,
+{
+    let n = data.nrows();
+    assert!(
+        k < n || n == 0,
+        "k must be smaller than the number of rows."
+    );
+
+    let mut heaps: Vec<BinaryHeap<Candidate<T>>> =
+        (0..n).map(|_| BinaryHeap::with_capacity(k)).collect();
+    if k == 0 {
+        return collect_graph(heaps, k);
+    }
+
+    for i in 0..n {
+        let row_i = data.row(i);
+        for j in (i + 1)..n {
+            let upper = worst(&heaps[i], k).max(worst(&heaps[j], k));
+            if let Some(distance) = metric.distance_bounded(&row_i, &data.row(j), upper) {
+                offer(&mut heaps[i], k, Candidate { distance, index: j });
+                offer(&mut heaps[j], k, Candidate { distance, index: i });
+            }
+        }
+    }
+
+    collect_graph(heaps, k)
+}
+
+/// Parallel version of [`knn_graph`], available with the `parallel` feature.
+///
+/// Rows are processed independently on the rayon thread pool, so each pair is evaluated twice
+/// (once per endpoint) instead of exploiting symmetry; the result is identical to
+/// [`knn_graph`].
+#[cfg(feature = "parallel")]
+pub fn par_knn_graph<T, M>(data: &ArrayView2<T>, k: usize, metric: &M) -> (Array2<usize>, Array2<T>)
+where
+    T: Float + Send + Sync,
+    M: Distance<T> + Sync + ?Sized,
+{
+    par_knn_graph_with(data, k, metric, crate::Parallelism::Global)
+}
+
+/// [`par_knn_graph`] on the thread pool selected by `parallelism`.
+///
+/// # Panics
+/// Panics if `k` is not smaller than the number of rows.
+#[cfg(feature = "parallel")]
+pub fn par_knn_graph_with<T, M>(
+    data: &ArrayView2<T>,
+    k: usize,
+    metric: &M,
+    parallelism: crate::Parallelism,
+) -> (Array2<usize>, Array2<T>)
+where
+    T: Float + Send + Sync,
+    M: Distance<T> + Sync + ?Sized,
+{
+    use rayon::prelude::*;
+
+    let n = data.nrows();
+    assert!(
+        k < n || n == 0,
+        "k must be smaller than the number of rows."
+    );
+
+    let heaps: Vec<BinaryHeap<Candidate<T>>> = parallelism.install(|| {
+        (0..n)
+            .into_par_iter()
+            .map(|i| {
+                let mut heap = BinaryHeap::with_capacity(k);
+                if k == 0 {
+                    return heap;
+                }
+                let row_i = data.row(i);
+                for j in (0..n).filter(|&j| j != i) {
+                    let upper = worst(&heap, k);
+                    if let Some(distance) = metric.distance_bounded(&row_i, &data.row(j), upper) {
+                        offer(&mut heap, k, Candidate { distance, index: j });
+                    }
+                }
+                heap
+            })
+            .collect()
+    });
+
+    collect_graph(heaps, k)
+}
+
+/// The largest distance a new candidate may have to enter `heap`.
+fn worst<T: Float>(heap: &BinaryHeap<Candidate<T>>, k: usize) -> T {
+    match heap.peek() {
+        Some(c) if heap.len() == k => c.distance,
+        _ => T::infinity(),
+    }
+}
+
+pub(crate) fn offer<T: Float>(
+    heap: &mut BinaryHeap<Candidate<T>>,
+    k: usize,
+    candidate: Candidate<T>,
+) {
+    if candidate.distance.is_nan() {
+        return;
+    }
+    if heap.len() < k {
+        heap.push(candidate);
+    } else if heap.peek().is_some_and(|w| candidate < *w) {
+        heap.pop();
+        heap.push(candidate);
+    }
+}
+
+pub(crate) fn collect_graph<T: Float>(
+    heaps: Vec<BinaryHeap<Candidate<T>>>,
+    k: usize,
+) -> (Array2<usize>, Array2<T>) {
+    let n = heaps.len();
+    let mut indices = Array2::from_elem((n, k), usize::MAX);
+    let mut distances = Array2::from_elem((n, k), T::infinity());
+    for (i, heap) in heaps.into_iter().enumerate() {
+        for (slot, c) in heap.into_sorted_vec().into_iter().enumerate() {
+            indices[(i, slot)] = c.index;
+            distances[(i, slot)] = c.distance;
+        }
+    }
+    (indices, distances)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use ndarray::{arr2, Array2};
+
+    fn brute_force(data: &Array2<f64>, k: usize) -> (Array2<usize>, Array2<f64>) {
+        let n = data.nrows();
+        let mut indices = Array2::zeros((n, k));
+        let mut distances = Array2::zeros((n, k));
+        for i in 0..n {
+            let neighbors: Vec<(usize, f64)> =
+                k_nearest(&data.view(), &data.row(i), k + 1, &Euclidean)
+                    .into_iter()
+                    .filter(|&(j, _)| j != i)
+                    .take(k)
+                    .collect();
+            for (slot, (j, d)) in neighbors.into_iter().enumerate() {
+                indices[(i, slot)] = j;
+                distances[(i, slot)] = d;
+            }
+        }
+        (indices, distances)
+    }
+
+    fn sample_data() -> Array2<f64> {
+        Array2::from_shape_fn((12, 3), |(i, j)| {
+            ((i * 7 + j * 3) % 11) as f64 + 0.1 * (i as f64)
+        })
+    }
+
+    #[test]
+    fn test_knn_graph_matches_k_nearest() {
+        let data = sample_data();
+        for k in [1, 3, 11] {
+            assert_eq!(
+                knn_graph(&data.view(), k, &Euclidean),
+                brute_force(&data, k)
+            );
+        }
+    }
+
+    #[test]
+    fn test_knn_graph_ties_and_nan() {
+        let data = arr2(&[[0.0], [1.0], [-1.0], [f64::NAN]]);
+        let (indices, distances) = knn_graph(&data.view(), 3, &Manhattan);
+
+        assert_eq!(indices.row(0).to_vec(), vec![1, 2, usize::MAX]);
+        assert_eq!(distances.row(0).to_vec(), vec![1.0, 1.0, f64::INFINITY]);
+        assert_eq!(indices.row(3).to_vec(), vec![usize::MAX; 3]);
+    }
+
+    #[test]
+    fn test_knn_graph_empty_and_zero_k() {
+        let data = Array2::<f64>::zeros((0, 2));
+        let (indices, _) = knn_graph(&data.view(), 0, &Euclidean);
+        assert_eq!(indices.dim(), (0, 0));
+
+        let data = sample_data();
+        let (indices, distances) = knn_graph(&data.view(), 0, &Euclidean);
+        assert_eq!(indices.dim(), (12, 0));
+        assert_eq!(distances.dim(), (12, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "k must be smaller than the number of rows.")]
+    fn test_knn_graph_k_too_large() {
+        let data = arr2(&[[0.0], [1.0]]);
+        knn_graph(&data.view(), 2, &Euclidean);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_par_knn_graph_matches_serial() {
+        let data = sample_data();
+        for k in [0, 2, 5] {
+            assert_eq!(
+                par_knn_graph(&data.view(), k, &Euclidean),
+                knn_graph(&data.view(), k, &Euclidean)
+            );
+            assert_eq!(
+                par_knn_graph_with(&data.view(), k, &Euclidean, Parallelism::Threads(2)),
+                knn_graph(&data.view(), k, &Euclidean)
+            );
+        }
+    }
+}
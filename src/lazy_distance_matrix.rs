@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+
+use ndarray::ArrayView2;
+use num::Float;
+
+use crate::{condensed_index, Distance};
+
+/// Counters describing how a [`LazyDistanceMatrix`] cache has been used.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Lookups answered from the cache.
+    pub hits: u64,
+    /// Lookups that had to compute the distance.
+    pub misses: u64,
+    /// Cached entries dropped to stay within the capacity.
+    pub evictions: u64,
+}
+
+#[derive(Debug, Clone)]
+struct Slot<T> {
+    key: usize,
+    value: T,
+    referenced: bool,
+}
+
+/// A symmetric distance matrix over the rows of `data` whose entries are computed on first
+/// access and memoized.
+///
+/// This suits algorithms that only touch a small subset of the `n * (n - 1) / 2` pairs, such as
+/// hierarchical clustering with early pruning, where [`DistanceMatrix`](crate::DistanceMatrix)
+/// would compute and store every pair up front. At most `capacity` entries are kept; when the
+/// cache is full, an entry that has not been read since the previous sweep is evicted (the
+/// CLOCK approximation of least-recently-used), so frequently used pairs stay cached. Each
+/// entry costs roughly `size_of::<T>()` plus two `usize` of bookkeeping.
+///
+/// The diagonal is implicitly zero and never cached.
+///
+/// # Example
+/// ```
+/// use ndarray::arr2;
+/// use fast_distances::*;
+///
+/// let data = arr2(&[[0.0, 0.0], [3.0, 4.0], [6.0, 8.0]]);
+/// let mut dm = LazyDistanceMatrix::new(data.view(), &Euclidean, 2);
+///
+/// assert_eq!(dm.get(0, 1), 5.0);
+/// assert_eq!(dm.get(1, 0), 5.0);
+/// assert_eq!(dm.stats().hits, 1);
+/// assert_eq!(dm.get(2, 2), 0.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LazyDistanceMatrix<'a, T, M: ?Sized> {
+    data: ArrayView2<'a, T>,
+    metric: &'a M,
+    capacity: usize,
+    slots: Vec<Slot<T>>,
+    index: HashMap<usize, usize>,
+    hand: usize,
+    stats: CacheStats,
+}
+
+impl<'a, T, M> LazyDistanceMatrix<'a, T, M>
+where
+    T: Float,
+    M: Distance<T> + ?Sized,
+{
+    /// Creates a lazy matrix over the rows of `data` that caches at most `capacity` distances.
+    ///
+    /// A capacity of zero disables caching, so every lookup recomputes the distance.
+    pub fn new(data: ArrayView2<'a, T>, metric: &'a M, capacity: usize) -> Self {
+        Self {
+            data,
+            metric,
+            capacity,
+            slots: Vec::new(),
+            index: HashMap::new(),
+            hand: 0,
+            stats: CacheStats::default(),
+        }
+    }
+
+    /// Creates a lazy matrix whose cache can hold every pair, so no entry is ever evicted.
+    pub fn unbounded(data: ArrayView2<'a, T>, metric: &'a M) -> Self {
+        let capacity = crate::condensed_len(data.nrows());
+        Self::new(data, metric, capacity)
+    }
+
+    /// Returns the number of points.
+    pub fn len(&self) -> usize {
+        self.data.nrows()
+    }
+
+    /// Returns `true` if the matrix covers no points.
+    pub fn is_empty(&self) -> bool {
+        self.data.nrows() == 0
+    }
+
+    /// Returns the maximum number of cached distances.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of distances currently cached.
+    pub fn cached_len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns `true` if the distance between `i` and `j` is currently cached.
+    ///
+    /// # Panics
+    /// Panics if `i == j` or if either index is out of range.
+    pub fn is_cached(&self, i: usize, j: usize) -> bool {
+        self.index.contains_key(&condensed_index(self.len(), i, j))
+    }
+
+    /// Returns the hit, miss and eviction counters.
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Drops every cached distance and resets the counters.
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.index.clear();
+        self.hand = 0;
+        self.stats = CacheStats::default();
+    }
+
+    /// Returns the distance between points `i` and `j`, computing and caching it if needed.
+    ///
+    /// # Panics
+    /// Panics if `i` or `j` is out of range.
+    pub fn get(&mut self, i: usize, j: usize) -> T {
+        let n = self.len();
+        if i == j {
+            assert!(i < n, "Indices must be smaller than the number of points.");
+            return T::zero();
+        }
+
+        let key = condensed_index(n, i, j);
+        if let Some(&slot) = self.index.get(&key) {
+            self.stats.hits += 1;
+            self.slots[slot].referenced = true;
+            return self.slots[slot].value;
+        }
+
+        self.stats.misses += 1;
+        let value = self.metric.distance(&self.data.row(i), &self.data.row(j));
+        self.insert(key, value);
+        value
+    }
+
+    fn insert(&mut self, key: usize, value: T) {
+        if self.capacity == 0 {
+            return;
+        }
+        let slot = Slot {
+            key,
+            value,
+            referenced: false,
+        };
+        if self.slots.len() < self.capacity {
+            self.index.insert(key, self.slots.len());
+            self.slots.push(slot);
+            return;
+        }
+
+        // Give every referenced entry a second chance before evicting the first one that was
+        // not read since the hand last passed it.
+        while self.slots[self.hand].referenced {
+            self.slots[self.hand].referenced = false;
+            self.hand = (self.hand + 1) % self.capacity;
+        }
+        self.index.remove(&self.slots[self.hand].key);
+        self.index.insert(key, self.hand);
+        self.slots[self.hand] = slot;
+        self.hand = (self.hand + 1) % self.capacity;
+        self.stats.evictions += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use ndarray::Array2;
+
+    fn sample() -> Array2<f64> {
+        Array2::from_shape_fn((6, 3), |(i, j)| ((i * 5 + j * 3) % 7) as f64)
+    }
+
+    #[test]
+    fn test_matches_distance_matrix() {
+        let data = sample();
+        let expected = DistanceMatrix::from_data(&data.view(), &Manhattan);
+        let mut lazy = LazyDistanceMatrix::unbounded(data.view(), &Manhattan);
+
+        for _ in 0..2 {
+            for i in 0..6 {
+                for j in 0..6 {
+                    assert_eq!(lazy.get(i, j), expected.get(i, j));
+                }
+            }
+        }
+        assert_eq!(lazy.cached_len(), 15);
+        assert_eq!(
+            lazy.stats(),
+            CacheStats {
+                hits: 45,
+                misses: 15,
+                evictions: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_capacity_and_eviction() {
+        let data = sample();
+        let mut lazy = LazyDistanceMatrix::new(data.view(), &Euclidean, 2);
+
+        lazy.get(0, 1);
+        lazy.get(0, 2);
+        // Reading (0, 1) again protects it from the next eviction.
+        lazy.get(1, 0);
+        lazy.get(0, 3);
+
+        assert_eq!(lazy.cached_len(), 2);
+        assert!(lazy.is_cached(0, 1));
+        assert!(!lazy.is_cached(0, 2));
+        assert!(lazy.is_cached(0, 3));
+        assert_eq!(lazy.stats().evictions, 1);
+
+        lazy.clear();
+        assert_eq!(lazy.cached_len(), 0);
+        assert_eq!(lazy.stats(), CacheStats::default());
+    }
+
+    #[test]
+    fn test_zero_capacity() {
+        let data = sample();
+        let mut lazy = LazyDistanceMatrix::new(data.view(), &Euclidean, 0);
+        assert_eq!(lazy.get(2, 4), euclidean(&data.row(2), &data.row(4)));
+        assert_eq!(lazy.get(2, 4), euclidean(&data.row(2), &data.row(4)));
+        assert_eq!(lazy.cached_len(), 0);
+        assert_eq!(lazy.stats().misses, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Indices must be smaller than the number of points.")]
+    fn test_out_of_range() {
+        let data = sample();
+        LazyDistanceMatrix::new(data.view(), &Euclidean, 4).get(6, 6);
+    }
+}
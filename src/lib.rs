@@ -1,4 +1,64 @@
+mod barycenter;
+#[cfg(feature = "npy")]
+pub mod batch;
+mod config;
+#[cfg(feature = "cuda")]
+pub mod cuda;
+pub mod diagnostics;
+mod distance_matrix;
+mod distance_profile;
 mod distances;
+mod error;
+mod frechet_mean;
+mod geo;
+#[cfg(feature = "gpu")]
+pub mod gpu;
+mod knn_graph;
+mod lazy_distance_matrix;
+mod matmul;
+mod medoid;
+mod metric;
+mod minhash;
+mod neighbors;
+mod online;
+mod pairwise;
+#[cfg(feature = "parallel")]
+mod parallelism;
+mod pq;
+mod prepared;
+mod projection;
+mod rng;
+#[cfg(feature = "simd")]
+pub mod simd;
+mod simhash;
+mod sparse;
+mod strings;
 pub mod utils;
+#[cfg(feature = "validate")]
+pub mod validate;
 
+pub use barycenter::*;
+pub use config::*;
+pub use distance_matrix::*;
+pub use distance_profile::*;
 pub use distances::*;
+pub use error::*;
+pub use frechet_mean::*;
+pub use geo::*;
+pub use knn_graph::*;
+pub use lazy_distance_matrix::*;
+pub use matmul::*;
+pub use medoid::*;
+pub use metric::*;
+pub use minhash::*;
+pub use neighbors::*;
+pub use online::*;
+pub use pairwise::*;
+#[cfg(feature = "parallel")]
+pub use parallelism::*;
+pub use pq::*;
+pub use prepared::*;
+pub use projection::*;
+pub use simhash::*;
+pub use sparse::*;
+pub use strings::*;
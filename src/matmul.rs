@@ -0,0 +1,226 @@
+//! All-pairs distances computed through a matrix multiplication.
+//!
+//! For wide matrices the cross term of the distance dominates the cost, and computing it with a
+//! single `a · bᵀ` product (via `ndarray`'s `dot`, which uses `matrixmultiply` or, with the
+//! `blas` feature, the system BLAS) is far faster than evaluating every pair separately.
+
+use ndarray::{Array1, Array2, ArrayView2, Axis};
+use num::Float;
+
+/// Computes the squared Euclidean distance between every row of `a` and every row of `b`.
+///
+/// Uses the expansion `‖a_i − b_j‖² = ‖a_i‖² + ‖b_j‖² − 2 a_i · b_j`, with the cross terms
+/// obtained from one matrix product. Rounding can make the expansion slightly negative for
+/// (nearly) identical rows, so the result is clamped at zero. The cancellation also limits the
+/// relative accuracy for very close rows; use [`cdist`](crate::cdist) with
+/// `Euclidean.as_fn()` when exact small distances matter.
+///
+/// # Panics
+/// Panics if `a` and `b` do not have the same number of columns.
+///
+/// # Example
+/// ```
+/// use ndarray::arr2;
+/// use fast_distances::sqeuclidean_cdist;
+///
+/// let a = arr2(&[[0.0, 0.0], [1.0, 1.0]]);
+/// let b = arr2(&[[3.0, 4.0]]);
+/// assert_eq!(sqeuclidean_cdist(&a.view(), &b.view()), arr2(&[[25.0], [13.0]]));
+/// ```
+pub fn sqeuclidean_cdist<T>(a: &ArrayView2<T>, b: &ArrayView2<T>) -> Array2<T>
+where
+    T: Float + 'static,
+{
+    assert_eq!(
+        a.ncols(),
+        b.ncols(),
+        "Input matrices must have the same number of columns."
+    );
+
+    let two = T::one() + T::one();
+    let a_norms = squared_row_norms(a);
+    let b_norms = squared_row_norms(b);
+
+    let mut result = a.dot(&b.t());
+    for ((i, j), value) in result.indexed_iter_mut() {
+        *value = (a_norms[i] + b_norms[j] - two * *value).max(T::zero());
+    }
+    result
+}
+
+/// Computes the [`euclidean`](crate::euclidean) distance between every row of `a` and every row
+/// of `b` with a matrix multiplication. See [`sqeuclidean_cdist`] for the accuracy caveats.
+///
+/// # Panics
+/// Panics if `a` and `b` do not have the same number of columns.
+pub fn euclidean_cdist<T>(a: &ArrayView2<T>, b: &ArrayView2<T>) -> Array2<T>
+where
+    T: Float + 'static,
+{
+    sqeuclidean_cdist(a, b).mapv_into(|v| v.sqrt())
+}
+
+/// Computes the [`cosine`](crate::cosine) distance between every row of `a` and every row of `b`
+/// with a matrix multiplication.
+///
+/// The row norms are computed once and the dot products come from a single `a · bᵀ` product,
+/// so every pair costs a division instead of a full pass over the coordinates. Zero-norm rows
+/// follow the scalar conventions: the distance between two zero rows is `0`, and between a zero
+/// row and a non-zero row it is `1`.
+///
+/// # Panics
+/// Panics if `a` and `b` do not have the same number of columns.
+///
+/// # Example
+/// ```
+/// use ndarray::arr2;
+/// use fast_distances::cosine_cdist;
+///
+/// let a = arr2(&[[1.0, 0.0], [0.0, 0.0]]);
+/// let b = arr2(&[[2.0, 0.0], [0.0, 3.0], [0.0, 0.0]]);
+/// assert_eq!(
+///     cosine_cdist(&a.view(), &b.view()),
+///     arr2(&[[0.0, 1.0, 1.0], [1.0, 1.0, 0.0]])
+/// );
+/// ```
+pub fn cosine_cdist<T>(a: &ArrayView2<T>, b: &ArrayView2<T>) -> Array2<T>
+where
+    T: Float + 'static,
+{
+    assert_eq!(
+        a.ncols(),
+        b.ncols(),
+        "Input matrices must have the same number of columns."
+    );
+
+    let a_norms = squared_row_norms(a);
+    let b_norms = squared_row_norms(b);
+
+    let mut result = a.dot(&b.t());
+    for ((i, j), value) in result.indexed_iter_mut() {
+        let (na, nb) = (a_norms[i], b_norms[j]);
+        *value = if na.is_zero() && nb.is_zero() {
+            T::zero()
+        } else if na.is_zero() || nb.is_zero() {
+            T::one()
+        } else {
+            T::one() - *value / (na.sqrt() * nb.sqrt())
+        };
+    }
+    result
+}
+
+/// Computes the [`neg_inner_product`](crate::neg_inner_product) between every row of `a` and
+/// every row of `b` with a single matrix multiplication.
+///
+/// # Panics
+/// Panics if `a` and `b` do not have the same number of columns.
+///
+/// # Example
+/// ```
+/// use ndarray::arr2;
+/// use fast_distances::neg_inner_product_cdist;
+///
+/// let a = arr2(&[[1.0, 2.0]]);
+/// let b = arr2(&[[3.0, 4.0], [-1.0, 0.0]]);
+/// assert_eq!(neg_inner_product_cdist(&a.view(), &b.view()), arr2(&[[-11.0, 1.0]]));
+/// ```
+pub fn neg_inner_product_cdist<T>(a: &ArrayView2<T>, b: &ArrayView2<T>) -> Array2<T>
+where
+    T: Float + 'static,
+{
+    assert_eq!(
+        a.ncols(),
+        b.ncols(),
+        "Input matrices must have the same number of columns."
+    );
+
+    a.dot(&b.t()).mapv_into(|v| -v)
+}
+
+fn squared_row_norms<T: Float>(m: &ArrayView2<T>) -> Array1<T> {
+    m.map_axis(Axis(1), |row| {
+        row.iter().fold(T::zero(), |acc, &v| acc + v * v)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use ndarray::Array2;
+
+    fn sample(rows: usize, cols: usize, seed: usize) -> Array2<f64> {
+        Array2::from_shape_fn((rows, cols), |(i, j)| {
+            (((i + seed) * 31 + j * 17) % 23) as f64 / 7.0 - 1.5
+        })
+    }
+
+    #[test]
+    fn test_euclidean_cdist_matches_cdist() {
+        let a = sample(13, 40, 0);
+        let b = sample(9, 40, 5);
+
+        let fast = euclidean_cdist(&a.view(), &b.view());
+        let slow = cdist(&a.view(), &b.view(), &Euclidean);
+        for (f, s) in fast.iter().zip(slow.iter()) {
+            assert!((f - s).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_sqeuclidean_cdist_f32() {
+        let a = sample(5, 8, 1).mapv(|v| v as f32);
+        let b = sample(4, 8, 2).mapv(|v| v as f32);
+
+        let fast = sqeuclidean_cdist(&a.view(), &b.view());
+        let slow = cdist(&a.view(), &b.view(), &Euclidean).mapv(|v| v * v);
+        for (f, s) in fast.iter().zip(slow.iter()) {
+            assert!((f - s).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_identical_rows_clamped() {
+        let a = sample(6, 16, 3).mapv(|v| v * 1e3 + 0.1);
+        let d = euclidean_cdist(&a.view(), &a.view());
+        assert!(d.iter().all(|v| *v >= 0.0 && !v.is_nan()));
+        assert!(d.diag().iter().all(|v| *v < 1e-4));
+    }
+
+    #[test]
+    #[should_panic(expected = "Input matrices must have the same number of columns.")]
+    fn test_dimension_mismatch() {
+        let a = sample(2, 3, 0);
+        let b = sample(2, 4, 0);
+        euclidean_cdist(&a.view(), &b.view());
+    }
+
+    #[test]
+    fn test_cosine_cdist_matches_cdist() {
+        let mut a = sample(11, 24, 4);
+        a.row_mut(3).fill(0.0);
+        let mut b = sample(7, 24, 9);
+        b.row_mut(0).fill(0.0);
+
+        let fast = cosine_cdist(&a.view(), &b.view());
+        let slow = cdist(&a.view(), &b.view(), &Cosine);
+        for (f, s) in fast.iter().zip(slow.iter()) {
+            assert!((f - s).abs() < 1e-12);
+        }
+        assert_eq!(fast[(3, 0)], 0.0);
+        assert_eq!(fast[(3, 1)], 1.0);
+    }
+
+    #[test]
+    fn test_neg_inner_product_cdist_matches_cdist() {
+        let a = sample(6, 10, 2);
+        let b = sample(5, 10, 7);
+
+        let fast = neg_inner_product_cdist(&a.view(), &b.view());
+        let slow = cdist(&a.view(), &b.view(), &NegInnerProduct);
+        for (f, s) in fast.iter().zip(slow.iter()) {
+            assert!((f - s).abs() < 1e-12);
+        }
+    }
+}
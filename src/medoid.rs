@@ -0,0 +1,173 @@
+use ndarray::{Array1, ArrayView1, ArrayView2};
+use num::Float;
+
+use crate::rng::SplitMix64;
+use crate::Distance;
+
+/// Finds the medoid of the rows of `data`: the row minimizing the total distance to all others.
+///
+/// Every unordered pair is evaluated once, for `n * (n - 1) / 2` distance evaluations. Ties are
+/// resolved in favour of the lowest index.
+///
+/// # Panics
+/// Panics if `data` has no rows.
+///
+/// # Example
+/// ```
+/// use ndarray::arr2;
+/// use fast_distances::*;
+///
+/// let data = arr2(&[[0.0], [1.0], [2.0], [10.0]]);
+/// assert_eq!(medoid(&data.view(), &Euclidean), 1);
+/// ```
+pub fn medoid<T, M>(data: &ArrayView2<T>, metric: &M) -> usize
+where
+    T: Float,
+    M: Distance<T> + ?Sized,
+{
+    let n = data.nrows();
+    weighted_medoid(data, &Array1::ones(n).view(), metric)
+}
+
+/// Finds the row of `data` minimizing the weighted total distance `sum_j w_j d(x_i, x_j)`.
+///
+/// # Panics
+/// Panics if `data` has no rows or if `weights` does not have one element per row.
+pub fn weighted_medoid<T, M>(data: &ArrayView2<T>, weights: &ArrayView1<T>, metric: &M) -> usize
+where
+    T: Float,
+    M: Distance<T> + ?Sized,
+{
+    let n = data.nrows();
+    assert!(n > 0, "Data must contain at least one row.");
+    assert_eq!(
+        weights.len(),
+        n,
+        "Weights must have one element per data row."
+    );
+
+    let mut totals = Array1::<T>::zeros(n);
+    for i in 0..n {
+        let row_i = data.row(i);
+        for j in (i + 1)..n {
+            let d = metric.distance(&row_i, &data.row(j));
+            totals[i] = totals[i] + weights[j] * d;
+            totals[j] = totals[j] + weights[i] * d;
+        }
+    }
+
+    argmin(&totals)
+}
+
+/// Approximates the medoid of the rows of `data` by measuring every row against a random sample.
+///
+/// The total distance of each row is estimated from `sample_size` reference rows drawn
+/// uniformly (with replacement) using a generator seeded with `seed`, which reduces the cost to
+/// `n * sample_size` distance evaluations. With `sample_size >= n` the exact [`medoid`] is
+/// returned instead.
+///
+/// # Panics
+/// Panics if `data` has no rows or if `sample_size` is zero.
+pub fn approximate_medoid<T, M>(
+    data: &ArrayView2<T>,
+    metric: &M,
+    sample_size: usize,
+    seed: u64,
+) -> usize
+where
+    T: Float,
+    M: Distance<T> + ?Sized,
+{
+    let n = data.nrows();
+    assert!(n > 0, "Data must contain at least one row.");
+    assert!(sample_size > 0, "Sample size must be positive.");
+    if sample_size >= n {
+        return medoid(data, metric);
+    }
+
+    let mut rng = SplitMix64::new(seed);
+    let sample: Vec<usize> = (0..sample_size).map(|_| rng.next_below(n)).collect();
+
+    let totals: Array1<T> = data
+        .rows()
+        .into_iter()
+        .map(|row| {
+            sample.iter().fold(T::zero(), |acc, &j| {
+                acc + metric.distance(&row, &data.row(j))
+            })
+        })
+        .collect();
+
+    argmin(&totals)
+}
+
+/// Index of the smallest non-NaN total, or 0 if every total is NaN.
+fn argmin<T: Float>(totals: &Array1<T>) -> usize {
+    let mut best = 0;
+    for (i, &total) in totals.iter().enumerate() {
+        if total < totals[best] || (totals[best].is_nan() && !total.is_nan()) {
+            best = i;
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use ndarray::{arr1, arr2, Array2};
+
+    #[test]
+    fn test_medoid_matches_cdist_row_sums() {
+        let data = Array2::from_shape_fn((9, 2), |(i, j)| ((i * 5 + j * 3) % 7) as f64);
+        let sums = cdist(&data.view(), &data.view(), &Manhattan).sum_axis(ndarray::Axis(1));
+
+        let expected = (0..9)
+            .min_by(|&a, &b| sums[a].partial_cmp(&sums[b]).unwrap())
+            .unwrap();
+        assert_eq!(medoid(&data.view(), &Manhattan), expected);
+    }
+
+    #[test]
+    fn test_weighted_medoid() {
+        let data = arr2(&[[0.0], [1.0], [2.0], [10.0]]);
+        let weights = arr1(&[1.0, 1.0, 1.0, 100.0]);
+
+        assert_eq!(
+            weighted_medoid(&data.view(), &weights.view(), &Euclidean),
+            3
+        );
+    }
+
+    #[test]
+    fn test_medoid_single_row() {
+        let data = arr2(&[[3.0, 4.0]]);
+        assert_eq!(medoid(&data.view(), &Euclidean), 0);
+    }
+
+    #[test]
+    fn test_approximate_medoid() {
+        let mut data = Array2::<f64>::zeros((50, 1));
+        for i in 0..50 {
+            data[(i, 0)] = i as f64;
+        }
+        data[(49, 0)] = 1000.0;
+
+        let approx = approximate_medoid(&data.view(), &Euclidean, 20, 3);
+        assert!((15..35).contains(&approx));
+        assert_eq!(approximate_medoid(&data.view(), &Euclidean, 20, 3), approx);
+        assert_eq!(
+            approximate_medoid(&data.view(), &Euclidean, 50, 3),
+            medoid(&data.view(), &Euclidean)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Weights must have one element per data row.")]
+    fn test_weighted_medoid_wrong_weights() {
+        let data = arr2(&[[0.0], [1.0]]);
+        let weights = arr1(&[1.0]);
+        weighted_medoid(&data.view(), &weights.view(), &Euclidean);
+    }
+}
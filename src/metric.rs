@@ -0,0 +1,1493 @@
+use std::iter::Sum;
+
+use ndarray::{Array1, Array2, ArrayView1, ArrayView2};
+use num::Float;
+
+use crate::distances::*;
+#[cfg(feature = "validate")]
+use crate::validate::*;
+use crate::DistanceError;
+
+/// A plain function pointer computing the distance between two vectors.
+///
+/// Every non-parameterized built-in metric can be turned into a `DistanceFn` through its
+/// `as_fn()` accessor, e.g. `Euclidean.as_fn::<f64>()`.
+pub type DistanceFn<T> = fn(&ArrayView1<T>, &ArrayView1<T>) -> T;
+
+/// A distance (or dissimilarity) between two vectors of type `T`.
+///
+/// This is the common interface accepted by the generic APIs of this crate. It is implemented
+/// by the built-in metric types below, by [`DistanceFn`] pointers, and by any closure wrapped
+/// with [`from_fn`].
+pub trait Distance<T> {
+    /// Computes the distance between `x` and `y`.
+    fn distance(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> T;
+
+    /// Computes the distance between `x` and `y` if it is at most `upper`.
+    ///
+    /// Returns `None` when the distance exceeds `upper` (or is NaN). The default implementation
+    /// computes the full distance; metrics with an early-abandoning kernel, such as
+    /// [`euclidean_bounded`], override it so that searches can stop accumulating once a
+    /// candidate is known to be worse than the current best.
+    fn distance_bounded(&self, x: &ArrayView1<T>, y: &ArrayView1<T>, upper: T) -> Option<T>
+    where
+        T: PartialOrd,
+    {
+        let d = self.distance(x, y);
+        if d <= upper {
+            Some(d)
+        } else {
+            None
+        }
+    }
+
+    /// Computes the distances between every row of `a` and every row of `b` at once, if this
+    /// metric has a kernel that is faster than evaluating the pairs one by one.
+    ///
+    /// The default implementation returns `None`, in which case callers such as
+    /// [`cdist`](crate::cdist) and [`k_nearest_batch`](crate::k_nearest_batch) fall back to
+    /// [`Distance::distance`].
+    /// [`Euclidean`] and [`Cosine`] return the matrix-multiplication results of
+    /// [`euclidean_cdist`](crate::euclidean_cdist) and [`cosine_cdist`](crate::cosine_cdist),
+    /// which reuse the row norms across the whole block but are only accurate up to rounding.
+    fn block_distances(&self, a: &ArrayView2<T>, b: &ArrayView2<T>) -> Option<Array2<T>>
+    where
+        T: Float + 'static,
+    {
+        let _ = (a, b);
+        None
+    }
+
+    /// Checks that `x` and `y` are valid inputs for this metric.
+    ///
+    /// The default implementation only checks that both inputs have the same length; the
+    /// built-in metrics additionally reject non-finite values and inputs outside their domain.
+    #[cfg(feature = "validate")]
+    fn validate(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> Result<(), DistanceError> {
+        check_same_length(x, y)
+    }
+
+    /// Validates the inputs with [`Distance::validate`] and computes the distance.
+    #[cfg(feature = "validate")]
+    fn try_distance(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> Result<T, DistanceError> {
+        self.validate(x, y)?;
+        Ok(self.distance(x, y))
+    }
+}
+
+impl<T> Distance<T> for DistanceFn<T> {
+    fn distance(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> T {
+        self(x, y)
+    }
+}
+
+impl<T, M> Distance<T> for &M
+where
+    M: Distance<T> + ?Sized,
+{
+    fn distance(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> T {
+        (**self).distance(x, y)
+    }
+
+    fn distance_bounded(&self, x: &ArrayView1<T>, y: &ArrayView1<T>, upper: T) -> Option<T>
+    where
+        T: PartialOrd,
+    {
+        (**self).distance_bounded(x, y, upper)
+    }
+
+    fn block_distances(&self, a: &ArrayView2<T>, b: &ArrayView2<T>) -> Option<Array2<T>>
+    where
+        T: Float + 'static,
+    {
+        (**self).block_distances(a, b)
+    }
+
+    #[cfg(feature = "validate")]
+    fn validate(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> Result<(), DistanceError> {
+        (**self).validate(x, y)
+    }
+}
+
+impl<T, M> Distance<T> for Box<M>
+where
+    M: Distance<T> + ?Sized,
+{
+    fn distance(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> T {
+        (**self).distance(x, y)
+    }
+
+    fn distance_bounded(&self, x: &ArrayView1<T>, y: &ArrayView1<T>, upper: T) -> Option<T>
+    where
+        T: PartialOrd,
+    {
+        (**self).distance_bounded(x, y, upper)
+    }
+
+    fn block_distances(&self, a: &ArrayView2<T>, b: &ArrayView2<T>) -> Option<Array2<T>>
+    where
+        T: Float + 'static,
+    {
+        (**self).block_distances(a, b)
+    }
+
+    #[cfg(feature = "validate")]
+    fn validate(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> Result<(), DistanceError> {
+        (**self).validate(x, y)
+    }
+}
+
+/// Adapter turning an arbitrary closure into a [`Distance`].
+///
+/// Built with [`from_fn`].
+#[derive(Debug, Clone, Copy)]
+pub struct FnDistance<F>(pub F);
+
+impl<T, F> Distance<T> for FnDistance<F>
+where
+    F: Fn(&ArrayView1<T>, &ArrayView1<T>) -> T,
+{
+    fn distance(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> T {
+        (self.0)(x, y)
+    }
+}
+
+/// Wraps a user-defined closure so it can be used wherever a [`Distance`] is expected.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let scale = 2.0;
+/// let scaled = from_fn(move |x: &ndarray::ArrayView1<f64>, y: &ndarray::ArrayView1<f64>| {
+///     scale * euclidean(x, y)
+/// });
+///
+/// let x = arr1(&[0.0, 0.0]);
+/// let y = arr1(&[3.0, 4.0]);
+/// assert_eq!(scaled.distance(&x.view(), &y.view()), 10.0);
+/// ```
+pub fn from_fn<T, F>(f: F) -> FnDistance<F>
+where
+    F: Fn(&ArrayView1<T>, &ArrayView1<T>) -> T,
+{
+    FnDistance(f)
+}
+
+macro_rules! builtin_metric {
+    ($(#[$doc:meta])* $name:ident, $func:expr) => {
+        builtin_metric!($(#[$doc])* $name, $func, check_pair);
+    };
+    ($(#[$doc:meta])* $name:ident, $func:expr, $validate:expr $(, $bounded:expr)? $(; $block:expr)?) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+        pub struct $name;
+
+        impl $name {
+            /// Returns this metric as a plain [`DistanceFn`].
+            pub fn as_fn<T>(&self) -> DistanceFn<T>
+            where
+                T: Float + Sum,
+            {
+                $func
+            }
+        }
+
+        impl<T> Distance<T> for $name
+        where
+            T: Float + Sum,
+        {
+            fn distance(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> T {
+                (self.as_fn())(x, y)
+            }
+
+            $(
+                fn distance_bounded(
+                    &self,
+                    x: &ArrayView1<T>,
+                    y: &ArrayView1<T>,
+                    upper: T,
+                ) -> Option<T> {
+                    $bounded(x, y, upper)
+                }
+            )?
+
+            $(
+                fn block_distances(
+                    &self,
+                    a: &ArrayView2<T>,
+                    b: &ArrayView2<T>,
+                ) -> Option<Array2<T>>
+                where
+                    T: Float + 'static,
+                {
+                    Some($block(a, b))
+                }
+            )?
+
+            #[cfg(feature = "validate")]
+            fn validate(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> Result<(), DistanceError> {
+                $validate(x, y)
+            }
+        }
+    };
+}
+
+builtin_metric!(
+    /// The [`euclidean`] distance.
+    Euclidean,
+    euclidean,
+    check_pair,
+    euclidean_bounded;
+    crate::euclidean_cdist
+);
+builtin_metric!(
+    /// The [`euclidean_stable`] distance, which avoids overflow and underflow for extreme
+    /// magnitudes. Pass it instead of [`Euclidean`] to the bulk APIs such as
+    /// [`cdist`](crate::cdist) to use the scaled kernel.
+    EuclideanStable,
+    euclidean_stable
+);
+builtin_metric!(
+    /// The [`manhattan`] distance.
+    Manhattan,
+    manhattan,
+    check_pair,
+    manhattan_bounded
+);
+builtin_metric!(
+    /// The [`chebyshev`] distance.
+    Chebyshev,
+    chebyshev
+);
+builtin_metric!(
+    /// The [`lorentzian`] distance.
+    Lorentzian,
+    lorentzian
+);
+builtin_metric!(
+    /// The [`average_distance`], the mean of the per-coordinate city block and Chebyshev
+    /// distances.
+    AverageDistance,
+    average_distance
+);
+builtin_metric!(
+    /// The [`canberra`] distance.
+    Canberra,
+    canberra
+);
+builtin_metric!(
+    /// The [`clark`] distance.
+    Clark,
+    clark
+);
+builtin_metric!(
+    /// The [`divergence`] distance.
+    Divergence,
+    divergence
+);
+builtin_metric!(
+    /// The [`canberra_normalized`] distance, averaged over the non-zero coordinate pairs.
+    CanberraNormalized,
+    canberra_normalized
+);
+builtin_metric!(
+    /// The [`cosine`] distance.
+    Cosine,
+    cosine,
+    check_pair;
+    crate::cosine_cdist
+);
+builtin_metric!(
+    /// The [`angular`] distance.
+    Angular,
+    angular
+);
+builtin_metric!(
+    /// The [`correlation`] distance.
+    Correlation,
+    correlation
+);
+builtin_metric!(
+    /// The [`cross_correlation_distance`] between rows treated as series.
+    CrossCorrelation,
+    cross_correlation_distance
+);
+builtin_metric!(
+    /// The [`dtw`] (dynamic time warping) distance between rows treated as sequences.
+    Dtw,
+    dtw,
+    check_sequence_pair,
+    dtw_bounded
+);
+builtin_metric!(
+    /// The [`min_symmetric_chi_square`] divergence.
+    MinSymmetricChiSquare,
+    min_symmetric_chi_square,
+    check_non_negative_pair
+);
+builtin_metric!(
+    /// The [`hellinger`] distance.
+    Hellinger,
+    hellinger,
+    check_probability_pair
+);
+builtin_metric!(
+    /// The [`squared_chord`] distance.
+    SquaredChord,
+    squared_chord,
+    check_probability_pair
+);
+builtin_metric!(
+    /// The [`bhattacharyya`] distance.
+    Bhattacharyya,
+    bhattacharyya,
+    check_probability_pair
+);
+builtin_metric!(
+    /// The [`kl_divergence`] of the second input from the first. It is not symmetric, so it is
+    /// not a metric, but it can be used wherever a [`Distance`] is expected.
+    KlDivergence,
+    kl_divergence,
+    check_probability_pair
+);
+builtin_metric!(
+    /// The [`jensen_shannon`] distance.
+    JensenShannon,
+    jensen_shannon,
+    check_probability_pair
+);
+builtin_metric!(
+    /// The [`wasserstein_1d`] distance between histograms over unit-spaced bins.
+    Wasserstein1d,
+    wasserstein_1d,
+    check_probability_pair
+);
+builtin_metric!(
+    /// The [`haversine`] distance.
+    Haversine,
+    haversine,
+    check_haversine_pair
+);
+builtin_metric!(
+    /// The [`kendall_tau`] rank dissimilarity.
+    KendallTau,
+    kendall_tau
+);
+builtin_metric!(
+    /// The [`quantitative_sorensen`] dissimilarity between nonnegative vectors.
+    QuantitativeSorensen,
+    quantitative_sorensen,
+    check_non_negative_pair
+);
+builtin_metric!(
+    /// The generalized [`tanimoto`] distance.
+    Tanimoto,
+    tanimoto
+);
+builtin_metric!(
+    /// The [`weighted_jaccard`] (Ružička) distance between nonnegative vectors.
+    WeightedJaccard,
+    weighted_jaccard,
+    check_non_negative_pair
+);
+builtin_metric!(
+    /// The [`true_angular`] distance, the same quantity as [`Angular`].
+    TrueAngular,
+    true_angular
+);
+builtin_metric!(
+    /// The [`tsss`] dissimilarity.
+    Tsss,
+    tsss
+);
+builtin_metric!(
+    /// The [`yule`] dissimilarity.
+    Yule,
+    yule
+);
+builtin_metric!(
+    /// The [`yule_scipy`] dissimilarity.
+    YuleScipy,
+    yule_scipy
+);
+builtin_metric!(
+    /// The [`sokal_michener`] dissimilarity.
+    SokalMichener,
+    sokal_michener
+);
+builtin_metric!(
+    /// The normalized [`hamming`] distance, computed in `T` rather than through `f64`.
+    Hamming,
+    |x, y| T::from(hamming_count(x, y)).unwrap() / T::from(x.len()).unwrap()
+);
+builtin_metric!(
+    /// The [`kulczynski`] distance.
+    Kulczynski,
+    kulczynski,
+    check_non_negative_pair
+);
+builtin_metric!(
+    /// The [`motyka`] dissimilarity.
+    Motyka,
+    motyka,
+    check_non_negative_pair
+);
+builtin_metric!(
+    /// The [`soft_dice`] distance.
+    SoftDice,
+    soft_dice
+);
+builtin_metric!(
+    /// The [`soergel`] distance.
+    Soergel,
+    soergel,
+    check_non_negative_pair
+);
+builtin_metric!(
+    /// The [`wave_hedges`] distance.
+    WaveHedges,
+    wave_hedges,
+    check_non_negative_pair
+);
+builtin_metric!(
+    /// The [`nan_cosine`] distance, which skips missing (NaN) coordinates.
+    NanCosine,
+    nan_cosine,
+    check_same_length
+);
+builtin_metric!(
+    /// The [`nan_euclidean`] distance, which skips missing (NaN) coordinates.
+    NanEuclidean,
+    nan_euclidean,
+    check_same_length
+);
+builtin_metric!(
+    /// The [`nan_manhattan`] distance, which skips missing (NaN) coordinates.
+    NanManhattan,
+    nan_manhattan,
+    check_same_length
+);
+builtin_metric!(
+    /// The [`neg_inner_product`], for maximum-inner-product search.
+    NegInnerProduct,
+    neg_inner_product,
+    check_pair;
+    crate::neg_inner_product_cdist
+);
+builtin_metric!(
+    /// The [`poincare`] distance.
+    Poincare,
+    |x, y| poincare(&x.to_owned(), &y.to_owned()),
+    check_poincare_pair
+);
+builtin_metric!(
+    /// The [`hyperboloid`] distance between spatial coordinates.
+    Hyperboloid,
+    hyperboloid
+);
+builtin_metric!(
+    /// The [`ll_dirichlet`] distance.
+    LlDirichlet,
+    |x, y| ll_dirichlet(&x.to_vec(), &y.to_vec()),
+    check_probability_pair
+);
+
+#[cfg(feature = "validate")]
+fn check_probability_pair<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> Result<(), DistanceError>
+where
+    T: Float,
+{
+    check_same_length(x, y)?;
+    check_probability(x, "x")?;
+    check_probability(y, "y")
+}
+
+#[cfg(feature = "validate")]
+fn check_non_negative_pair<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> Result<(), DistanceError>
+where
+    T: Float,
+{
+    check_pair(x, y)?;
+    check_non_negative(x, "x")?;
+    check_non_negative(y, "y")
+}
+
+#[cfg(feature = "validate")]
+fn check_haversine_pair<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> Result<(), DistanceError>
+where
+    T: Float,
+{
+    check_length(x, 2)?;
+    check_length(y, 2)?;
+    check_pair(x, y)
+}
+
+#[cfg(feature = "validate")]
+fn check_poincare_pair<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> Result<(), DistanceError>
+where
+    T: Float + Sum,
+{
+    check_pair(x, y)?;
+    check_in_unit_ball(x, "x")?;
+    check_in_unit_ball(y, "y")
+}
+
+#[cfg(feature = "validate")]
+fn check_in_unit_ball<T>(v: &ArrayView1<T>, argument: &'static str) -> Result<(), DistanceError>
+where
+    T: Float + Sum,
+{
+    if v.iter().map(|&vi| vi * vi).sum::<T>() >= T::one() {
+        return Err(DistanceError::InvalidInput(format!(
+            "`{}` must lie strictly inside the unit ball",
+            argument
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "validate")]
+fn check_order<T>(p: T) -> Result<(), DistanceError>
+where
+    T: Float,
+{
+    if !(p.is_finite() && p > T::zero()) {
+        return Err(DistanceError::InvalidParameter(
+            "the order `p` must be finite and positive".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+builtin_metric!(
+    /// The [`bray_curtis`] dissimilarity.
+    BrayCurtis,
+    bray_curtis
+);
+builtin_metric!(
+    /// The [`dice`] dissimilarity.
+    Dice,
+    dice
+);
+builtin_metric!(
+    /// The [`jaccard`] distance.
+    Jaccard,
+    jaccard
+);
+builtin_metric!(
+    /// The [`kulsinski`] dissimilarity.
+    Kulsinski,
+    kulsinski
+);
+builtin_metric!(
+    /// The [`matching`] dissimilarity.
+    Matching,
+    matching
+);
+builtin_metric!(
+    /// The [`rogers_tanimoto`] dissimilarity.
+    RogersTanimoto,
+    rogers_tanimoto
+);
+builtin_metric!(
+    /// The [`russell_rao`] dissimilarity.
+    RussellRao,
+    russell_rao
+);
+builtin_metric!(
+    /// The [`sokal_sneath`] dissimilarity.
+    SokalSneath,
+    sokal_sneath
+);
+
+/// The [`minkowski`] distance of order `p`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Minkowski<T> {
+    pub p: T,
+}
+
+impl<T> Distance<T> for Minkowski<T>
+where
+    T: Float,
+{
+    fn distance(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> T {
+        minkowski(x, y, self.p)
+    }
+
+    fn distance_bounded(&self, x: &ArrayView1<T>, y: &ArrayView1<T>, upper: T) -> Option<T> {
+        minkowski_bounded(x, y, self.p, upper)
+    }
+
+    #[cfg(feature = "validate")]
+    fn validate(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> Result<(), DistanceError> {
+        check_order(self.p)?;
+        check_pair(x, y)
+    }
+}
+
+/// The [`shifted_inner_product`] with offset `shift`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShiftedInnerProduct<T> {
+    pub shift: T,
+}
+
+impl<T> Distance<T> for ShiftedInnerProduct<T>
+where
+    T: Float,
+{
+    fn distance(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> T {
+        shifted_inner_product(x, y, self.shift)
+    }
+
+    #[cfg(feature = "validate")]
+    fn validate(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> Result<(), DistanceError> {
+        check_pair(x, y)
+    }
+}
+
+/// The [`edr`] distance with matching tolerance `epsilon` between rows treated as sequences.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Edr<T> {
+    pub epsilon: T,
+}
+
+impl<T> Distance<T> for Edr<T>
+where
+    T: Float,
+{
+    fn distance(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> T {
+        edr(x, y, self.epsilon)
+    }
+
+    #[cfg(feature = "validate")]
+    fn validate(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> Result<(), DistanceError> {
+        check_finite_pair(x, y)
+    }
+}
+
+/// The [`lcss`] distance with value tolerance `epsilon` and position tolerance `delta` between
+/// rows treated as sequences.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lcss<T> {
+    pub epsilon: T,
+    pub delta: usize,
+}
+
+impl<T> Distance<T> for Lcss<T>
+where
+    T: Float,
+{
+    fn distance(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> T {
+        lcss(x, y, self.epsilon, self.delta)
+    }
+
+    #[cfg(feature = "validate")]
+    fn validate(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> Result<(), DistanceError> {
+        check_finite_pair(x, y)
+    }
+}
+
+/// The [`circular_kantorovich`] distance of order `p` between periodic histograms.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CircularKantorovich<T> {
+    pub p: T,
+}
+
+impl<T> Distance<T> for CircularKantorovich<T>
+where
+    T: Float,
+{
+    fn distance(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> T {
+        circular_kantorovich(x, y, self.p)
+    }
+
+    #[cfg(feature = "validate")]
+    fn validate(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> Result<(), DistanceError> {
+        if !(self.p == T::one() || self.p >= T::one() + T::one()) {
+            return Err(DistanceError::InvalidParameter(
+                "the order `p` must be 1 or at least 2".to_string(),
+            ));
+        }
+        check_probability_pair(x, y)
+    }
+}
+
+/// The [`renyi_divergence`] of order `alpha` of the second input from the first.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Renyi<T> {
+    pub alpha: T,
+}
+
+impl<T> Distance<T> for Renyi<T>
+where
+    T: Float,
+{
+    fn distance(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> T {
+        renyi_divergence(x, y, self.alpha)
+    }
+
+    #[cfg(feature = "validate")]
+    fn validate(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> Result<(), DistanceError> {
+        if self.alpha.is_nan() || self.alpha < T::zero() {
+            return Err(DistanceError::InvalidParameter(
+                "the order `alpha` must be non-negative".to_string(),
+            ));
+        }
+        check_probability_pair(x, y)
+    }
+}
+
+/// The [`weighted_minkowski`] distance of order `p` with optional per-coordinate weights.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightedMinkowski<T> {
+    pub w: Option<Array1<T>>,
+    pub p: T,
+}
+
+impl<T> Distance<T> for WeightedMinkowski<T>
+where
+    T: Float,
+{
+    fn distance(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> T {
+        weighted_minkowski(&x.to_owned(), &y.to_owned(), self.w.as_ref(), self.p)
+    }
+
+    #[cfg(feature = "validate")]
+    fn validate(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> Result<(), DistanceError> {
+        check_order(self.p)?;
+        check_pair(x, y)?;
+        if let Some(w) = &self.w {
+            check_length(&w.view(), x.len())?;
+            check_finite(&w.view(), "w")?;
+            check_non_negative(&w.view(), "w")?;
+        }
+        Ok(())
+    }
+}
+
+/// The [`adjusted_cosine`] distance for fixed per-dimension means.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdjustedCosine<T> {
+    pub means: Array1<T>,
+}
+
+impl<T> AdjustedCosine<T>
+where
+    T: Float,
+{
+    /// Creates the metric with the column means of `data`, so that distances between rows of
+    /// new data are measured after removing the average of each dimension.
+    ///
+    /// # Panics
+    /// Panics if `data` has no rows.
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::{arr1, arr2};
+    /// use fast_distances::*;
+    ///
+    /// let data = arr2(&[[5.0f64, 1.0], [3.0, 3.0]]);
+    /// let metric = AdjustedCosine::fit(&data.view());
+    /// assert_eq!(metric.means, arr1(&[4.0, 2.0]));
+    /// // The centered rows point in opposite directions.
+    /// assert!((metric.distance(&data.row(0), &data.row(1)) - 2.0).abs() < 1e-12);
+    /// ```
+    pub fn fit(data: &ArrayView2<T>) -> Self {
+        assert!(data.nrows() > 0, "Data must have at least one row.");
+        let rows = T::from(data.nrows()).unwrap();
+        let means = data.map_axis(ndarray::Axis(0), |column| {
+            column.fold(T::zero(), |acc, &v| acc + v) / rows
+        });
+        Self { means }
+    }
+}
+
+impl<T> Distance<T> for AdjustedCosine<T>
+where
+    T: Float,
+{
+    fn distance(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> T {
+        adjusted_cosine(x, y, &self.means.view())
+    }
+
+    #[cfg(feature = "validate")]
+    fn validate(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> Result<(), DistanceError> {
+        check_pair(x, y)?;
+        check_length(&self.means.view(), x.len())?;
+        check_finite(&self.means.view(), "means")
+    }
+}
+
+/// The [`hierarchical_categorical`] distance between taxonomy paths with per-level weights.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HierarchicalCategorical<T> {
+    pub level_weights: Array1<T>,
+}
+
+impl<T> Distance<T> for HierarchicalCategorical<T>
+where
+    T: Float,
+{
+    fn distance(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> T {
+        hierarchical_categorical(x, y, &self.level_weights.view())
+    }
+
+    #[cfg(feature = "validate")]
+    fn validate(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> Result<(), DistanceError> {
+        check_pair(x, y)?;
+        check_length(&self.level_weights.view(), x.len())?;
+        check_finite(&self.level_weights.view(), "level_weights")?;
+        check_non_negative(&self.level_weights.view(), "level_weights")
+    }
+}
+
+/// The [`standardised_euclidean`] distance for fixed per-coordinate standard deviations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StandardisedEuclidean<T> {
+    pub sigma: Array1<T>,
+}
+
+impl<T> Distance<T> for StandardisedEuclidean<T>
+where
+    T: Float,
+{
+    fn distance(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> T {
+        standardised_euclidean_with_sigma(x, y, &self.sigma.view())
+    }
+
+    #[cfg(feature = "validate")]
+    fn validate(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> Result<(), DistanceError> {
+        check_pair(x, y)?;
+        check_length(&self.sigma.view(), x.len())?;
+        check_finite(&self.sigma.view(), "sigma")?;
+        if self.sigma.iter().any(|&s| s <= T::zero()) {
+            return Err(DistanceError::InvalidParameter(
+                "`sigma` must be strictly positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The [`mahalanobis`] distance for a fixed inverse covariance matrix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mahalanobis<T> {
+    pub vinv: Array2<T>,
+}
+
+impl<T> Distance<T> for Mahalanobis<T>
+where
+    T: Float,
+{
+    fn distance(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> T {
+        mahalanobis_with_vinv(x, y, &self.vinv.view())
+    }
+
+    #[cfg(feature = "validate")]
+    fn validate(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> Result<(), DistanceError> {
+        check_pair(x, y)?;
+        if self.vinv.dim() != (x.len(), x.len()) {
+            return Err(DistanceError::InvalidParameter(format!(
+                "`vinv` must be a {0}x{0} matrix, found {1}x{2}",
+                x.len(),
+                self.vinv.nrows(),
+                self.vinv.ncols()
+            )));
+        }
+        if self.vinv.iter().any(|v| !v.is_finite()) {
+            return Err(DistanceError::InvalidParameter(
+                "`vinv` contains non-finite values".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The [`mahalanobis_cholesky`] distance for a fixed Cholesky factor of the covariance matrix.
+///
+/// Whitening `x - y` with the factor `L` (where the covariance is `L · Lᵀ`) is better
+/// conditioned than multiplying by an explicitly inverted covariance, as [`Mahalanobis`] does.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MahalanobisCholesky<T> {
+    pub l: Array2<T>,
+}
+
+impl<T> MahalanobisCholesky<T> {
+    /// Creates the metric from the lower-triangular Cholesky factor `l` of the covariance matrix.
+    pub fn from_cholesky(l: Array2<T>) -> Self {
+        Self { l }
+    }
+}
+
+impl<T> MahalanobisCholesky<T>
+where
+    T: Float + 'static,
+{
+    /// Creates the metric from a covariance matrix by factorizing it once.
+    ///
+    /// Only the lower triangle of `cov` is read.
+    ///
+    /// # Errors
+    /// Returns [`DistanceError::InvalidInput`] if `cov` is not square or not positive definite.
+    pub fn from_covariance(cov: &ArrayView2<T>) -> Result<Self, DistanceError> {
+        if cov.nrows() != cov.ncols() {
+            return Err(DistanceError::InvalidInput(format!(
+                "the covariance must be a square matrix, found {}x{}",
+                cov.nrows(),
+                cov.ncols()
+            )));
+        }
+        cholesky(cov).map(Self::from_cholesky).ok_or_else(|| {
+            DistanceError::InvalidInput("the covariance is not positive definite".to_string())
+        })
+    }
+
+    /// Estimates the covariance of the rows of `data` and factorizes it once, so the metric can
+    /// compare many pairs of points drawn from the same distribution.
+    ///
+    /// Equivalent to [`fit_with_shrinkage`](Self::fit_with_shrinkage) with no shrinkage.
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::{arr1, arr2};
+    /// use fast_distances::*;
+    ///
+    /// let data = arr2(&[[0.0f64, 0.0], [2.0, 1.0], [4.0, 1.0], [2.0, 2.0]]);
+    /// let metric = MahalanobisCholesky::fit(&data.view()).unwrap();
+    /// let (x, y) = (arr1(&[1.0, 1.0]), arr1(&[3.0, 1.0]));
+    /// // The sample covariance is [[8/3, 2/3], [2/3, 2/3]].
+    /// let cov = arr2(&[[8.0 / 3.0, 2.0 / 3.0], [2.0 / 3.0, 2.0 / 3.0]]);
+    /// let expected = mahalanobis_from_cov(&x.view(), &y.view(), &cov.view());
+    /// assert!((metric.distance(&x.view(), &y.view()) - expected).abs() < 1e-12);
+    /// ```
+    pub fn fit(data: &ArrayView2<T>) -> Result<Self, DistanceError> {
+        Self::fit_with_shrinkage(data, T::zero())
+    }
+
+    /// Estimates the covariance `S` of the rows of `data`, shrinks it towards a scaled identity,
+    /// and factorizes the result once.
+    ///
+    /// The shrunk covariance is `(1 - shrinkage) · S + shrinkage · (tr(S) / d) · I`, which keeps
+    /// the total variance while pulling the eigenvalues towards their mean. Any positive
+    /// shrinkage makes the estimate positive definite when the data varies at all, which helps
+    /// when there are fewer points than dimensions or the coordinates are collinear.
+    ///
+    /// # Errors
+    /// Returns [`DistanceError::InvalidParameter`] if `shrinkage` is not in `[0, 1]`, and
+    /// [`DistanceError::InvalidInput`] if `data` has fewer than two rows, no columns, non-finite
+    /// values, or a covariance that is not positive definite.
+    pub fn fit_with_shrinkage(data: &ArrayView2<T>, shrinkage: T) -> Result<Self, DistanceError> {
+        if shrinkage.is_nan() || shrinkage < T::zero() || shrinkage > T::one() {
+            return Err(DistanceError::InvalidParameter(
+                "the shrinkage must be in [0, 1]".to_string(),
+            ));
+        }
+        if data.nrows() < 2 || data.ncols() == 0 {
+            return Err(DistanceError::InvalidInput(
+                "estimating a covariance needs at least two rows and one column".to_string(),
+            ));
+        }
+        if data.iter().any(|v| !v.is_finite()) {
+            return Err(DistanceError::InvalidInput(
+                "the data contains non-finite values".to_string(),
+            ));
+        }
+        Self::from_covariance(&shrunk_covariance(data, shrinkage).view())
+    }
+}
+
+impl<T> Distance<T> for MahalanobisCholesky<T>
+where
+    T: Float,
+{
+    fn distance(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> T {
+        mahalanobis_cholesky(x, y, &self.l.view())
+    }
+
+    #[cfg(feature = "validate")]
+    fn validate(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> Result<(), DistanceError> {
+        check_pair(x, y)?;
+        if self.l.dim() != (x.len(), x.len()) {
+            return Err(DistanceError::InvalidParameter(format!(
+                "`l` must be a {0}x{0} matrix, found {1}x{2}",
+                x.len(),
+                self.l.nrows(),
+                self.l.ncols()
+            )));
+        }
+        if self.l.iter().any(|v| !v.is_finite()) {
+            return Err(DistanceError::InvalidParameter(
+                "`l` contains non-finite values".to_string(),
+            ));
+        }
+        if self.l.diag().iter().any(|v| v.is_zero()) {
+            return Err(DistanceError::InvalidParameter(
+                "`l` must have a non-zero diagonal".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Wraps a metric on probability vectors so that it L1-normalizes both inputs first.
+///
+/// [`Hellinger`], [`JensenShannon`] and [`KlDivergence`] assume that their inputs sum to one,
+/// but count data such as histograms or term frequencies rarely do. `Normalized` rescales each
+/// input by its sum before calling the wrapped metric, so counts can be passed directly. An
+/// input that sums to zero is passed through unchanged, so the wrapped metric's zero-vector
+/// semantics apply (for example, [`hellinger`] is `1` when exactly one input is zero).
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let counts = arr1(&[2.0, 6.0, 2.0]);
+/// let probabilities = arr1(&[0.2, 0.6, 0.2]);
+/// let other = arr1(&[0.5, 0.25, 0.25]);
+///
+/// let metric = Normalized(JensenShannon);
+/// assert_eq!(
+///     metric.distance(&counts.view(), &other.view()),
+///     jensen_shannon(&probabilities.view(), &other.view())
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Normalized<M>(pub M);
+
+impl<M> Normalized<M> {
+    fn normalize<T: Float>(x: &ArrayView1<T>) -> Array1<T> {
+        let total = x.iter().fold(T::zero(), |acc, &v| acc + v);
+        if total.is_zero() {
+            x.to_owned()
+        } else {
+            x.mapv(|v| v / total)
+        }
+    }
+}
+
+impl<T, M> Distance<T> for Normalized<M>
+where
+    T: Float,
+    M: Distance<T>,
+{
+    fn distance(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> T {
+        let (x, y) = (Self::normalize(x), Self::normalize(y));
+        self.0.distance(&x.view(), &y.view())
+    }
+
+    /// Checks that both inputs are finite and non-negative, then applies the wrapped metric's
+    /// checks to the normalized inputs.
+    #[cfg(feature = "validate")]
+    fn validate(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> Result<(), DistanceError> {
+        check_probability_pair(x, y)?;
+        let (x, y) = (Self::normalize(x), Self::normalize(y));
+        self.0.validate(&x.view(), &y.view())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{arr1, arr2};
+
+    #[test]
+    fn test_builtin_metrics_match_functions() {
+        let x = arr1(&[1.0, 2.0, 3.0]);
+        let y = arr1(&[4.0, 5.0, 7.0]);
+
+        assert_eq!(
+            Euclidean.distance(&x.view(), &y.view()),
+            euclidean(&x.view(), &y.view())
+        );
+        assert_eq!(
+            Cosine.distance(&x.view(), &y.view()),
+            cosine(&x.view(), &y.view())
+        );
+        assert_eq!(
+            Angular.distance(&x.view(), &y.view()),
+            angular(&x.view(), &y.view())
+        );
+        assert_eq!(
+            YuleScipy.distance(&x.view(), &y.view()),
+            yule_scipy(&x.view(), &y.view())
+        );
+        let (a, b) = (arr1(&[1.0f32, 0.0, 2.0]), arr1(&[1.0f32, 1.0, 3.0]));
+        assert_eq!(
+            Hamming.distance(&a.view(), &b.view()),
+            hamming(&a.view(), &b.view()) as f32
+        );
+        assert_eq!(
+            BrayCurtis.distance(&x.view(), &y.view()),
+            bray_curtis(&x.view(), &y.view())
+        );
+        assert_eq!(
+            CanberraNormalized.distance(&x.view(), &y.view()),
+            canberra_normalized(&x.view(), &y.view())
+        );
+        assert_eq!(
+            Clark.distance(&x.view(), &y.view()),
+            clark(&x.view(), &y.view())
+        );
+        assert_eq!(
+            Divergence.distance(&x.view(), &y.view()),
+            divergence(&x.view(), &y.view())
+        );
+        assert_eq!(
+            AverageDistance.distance(&x.view(), &y.view()),
+            average_distance(&x.view(), &y.view())
+        );
+        assert_eq!(
+            MinSymmetricChiSquare.distance(&x.view(), &y.view()),
+            min_symmetric_chi_square(&x.view(), &y.view())
+        );
+        assert_eq!(
+            SoftDice.distance(&x.view(), &y.view()),
+            soft_dice(&x.view(), &y.view())
+        );
+        assert_eq!(
+            Lorentzian.distance(&x.view(), &y.view()),
+            lorentzian(&x.view(), &y.view())
+        );
+        assert_eq!(
+            Kulczynski.distance(&x.view(), &y.view()),
+            kulczynski(&x.view(), &y.view())
+        );
+        assert_eq!(
+            Motyka.distance(&x.view(), &y.view()),
+            motyka(&x.view(), &y.view())
+        );
+        assert_eq!(
+            Soergel.distance(&x.view(), &y.view()),
+            soergel(&x.view(), &y.view())
+        );
+        assert_eq!(
+            WaveHedges.distance(&x.view(), &y.view()),
+            wave_hedges(&x.view(), &y.view())
+        );
+        assert_eq!(
+            NanEuclidean.distance(&x.view(), &y.view()),
+            nan_euclidean(&x.view(), &y.view())
+        );
+        assert_eq!(
+            NegInnerProduct.distance(&x.view(), &y.view()),
+            neg_inner_product(&x.view(), &y.view())
+        );
+        assert_eq!(
+            ShiftedInnerProduct { shift: 50.0 }.distance(&x.view(), &y.view()),
+            shifted_inner_product(&x.view(), &y.view(), 50.0)
+        );
+        assert_eq!(
+            Poincare.distance(&(&x / 10.0).view(), &(&y / 10.0).view()),
+            poincare(&(&x / 10.0), &(&y / 10.0))
+        );
+        assert_eq!(
+            Hyperboloid.distance(&x.view(), &y.view()),
+            hyperboloid(&x.view(), &y.view())
+        );
+        assert_eq!(
+            KendallTau.distance(&x.view(), &y.view()),
+            kendall_tau(&x.view(), &y.view())
+        );
+        assert_eq!(
+            QuantitativeSorensen.distance(&x.view(), &y.view()),
+            quantitative_sorensen(&x.view(), &y.view())
+        );
+        assert_eq!(
+            Tanimoto.distance(&x.view(), &y.view()),
+            tanimoto(&x.view(), &y.view())
+        );
+        assert_eq!(
+            TrueAngular.distance(&x.view(), &y.view()),
+            true_angular(&x.view(), &y.view())
+        );
+        assert_eq!(
+            Tsss.distance(&x.view(), &y.view()),
+            tsss(&x.view(), &y.view())
+        );
+        assert_eq!(
+            WeightedJaccard.distance(&x.view(), &y.view()),
+            weighted_jaccard(&x.view(), &y.view())
+        );
+        assert_eq!(
+            JensenShannon.distance(&x.view(), &y.view()),
+            jensen_shannon(&x.view(), &y.view())
+        );
+        assert_eq!(
+            Edr { epsilon: 0.5 }.distance(&x.view(), &y.view()),
+            edr(&x.view(), &y.view(), 0.5)
+        );
+        assert_eq!(
+            Lcss {
+                epsilon: 1.0,
+                delta: 1
+            }
+            .distance(&x.view(), &y.view()),
+            lcss(&x.view(), &y.view(), 1.0, 1)
+        );
+        assert_eq!(
+            Wasserstein1d.distance(&x.view(), &y.view()),
+            wasserstein_1d(&x.view(), &y.view())
+        );
+    }
+
+    #[test]
+    fn test_as_fn() {
+        let x = arr1(&[1.0f32, 2.0, 3.0]);
+        let y = arr1(&[4.0f32, 5.0, 6.0]);
+
+        let f: DistanceFn<f32> = Manhattan.as_fn();
+        assert_eq!(f(&x.view(), &y.view()), 9.0);
+        assert_eq!(f.distance(&x.view(), &y.view()), 9.0);
+    }
+
+    #[test]
+    fn test_from_fn_closure() {
+        let offset = 1.0;
+        let metric =
+            from_fn(move |x: &ArrayView1<f64>, y: &ArrayView1<f64>| chebyshev(x, y) + offset);
+
+        let x = arr1(&[1.0, 2.0]);
+        let y = arr1(&[4.0, 6.0]);
+        assert_eq!(metric.distance(&x.view(), &y.view()), 5.0);
+    }
+
+    #[test]
+    fn test_parameterized_metrics() {
+        let x = arr1(&[1.0, 2.0, 3.0]);
+        let y = arr1(&[4.0, 5.0, 6.0]);
+
+        let metric = Minkowski { p: 1.0 };
+        assert!((metric.distance(&x.view(), &y.view()) - 9.0).abs() < 1e-12);
+
+        let metric = Mahalanobis {
+            vinv: arr2(&[[1.0, 0.5, 0.0], [0.5, 1.0, 0.5], [0.0, 0.5, 1.0]]),
+        };
+        assert_eq!(
+            metric.distance(&x.view(), &y.view()),
+            mahalanobis(&x.view(), &y.view(), Some(metric.vinv.clone()))
+        );
+
+        let metric = StandardisedEuclidean {
+            sigma: arr1(&[2.0, 2.0, 2.0]),
+        };
+        assert_eq!(metric.distance(&x.view(), &y.view()), 3.6742346141747673);
+
+        let metric = MahalanobisCholesky::from_cholesky(arr2(&[
+            [1.0, 0.0, 0.0],
+            [0.5, 2.0, 0.0],
+            [0.0, 1.0, 1.5],
+        ]));
+        assert_eq!(
+            metric.distance(&x.view(), &y.view()),
+            mahalanobis_cholesky(&x.view(), &y.view(), &metric.l.view())
+        );
+    }
+
+    #[test]
+    fn test_mahalanobis_fit() {
+        // Collinear points: the sample covariance is singular.
+        let data = arr2(&[[0.0, 0.0], [1.0, 2.0], [2.0, 4.0], [3.0, 6.0]]);
+        assert!(matches!(
+            MahalanobisCholesky::fit(&data.view()),
+            Err(DistanceError::InvalidInput(_))
+        ));
+        assert!(MahalanobisCholesky::fit(&data.slice(ndarray::s![..1, ..])).is_err());
+        assert!(matches!(
+            MahalanobisCholesky::fit_with_shrinkage(&data.view(), 1.5),
+            Err(DistanceError::InvalidParameter(_))
+        ));
+
+        // Full shrinkage leaves (tr(S) / d) · I, with tr(S) = 5/3 + 20/3.
+        let metric = MahalanobisCholesky::fit_with_shrinkage(&data.view(), 1.0).unwrap();
+        let (x, y) = (arr1(&[1.0, 0.0]), arr1(&[0.0, 2.0]));
+        let expected = euclidean(&x.view(), &y.view()) / (25.0f64 / 6.0).sqrt();
+        assert!((metric.distance(&x.view(), &y.view()) - expected).abs() < 1e-12);
+
+        let metric = MahalanobisCholesky::fit_with_shrinkage(&data.view(), 0.1).unwrap();
+        let cov = metric.l.dot(&metric.l.t());
+        let from_cov = mahalanobis_from_cov(&x.view(), &y.view(), &cov.view());
+        assert!((metric.distance(&x.view(), &y.view()) - from_cov).abs() < 1e-12);
+    }
+
+    #[cfg(feature = "validate")]
+    #[test]
+    fn test_try_distance() {
+        let x = arr1(&[0.1, 0.2]);
+        let y = arr1(&[0.3, 0.4]);
+        assert_eq!(
+            Euclidean.try_distance(&x.view(), &y.view()),
+            Ok(euclidean(&x.view(), &y.view()))
+        );
+
+        let z = arr1(&[0.3, f64::NAN]);
+        assert_eq!(
+            Euclidean.try_distance(&x.view(), &z.view()),
+            Err(DistanceError::NonFinite {
+                argument: "y",
+                index: 1
+            })
+        );
+
+        assert_eq!(
+            NanEuclidean.try_distance(&x.view(), &z.view()),
+            Ok(nan_euclidean(&x.view(), &z.view()))
+        );
+
+        // Sequence metrics compare inputs of different lengths.
+        let short = arr1(&[0.0, 1.0, 2.0]);
+        let long = arr1(&[0.0, 1.0, 1.0, 2.0, 2.0]);
+        assert_eq!(
+            Dtw.try_distance(&short.view(), &long.view()),
+            Ok(dtw(&short.view(), &long.view()))
+        );
+        assert!(Edr { epsilon: 0.1 }
+            .try_distance(&short.view(), &long.view())
+            .is_ok());
+        assert!(Lcss {
+            epsilon: 0.1,
+            delta: 2
+        }
+        .try_distance(&short.view(), &long.view())
+        .is_ok());
+        let empty = ndarray::Array1::<f64>::zeros(0);
+        assert!(matches!(
+            Dtw.try_distance(&short.view(), &empty.view()),
+            Err(DistanceError::InvalidInput(_))
+        ));
+        let nan = arr1(&[0.0, f64::NAN, 1.0, 2.0, 3.0]);
+        assert!(Dtw.try_distance(&short.view(), &nan.view()).is_err());
+
+        let z = arr1(&[0.3, 0.4, 0.5]);
+        assert!(Haversine.try_distance(&z.view(), &z.view()).is_err());
+        assert!(from_fn(euclidean::<f64>)
+            .try_distance(&x.view(), &z.view())
+            .is_err());
+
+        let z = arr1(&[0.3, -0.4]);
+        assert_eq!(
+            Hellinger.try_distance(&x.view(), &z.view()),
+            Err(DistanceError::Negative {
+                argument: "y",
+                index: 1
+            })
+        );
+        assert!(WeightedJaccard.try_distance(&z.view(), &x.view()).is_err());
+
+        let z = arr1(&[0.9, 0.9]);
+        assert!(Poincare.try_distance(&x.view(), &z.view()).is_err());
+
+        let metric = WeightedMinkowski {
+            w: Some(arr1(&[1.0, -1.0])),
+            p: 2.0,
+        };
+        assert!(metric.try_distance(&x.view(), &y.view()).is_err());
+
+        let metric = HierarchicalCategorical {
+            level_weights: arr1(&[0.5, 0.5, 0.5]),
+        };
+        assert!(metric.try_distance(&x.view(), &y.view()).is_err());
+
+        let metric = Minkowski { p: 0.0 };
+        assert!(metric.try_distance(&x.view(), &y.view()).is_err());
+
+        let metric = Mahalanobis {
+            vinv: arr2(&[[1.0, 0.0, 0.0]]),
+        };
+        assert!(metric.try_distance(&x.view(), &y.view()).is_err());
+
+        let metric = MahalanobisCholesky::from_cholesky(arr2(&[[1.0, 0.0], [2.0, 0.0]]));
+        assert!(metric.try_distance(&x.view(), &y.view()).is_err());
+    }
+
+    #[test]
+    fn test_trait_objects() {
+        let x = arr1(&[1.0, 2.0, 3.0]);
+        let y = arr1(&[4.0, 5.0, 6.0]);
+
+        let metrics: Vec<Box<dyn Distance<f64>>> = vec![
+            Box::new(Euclidean),
+            Box::new(Manhattan),
+            Box::new(Minkowski { p: 3.0 }),
+        ];
+        for metric in metrics.iter() {
+            assert!(metric.distance(&x.view(), &y.view()) > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_distance_bounded() {
+        let x = arr1(&[0.0, 0.0]);
+        let y = arr1(&[3.0, 4.0]);
+
+        assert_eq!(
+            Euclidean.distance_bounded(&x.view(), &y.view(), 5.0),
+            Some(5.0)
+        );
+        assert_eq!(Euclidean.distance_bounded(&x.view(), &y.view(), 4.9), None);
+        assert_eq!(Manhattan.distance_bounded(&x.view(), &y.view(), 6.0), None);
+        assert_eq!(
+            (Minkowski { p: 1.0 }).distance_bounded(&x.view(), &y.view(), 7.0),
+            Some(7.0)
+        );
+        // Metrics without an early-abandoning kernel fall back to the full distance.
+        assert_eq!(
+            Chebyshev.distance_bounded(&x.view(), &y.view(), 4.0),
+            Some(4.0)
+        );
+        assert_eq!(Chebyshev.distance_bounded(&x.view(), &y.view(), 3.0), None);
+    }
+
+    #[test]
+    fn test_normalized_metrics() {
+        let counts_x = arr1(&[3.0, 1.0, 0.0, 4.0]);
+        let counts_y = arr1(&[1.0, 1.0, 1.0, 1.0]);
+        let (x, y) = (&counts_x / 8.0, &counts_y / 4.0);
+
+        let d = Normalized(Hellinger).distance(&counts_x.view(), &counts_y.view());
+        assert!((d - hellinger(&x.view(), &y.view())).abs() < 1e-15);
+        let d = Normalized(KlDivergence).distance(&counts_y.view(), &counts_x.view());
+        assert_eq!(d, f64::INFINITY);
+        let d = Normalized(KlDivergence).distance(&counts_x.view(), &counts_y.view());
+        assert!((d - kl_divergence(&x.view(), &y.view())).abs() < 1e-15);
+
+        let zeros = arr1(&[0.0; 4]);
+        let d = Normalized(Hellinger).distance(&zeros.view(), &counts_y.view());
+        assert_eq!(d, 1.0);
+        assert_eq!(
+            Normalized(Hellinger).distance(&zeros.view(), &zeros.view()),
+            0.0
+        );
+    }
+
+    #[cfg(feature = "validate")]
+    #[test]
+    fn test_normalized_validate() {
+        let x = arr1(&[1.0, 2.0]);
+        let negative = arr1(&[1.0, -2.0]);
+        assert!(Normalized(Hellinger)
+            .try_distance(&x.view(), &x.view())
+            .is_ok());
+        assert!(Normalized(Hellinger)
+            .try_distance(&x.view(), &negative.view())
+            .is_err());
+    }
+
+    #[cfg(feature = "validate")]
+    #[test]
+    fn test_renyi_validate() {
+        let p = arr1(&[0.5, 0.5]);
+        let q = arr1(&[0.25, 0.75]);
+        let d = Renyi { alpha: 2.0 }.try_distance(&p.view(), &q.view());
+        assert_eq!(d, Ok(renyi_divergence(&p.view(), &q.view(), 2.0)));
+        assert!(matches!(
+            Renyi { alpha: -1.0 }.try_distance(&p.view(), &q.view()),
+            Err(DistanceError::InvalidParameter(_))
+        ));
+        assert!(Renyi { alpha: f64::NAN }
+            .try_distance(&p.view(), &q.view())
+            .is_err());
+    }
+}
@@ -0,0 +1,143 @@
+//! MinHash signatures for estimating Jaccard distances between sets.
+//!
+//! For a random permutation of the universe, the probability that two sets have the same
+//! smallest element is their Jaccard similarity. A [`MinHasher`] applies `k` seeded hash
+//! functions in place of permutations and keeps the minimum of each, so two sets can be
+//! compared through their `k` signature slots, in time independent of the set sizes, with a
+//! standard error of about `sqrt(J (1 - J) / k)`.
+
+use crate::hamming;
+use crate::rng::SplitMix64;
+
+/// The Mersenne prime `2^61 - 1`, the modulus of the hash functions.
+const PRIME: u64 = (1 << 61) - 1;
+
+/// A seeded family of `k` hash functions that maps index sets to MinHash signatures.
+///
+/// Each element is first scrambled by a fixed 64-bit mixer, then hashed by
+/// `h(x) = (a x + b) mod (2^61 - 1)` with random `a` and `b`, which behaves like a random
+/// permutation for the purpose of taking minima. The mixer keeps the estimates unbiased for
+/// structured sets such as arithmetic progressions, on which affine hashes alone correlate. Signatures are only comparable
+/// when they come from the same hasher, i.e. the same `num_perm` and `seed`.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// let a: Vec<usize> = (0..1000).collect();
+/// let b: Vec<usize> = (500..1500).collect();
+///
+/// let hasher = MinHasher::new(256, 42);
+/// let estimate = estimated_jaccard(&hasher.signature(&a), &hasher.signature(&b));
+///
+/// let exact = sparse_jaccard(&a, &b);
+/// assert!((estimate - exact).abs() < 0.1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinHasher {
+    coefficients: Vec<(u64, u64)>,
+}
+
+impl MinHasher {
+    /// Creates `num_perm` hash functions drawn from `seed`.
+    ///
+    /// # Panics
+    /// Panics if `num_perm` is zero.
+    pub fn new(num_perm: usize, seed: u64) -> Self {
+        assert!(num_perm > 0, "Number of permutations must be positive.");
+        let mut rng = SplitMix64::new(seed);
+        let coefficients = (0..num_perm)
+            .map(|_| {
+                let a = 1 + rng.next_below_u64(PRIME - 1);
+                let b = rng.next_below_u64(PRIME);
+                (a, b)
+            })
+            .collect();
+        Self { coefficients }
+    }
+
+    /// Returns the number of hash functions, which is the length of every signature.
+    pub fn num_perm(&self) -> usize {
+        self.coefficients.len()
+    }
+
+    /// Computes the MinHash signature of a set given by its elements, such as the nonzero
+    /// indices of a sparse binary vector.
+    ///
+    /// The indices may be in any order, and duplicates do not change the signature. The empty
+    /// set has every slot equal to `u64::MAX`, which no hash value reaches.
+    pub fn signature(&self, indices: &[usize]) -> Vec<u64> {
+        let mut signature = vec![u64::MAX; self.num_perm()];
+        for &index in indices {
+            let x = mix(index as u64) as u128;
+            for (slot, &(a, b)) in signature.iter_mut().zip(&self.coefficients) {
+                let hash = ((a as u128 * x + b as u128) % PRIME as u128) as u64;
+                *slot = (*slot).min(hash);
+            }
+        }
+        signature
+    }
+}
+
+/// The SplitMix64 finalizer, a bijection on `u64` that spreads nearby inputs apart.
+fn mix(x: u64) -> u64 {
+    let z = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    let z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Estimates the Jaccard distance between two sets from their MinHash signatures.
+///
+/// The estimate is the fraction of slots in which the signatures differ, an unbiased estimate
+/// of the exact [`sparse_jaccard`](crate::sparse_jaccard) distance. Two empty sets are at
+/// distance `0` and an empty set is at distance `1` from any other set, as for the exact
+/// distance.
+///
+/// # Panics
+/// Panics if the signatures do not have the same length.
+pub fn estimated_jaccard(sig_a: &[u64], sig_b: &[u64]) -> f64 {
+    hamming(sig_a, sig_b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sparse_jaccard;
+
+    #[test]
+    fn test_estimate_tracks_exact_distance() {
+        let hasher = MinHasher::new(512, 7);
+        let a: Vec<usize> = (0..400).map(|i| i * 3).collect();
+        for shift in [0, 100, 300, 600, 1200] {
+            let b: Vec<usize> = (0..400).map(|i| i * 3 + shift).collect();
+            let exact = sparse_jaccard(&a, &b);
+            let estimate = estimated_jaccard(&hasher.signature(&a), &hasher.signature(&b));
+            assert!(
+                (estimate - exact).abs() < 0.08,
+                "{shift}: {estimate} vs {exact}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_signature_properties() {
+        let hasher = MinHasher::new(16, 1);
+        assert_eq!(hasher.num_perm(), 16);
+        assert_eq!(hasher, MinHasher::new(16, 1));
+        assert_ne!(hasher, MinHasher::new(16, 2));
+        assert_eq!(
+            hasher.signature(&[5, 1, 9]),
+            hasher.signature(&[1, 9, 9, 5])
+        );
+
+        let empty = hasher.signature(&[]);
+        assert_eq!(estimated_jaccard(&empty, &empty), 0.0);
+        assert_eq!(estimated_jaccard(&empty, &hasher.signature(&[3])), 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Number of permutations must be positive.")]
+    fn test_zero_permutations() {
+        MinHasher::new(0, 0);
+    }
+}
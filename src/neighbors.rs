@@ -0,0 +1,422 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use ndarray::{s, Array2, ArrayView1, ArrayView2};
+use num::Float;
+
+use crate::knn_graph::{collect_graph, offer};
+use crate::{cdist_into, default_tile_size, Distance};
+
+/// Finds the row of `data` closest to `query`.
+///
+/// Candidates are evaluated with [`Distance::distance_bounded`] using the best distance found so
+/// far as the bound, so metrics with an early-abandoning kernel skip most of the work for rows
+/// that cannot win. Ties are resolved in favour of the lowest index.
+///
+/// # Arguments
+/// * `data` - A 2D array view whose rows are the candidate points.
+/// * `query` - The reference point.
+/// * `metric` - Any [`Distance`].
+///
+/// # Returns
+/// The index of the closest row and its distance to `query`. Rows whose distance is NaN are
+/// only returned if every distance is NaN.
+///
+/// # Panics
+/// Panics if `data` has no rows or if its number of columns differs from the length of `query`.
+///
+/// # Example
+/// ```
+/// use ndarray::{arr1, arr2};
+/// use fast_distances::*;
+///
+/// let data = arr2(&[[0.0, 0.0], [3.0, 4.0], [1.0, 1.0]]);
+/// let query = arr1(&[2.0, 2.0]);
+/// assert_eq!(nearest(&data.view(), &query.view(), &Manhattan), (2, 2.0));
+/// ```
+pub fn nearest<T, M>(data: &ArrayView2<T>, query: &ArrayView1<T>, metric: &M) -> (usize, T)
+where
+    T: Float,
+    M: Distance<T> + ?Sized,
+{
+    assert_eq!(
+        data.ncols(),
+        query.len(),
+        "Data columns and query must have the same length."
+    );
+    assert!(data.nrows() > 0, "Data must contain at least one row.");
+
+    let mut best = (0, metric.distance(&data.row(0), query));
+    for (i, row) in data.rows().into_iter().enumerate().skip(1) {
+        let upper = if best.1.is_nan() {
+            T::infinity()
+        } else {
+            best.1
+        };
+        if let Some(d) = metric.distance_bounded(&row, query, upper) {
+            if d < best.1 || best.1.is_nan() {
+                best = (i, d);
+            }
+        }
+    }
+    best
+}
+
+/// Finds the `k` rows of `data` closest to `query`.
+///
+/// A max-heap holds the current `k` best candidates; once it is full, further rows are evaluated
+/// with [`Distance::distance_bounded`] against the worst retained distance. Rows whose distance is
+/// NaN are skipped, and ties are resolved in favour of the lowest index.
+///
+/// # Arguments
+/// * `data` - A 2D array view whose rows are the candidate points.
+/// * `query` - The reference point.
+/// * `k` - The number of neighbors to return.
+/// * `metric` - Any [`Distance`].
+///
+/// # Returns
+/// Up to `k` `(index, distance)` pairs sorted by increasing distance. Fewer than `k` pairs are
+/// returned if `data` has fewer than `k` rows with a non-NaN distance.
+///
+/// # Panics
+/// Panics if the number of columns of `data` differs from the length of `query`.
+///
+/// # Example
+/// ```
+/// use ndarray::{arr1, arr2};
+/// use fast_distances::*;
+///
+/// let data = arr2(&[[0.0, 0.0], [3.0, 4.0], [1.0, 1.0]]);
+/// let query = arr1(&[0.0, 0.0]);
+/// let neighbors = k_nearest(&data.view(), &query.view(), 2, &Manhattan);
+/// assert_eq!(neighbors, vec![(0, 0.0), (2, 2.0)]);
+/// ```
+pub fn k_nearest<T, M>(
+    data: &ArrayView2<T>,
+    query: &ArrayView1<T>,
+    k: usize,
+    metric: &M,
+) -> Vec<(usize, T)>
+where
+    T: Float,
+    M: Distance<T> + ?Sized,
+{
+    assert_eq!(
+        data.ncols(),
+        query.len(),
+        "Data columns and query must have the same length."
+    );
+
+    if k == 0 {
+        return Vec::new();
+    }
+
+    let mut heap = BinaryHeap::with_capacity(k);
+
+    for (index, row) in data.rows().into_iter().enumerate() {
+        if heap.len() < k {
+            let distance = metric.distance(&row, query);
+            if !distance.is_nan() {
+                heap.push(Candidate { distance, index });
+            }
+        } else {
+            let worst = heap.peek().map(|c: &Candidate<T>| c.distance).unwrap();
+            if let Some(distance) = metric.distance_bounded(&row, query, worst) {
+                if distance < worst {
+                    heap.pop();
+                    heap.push(Candidate { distance, index });
+                }
+            }
+        }
+    }
+
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|c| (c.index, c.distance))
+        .collect()
+}
+
+/// Finds the `k` rows of `data` closest to every row of `queries`.
+///
+/// Queries and candidates are processed in blocks of [`default_tile_size`] rows: each block of
+/// distances is computed in one go and then offered to the neighbor heaps of its queries. For
+/// metrics that provide [`Distance::block_distances`], such as [`Euclidean`](crate::Euclidean)
+/// and [`Cosine`](crate::Cosine), every block is a single matrix multiplication with cached row
+/// norms, which is typically an order of magnitude faster than calling [`k_nearest`] once per
+/// query. The distances of the selected neighbors are then recomputed exactly with
+/// [`Distance::distance`], so only near-ties can be ordered differently than by [`k_nearest`].
+/// Other metrics fill a reused block buffer with [`cdist_into`] and give identical results.
+///
+/// # Arguments
+/// * `data` - A 2D array view whose rows are the candidate points.
+/// * `queries` - A 2D array view whose rows are the reference points.
+/// * `k` - The number of neighbors per query.
+/// * `metric` - Any [`Distance`].
+///
+/// # Returns
+/// A pair `(indices, distances)` of `(queries.nrows(), k)` arrays. Row `i` lists the neighbors of
+/// query `i` by increasing distance, with ties resolved in favour of the lowest index. If fewer
+/// than `k` rows have a non-NaN distance, the remaining slots hold `usize::MAX` and infinity.
+///
+/// # Panics
+/// Panics if `data` and `queries` do not have the same number of columns.
+///
+/// # Example
+/// ```
+/// use ndarray::arr2;
+/// use fast_distances::*;
+///
+/// let data = arr2(&[[0.0, 0.0], [3.0, 4.0], [1.0, 1.0]]);
+/// let queries = arr2(&[[0.0, 0.0], [3.0, 3.0]]);
+/// let (indices, distances) = k_nearest_batch(&data.view(), &queries.view(), 2, &Manhattan);
+/// assert_eq!(indices, arr2(&[[0, 2], [1, 2]]));
+/// assert_eq!(distances, arr2(&[[0.0, 2.0], [1.0, 4.0]]));
+/// ```
+pub fn k_nearest_batch<T, M>(
+    data: &ArrayView2<T>,
+    queries: &ArrayView2<T>,
+    k: usize,
+    metric: &M,
+) -> (Array2<usize>, Array2<T>)
+where
+    T: Float + 'static,
+    M: Distance<T> + ?Sized,
+{
+    assert_eq!(
+        data.ncols(),
+        queries.ncols(),
+        "Input matrices must have the same number of columns."
+    );
+
+    let (n, nq) = (data.nrows(), queries.nrows());
+    let mut heaps: Vec<BinaryHeap<Candidate<T>>> =
+        (0..nq).map(|_| BinaryHeap::with_capacity(k)).collect();
+    if k == 0 || n == 0 {
+        return collect_graph(heaps, k);
+    }
+
+    let tile = default_tile_size::<T>(data.ncols());
+    let mut buffer = Array2::zeros((tile.min(nq), tile.min(n)));
+    let mut approximate = false;
+
+    for q0 in (0..nq).step_by(tile) {
+        let q1 = (q0 + tile).min(nq);
+        let query_block = queries.slice(s![q0..q1, ..]);
+        for d0 in (0..n).step_by(tile) {
+            let d1 = (d0 + tile).min(n);
+            let data_block = data.slice(s![d0..d1, ..]);
+
+            let computed = metric.block_distances(&query_block, &data_block);
+            approximate |= computed.is_some();
+            let block = match &computed {
+                Some(block) => block.view(),
+                None => {
+                    let mut out = buffer.slice_mut(s![..q1 - q0, ..d1 - d0]);
+                    cdist_into(&query_block, &data_block, metric, &mut out);
+                    buffer.slice(s![..q1 - q0, ..d1 - d0])
+                }
+            };
+
+            for (heap, row) in heaps[q0..q1].iter_mut().zip(block.rows()) {
+                for (j, &distance) in row.iter().enumerate() {
+                    offer(
+                        heap,
+                        k,
+                        Candidate {
+                            distance,
+                            index: d0 + j,
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    if approximate {
+        for (i, heap) in heaps.iter_mut().enumerate() {
+            let query = queries.row(i);
+            *heap = heap
+                .drain()
+                .map(|c| Candidate {
+                    distance: metric.distance(&query, &data.row(c.index)),
+                    index: c.index,
+                })
+                .filter(|c| !c.distance.is_nan())
+                .collect();
+        }
+    }
+
+    collect_graph(heaps, k)
+}
+
+/// A neighbor candidate ordered by distance, then by index.
+///
+/// Only non-NaN distances are stored, so the partial order on `T` is total here.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Candidate<T> {
+    pub(crate) distance: T,
+    pub(crate) index: usize,
+}
+
+impl<T: Float> PartialEq for Candidate<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: Float> Eq for Candidate<T> {}
+
+impl<T: Float> PartialOrd for Candidate<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Float> Ord for Candidate<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .partial_cmp(&other.distance)
+            .unwrap_or(Ordering::Equal)
+            .then(self.index.cmp(&other.index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use ndarray::{arr1, arr2, Array2};
+
+    #[test]
+    fn test_nearest_matches_distances_to_point() {
+        let data = arr2(&[
+            [1.0, 2.0, 3.0],
+            [4.0, 5.0, 6.0],
+            [1.0, 2.0, 4.0],
+            [0.0, 0.0, 0.0],
+        ]);
+        let query = arr1(&[1.5, 2.0, 3.5]);
+
+        let dists = distances_to_point(&data.view(), &query.view(), &Euclidean);
+        let (index, d) = nearest(&data.view(), &query.view(), &Euclidean);
+        assert_eq!(index, 0);
+        assert_eq!(d, dists[0]);
+
+        let (index, _) = nearest(&data.view(), &query.view(), &Minkowski { p: 3.0 });
+        assert_eq!(index, 0);
+    }
+
+    #[test]
+    fn test_nearest_ties_and_default_bound() {
+        let data = arr2(&[[1.0, 0.0], [0.0, 1.0], [5.0, 5.0]]);
+        let query = arr1(&[0.0, 0.0]);
+
+        assert_eq!(nearest(&data.view(), &query.view(), &Euclidean), (0, 1.0));
+        assert_eq!(nearest(&data.view(), &query.view(), &Chebyshev), (0, 1.0));
+    }
+
+    #[test]
+    fn test_nearest_skips_nan() {
+        let data = arr2(&[[f64::NAN, 0.0], [2.0, 0.0], [1.0, 0.0]]);
+        let query = arr1(&[0.0, 0.0]);
+
+        assert_eq!(nearest(&data.view(), &query.view(), &Manhattan), (2, 1.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Data must contain at least one row.")]
+    fn test_nearest_empty() {
+        let data = Array2::<f64>::zeros((0, 2));
+        let query = arr1(&[0.0, 0.0]);
+        nearest(&data.view(), &query.view(), &Euclidean);
+    }
+
+    #[test]
+    fn test_k_nearest_matches_sorted_distances() {
+        let data = arr2(&[
+            [1.0, 2.0, 3.0],
+            [4.0, 5.0, 6.0],
+            [1.0, 2.0, 4.0],
+            [0.0, 0.0, 0.0],
+            [2.0, 2.0, 2.0],
+        ]);
+        let query = arr1(&[1.0, 1.0, 1.0]);
+
+        let dists = distances_to_point(&data.view(), &query.view(), &Euclidean);
+        let mut expected: Vec<(usize, f64)> = dists.iter().cloned().enumerate().collect();
+        expected.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+
+        for k in 0..=5 {
+            let neighbors = k_nearest(&data.view(), &query.view(), k, &Euclidean);
+            assert_eq!(neighbors, expected[..k].to_vec());
+        }
+    }
+
+    #[test]
+    fn test_k_nearest_more_than_rows() {
+        let data = arr2(&[[1.0f32, 0.0], [0.0, 1.0]]);
+        let query = arr1(&[0.0f32, 0.0]);
+
+        let neighbors = k_nearest(&data.view(), &query.view(), 5, &Chebyshev);
+        assert_eq!(neighbors, vec![(0, 1.0), (1, 1.0)]);
+    }
+
+    #[test]
+    fn test_k_nearest_skips_nan() {
+        let data = arr2(&[[f64::NAN, 0.0], [2.0, 0.0], [1.0, 0.0]]);
+        let query = arr1(&[0.0, 0.0]);
+
+        let neighbors = k_nearest(&data.view(), &query.view(), 3, &Minkowski { p: 2.0 });
+        assert_eq!(neighbors, vec![(2, 1.0), (1, 2.0)]);
+    }
+
+    #[test]
+    fn test_k_nearest_batch_matches_k_nearest() {
+        // More rows than one tile, so both the query and the candidate blocks wrap around.
+        let data = Array2::from_shape_fn((600, 64), |(i, j)| {
+            (((i * 37 + j * 11) % 97) as f64 / 13.0).sin()
+        });
+        let queries = Array2::from_shape_fn((300, 64), |(i, j)| {
+            (((i * 53 + j * 7) % 89) as f64 / 11.0).cos()
+        });
+
+        let check = |metric: &dyn Distance<f64>, exact: bool| {
+            let (indices, distances) = k_nearest_batch(&data.view(), &queries.view(), 5, metric);
+            for q in (0..queries.nrows()).step_by(23) {
+                let expected = k_nearest(&data.view(), &queries.row(q), 5, metric);
+                for (slot, (j, d)) in expected.into_iter().enumerate() {
+                    assert_eq!(indices[(q, slot)], j);
+                    if exact {
+                        assert_eq!(distances[(q, slot)], d);
+                    } else {
+                        assert!((distances[(q, slot)] - d).abs() < 1e-12);
+                    }
+                }
+            }
+        };
+        check(&Manhattan, true);
+        check(&Euclidean, false);
+        check(&Cosine, false);
+    }
+
+    #[test]
+    fn test_k_nearest_batch_short_and_nan() {
+        let data = arr2(&[[0.0], [f64::NAN], [2.0]]);
+        let queries = arr2(&[[1.5]]);
+        let (indices, distances) = k_nearest_batch(&data.view(), &queries.view(), 3, &Euclidean);
+
+        assert_eq!(indices.row(0).to_vec(), vec![2, 0, usize::MAX]);
+        assert_eq!(distances.row(0).to_vec(), vec![0.5, 1.5, f64::INFINITY]);
+
+        let (indices, _) = k_nearest_batch(&data.view(), &queries.view(), 0, &Euclidean);
+        assert_eq!(indices.dim(), (1, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Input matrices must have the same number of columns.")]
+    fn test_k_nearest_batch_column_mismatch() {
+        let data = Array2::<f64>::zeros((3, 2));
+        let queries = Array2::<f64>::zeros((1, 3));
+        k_nearest_batch(&data.view(), &queries.view(), 1, &Euclidean);
+    }
+}
@@ -0,0 +1,301 @@
+//! Incrementally updated distances for vectors that change one coordinate at a time.
+//!
+//! Each updater keeps the fixed vector `y` together with running sums over the evolving vector
+//! `x`, so that [`update_coord`](OnlineEuclidean::update_coord) runs in O(1) instead of
+//! recomputing the whole distance. Long update sequences accumulate rounding error; call
+//! `reset` with the current `x` to resynchronize the running sums.
+
+use ndarray::{Array1, ArrayView1};
+use num::Float;
+
+/// Incrementally maintained [`euclidean`](crate::euclidean) distance.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = arr1(&[0.0, 0.0]);
+/// let y = arr1(&[3.0, 0.0]);
+/// let mut online = OnlineEuclidean::new(&x.view(), &y.view());
+/// assert_eq!(online.distance(), 3.0);
+///
+/// online.update_coord(1, 0.0, 4.0);
+/// assert_eq!(online.distance(), 5.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct OnlineEuclidean<T> {
+    y: Array1<T>,
+    sum_sq: T,
+}
+
+impl<T> OnlineEuclidean<T>
+where
+    T: Float,
+{
+    /// Creates an updater for the distance between `x` and the fixed vector `y`.
+    ///
+    /// # Panics
+    /// Panics if `x` and `y` do not have the same length.
+    pub fn new(x: &ArrayView1<T>, y: &ArrayView1<T>) -> Self {
+        assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+        let mut online = Self {
+            y: y.to_owned(),
+            sum_sq: T::zero(),
+        };
+        online.reset(x);
+        online
+    }
+
+    /// Recomputes the running sum from scratch for the current `x`.
+    pub fn reset(&mut self, x: &ArrayView1<T>) {
+        assert_eq!(
+            x.len(),
+            self.y.len(),
+            "Input arrays must have the same length."
+        );
+        self.sum_sq = x
+            .iter()
+            .zip(self.y.iter())
+            .fold(T::zero(), |acc, (&xi, &yi)| acc + (xi - yi) * (xi - yi));
+    }
+
+    /// Records that coordinate `i` of `x` changed from `old` to `new`.
+    pub fn update_coord(&mut self, i: usize, old: T, new: T) {
+        let old_diff = old - self.y[i];
+        let new_diff = new - self.y[i];
+        self.sum_sq = self.sum_sq + new_diff * new_diff - old_diff * old_diff;
+    }
+
+    /// Returns the current distance.
+    pub fn distance(&self) -> T {
+        self.sum_sq.max(T::zero()).sqrt()
+    }
+}
+
+/// Incrementally maintained [`manhattan`](crate::manhattan) distance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OnlineManhattan<T> {
+    y: Array1<T>,
+    sum_abs: T,
+}
+
+impl<T> OnlineManhattan<T>
+where
+    T: Float,
+{
+    /// Creates an updater for the distance between `x` and the fixed vector `y`.
+    ///
+    /// # Panics
+    /// Panics if `x` and `y` do not have the same length.
+    pub fn new(x: &ArrayView1<T>, y: &ArrayView1<T>) -> Self {
+        assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+        let mut online = Self {
+            y: y.to_owned(),
+            sum_abs: T::zero(),
+        };
+        online.reset(x);
+        online
+    }
+
+    /// Recomputes the running sum from scratch for the current `x`.
+    pub fn reset(&mut self, x: &ArrayView1<T>) {
+        assert_eq!(
+            x.len(),
+            self.y.len(),
+            "Input arrays must have the same length."
+        );
+        self.sum_abs = x
+            .iter()
+            .zip(self.y.iter())
+            .fold(T::zero(), |acc, (&xi, &yi)| acc + (xi - yi).abs());
+    }
+
+    /// Records that coordinate `i` of `x` changed from `old` to `new`.
+    pub fn update_coord(&mut self, i: usize, old: T, new: T) {
+        self.sum_abs = self.sum_abs + (new - self.y[i]).abs() - (old - self.y[i]).abs();
+    }
+
+    /// Returns the current distance.
+    pub fn distance(&self) -> T {
+        self.sum_abs.max(T::zero())
+    }
+}
+
+/// Incrementally maintained [`cosine`](crate::cosine) distance.
+///
+/// The norm of the fixed vector `y` is computed once; the dot product and the squared norm of
+/// `x` are updated per coordinate. Zero-norm inputs follow the same conventions as `cosine`.
+///
+/// Adding a coordinate and removing it again leaves a rounding residue in the running squared
+/// norm, which would turn a zero `x` into a tiny (or negative) norm. The updater therefore keeps
+/// a copy of `x`, and recomputes the sums from it whenever the squared norm falls to the
+/// rounding level of the values that went into it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OnlineCosine<T> {
+    x: Array1<T>,
+    y: Array1<T>,
+    dot: T,
+    norm_x_sq: T,
+    norm_y_sq: T,
+    /// The sum of the magnitudes accumulated into `norm_x_sq` since it was last exact.
+    norm_x_scale: T,
+}
+
+impl<T> OnlineCosine<T>
+where
+    T: Float,
+{
+    /// Creates an updater for the distance between `x` and the fixed vector `y`.
+    ///
+    /// # Panics
+    /// Panics if `x` and `y` do not have the same length.
+    pub fn new(x: &ArrayView1<T>, y: &ArrayView1<T>) -> Self {
+        assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+        let mut online = Self {
+            x: x.to_owned(),
+            y: y.to_owned(),
+            dot: T::zero(),
+            norm_x_sq: T::zero(),
+            norm_y_sq: y.iter().fold(T::zero(), |acc, &yi| acc + yi * yi),
+            norm_x_scale: T::zero(),
+        };
+        online.recompute();
+        online
+    }
+
+    /// Recomputes the running sums from scratch for the current `x`.
+    pub fn reset(&mut self, x: &ArrayView1<T>) {
+        assert_eq!(
+            x.len(),
+            self.y.len(),
+            "Input arrays must have the same length."
+        );
+        self.x.assign(x);
+        self.recompute();
+    }
+
+    /// Records that coordinate `i` of `x` changed from `old` to `new`.
+    pub fn update_coord(&mut self, i: usize, old: T, new: T) {
+        self.x[i] = new;
+        self.dot = self.dot + (new - old) * self.y[i];
+        self.norm_x_sq = self.norm_x_sq + new * new - old * old;
+        self.norm_x_scale = self.norm_x_scale + new * new + old * old;
+        // Cancellation can only be told apart from a genuinely small norm by recomputing it.
+        if self.norm_x_sq <= T::epsilon().sqrt() * self.norm_x_scale {
+            self.recompute();
+        }
+    }
+
+    fn recompute(&mut self) {
+        self.dot = T::zero();
+        self.norm_x_sq = T::zero();
+        for (&xi, &yi) in self.x.iter().zip(self.y.iter()) {
+            self.dot = self.dot + xi * yi;
+            self.norm_x_sq = self.norm_x_sq + xi * xi;
+        }
+        self.norm_x_scale = self.norm_x_sq;
+    }
+
+    /// Returns the current distance.
+    pub fn distance(&self) -> T {
+        let norm_x_sq = self.norm_x_sq.max(T::zero());
+        if norm_x_sq.is_zero() && self.norm_y_sq.is_zero() {
+            T::zero()
+        } else if norm_x_sq.is_zero() || self.norm_y_sq.is_zero() {
+            T::one()
+        } else {
+            T::one() - self.dot / (norm_x_sq.sqrt() * self.norm_y_sq.sqrt())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cosine, euclidean, manhattan};
+    use ndarray::arr1;
+
+    #[test]
+    fn test_online_euclidean_tracks_updates() {
+        let mut x = arr1(&[1.0, 2.0, 3.0]);
+        let y = arr1(&[4.0, 5.0, 6.0]);
+        let mut online = OnlineEuclidean::new(&x.view(), &y.view());
+
+        for (i, new) in [(0, 0.5), (2, 7.0), (0, -1.0)] {
+            let old = x[i];
+            x[i] = new;
+            online.update_coord(i, old, new);
+            assert!((online.distance() - euclidean(&x.view(), &y.view())).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_online_manhattan_tracks_updates() {
+        let mut x = arr1(&[1.0f32, 2.0, 3.0]);
+        let y = arr1(&[4.0f32, 5.0, 6.0]);
+        let mut online = OnlineManhattan::new(&x.view(), &y.view());
+
+        for (i, new) in [(1, 9.0), (2, 6.0)] {
+            let old = x[i];
+            x[i] = new;
+            online.update_coord(i, old, new);
+            assert!((online.distance() - manhattan(&x.view(), &y.view())).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_online_cosine_tracks_updates() {
+        let mut x = arr1(&[1.0, 0.0, 3.0]);
+        let y = arr1(&[4.0, 5.0, 6.0]);
+        let mut online = OnlineCosine::new(&x.view(), &y.view());
+
+        for (i, new) in [(1, 2.0), (0, -1.0), (2, 0.0)] {
+            let old = x[i];
+            x[i] = new;
+            online.update_coord(i, old, new);
+            assert!((online.distance() - cosine(&x.view(), &y.view())).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_online_cosine_zero_norms() {
+        let x = arr1(&[0.0, 0.0]);
+        let y = arr1(&[0.0, 0.0]);
+        let mut online = OnlineCosine::new(&x.view(), &y.view());
+        assert_eq!(online.distance(), 0.0);
+
+        online.update_coord(0, 0.0, 1.0);
+        assert_eq!(online.distance(), 1.0);
+    }
+
+    #[test]
+    fn test_online_cosine_add_then_remove() {
+        let mut x = arr1(&[0.0, 0.0, 0.0]);
+        let y = arr1(&[1.0, 2.0, 3.0]);
+        let mut online = OnlineCosine::new(&x.view(), &y.view());
+
+        for (i, new) in [(0, 0.1), (1, 0.2), (2, 3.3), (0, 0.0), (1, 0.0), (2, 0.0)] {
+            let old = x[i];
+            x[i] = new;
+            online.update_coord(i, old, new);
+        }
+        assert_eq!(online.distance(), 1.0);
+        assert_eq!(online.distance(), cosine(&x.view(), &y.view()));
+
+        online.update_coord(1, 0.0, 1e-3);
+        x[1] = 1e-3;
+        assert!((online.distance() - cosine(&x.view(), &y.view())).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_reset_resynchronizes() {
+        let x = arr1(&[1.0, 2.0]);
+        let y = arr1(&[1.0, 2.0]);
+        let mut online = OnlineEuclidean::new(&x.view(), &y.view());
+        online.update_coord(0, 1.0, 100.0);
+
+        online.reset(&x.view());
+        assert_eq!(online.distance(), 0.0);
+    }
+}
@@ -0,0 +1,948 @@
+use std::ops::Range;
+
+use ndarray::{s, Array1, Array2, ArrayView1, ArrayView2, ArrayViewMut1, ArrayViewMut2};
+use num::{Float, Zero};
+
+use crate::Distance;
+#[cfg(feature = "parallel")]
+use crate::Parallelism;
+
+/// Cache budget, in bytes, for the operand rows of one tile. Chosen to fit a typical L2 cache.
+const TILE_CACHE_BYTES: usize = 256 * 1024;
+
+/// Returns the default tile size used by [`cdist`] and [`pdist`] for rows of `ncols` elements
+/// of type `T`.
+///
+/// The bulk APIs evaluate the distance matrix in square blocks of `tile x tile` pairs, so the
+/// rows of both operands in a block are reused from cache instead of being streamed from memory
+/// once per output row. The default picks the largest tile whose `2 * tile` operand rows fit in
+/// roughly 256 KiB.
+pub fn default_tile_size<T>(ncols: usize) -> usize {
+    let row_bytes = (ncols * std::mem::size_of::<T>()).max(1);
+    (TILE_CACHE_BYTES / (2 * row_bytes)).max(1)
+}
+
+/// Computes the distance from every row of `data` to `query`.
+///
+/// # Arguments
+/// * `data` - A 2D array view whose rows are the points to compare.
+/// * `query` - The reference point.
+/// * `metric` - Any [`Distance`], e.g. a built-in metric such as [`Euclidean`](crate::Euclidean).
+///
+/// # Returns
+/// An `Array1<T>` whose `i`-th element is the distance between row `i` of `data` and `query`.
+///
+/// # Panics
+/// Panics if the number of columns of `data` differs from the length of `query`.
+///
+/// # Example
+/// ```
+/// use ndarray::{arr1, arr2};
+/// use fast_distances::*;
+///
+/// let data = arr2(&[[0.0, 0.0], [3.0, 4.0]]);
+/// let query = arr1(&[0.0, 0.0]);
+/// let dists = distances_to_point(&data.view(), &query.view(), &Euclidean);
+/// assert_eq!(dists, arr1(&[0.0, 5.0]));
+/// ```
+pub fn distances_to_point<T, M>(
+    data: &ArrayView2<T>,
+    query: &ArrayView1<T>,
+    metric: &M,
+) -> Array1<T>
+where
+    M: Distance<T> + ?Sized,
+{
+    assert_eq!(
+        data.ncols(),
+        query.len(),
+        "Data columns and query must have the same length."
+    );
+
+    data.rows()
+        .into_iter()
+        .map(|row| metric.distance(&row, query))
+        .collect()
+}
+
+/// Computes the distance from every row of `data` to `query`, writing into `out`.
+///
+/// This is the allocation-free counterpart of [`distances_to_point`].
+///
+/// # Panics
+/// Panics if the number of columns of `data` differs from the length of `query`, or if `out`
+/// does not have one element per row of `data`.
+pub fn distances_to_point_into<T, M>(
+    data: &ArrayView2<T>,
+    query: &ArrayView1<T>,
+    metric: &M,
+    out: &mut ArrayViewMut1<T>,
+) where
+    M: Distance<T> + ?Sized,
+{
+    assert_eq!(
+        data.ncols(),
+        query.len(),
+        "Data columns and query must have the same length."
+    );
+    assert_eq!(
+        out.len(),
+        data.nrows(),
+        "Output must have one element per data row."
+    );
+
+    for (o, row) in out.iter_mut().zip(data.rows()) {
+        *o = metric.distance(&row, query);
+    }
+}
+
+/// Computes the full pairwise distance matrix between the rows of `a` and the rows of `b`,
+/// like SciPy's `cdist`.
+///
+/// # Arguments
+/// * `a` - A 2D array view with `n` rows.
+/// * `b` - A 2D array view with `m` rows and the same number of columns as `a`.
+/// * `metric` - Any [`Distance`], including parameterized metrics such as
+///   [`Minkowski`](crate::Minkowski) or user closures wrapped with [`from_fn`](crate::from_fn).
+///
+/// # Returns
+/// An `n x m` array whose `(i, j)` entry is the distance between row `i` of `a` and row `j` of `b`.
+///
+/// The matrix is computed in cache-sized tiles of [`default_tile_size`] rows of each operand;
+/// use [`cdist_tiled`] to choose the tile size. Metrics that provide
+/// [`Distance::block_distances`], such as [`Euclidean`](crate::Euclidean) and
+/// [`Cosine`](crate::Cosine), compute each tile with one matrix multiplication, which is only
+/// accurate up to rounding; pass their [`as_fn`](crate::Euclidean::as_fn) pointer instead to
+/// evaluate every pair exactly.
+///
+/// # Panics
+/// Panics if `a` and `b` do not have the same number of columns.
+///
+/// # Example
+/// ```
+/// use ndarray::arr2;
+/// use fast_distances::*;
+///
+/// let a = arr2(&[[0.0, 0.0], [1.0, 1.0]]);
+/// let b = arr2(&[[1.0, 0.0], [0.0, 1.0], [2.0, 2.0]]);
+/// let d = cdist(&a.view(), &b.view(), &Manhattan);
+/// assert_eq!(d, arr2(&[[1.0, 1.0, 4.0], [1.0, 1.0, 2.0]]));
+/// ```
+pub fn cdist<T, M>(a: &ArrayView2<T>, b: &ArrayView2<T>, metric: &M) -> Array2<T>
+where
+    T: Float + 'static,
+    M: Distance<T> + ?Sized,
+{
+    cdist_tiled(a, b, metric, default_tile_size::<T>(a.ncols()))
+}
+
+/// [`cdist`] with an explicit tile size.
+///
+/// Each block of `tile` rows of `a` is compared against each block of `tile` rows of `b` before
+/// moving on, so both blocks stay in cache. The result does not depend on the tile size; only
+/// the order in which the pairs are evaluated does.
+///
+/// # Panics
+/// Panics if `a` and `b` do not have the same number of columns, or if `tile` is zero.
+pub fn cdist_tiled<T, M>(a: &ArrayView2<T>, b: &ArrayView2<T>, metric: &M, tile: usize) -> Array2<T>
+where
+    T: Float + 'static,
+    M: Distance<T> + ?Sized,
+{
+    let mut out = Array2::zeros((a.nrows(), b.nrows()));
+    cdist_into_tiled(a, b, metric, tile, &mut out.view_mut());
+    out
+}
+
+/// Computes the pairwise distance matrix between the rows of `a` and `b`, writing into `out`.
+///
+/// This is the allocation-free counterpart of [`cdist`].
+///
+/// # Panics
+/// Panics if `a` and `b` do not have the same number of columns, or if `out` is not
+/// `a.nrows() x b.nrows()`.
+pub fn cdist_into<T, M>(
+    a: &ArrayView2<T>,
+    b: &ArrayView2<T>,
+    metric: &M,
+    out: &mut ArrayViewMut2<T>,
+) where
+    T: Float + 'static,
+    M: Distance<T> + ?Sized,
+{
+    cdist_into_tiled(a, b, metric, default_tile_size::<T>(a.ncols()), out);
+}
+
+/// [`cdist_into`] with an explicit tile size, see [`cdist_tiled`].
+///
+/// # Panics
+/// Panics if `a` and `b` do not have the same number of columns, if `out` is not
+/// `a.nrows() x b.nrows()`, or if `tile` is zero.
+pub fn cdist_into_tiled<T, M>(
+    a: &ArrayView2<T>,
+    b: &ArrayView2<T>,
+    metric: &M,
+    tile: usize,
+    out: &mut ArrayViewMut2<T>,
+) where
+    T: Float + 'static,
+    M: Distance<T> + ?Sized,
+{
+    assert!(tile > 0, "Tile size must be positive.");
+    assert_eq!(
+        a.ncols(),
+        b.ncols(),
+        "Input matrices must have the same number of columns."
+    );
+    assert_eq!(
+        out.dim(),
+        (a.nrows(), b.nrows()),
+        "Output must have shape (a.nrows(), b.nrows())."
+    );
+
+    fill_tiles(a, b, metric, tile, 0, out);
+}
+
+/// Fills `out` with the distances between the rows of `a` and `b`, one `tile x tile` block at a
+/// time. Row `i` of `out` corresponds to row `row_offset + i` of `a`.
+///
+/// Each block is first offered to [`Distance::block_distances`], so metrics with a
+/// matrix-multiplication kernel fill it in one product; the others are evaluated pair by pair.
+fn fill_tiles<T, M>(
+    a: &ArrayView2<T>,
+    b: &ArrayView2<T>,
+    metric: &M,
+    tile: usize,
+    row_offset: usize,
+    out: &mut ArrayViewMut2<T>,
+) where
+    T: Float + 'static,
+    M: Distance<T> + ?Sized,
+{
+    let (n, m) = out.dim();
+    for i0 in (0..n).step_by(tile) {
+        let i1 = (i0 + tile).min(n);
+        for j0 in (0..m).step_by(tile) {
+            let j1 = (j0 + tile).min(m);
+            let a_tile = a.slice(s![row_offset + i0..row_offset + i1, ..]);
+            let b_tile = b.slice(s![j0..j1, ..]);
+            if let Some(block) = metric.block_distances(&a_tile, &b_tile) {
+                out.slice_mut(s![i0..i1, j0..j1]).assign(&block);
+                continue;
+            }
+            for i in i0..i1 {
+                let a_row = a.row(row_offset + i);
+                for j in j0..j1 {
+                    out[(i, j)] = metric.distance(&a_row, &b.row(j));
+                }
+            }
+        }
+    }
+}
+
+/// Computes the pairwise distances between the rows of `data` in SciPy's condensed form.
+///
+/// Only the upper triangle of the (symmetric) distance matrix is computed, ordered row by row:
+/// `d(0, 1), d(0, 2), ..., d(0, n - 1), d(1, 2), ...`. Use [`condensed_index`] to locate the
+/// entry for a pair of rows.
+///
+/// # Arguments
+/// * `data` - A 2D array view with `n` rows.
+/// * `metric` - Any [`Distance`].
+///
+/// # Returns
+/// An `Array1<T>` of length `n * (n - 1) / 2`.
+///
+/// Like [`cdist`], the triangle is computed in cache-sized tiles; use [`pdist_tiled`] to choose
+/// the tile size.
+///
+/// # Example
+/// ```
+/// use ndarray::{arr1, arr2};
+/// use fast_distances::*;
+///
+/// let data = arr2(&[[0.0, 0.0], [3.0, 4.0], [6.0, 8.0]]);
+/// let d = pdist(&data.view(), &Euclidean);
+/// assert_eq!(d, arr1(&[5.0, 10.0, 5.0]));
+/// assert_eq!(d[condensed_index(3, 2, 0)], 10.0);
+/// ```
+pub fn pdist<T, M>(data: &ArrayView2<T>, metric: &M) -> Array1<T>
+where
+    T: Clone + Zero,
+    M: Distance<T> + ?Sized,
+{
+    pdist_tiled(data, metric, default_tile_size::<T>(data.ncols()))
+}
+
+/// [`pdist`] with an explicit tile size, see [`cdist_tiled`].
+///
+/// # Panics
+/// Panics if `tile` is zero.
+pub fn pdist_tiled<T, M>(data: &ArrayView2<T>, metric: &M, tile: usize) -> Array1<T>
+where
+    T: Clone + Zero,
+    M: Distance<T> + ?Sized,
+{
+    let mut out = Array1::zeros(condensed_len(data.nrows()));
+    pdist_into_tiled(data, metric, tile, &mut out.view_mut());
+    out
+}
+
+/// Computes the condensed pairwise distances between the rows of `data`, writing into `out`.
+///
+/// This is the allocation-free counterpart of [`pdist`].
+///
+/// # Panics
+/// Panics if `out` does not have length `condensed_len(data.nrows())`.
+pub fn pdist_into<T, M>(data: &ArrayView2<T>, metric: &M, out: &mut ArrayViewMut1<T>)
+where
+    M: Distance<T> + ?Sized,
+{
+    pdist_into_tiled(data, metric, default_tile_size::<T>(data.ncols()), out);
+}
+
+/// [`pdist_into`] with an explicit tile size, see [`cdist_tiled`].
+///
+/// # Panics
+/// Panics if `out` does not have length `condensed_len(data.nrows())`, or if `tile` is zero.
+pub fn pdist_into_tiled<T, M>(
+    data: &ArrayView2<T>,
+    metric: &M,
+    tile: usize,
+    out: &mut ArrayViewMut1<T>,
+) where
+    M: Distance<T> + ?Sized,
+{
+    assert!(tile > 0, "Tile size must be positive.");
+    let n = data.nrows();
+    assert_eq!(
+        out.len(),
+        condensed_len(n),
+        "Output must have length n * (n - 1) / 2."
+    );
+
+    fill_condensed_tiles(data, metric, tile, 0..n, out);
+}
+
+/// Returns the position of the pair `(i, i + 1)` in a condensed vector over `n` points, which is
+/// also the number of pairs in the rows before `i`.
+fn condensed_row_start(n: usize, i: usize) -> usize {
+    n * i - i * (i + 1) / 2
+}
+
+/// Fills the condensed distances of the pairs `(i, j)` with `i` in `rows` and `i < j`, one
+/// `tile x tile` block at a time. `out` starts at the pair `(rows.start, rows.start + 1)`.
+fn fill_condensed_tiles<T, M>(
+    data: &ArrayView2<T>,
+    metric: &M,
+    tile: usize,
+    rows: Range<usize>,
+    out: &mut ArrayViewMut1<T>,
+) where
+    M: Distance<T> + ?Sized,
+{
+    let n = data.nrows();
+    let base = condensed_row_start(n, rows.start);
+    // Only the blocks on or above the diagonal contain pairs with i < j.
+    for i0 in rows.clone().step_by(tile) {
+        let i1 = (i0 + tile).min(rows.end);
+        for j0 in (i0..n).step_by(tile) {
+            let j1 = (j0 + tile).min(n);
+            for i in i0..i1 {
+                let row_i = data.row(i);
+                let row_start = condensed_row_start(n, i) - base;
+                for j in (j0.max(i + 1))..j1 {
+                    out[row_start + j - i - 1] = metric.distance(&row_i, &data.row(j));
+                }
+            }
+        }
+    }
+}
+
+/// Parallel version of [`cdist`], available with the `parallel` feature.
+///
+/// The output rows are split into one band per thread of the rayon thread pool; each band is
+/// filled independently, tile by tile, so the result is identical to [`cdist`] (up to rounding
+/// for metrics with a [`Distance::block_distances`] kernel, whose tiles start at different rows).
+///
+/// # Panics
+/// Panics if `a` and `b` do not have the same number of columns.
+#[cfg(feature = "parallel")]
+pub fn par_cdist<T, M>(a: &ArrayView2<T>, b: &ArrayView2<T>, metric: &M) -> Array2<T>
+where
+    T: Float + Send + Sync + 'static,
+    M: Distance<T> + Sync + ?Sized,
+{
+    par_cdist_with(a, b, metric, Parallelism::Global)
+}
+
+/// [`par_cdist`] on the thread pool selected by `parallelism`.
+///
+/// # Panics
+/// Panics if `a` and `b` do not have the same number of columns.
+#[cfg(feature = "parallel")]
+pub fn par_cdist_with<T, M>(
+    a: &ArrayView2<T>,
+    b: &ArrayView2<T>,
+    metric: &M,
+    parallelism: Parallelism,
+) -> Array2<T>
+where
+    T: Float + Send + Sync + 'static,
+    M: Distance<T> + Sync + ?Sized,
+{
+    par_cdist_tiled_with(a, b, metric, default_tile_size::<T>(a.ncols()), parallelism)
+}
+
+/// [`par_cdist`] with an explicit tile size, see [`cdist_tiled`].
+///
+/// # Panics
+/// Panics if `a` and `b` do not have the same number of columns, or if `tile` is zero.
+#[cfg(feature = "parallel")]
+pub fn par_cdist_tiled<T, M>(
+    a: &ArrayView2<T>,
+    b: &ArrayView2<T>,
+    metric: &M,
+    tile: usize,
+) -> Array2<T>
+where
+    T: Float + Send + Sync + 'static,
+    M: Distance<T> + Sync + ?Sized,
+{
+    par_cdist_tiled_with(a, b, metric, tile, Parallelism::Global)
+}
+
+/// [`par_cdist_tiled`] on the thread pool selected by `parallelism`.
+///
+/// # Panics
+/// Panics if `a` and `b` do not have the same number of columns, or if `tile` is zero.
+#[cfg(feature = "parallel")]
+pub fn par_cdist_tiled_with<T, M>(
+    a: &ArrayView2<T>,
+    b: &ArrayView2<T>,
+    metric: &M,
+    tile: usize,
+    parallelism: Parallelism,
+) -> Array2<T>
+where
+    T: Float + Send + Sync + 'static,
+    M: Distance<T> + Sync + ?Sized,
+{
+    use ndarray::parallel::prelude::*;
+    use ndarray::Axis;
+
+    assert!(tile > 0, "Tile size must be positive.");
+    assert_eq!(
+        a.ncols(),
+        b.ncols(),
+        "Input matrices must have the same number of columns."
+    );
+
+    let mut out = Array2::zeros((a.nrows(), b.nrows()));
+    parallelism.install(|| {
+        // The parallel split is independent of the cache tile: with the default tile, a few
+        // thousand rows would otherwise form a single band and run on one thread.
+        let band_rows = par_band_rows(a.nrows(), rayon::current_num_threads());
+        out.axis_chunks_iter_mut(Axis(0), band_rows)
+            .into_par_iter()
+            .enumerate()
+            .for_each(|(band, mut chunk)| {
+                fill_tiles(a, b, metric, tile, band * band_rows, &mut chunk)
+            })
+    });
+    out
+}
+
+/// Returns the number of output rows per parallel band, so that `nrows` rows form one band for
+/// each of `threads` threads.
+#[cfg(feature = "parallel")]
+fn par_band_rows(nrows: usize, threads: usize) -> usize {
+    nrows.div_ceil(threads.max(1)).max(1)
+}
+
+/// Parallel version of [`pdist`], available with the `parallel` feature.
+///
+/// The upper triangle is split into one band of consecutive rows per thread of the rayon
+/// thread pool, with roughly the same number of pairs in every band. Each band fills its own
+/// slice of the condensed output tile by tile, as [`pdist`] does, so the result is identical.
+#[cfg(feature = "parallel")]
+pub fn par_pdist<T, M>(data: &ArrayView2<T>, metric: &M) -> Array1<T>
+where
+    T: Clone + Zero + Send + Sync,
+    M: Distance<T> + Sync + ?Sized,
+{
+    par_pdist_with(data, metric, Parallelism::Global)
+}
+
+/// [`par_pdist`] on the thread pool selected by `parallelism`.
+#[cfg(feature = "parallel")]
+pub fn par_pdist_with<T, M>(data: &ArrayView2<T>, metric: &M, parallelism: Parallelism) -> Array1<T>
+where
+    T: Clone + Zero + Send + Sync,
+    M: Distance<T> + Sync + ?Sized,
+{
+    use rayon::prelude::*;
+
+    let n = data.nrows();
+    let tile = default_tile_size::<T>(data.ncols());
+    let mut out = Array1::zeros(condensed_len(n));
+    parallelism.install(|| {
+        let bounds = pdist_band_bounds(n, rayon::current_num_threads());
+        let mut rest = out.as_slice_mut().expect("A new array is contiguous.");
+        let mut bands = Vec::with_capacity(bounds.len());
+        for rows in bounds.windows(2) {
+            let len = condensed_row_start(n, rows[1]) - condensed_row_start(n, rows[0]);
+            let (band, tail) = std::mem::take(&mut rest).split_at_mut(len);
+            bands.push((rows[0]..rows[1], band));
+            rest = tail;
+        }
+        bands.into_par_iter().for_each(|(rows, band)| {
+            fill_condensed_tiles(data, metric, tile, rows, &mut ArrayViewMut1::from(band))
+        });
+    });
+    out
+}
+
+/// Returns the row boundaries `0 = r_0 < r_1 < ... = n` that split the upper triangle over `n`
+/// points into at most `bands` bands of consecutive rows with roughly equal numbers of pairs.
+///
+/// Row `i` holds `n - 1 - i` pairs, so the early bands have fewer rows than the late ones.
+#[cfg(feature = "parallel")]
+fn pdist_band_bounds(n: usize, bands: usize) -> Vec<usize> {
+    let (total, bands) = (condensed_len(n), bands.max(1));
+    let mut bounds = vec![0];
+    let mut done = 0;
+    for i in 0..n {
+        done += n - 1 - i;
+        if bounds.len() < bands && done * bands >= total * bounds.len() {
+            bounds.push(i + 1);
+        }
+    }
+    if bounds.last() != Some(&n) {
+        bounds.push(n);
+    }
+    bounds
+}
+
+/// Returns the length of the condensed distance vector for `n` points, `n * (n - 1) / 2`.
+pub fn condensed_len(n: usize) -> usize {
+    n * n.saturating_sub(1) / 2
+}
+
+/// Returns the position of the pair `(i, j)` in a condensed distance vector over `n` points.
+///
+/// The pair is unordered, so `condensed_index(n, i, j) == condensed_index(n, j, i)`.
+///
+/// # Panics
+/// Panics if `i == j` (the diagonal is not stored) or if either index is not smaller than `n`.
+pub fn condensed_index(n: usize, i: usize, j: usize) -> usize {
+    assert!(
+        i < n && j < n,
+        "Indices must be smaller than the number of points."
+    );
+    assert_ne!(i, j, "The diagonal is not stored in the condensed form.");
+
+    let (i, j) = if i < j { (i, j) } else { (j, i) };
+    n * i - i * (i + 1) / 2 + j - i - 1
+}
+
+/// Returns the pair `(i, j)` with `i < j` stored at position `k` of a condensed distance vector
+/// over `n` points. This is the inverse of [`condensed_index`].
+///
+/// # Panics
+/// Panics if `k` is not smaller than `condensed_len(n)`.
+pub fn condensed_to_pair(n: usize, k: usize) -> (usize, usize) {
+    assert!(k < condensed_len(n), "Condensed index out of range.");
+
+    let mut i = 0;
+    let mut row_start = 0;
+    while row_start + (n - i - 1) <= k {
+        row_start += n - i - 1;
+        i += 1;
+    }
+    (i, i + 1 + k - row_start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use ndarray::{arr1, arr2, Array2};
+
+    #[test]
+    fn test_distances_to_point_euclidean() {
+        let data = arr2(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [1.0, 2.0, 4.0]]);
+        let query = arr1(&[1.0, 2.0, 3.0]);
+
+        let dists = distances_to_point(&data.view(), &query.view(), &Euclidean);
+        for (i, row) in data.rows().into_iter().enumerate() {
+            assert_eq!(dists[i], euclidean(&row, &query.view()));
+        }
+    }
+
+    #[test]
+    fn test_distances_to_point_parameterized_and_closure() {
+        let data = arr2(&[[0.0f32, 0.0], [1.0, 1.0]]);
+        let query = arr1(&[1.0f32, 0.0]);
+
+        let dists = distances_to_point(&data.view(), &query.view(), &Minkowski { p: 1.0 });
+        assert_eq!(dists, arr1(&[1.0, 1.0]));
+
+        let metric = from_fn(|x: &ArrayView1<f32>, y: &ArrayView1<f32>| chebyshev(x, y) * 2.0);
+        let dists = distances_to_point(&data.view(), &query.view(), &metric);
+        assert_eq!(dists, arr1(&[2.0, 2.0]));
+    }
+
+    #[test]
+    fn test_distances_to_point_empty() {
+        let data = Array2::<f64>::zeros((0, 3));
+        let query = arr1(&[1.0, 2.0, 3.0]);
+
+        let dists = distances_to_point(&data.view(), &query.view(), &Cosine);
+        assert_eq!(dists.len(), 0);
+    }
+
+    #[test]
+    fn test_cdist_matches_pairwise_calls() {
+        let a = arr2(&[[1.0, 2.0, 3.0], [0.0, -1.0, 2.0]]);
+        let b = arr2(&[[4.0, 5.0, 6.0], [1.0, 2.0, 3.0], [0.5, 0.5, 0.5]]);
+
+        let d = cdist(&a.view(), &b.view(), &Cosine);
+        assert_eq!(d.dim(), (2, 3));
+        for i in 0..2 {
+            for j in 0..3 {
+                assert_eq!(d[(i, j)], cosine(&a.row(i), &b.row(j)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_cdist_parameterized_metrics() {
+        let a = arr2(&[[1.0, 2.0], [3.0, 4.0]]);
+        let b = arr2(&[[0.0, 0.0]]);
+
+        let metric = WeightedMinkowski {
+            w: Some(arr1(&[1.0, 0.0])),
+            p: 2.0,
+        };
+        assert_eq!(cdist(&a.view(), &b.view(), &metric), arr2(&[[1.0], [3.0]]));
+
+        let metric = Mahalanobis {
+            vinv: arr2(&[[4.0, 0.0], [0.0, 0.0]]),
+        };
+        assert_eq!(cdist(&a.view(), &b.view(), &metric), arr2(&[[2.0], [6.0]]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Input matrices must have the same number of columns.")]
+    fn test_cdist_dimension_mismatch() {
+        let a = arr2(&[[1.0, 2.0]]);
+        let b = arr2(&[[1.0, 2.0, 3.0]]);
+        cdist(&a.view(), &b.view(), &Euclidean);
+    }
+
+    #[test]
+    fn test_pdist_matches_cdist_upper_triangle() {
+        let data = arr2(&[[1.0, 2.0], [0.0, 5.0], [3.0, -1.0], [2.0, 2.0]]);
+
+        let condensed = pdist(&data.view(), &Canberra);
+        let square = cdist(&data.view(), &data.view(), &Canberra);
+        assert_eq!(condensed.len(), condensed_len(4));
+        for i in 0..4 {
+            for j in 0..4 {
+                if i != j {
+                    assert_eq!(condensed[condensed_index(4, i, j)], square[(i, j)]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pdist_small_inputs() {
+        assert_eq!(
+            pdist(&Array2::<f64>::zeros((0, 2)).view(), &Euclidean).len(),
+            0
+        );
+        assert_eq!(pdist(&arr2(&[[1.0, 2.0]]).view(), &Euclidean).len(), 0);
+    }
+
+    #[test]
+    fn test_condensed_index_roundtrip() {
+        let n = 6;
+        for k in 0..condensed_len(n) {
+            let (i, j) = condensed_to_pair(n, k);
+            assert!(i < j);
+            assert_eq!(condensed_index(n, i, j), k);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "The diagonal is not stored in the condensed form.")]
+    fn test_condensed_index_diagonal() {
+        condensed_index(4, 2, 2);
+    }
+
+    #[test]
+    fn test_into_variants_match_allocating_versions() {
+        let a = arr2(&[[1.0, 2.0], [0.0, 5.0], [3.0, -1.0]]);
+        let b = arr2(&[[2.0, 2.0], [1.0, 0.0]]);
+        let query = arr1(&[0.5, 0.5]);
+
+        let mut out = Array2::<f64>::zeros((3, 2));
+        cdist_into(&a.view(), &b.view(), &Euclidean, &mut out.view_mut());
+        assert_eq!(out, cdist(&a.view(), &b.view(), &Euclidean));
+
+        let mut out = Array1::<f64>::zeros(3);
+        distances_to_point_into(&a.view(), &query.view(), &Euclidean, &mut out.view_mut());
+        assert_eq!(
+            out,
+            distances_to_point(&a.view(), &query.view(), &Euclidean)
+        );
+
+        let mut out = Array1::<f64>::zeros(3);
+        pdist_into(&a.view(), &Euclidean, &mut out.view_mut());
+        assert_eq!(out, pdist(&a.view(), &Euclidean));
+    }
+
+    #[test]
+    fn test_cdist_into_reuses_buffer_slice() {
+        let a = arr2(&[[1.0, 2.0]]);
+        let b = arr2(&[[2.0, 2.0], [1.0, 0.0]]);
+
+        let mut buffer = Array2::<f64>::from_elem((2, 2), -1.0);
+        cdist_into(
+            &a.view(),
+            &b.view(),
+            &Manhattan,
+            &mut buffer.slice_mut(ndarray::s![0..1, ..]),
+        );
+        assert_eq!(buffer, arr2(&[[1.0, 2.0], [-1.0, -1.0]]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Output must have shape (a.nrows(), b.nrows()).")]
+    fn test_cdist_into_wrong_shape() {
+        let a = arr2(&[[1.0, 2.0]]);
+        let b = arr2(&[[2.0, 2.0], [1.0, 0.0]]);
+        let mut out = Array2::<f64>::zeros((2, 1));
+        cdist_into(&a.view(), &b.view(), &Euclidean, &mut out.view_mut());
+    }
+
+    #[test]
+    #[should_panic(expected = "Output must have length n * (n - 1) / 2.")]
+    fn test_pdist_into_wrong_length() {
+        let a = arr2(&[[1.0, 2.0], [0.0, 5.0], [3.0, -1.0]]);
+        let mut out = Array1::<f64>::zeros(2);
+        pdist_into(&a.view(), &Euclidean, &mut out.view_mut());
+    }
+
+    #[test]
+    #[should_panic(expected = "Data columns and query must have the same length.")]
+    fn test_distances_to_point_dimension_mismatch() {
+        let data = arr2(&[[1.0, 2.0]]);
+        let query = arr1(&[1.0, 2.0, 3.0]);
+        distances_to_point(&data.view(), &query.view(), &Euclidean);
+    }
+
+    #[test]
+    fn test_cdist_stable_euclidean() {
+        let a = arr2(&[[3e30f32, 0.0], [1.0, 1.0]]);
+        let b = arr2(&[[0.0f32, 4e30]]);
+
+        let d = cdist(&a.view(), &b.view(), &EuclideanStable);
+        assert_eq!(d[(0, 0)], 5e30);
+        assert!(cdist(&a.view(), &b.view(), &Euclidean)[(0, 0)].is_infinite());
+    }
+
+    #[test]
+    fn test_cdist_uses_block_kernels() {
+        // Large offsets make the GEMM expansion cancel badly, so identical rows come out
+        // slightly negative before the clamp.
+        let a = Array2::from_shape_fn((7, 6), |(i, j)| 1.0e8 + ((i * 5 + j * 3) % 7) as f64);
+        let b = Array2::from_shape_fn((5, 6), |(i, j)| 1.0e8 + ((i * 5 + j * 3) % 7) as f64);
+
+        let scalar = cdist(&a.view(), &b.view(), &Euclidean.as_fn());
+        // The squared distances are accurate up to rounding of the squared norms.
+        let tolerance = 16.0 * f64::EPSILON * 2.0 * 6.0 * (1.0e8 + 6.0f64).powi(2);
+        for tile in [2, 100] {
+            let d = cdist_tiled(&a.view(), &b.view(), &Euclidean, tile);
+            assert!(d.iter().all(|&v| v >= 0.0));
+            assert!(d
+                .iter()
+                .zip(&scalar)
+                .all(|(x, y)| (x * x - y * y).abs() <= tolerance));
+            #[cfg(feature = "parallel")]
+            assert_eq!(par_cdist_tiled(&a.view(), &b.view(), &Euclidean, tile), d);
+        }
+        assert_eq!(
+            cdist(&a.view(), &b.view(), &Euclidean),
+            euclidean_cdist(&a.view(), &b.view())
+        );
+    }
+
+    #[test]
+    fn test_cdist_uses_cosine_block_kernel() {
+        let a = Array2::from_shape_fn((7, 6), |(i, j)| ((i * 5 + j * 3) % 7) as f64 - 2.0);
+        let b = Array2::from_shape_fn((5, 6), |(i, j)| ((i * 2 + j * 5) % 7) as f64 - 3.0);
+
+        assert_eq!(
+            cdist(&a.view(), &b.view(), &Cosine),
+            cosine_cdist(&a.view(), &b.view())
+        );
+        let scalar = cdist(&a.view(), &b.view(), &Cosine.as_fn());
+        for tile in [3, 100] {
+            let d = cdist_tiled(&a.view(), &b.view(), &Cosine, tile);
+            assert!(d.iter().zip(&scalar).all(|(x, y)| (x - y).abs() < 1e-12));
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_par_variants_match_serial() {
+        let a = Array2::from_shape_fn((23, 4), |(i, j)| ((i * 3 + j * 7) % 11) as f64 / 11.0);
+        let b = Array2::from_shape_fn((17, 4), |(i, j)| ((i * 5 + j) % 13) as f64 / 13.0);
+
+        assert_eq!(
+            par_cdist(&a.view(), &b.view(), &Hellinger),
+            cdist(&a.view(), &b.view(), &Hellinger)
+        );
+        assert_eq!(
+            par_pdist(&a.view(), &Minkowski { p: 3.0 }),
+            pdist(&a.view(), &Minkowski { p: 3.0 })
+        );
+        assert_eq!(
+            par_pdist(&a.slice(ndarray::s![..1, ..]), &Euclidean).len(),
+            0
+        );
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_par_variants_with_explicit_pool() {
+        let a = Array2::from_shape_fn((19, 3), |(i, j)| ((i * 4 + j * 5) % 9) as f64);
+        let b = Array2::from_shape_fn((11, 3), |(i, j)| ((i + j * 2) % 5) as f64);
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap();
+
+        for parallelism in [
+            Parallelism::Global,
+            Parallelism::Threads(1),
+            Parallelism::Threads(0),
+            Parallelism::Pool(&pool),
+        ] {
+            assert_eq!(
+                par_cdist_with(&a.view(), &b.view(), &Manhattan, parallelism),
+                cdist(&a.view(), &b.view(), &Manhattan)
+            );
+            assert_eq!(
+                par_cdist_tiled_with(&a.view(), &b.view(), &Manhattan, 4, parallelism),
+                cdist(&a.view(), &b.view(), &Manhattan)
+            );
+            assert_eq!(
+                par_pdist_with(&a.view(), &Euclidean, parallelism),
+                pdist(&a.view(), &Euclidean)
+            );
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_par_cdist_splits_rows_across_threads() {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build()
+            .unwrap();
+        let band_rows = pool.install(|| par_band_rows(300, rayon::current_num_threads()));
+        assert_eq!(band_rows, 75);
+        assert_eq!(300usize.div_ceil(band_rows), 4);
+        // The default tile would hold all 300 rows, which previously meant a single band.
+        assert!(default_tile_size::<f64>(4) >= 300);
+        assert_eq!(par_band_rows(7, 4), 2);
+        assert_eq!(par_band_rows(0, 4), 1);
+        assert_eq!(par_band_rows(5, 0), 5);
+
+        let a = Array2::from_shape_fn((300, 4), |(i, j)| ((i * 3 + j * 7) % 11) as f64);
+        let b = Array2::from_shape_fn((5, 4), |(i, j)| ((i * 5 + j) % 13) as f64);
+        assert_eq!(
+            par_cdist_with(&a.view(), &b.view(), &Euclidean, Parallelism::Pool(&pool)),
+            cdist(&a.view(), &b.view(), &Euclidean)
+        );
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_par_pdist_bands_balance_pairs() {
+        let n = 300;
+        let bounds = pdist_band_bounds(n, 4);
+        assert_eq!(bounds.len(), 5);
+        assert_eq!((bounds[0], bounds[4]), (0, n));
+        let quarter = condensed_len(n) / 4;
+        for rows in bounds.windows(2) {
+            let pairs = condensed_row_start(n, rows[1]) - condensed_row_start(n, rows[0]);
+            // Each band overshoots its share by at most one row of pairs.
+            assert!(pairs.abs_diff(quarter) < n);
+        }
+        assert!(bounds[1] - bounds[0] < bounds[4] - bounds[3]);
+
+        assert_eq!(pdist_band_bounds(0, 4), vec![0]);
+        assert_eq!(pdist_band_bounds(1, 4), vec![0, 1]);
+        assert_eq!(pdist_band_bounds(5, 1), vec![0, 5]);
+
+        let data = Array2::from_shape_fn((n, 3), |(i, j)| ((i * 7 + j * 3) % 13) as f64);
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build()
+            .unwrap();
+        assert_eq!(
+            par_pdist_with(&data.view(), &Manhattan, Parallelism::Pool(&pool)),
+            pdist(&data.view(), &Manhattan)
+        );
+    }
+
+    #[test]
+    fn test_tile_size_does_not_change_result() {
+        let a = Array2::from_shape_fn((13, 5), |(i, j)| ((i * 7 + j * 3) % 10) as f64 - 4.5);
+        let b = Array2::from_shape_fn((9, 5), |(i, j)| ((i * 2 + j * 5) % 7) as f64 - 3.0);
+
+        let expected = Array2::from_shape_fn((13, 9), |(i, j)| manhattan(&a.row(i), &b.row(j)));
+        let mut condensed = Vec::new();
+        for i in 0..13 {
+            for j in (i + 1)..13 {
+                condensed.push(manhattan(&a.row(i), &a.row(j)));
+            }
+        }
+        let condensed = Array1::from_vec(condensed);
+
+        for tile in [1, 2, 4, 5, 13, 100] {
+            assert_eq!(
+                cdist_tiled(&a.view(), &b.view(), &Manhattan, tile),
+                expected
+            );
+            assert_eq!(pdist_tiled(&a.view(), &Manhattan, tile), condensed);
+            #[cfg(feature = "parallel")]
+            assert_eq!(
+                par_cdist_tiled(&a.view(), &b.view(), &Manhattan, tile),
+                expected
+            );
+        }
+        assert_eq!(cdist(&a.view(), &b.view(), &Manhattan), expected);
+        assert_eq!(pdist(&a.view(), &Manhattan), condensed);
+    }
+
+    #[test]
+    fn test_default_tile_size() {
+        assert_eq!(default_tile_size::<f64>(16), 1024);
+        assert_eq!(default_tile_size::<f32>(16), 2048);
+        assert_eq!(default_tile_size::<f64>(1 << 20), 1);
+        assert!(default_tile_size::<f64>(0) > 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Tile size must be positive.")]
+    fn test_zero_tile_size() {
+        let a = arr2(&[[1.0, 2.0]]);
+        cdist_tiled(&a.view(), &a.view(), &Euclidean, 0);
+    }
+}
@@ -0,0 +1,79 @@
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+/// Selects the rayon thread pool that the `par_*_with` functions run on.
+///
+/// The plain `par_*` functions always use rayon's global pool, which is sized to the whole
+/// machine. Applications that already manage their own threads, such as servers with a
+/// dedicated compute pool, can pass an existing pool or cap the number of threads instead.
+///
+/// # Example
+/// ```
+/// use ndarray::arr2;
+/// use fast_distances::*;
+///
+/// let a = arr2(&[[0.0, 0.0], [3.0, 4.0]]);
+/// let pool = rayon::ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+///
+/// let expected = cdist(&a.view(), &a.view(), &Euclidean);
+/// assert_eq!(par_cdist_with(&a.view(), &a.view(), &Euclidean, Parallelism::Pool(&pool)), expected);
+/// assert_eq!(par_cdist_with(&a.view(), &a.view(), &Euclidean, Parallelism::Threads(1)), expected);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Parallelism<'a> {
+    /// Runs on rayon's global thread pool, like the plain `par_*` functions.
+    #[default]
+    Global,
+    /// Runs on a temporary pool with the given number of threads, built for each call.
+    ///
+    /// Zero lets rayon pick the number of threads, as for the global pool. Building a pool
+    /// spawns threads, so prefer [`Parallelism::Pool`] when calling repeatedly.
+    Threads(usize),
+    /// Runs on an existing pool owned by the caller.
+    Pool(&'a ThreadPool),
+}
+
+impl Parallelism<'_> {
+    /// Runs `op` on the selected thread pool and returns its result.
+    ///
+    /// Rayon calls made inside `op` use that pool rather than the global one.
+    ///
+    /// # Panics
+    /// Panics if a [`Parallelism::Threads`] pool cannot be built.
+    pub fn install<R, F>(self, op: F) -> R
+    where
+        R: Send,
+        F: FnOnce() -> R + Send,
+    {
+        match self {
+            Parallelism::Global => op(),
+            Parallelism::Threads(num_threads) => ThreadPoolBuilder::new()
+                .num_threads(num_threads)
+                .build()
+                .expect("Failed to build the rayon thread pool.")
+                .install(op),
+            Parallelism::Pool(pool) => pool.install(op),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_install_uses_selected_pool() {
+        let pool = ThreadPoolBuilder::new().num_threads(3).build().unwrap();
+        assert_eq!(
+            Parallelism::Pool(&pool).install(rayon::current_num_threads),
+            3
+        );
+        assert_eq!(
+            Parallelism::Threads(2).install(rayon::current_num_threads),
+            2
+        );
+        assert_eq!(
+            Parallelism::Global.install(rayon::current_num_threads),
+            rayon::current_num_threads()
+        );
+    }
+}
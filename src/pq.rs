@@ -0,0 +1,366 @@
+//! Product quantization for compact storage and fast approximate distances.
+//!
+//! A [`ProductQuantizer`] splits vectors into `m` equally sized subvectors and learns a
+//! codebook of up to 256 centroids per subspace with k-means. Every vector is then stored as `m`
+//! bytes, the indices of its nearest centroid in each subspace. To compare a query against many
+//! codes, [`ProductQuantizer::distance_table`] precomputes the distance from each query
+//! subvector to every centroid once; the distance to a code is then the sum of `m` table
+//! lookups (asymmetric distance computation), independently of the original dimension.
+
+use ndarray::{s, Array1, Array2, ArrayView1, ArrayView2};
+use num::Float;
+
+use crate::rdist;
+use crate::rng::SplitMix64;
+
+/// The quantity approximated by a [`DistanceTable`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PqDistance {
+    /// The [`euclidean`](crate::euclidean) distance between the query and the decoded vector.
+    Euclidean,
+    /// The inner product between the query and the decoded vector (a similarity).
+    InnerProduct,
+}
+
+/// Codebooks learned on a sample of vectors, one per subspace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProductQuantizer<T> {
+    codebooks: Vec<Array2<T>>,
+    subspace_dim: usize,
+}
+
+impl<T> ProductQuantizer<T>
+where
+    T: Float + 'static,
+{
+    /// Trains `n_subspaces` codebooks of `n_centroids` centroids each on the rows of `data`.
+    ///
+    /// Each codebook is fitted with `iterations` rounds of Lloyd's k-means, starting from
+    /// distinct rows of `data` picked with the given seed. Centroids that lose all their points
+    /// keep their previous position.
+    ///
+    /// # Panics
+    /// Panics if the number of columns of `data` is not a positive multiple of `n_subspaces`,
+    /// if `n_centroids` is not in `1..=256`, or if `data` has fewer rows than `n_centroids`.
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::Array2;
+    /// use fast_distances::*;
+    ///
+    /// let data = Array2::from_shape_fn((500, 16), |(i, j)| ((i * 7 + j * 3) % 13) as f64);
+    /// let pq = ProductQuantizer::train(&data.view(), 4, 16, 10, 0);
+    ///
+    /// let codes = pq.encode(&data.row(0));
+    /// assert_eq!(codes.len(), 4);
+    ///
+    /// let table = pq.distance_table(&data.row(1), PqDistance::Euclidean);
+    /// let approx = table.distance(&codes);
+    /// assert_eq!(approx, euclidean(&data.row(1), &pq.decode(&codes).view()));
+    /// ```
+    pub fn train(
+        data: &ArrayView2<T>,
+        n_subspaces: usize,
+        n_centroids: usize,
+        iterations: usize,
+        seed: u64,
+    ) -> Self {
+        assert!(
+            n_subspaces > 0 && data.ncols() > 0 && data.ncols().is_multiple_of(n_subspaces),
+            "The number of columns must be a positive multiple of the number of subspaces."
+        );
+        assert!(
+            (1..=256).contains(&n_centroids),
+            "The number of centroids must be between 1 and 256."
+        );
+        assert!(
+            data.nrows() >= n_centroids,
+            "Data must contain at least one row per centroid."
+        );
+
+        let subspace_dim = data.ncols() / n_subspaces;
+        let mut rng = SplitMix64::new(seed);
+        let codebooks = (0..n_subspaces)
+            .map(|m| {
+                let sub = data.slice(s![.., m * subspace_dim..(m + 1) * subspace_dim]);
+                kmeans(&sub, n_centroids, iterations, &mut rng)
+            })
+            .collect();
+
+        Self {
+            codebooks,
+            subspace_dim,
+        }
+    }
+
+    /// Returns the number of subspaces, i.e. the length of a code.
+    pub fn n_subspaces(&self) -> usize {
+        self.codebooks.len()
+    }
+
+    /// Returns the number of centroids per subspace.
+    pub fn n_centroids(&self) -> usize {
+        self.codebooks[0].nrows()
+    }
+
+    /// Returns the dimension of the vectors being quantized.
+    pub fn dim(&self) -> usize {
+        self.codebooks.len() * self.subspace_dim
+    }
+
+    /// Returns the `n_centroids x subspace_dim` codebook of each subspace.
+    pub fn codebooks(&self) -> &[Array2<T>] {
+        &self.codebooks
+    }
+
+    /// Encodes `x` as the index of the nearest centroid in every subspace.
+    ///
+    /// # Panics
+    /// Panics if `x` does not have [`dim`](Self::dim) elements.
+    pub fn encode(&self, x: &ArrayView1<T>) -> Vec<u8> {
+        assert_eq!(x.len(), self.dim(), "Input must have `dim` elements.");
+        self.codebooks
+            .iter()
+            .enumerate()
+            .map(|(m, codebook)| {
+                let sub = x.slice(s![m * self.subspace_dim..(m + 1) * self.subspace_dim]);
+                nearest_centroid(codebook, &sub) as u8
+            })
+            .collect()
+    }
+
+    /// Encodes every row of `data`, returning an `n x n_subspaces` matrix of codes.
+    ///
+    /// # Panics
+    /// Panics if `data` does not have [`dim`](Self::dim) columns.
+    pub fn encode_rows(&self, data: &ArrayView2<T>) -> Array2<u8> {
+        let mut codes = Array2::zeros((data.nrows(), self.n_subspaces()));
+        for (mut code, row) in codes.rows_mut().into_iter().zip(data.rows()) {
+            for (c, v) in code.iter_mut().zip(self.encode(&row)) {
+                *c = v;
+            }
+        }
+        codes
+    }
+
+    /// Reconstructs the vector represented by `codes` by concatenating its centroids.
+    ///
+    /// # Panics
+    /// Panics if `codes` does not have [`n_subspaces`](Self::n_subspaces) elements or contains
+    /// an index that is not smaller than [`n_centroids`](Self::n_centroids).
+    pub fn decode(&self, codes: &[u8]) -> Array1<T> {
+        assert_eq!(
+            codes.len(),
+            self.n_subspaces(),
+            "Codes must have one element per subspace."
+        );
+        let mut x = Array1::zeros(self.dim());
+        for (m, (codebook, &code)) in self.codebooks.iter().zip(codes).enumerate() {
+            x.slice_mut(s![m * self.subspace_dim..(m + 1) * self.subspace_dim])
+                .assign(&codebook.row(code as usize));
+        }
+        x
+    }
+
+    /// Precomputes the lookup table for comparing `query` against encoded vectors.
+    ///
+    /// Building the table costs as much as comparing `query` with `n_centroids` full vectors;
+    /// every [`DistanceTable::distance`] afterwards costs `n_subspaces` lookups.
+    ///
+    /// # Panics
+    /// Panics if `query` does not have [`dim`](Self::dim) elements.
+    pub fn distance_table(&self, query: &ArrayView1<T>, kind: PqDistance) -> DistanceTable<T> {
+        assert_eq!(query.len(), self.dim(), "Input must have `dim` elements.");
+
+        let mut table = Array2::zeros((self.n_subspaces(), self.n_centroids()));
+        for (m, codebook) in self.codebooks.iter().enumerate() {
+            let sub = query.slice(s![m * self.subspace_dim..(m + 1) * self.subspace_dim]);
+            for (entry, centroid) in table.row_mut(m).iter_mut().zip(codebook.rows()) {
+                *entry = match kind {
+                    PqDistance::Euclidean => rdist(&sub, &centroid),
+                    PqDistance::InnerProduct => sub.dot(&centroid),
+                };
+            }
+        }
+        DistanceTable { table, kind }
+    }
+}
+
+/// Per-subspace distances from one query to every centroid, see
+/// [`ProductQuantizer::distance_table`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistanceTable<T> {
+    table: Array2<T>,
+    kind: PqDistance,
+}
+
+impl<T: Float> DistanceTable<T> {
+    /// Returns the approximate distance (or inner product) between the query and the vector
+    /// encoded by `codes`.
+    ///
+    /// # Panics
+    /// Panics if `codes` does not have one element per subspace or contains an index that is
+    /// out of range.
+    pub fn distance(&self, codes: &[u8]) -> T {
+        assert_eq!(
+            codes.len(),
+            self.table.nrows(),
+            "Codes must have one element per subspace."
+        );
+        let total = codes
+            .iter()
+            .enumerate()
+            .fold(T::zero(), |acc, (m, &c)| acc + self.table[(m, c as usize)]);
+        match self.kind {
+            PqDistance::Euclidean => total.sqrt(),
+            PqDistance::InnerProduct => total,
+        }
+    }
+
+    /// Returns [`distance`](Self::distance) for every row of `codes`, e.g. the output of
+    /// [`ProductQuantizer::encode_rows`].
+    pub fn distances(&self, codes: &ArrayView2<u8>) -> Array1<T> {
+        codes
+            .rows()
+            .into_iter()
+            .map(|row| match row.as_slice() {
+                Some(slice) => self.distance(slice),
+                None => self.distance(&row.to_vec()),
+            })
+            .collect()
+    }
+}
+
+/// Runs Lloyd's k-means on the rows of `data`, starting from distinct random rows.
+fn kmeans<T: Float>(
+    data: &ArrayView2<T>,
+    k: usize,
+    iterations: usize,
+    rng: &mut SplitMix64,
+) -> Array2<T> {
+    // Partial Fisher-Yates shuffle to pick `k` distinct starting rows.
+    let n = data.nrows();
+    let mut indices: Vec<usize> = (0..n).collect();
+    for i in 0..k {
+        let j = i + rng.next_below(n - i);
+        indices.swap(i, j);
+    }
+    let mut centroids = Array2::zeros((k, data.ncols()));
+    for (mut centroid, &i) in centroids.rows_mut().into_iter().zip(&indices[..k]) {
+        centroid.assign(&data.row(i));
+    }
+
+    let mut assignments = vec![usize::MAX; n];
+    for _ in 0..iterations {
+        let mut changed = false;
+        for (assignment, row) in assignments.iter_mut().zip(data.rows()) {
+            let nearest = nearest_centroid(&centroids, &row);
+            changed |= *assignment != nearest;
+            *assignment = nearest;
+        }
+        if !changed {
+            break;
+        }
+
+        let mut sums = Array2::<T>::zeros(centroids.dim());
+        let mut counts = vec![0usize; k];
+        for (&c, row) in assignments.iter().zip(data.rows()) {
+            let mut sum = sums.row_mut(c);
+            sum.zip_mut_with(&row, |s, &v| *s = *s + v);
+            counts[c] += 1;
+        }
+        for (c, &count) in counts.iter().enumerate() {
+            if count > 0 {
+                let count = T::from(count).unwrap();
+                centroids
+                    .row_mut(c)
+                    .assign(&sums.row(c).mapv(|s| s / count));
+            }
+        }
+    }
+    centroids
+}
+
+fn nearest_centroid<T: Float>(centroids: &Array2<T>, x: &ArrayView1<T>) -> usize {
+    let mut best = (0, T::infinity());
+    for (i, centroid) in centroids.rows().into_iter().enumerate() {
+        let d = rdist(x, &centroid);
+        if d < best.1 {
+            best = (i, d);
+        }
+    }
+    best.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::euclidean;
+
+    fn sample(rows: usize, cols: usize) -> Array2<f64> {
+        Array2::from_shape_fn((rows, cols), |(i, j)| {
+            (((i * 37 + j * 11) % 97) as f64 / 13.0).sin()
+        })
+    }
+
+    #[test]
+    fn test_exact_when_centroids_cover_data() {
+        // Eight distinct rows and eight centroids: every subvector becomes a centroid.
+        let data = sample(8, 6);
+        let pq = ProductQuantizer::train(&data.view(), 3, 8, 20, 1);
+        let codes = pq.encode_rows(&data.view());
+        for (row, code) in data.rows().into_iter().zip(codes.rows()) {
+            let decoded = pq.decode(code.as_slice().unwrap());
+            assert!(euclidean(&row, &decoded.view()) < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_tables_match_decoded_vectors() {
+        let data = sample(200, 12);
+        let pq = ProductQuantizer::train(&data.view(), 4, 16, 15, 7);
+        let query = data.row(3).mapv(|v| v + 0.1);
+        let codes = pq.encode_rows(&data.view());
+
+        let euclidean_table = pq.distance_table(&query.view(), PqDistance::Euclidean);
+        let inner_table = pq.distance_table(&query.view(), PqDistance::InnerProduct);
+        let approx = euclidean_table.distances(&codes.view());
+        for (i, code) in codes.rows().into_iter().enumerate() {
+            let decoded = pq.decode(code.as_slice().unwrap());
+            let expected = euclidean(&query.view(), &decoded.view());
+            assert!((approx[i] - expected).abs() < 1e-12);
+            let expected = query.dot(&decoded);
+            assert!((inner_table.distance(code.as_slice().unwrap()) - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_more_centroids_reduce_error() {
+        let data = sample(300, 8);
+        let error = |k: usize| {
+            let pq = ProductQuantizer::train(&data.view(), 2, k, 25, 3);
+            data.rows()
+                .into_iter()
+                .map(|row| rdist(&row, &pq.decode(&pq.encode(&row)).view()))
+                .sum::<f64>()
+        };
+        assert!(error(64) < error(4));
+    }
+
+    #[test]
+    fn test_accessors() {
+        let data = sample(20, 6);
+        let pq = ProductQuantizer::<f64>::train(&data.view(), 2, 5, 3, 0);
+        assert_eq!((pq.n_subspaces(), pq.n_centroids(), pq.dim()), (2, 5, 6));
+        assert_eq!(pq.codebooks()[1].dim(), (5, 3));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "The number of columns must be a positive multiple of the number of subspaces."
+    )]
+    fn test_invalid_subspaces() {
+        let data = sample(10, 5);
+        ProductQuantizer::train(&data.view(), 2, 4, 3, 0);
+    }
+}
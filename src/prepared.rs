@@ -0,0 +1,206 @@
+//! Query-side caching for one-to-many comparisons.
+//!
+//! When a single query is compared against many candidates, metrics such as
+//! [`cosine`](crate::cosine) and [`correlation`](crate::correlation) recompute the same
+//! query statistics (norm, mean) on every call. The prepared wrappers compute them once in
+//! `new` and only do the candidate-side work in `distance_to`.
+
+use ndarray::{Array1, ArrayView1};
+use num::Float;
+
+/// A distance with one argument fixed in advance.
+pub trait PreparedDistance<T> {
+    /// Computes the distance between the prepared query and `candidate`.
+    fn distance_to(&self, candidate: &ArrayView1<T>) -> T;
+}
+
+/// [`cosine`](crate::cosine) distance to a fixed query, with the query norm cached.
+///
+/// The result is identical to `cosine(query, candidate)`.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let query = arr1(&[1.0, 2.0, 3.0]);
+/// let prepared = PreparedCosine::new(&query.view());
+///
+/// let candidate = arr1(&[4.0, 5.0, 6.0]);
+/// assert_eq!(
+///     prepared.distance_to(&candidate.view()),
+///     cosine(&query.view(), &candidate.view())
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreparedCosine<T> {
+    query: Array1<T>,
+    norm_sq: T,
+}
+
+impl<T: Float> PreparedCosine<T> {
+    /// Caches the squared norm of `query`.
+    pub fn new(query: &ArrayView1<T>) -> Self {
+        Self {
+            query: query.to_owned(),
+            norm_sq: query.iter().fold(T::zero(), |acc, &q| acc + q * q),
+        }
+    }
+
+    /// Computes the cosine distance between the query and `candidate`.
+    ///
+    /// # Panics
+    /// Panics if `candidate` does not have the same length as the query.
+    pub fn distance_to(&self, candidate: &ArrayView1<T>) -> T {
+        assert_eq!(
+            candidate.len(),
+            self.query.len(),
+            "Input arrays must have the same length."
+        );
+
+        let mut dot = T::zero();
+        let mut norm_y = T::zero();
+        for (&q, &c) in self.query.iter().zip(candidate.iter()) {
+            dot = dot + q * c;
+            norm_y = norm_y + c * c;
+        }
+
+        let norm_x = self.norm_sq;
+        if norm_x.is_zero() && norm_y.is_zero() {
+            T::zero()
+        } else if norm_x.is_zero() || norm_y.is_zero() {
+            T::one()
+        } else {
+            T::one() - (dot / (norm_x.sqrt() * norm_y.sqrt()))
+        }
+    }
+}
+
+impl<T: Float> PreparedDistance<T> for PreparedCosine<T> {
+    fn distance_to(&self, candidate: &ArrayView1<T>) -> T {
+        PreparedCosine::distance_to(self, candidate)
+    }
+}
+
+/// [`correlation`](crate::correlation) distance to a fixed query, with the centered query and its
+/// norm cached.
+///
+/// The result is identical to `correlation(query, candidate)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreparedCorrelation<T> {
+    centered: Array1<T>,
+    norm_sq: T,
+}
+
+impl<T: Float> PreparedCorrelation<T> {
+    /// Caches the mean-centered `query` and its squared norm.
+    pub fn new(query: &ArrayView1<T>) -> Self {
+        let mean = query.iter().fold(T::zero(), |acc, &q| acc + q) / T::from(query.len()).unwrap();
+        let centered = query.mapv(|q| q - mean);
+        let norm_sq = centered.iter().fold(T::zero(), |acc, &q| acc + q * q);
+        Self { centered, norm_sq }
+    }
+
+    /// Computes the correlation distance between the query and `candidate`.
+    ///
+    /// # Panics
+    /// Panics if `candidate` does not have the same length as the query.
+    pub fn distance_to(&self, candidate: &ArrayView1<T>) -> T {
+        assert_eq!(
+            candidate.len(),
+            self.centered.len(),
+            "Input arrays must have the same length."
+        );
+
+        let mu_y =
+            candidate.iter().fold(T::zero(), |acc, &c| acc + c) / T::from(candidate.len()).unwrap();
+
+        let mut dot_product = T::zero();
+        let mut norm_y = T::zero();
+        for (&shifted_x, &c) in self.centered.iter().zip(candidate.iter()) {
+            let shifted_y = c - mu_y;
+            norm_y = norm_y + shifted_y * shifted_y;
+            dot_product = dot_product + shifted_x * shifted_y;
+        }
+
+        let norm_x = self.norm_sq;
+        if norm_x.is_zero() && norm_y.is_zero() {
+            T::zero()
+        } else if dot_product.is_zero() {
+            T::one()
+        } else {
+            T::one() - (dot_product / (norm_x.sqrt() * norm_y.sqrt()))
+        }
+    }
+}
+
+impl<T: Float> PreparedDistance<T> for PreparedCorrelation<T> {
+    fn distance_to(&self, candidate: &ArrayView1<T>) -> T {
+        PreparedCorrelation::distance_to(self, candidate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{correlation, cosine};
+    use ndarray::{arr1, Array2};
+
+    fn candidates() -> Array2<f64> {
+        Array2::from_shape_fn((10, 6), |(i, j)| ((i * 7 + j * 5) % 9) as f64 - 4.0)
+    }
+
+    #[test]
+    fn test_prepared_cosine_matches_cosine() {
+        let query = arr1(&[0.5, -1.0, 2.0, 0.0, 3.0, 1.5]);
+        let prepared = PreparedCosine::new(&query.view());
+
+        for row in candidates().rows() {
+            assert_eq!(prepared.distance_to(&row), cosine(&query.view(), &row));
+        }
+    }
+
+    #[test]
+    fn test_prepared_correlation_matches_correlation() {
+        let query = arr1(&[0.5f32, -1.0, 2.0, 0.0, 3.0, 1.5]);
+        let prepared = PreparedCorrelation::new(&query.view());
+
+        for row in candidates().mapv(|v| v as f32).rows() {
+            assert_eq!(prepared.distance_to(&row), correlation(&query.view(), &row));
+        }
+    }
+
+    #[test]
+    fn test_zero_and_constant_queries() {
+        let zero = arr1(&[0.0, 0.0, 0.0]);
+        let candidate = arr1(&[1.0, 2.0, 3.0]);
+        assert_eq!(
+            PreparedCosine::new(&zero.view()).distance_to(&zero.view()),
+            0.0
+        );
+        assert_eq!(
+            PreparedCosine::new(&zero.view()).distance_to(&candidate.view()),
+            1.0
+        );
+
+        let constant = arr1(&[2.0, 2.0, 2.0]);
+        let prepared = PreparedCorrelation::new(&constant.view());
+        assert_eq!(prepared.distance_to(&constant.view()), 0.0);
+        assert_eq!(prepared.distance_to(&candidate.view()), 1.0);
+    }
+
+    #[test]
+    fn test_trait_object() {
+        let query = arr1(&[1.0, 0.0]);
+        let prepared: Vec<Box<dyn PreparedDistance<f64>>> = vec![
+            Box::new(PreparedCosine::new(&query.view())),
+            Box::new(PreparedCorrelation::new(&query.view())),
+        ];
+        let candidate = arr1(&[0.0, 1.0]);
+        assert_eq!(prepared[0].distance_to(&candidate.view()), 1.0);
+        assert_eq!(
+            prepared[1].distance_to(&candidate.view()),
+            correlation(&query.view(), &candidate.view())
+        );
+    }
+}
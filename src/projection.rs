@@ -0,0 +1,246 @@
+//! Random projections for approximate Euclidean distances.
+//!
+//! By the Johnson–Lindenstrauss lemma, multiplying `n` points by a random `k x d` matrix with
+//! suitably scaled entries preserves all their pairwise Euclidean distances within a factor of
+//! `1 ± eps` with high probability, as long as `k` is at least [`jl_min_dim`]`(n, eps)`. The
+//! target dimension depends only on the number of points and the tolerated distortion, not on
+//! the original dimension `d`.
+
+use ndarray::{Array1, Array2, ArrayView1, ArrayView2};
+use num::Float;
+
+use crate::euclidean;
+use crate::rng::SplitMix64;
+
+/// A seeded random projection from `input_dim` to `output_dim` dimensions.
+///
+/// # Example
+/// ```
+/// use ndarray::Array1;
+/// use fast_distances::*;
+///
+/// let x = Array1::from_shape_fn(1000, |i| (i as f64 * 0.1).sin());
+/// let y = Array1::from_shape_fn(1000, |i| (i as f64 * 0.1).cos());
+///
+/// let projector = RandomProjector::gaussian(1000, 400, 42);
+/// let exact = euclidean(&x.view(), &y.view());
+/// let approx = projector.approx_euclidean(&x.view(), &y.view());
+/// assert!((approx / exact - 1.0).abs() < 0.3);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RandomProjector<T> {
+    matrix: Array2<T>,
+}
+
+impl<T> RandomProjector<T>
+where
+    T: Float + 'static,
+{
+    /// Builds a dense projection with independent `N(0, 1 / output_dim)` entries.
+    ///
+    /// # Panics
+    /// Panics if `output_dim` is zero.
+    pub fn gaussian(input_dim: usize, output_dim: usize, seed: u64) -> Self {
+        assert!(output_dim > 0, "Output dimension must be positive.");
+        let mut rng = SplitMix64::new(seed);
+        let scale = (output_dim as f64).sqrt().recip();
+        let matrix = Array2::from_shape_simple_fn((output_dim, input_dim), || {
+            T::from(rng.next_gaussian() * scale).unwrap()
+        });
+        Self { matrix }
+    }
+
+    /// Builds a sparse (Achlioptas) projection whose entries are `±sqrt(3 / output_dim)` with
+    /// probability `1/6` each and zero otherwise.
+    ///
+    /// It satisfies the same distortion bounds as [`gaussian`](Self::gaussian) while two thirds
+    /// of the entries are zero, and it only needs uniform random numbers to build.
+    ///
+    /// # Panics
+    /// Panics if `output_dim` is zero.
+    pub fn sparse(input_dim: usize, output_dim: usize, seed: u64) -> Self {
+        assert!(output_dim > 0, "Output dimension must be positive.");
+        let mut rng = SplitMix64::new(seed);
+        let value = T::from((3.0 / output_dim as f64).sqrt()).unwrap();
+        let matrix =
+            Array2::from_shape_simple_fn((output_dim, input_dim), || match rng.next_below(6) {
+                0 => value,
+                1 => -value,
+                _ => T::zero(),
+            });
+        Self { matrix }
+    }
+
+    /// Returns the dimension of the vectors being projected.
+    pub fn input_dim(&self) -> usize {
+        self.matrix.ncols()
+    }
+
+    /// Returns the dimension of the projected vectors.
+    pub fn output_dim(&self) -> usize {
+        self.matrix.nrows()
+    }
+
+    /// Returns the `output_dim x input_dim` projection matrix.
+    pub fn matrix(&self) -> &Array2<T> {
+        &self.matrix
+    }
+
+    /// Projects `x` to `output_dim` dimensions.
+    ///
+    /// # Panics
+    /// Panics if `x` does not have `input_dim` elements.
+    pub fn project(&self, x: &ArrayView1<T>) -> Array1<T> {
+        assert_eq!(
+            x.len(),
+            self.input_dim(),
+            "Input must have `input_dim` elements."
+        );
+        self.matrix.dot(x)
+    }
+
+    /// Projects every row of `data`, returning an `n x output_dim` matrix.
+    ///
+    /// # Panics
+    /// Panics if `data` does not have `input_dim` columns.
+    pub fn project_rows(&self, data: &ArrayView2<T>) -> Array2<T> {
+        assert_eq!(
+            data.ncols(),
+            self.input_dim(),
+            "Input must have `input_dim` elements."
+        );
+        data.dot(&self.matrix.t())
+    }
+
+    /// Approximates `euclidean(x, y)` by the distance between the projections of `x` and `y`.
+    ///
+    /// When comparing many pairs, project the points once with
+    /// [`project_rows`](Self::project_rows) and compute distances between the projections
+    /// instead.
+    ///
+    /// # Panics
+    /// Panics if `x` or `y` does not have `input_dim` elements.
+    pub fn approx_euclidean(&self, x: &ArrayView1<T>, y: &ArrayView1<T>) -> T {
+        euclidean(&self.project(x).view(), &self.project(y).view())
+    }
+}
+
+/// Returns the target dimension that, by the Johnson–Lindenstrauss lemma, preserves the
+/// pairwise distances of `n_samples` points within a factor of `1 ± eps`.
+///
+/// ..math::
+///    k \geq \frac{4 \ln n}{\varepsilon^2 / 2 - \varepsilon^3 / 3}
+///
+/// This is the same bound as scikit-learn's `johnson_lindenstrauss_min_dim`. It is
+/// conservative; in practice smaller dimensions often work.
+///
+/// # Panics
+/// Panics if `eps` is not in `(0, 1)`.
+///
+/// # Example
+/// ```
+/// use fast_distances::jl_min_dim;
+///
+/// assert_eq!(jl_min_dim(1_000_000, 0.5), 663);
+/// assert_eq!(jl_min_dim(1_000_000, 0.1), 11841);
+/// ```
+pub fn jl_min_dim(n_samples: usize, eps: f64) -> usize {
+    assert!(eps > 0.0 && eps < 1.0, "Distortion must be in (0, 1).");
+    let denominator = eps * eps / 2.0 - eps * eps * eps / 3.0;
+    (4.0 * (n_samples.max(1) as f64).ln() / denominator) as usize
+}
+
+/// Returns the smallest distortion `eps` guaranteed by the Johnson–Lindenstrauss bound for
+/// `n_samples` points projected to `output_dim` dimensions, the inverse of [`jl_min_dim`].
+///
+/// Returns `None` if even a distortion close to `1` would need more than `output_dim`
+/// dimensions.
+///
+/// # Panics
+/// Panics if `output_dim` is zero.
+pub fn jl_distortion(n_samples: usize, output_dim: usize) -> Option<f64> {
+    assert!(output_dim > 0, "Output dimension must be positive.");
+    let required = |eps: f64| {
+        let denominator = eps * eps / 2.0 - eps * eps * eps / 3.0;
+        4.0 * (n_samples.max(1) as f64).ln() / denominator
+    };
+
+    // `required` decreases on (0, 1), so bisect for the point where it meets `output_dim`.
+    let k = output_dim as f64;
+    let (mut lo, mut hi) = (0.0, 1.0);
+    if required(hi - f64::EPSILON) > k {
+        return None;
+    }
+    for _ in 0..100 {
+        let mid = (lo + hi) / 2.0;
+        if required(mid) > k {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Some(hi)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdist;
+    use crate::Euclidean;
+
+    fn sample(rows: usize, cols: usize) -> Array2<f64> {
+        Array2::from_shape_fn((rows, cols), |(i, j)| {
+            (((i * 37 + j * 11) % 97) as f64 / 13.0).sin()
+        })
+    }
+
+    #[test]
+    fn test_reproducible() {
+        let a = RandomProjector::<f64>::gaussian(20, 5, 7);
+        assert_eq!(a, RandomProjector::gaussian(20, 5, 7));
+        assert_ne!(a, RandomProjector::gaussian(20, 5, 8));
+        assert_eq!(a.matrix().dim(), (5, 20));
+        assert_eq!((a.input_dim(), a.output_dim()), (20, 5));
+    }
+
+    #[test]
+    fn test_distortion_within_bound() {
+        let data = sample(30, 500);
+        let exact = pdist(&data.view(), &Euclidean);
+        let eps = 0.5;
+        let k = jl_min_dim(30, eps);
+
+        for projector in [
+            RandomProjector::gaussian(500, k, 1),
+            RandomProjector::sparse(500, k, 2),
+        ] {
+            let projected = projector.project_rows(&data.view());
+            let approx = pdist(&projected.view(), &Euclidean);
+            for (a, e) in approx.iter().zip(exact.iter()) {
+                let ratio = a / e;
+                assert!(ratio > 1.0 - eps && ratio < 1.0 + eps);
+            }
+            assert!(
+                (projector.approx_euclidean(&data.row(0), &data.row(1)) - approx[0]).abs() < 1e-9
+            );
+        }
+    }
+
+    #[test]
+    fn test_sparse_density() {
+        let projector = RandomProjector::<f32>::sparse(300, 100, 5);
+        let nonzero = projector.matrix().iter().filter(|v| **v != 0.0).count();
+        let density = nonzero as f64 / 30_000.0;
+        assert!((density - 1.0 / 3.0).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_jl_distortion_inverts_min_dim() {
+        for eps in [0.1, 0.25, 0.5] {
+            let k = jl_min_dim(10_000, eps);
+            let found = jl_distortion(10_000, k).unwrap();
+            assert!((found - eps).abs() < 1e-3);
+        }
+        assert_eq!(jl_distortion(10_000, 10), None);
+    }
+}
@@ -0,0 +1,85 @@
+/// A small seeded SplitMix64 generator for the sampling-based algorithms.
+///
+/// It keeps results reproducible for a given seed without pulling in a random-number crate.
+#[derive(Debug, Clone)]
+pub(crate) struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a uniformly distributed value in `[0, 1)`.
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns a standard normal sample, using the Box-Muller transform.
+    pub(crate) fn next_gaussian(&mut self) -> f64 {
+        // 1 - u lies in (0, 1], so the logarithm is finite.
+        let u = 1.0 - self.next_f64();
+        let v = self.next_f64();
+        (-2.0 * u.ln()).sqrt() * (std::f64::consts::TAU * v).cos()
+    }
+
+    /// Returns a uniformly distributed index in `0..n`.
+    pub(crate) fn next_below(&mut self, n: usize) -> usize {
+        self.next_below_u64(n as u64) as usize
+    }
+
+    /// Returns a uniformly distributed value in `0..n`, for ranges wider than `usize`.
+    pub(crate) fn next_below_u64(&mut self, n: u64) -> u64 {
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reproducible() {
+        let mut a = SplitMix64::new(42);
+        let mut b = SplitMix64::new(42);
+        for _ in 0..10 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+    }
+
+    #[test]
+    fn test_next_below_range() {
+        let mut rng = SplitMix64::new(7);
+        for _ in 0..1000 {
+            assert!(rng.next_below(5) < 5);
+        }
+    }
+
+    #[test]
+    fn test_next_below_u64_wide_range() {
+        let n = (1_u64 << 61) - 2;
+        let mut rng = SplitMix64::new(7);
+        let samples: Vec<u64> = (0..1000).map(|_| rng.next_below_u64(n)).collect();
+        assert!(samples.iter().all(|&s| s < n));
+        assert!(samples.iter().any(|&s| s > u32::MAX as u64));
+    }
+
+    #[test]
+    fn test_gaussian_moments() {
+        let mut rng = SplitMix64::new(3);
+        let samples: Vec<f64> = (0..20_000).map(|_| rng.next_gaussian()).collect();
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        let var = samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / samples.len() as f64;
+        assert!(mean.abs() < 0.05);
+        assert!((var - 1.0).abs() < 0.05);
+    }
+}
@@ -0,0 +1,546 @@
+//! Explicit SIMD kernels for the hottest metrics.
+//!
+//! Only compiled with the `simd` feature. The float kernels pick their implementation at runtime,
+//! once per process, from the instruction sets reported by the CPU (see [`simd_level`]):
+//!
+//! * on x86_64 with AVX-512F, sixteen `f64` or thirty-two `f32` lanes per iteration;
+//! * on x86_64 with AVX2 and FMA, eight `f64` or sixteen `f32` lanes per iteration;
+//! * otherwise, the portable vectors of the [`wide`] crate, four `f64` lanes (`f64x4`) or eight
+//!   `f32` lanes (`f32x8`) compiled for the target's baseline features, which are NEON on
+//!   aarch64 and SSE2 on x86_64.
+//!
+//! A binary built for a generic target therefore still uses the widest registers of the machine
+//! it runs on. The remaining elements are finished with a scalar loop, and inputs that are not
+//! contiguous in memory fall back to the scalar implementations.
+//!
+//! Lane-wise accumulation sums the terms in a different order than the scalar loops, so results
+//! agree with [`euclidean`](crate::euclidean), [`manhattan`](crate::manhattan) and
+//! [`cosine`](crate::cosine) up to rounding rather than bit for bit, and may differ slightly
+//! between machines.
+//!
+//! The byte kernels [`hamming_u8`] and [`manhattan_u8`] work on integers and return exactly the
+//! same values as [`hamming_bytes`](crate::hamming_bytes) and
+//! [`manhattan_bytes`](crate::manhattan_bytes).
+
+use std::fmt;
+use std::sync::OnceLock;
+
+use ndarray::ArrayView1;
+use num::Float;
+use wide::{f32x8, f64x4, u16x16, u8x16, u8x32};
+
+/// The instruction set used by the float kernels of this module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SimdLevel {
+    /// The portable [`wide`] kernels, compiled for the target's baseline features.
+    Baseline,
+    /// The portable [`wide`] kernels on aarch64, where NEON is part of the baseline.
+    Neon,
+    /// 256-bit kernels for x86_64 CPUs with AVX2 and FMA.
+    Avx2,
+    /// 512-bit kernels for x86_64 CPUs with AVX-512F.
+    Avx512,
+}
+
+impl SimdLevel {
+    /// Returns a short lowercase name, e.g. `"avx2"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SimdLevel::Baseline => "baseline",
+            SimdLevel::Neon => "neon",
+            SimdLevel::Avx2 => "avx2",
+            SimdLevel::Avx512 => "avx512",
+        }
+    }
+}
+
+impl fmt::Display for SimdLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Returns the instruction set the float kernels dispatch to on this CPU.
+///
+/// The CPU is queried on the first call and the answer is cached for the rest of the process.
+///
+/// # Example
+/// ```
+/// use fast_distances::simd::{simd_level, SimdLevel};
+///
+/// let level = simd_level();
+/// println!("distance kernels use {level}");
+/// assert!(level >= SimdLevel::Baseline);
+/// ```
+pub fn simd_level() -> SimdLevel {
+    static LEVEL: OnceLock<SimdLevel> = OnceLock::new();
+    *LEVEL.get_or_init(detect_level)
+}
+
+fn detect_level() -> SimdLevel {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if std::arch::is_x86_feature_detected!("avx512f") {
+            return SimdLevel::Avx512;
+        }
+        if std::arch::is_x86_feature_detected!("avx2") && std::arch::is_x86_feature_detected!("fma")
+        {
+            return SimdLevel::Avx2;
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        return SimdLevel::Neon;
+    }
+    #[allow(unreachable_code)]
+    SimdLevel::Baseline
+}
+
+/// Portable reductions over `L` independent accumulators.
+///
+/// They are instantiated inside functions compiled with `#[target_feature]`, where LLVM turns
+/// the fixed-size accumulator arrays into full-width vector registers.
+mod lanes {
+    use num::Float;
+
+    #[inline(always)]
+    pub(super) fn sum_with<T: Float, const L: usize>(
+        x: &[T],
+        y: &[T],
+        term: impl Fn(T, T) -> T,
+    ) -> T {
+        let mut acc = [T::zero(); L];
+        let (xc, yc) = (x.chunks_exact(L), y.chunks_exact(L));
+        let (xr, yr) = (xc.remainder(), yc.remainder());
+        for (a, b) in xc.zip(yc) {
+            for k in 0..L {
+                acc[k] = acc[k] + term(a[k], b[k]);
+            }
+        }
+
+        let mut result = acc.iter().fold(T::zero(), |s, &v| s + v);
+        for (&a, &b) in xr.iter().zip(yr) {
+            result = result + term(a, b);
+        }
+        result
+    }
+
+    #[inline(always)]
+    pub(super) fn dot_norms<T: Float, const L: usize>(x: &[T], y: &[T]) -> (T, T, T) {
+        let (mut dot, mut norm_x, mut norm_y) = ([T::zero(); L], [T::zero(); L], [T::zero(); L]);
+        let (xc, yc) = (x.chunks_exact(L), y.chunks_exact(L));
+        let (xr, yr) = (xc.remainder(), yc.remainder());
+        for (a, b) in xc.zip(yc) {
+            for k in 0..L {
+                dot[k] = a[k].mul_add(b[k], dot[k]);
+                norm_x[k] = a[k].mul_add(a[k], norm_x[k]);
+                norm_y[k] = b[k].mul_add(b[k], norm_y[k]);
+            }
+        }
+
+        let sum = |v: [T; L]| v.iter().fold(T::zero(), |s, &e| s + e);
+        let (mut dot, mut norm_x, mut norm_y) = (sum(dot), sum(norm_x), sum(norm_y));
+        for (&a, &b) in xr.iter().zip(yr) {
+            dot = dot + a * b;
+            norm_x = norm_x + a * a;
+            norm_y = norm_y + b * b;
+        }
+        (dot, norm_x, norm_y)
+    }
+}
+
+/// Instantiates the [`lanes`] reductions for one set of x86_64 target features.
+#[cfg(target_arch = "x86_64")]
+macro_rules! x86_kernels {
+    ($module:ident, $features:literal, $lanes64:expr, $lanes32:expr) => {
+        mod $module {
+            use super::lanes;
+
+            #[target_feature(enable = $features)]
+            pub(super) fn sq_diff_f64(x: &[f64], y: &[f64]) -> f64 {
+                lanes::sum_with::<f64, $lanes64>(x, y, |a, b| (a - b) * (a - b))
+            }
+
+            #[target_feature(enable = $features)]
+            pub(super) fn abs_diff_f64(x: &[f64], y: &[f64]) -> f64 {
+                lanes::sum_with::<f64, $lanes64>(x, y, |a, b| (a - b).abs())
+            }
+
+            #[target_feature(enable = $features)]
+            pub(super) fn dot_norms_f64(x: &[f64], y: &[f64]) -> (f64, f64, f64) {
+                lanes::dot_norms::<f64, $lanes64>(x, y)
+            }
+
+            #[target_feature(enable = $features)]
+            pub(super) fn sq_diff_f32(x: &[f32], y: &[f32]) -> f32 {
+                lanes::sum_with::<f32, $lanes32>(x, y, |a, b| (a - b) * (a - b))
+            }
+
+            #[target_feature(enable = $features)]
+            pub(super) fn abs_diff_f32(x: &[f32], y: &[f32]) -> f32 {
+                lanes::sum_with::<f32, $lanes32>(x, y, |a, b| (a - b).abs())
+            }
+
+            #[target_feature(enable = $features)]
+            pub(super) fn dot_norms_f32(x: &[f32], y: &[f32]) -> (f32, f32, f32) {
+                lanes::dot_norms::<f32, $lanes32>(x, y)
+            }
+        }
+    };
+}
+
+#[cfg(target_arch = "x86_64")]
+x86_kernels!(avx2, "avx2,fma", 8, 16);
+#[cfg(target_arch = "x86_64")]
+x86_kernels!(avx512, "avx512f", 16, 32);
+
+/// The portable [`wide`] reductions used when no wider instruction set is available.
+macro_rules! wide_kernels {
+    ($t:ty, $v:ty, $lanes:expr, $sq_diff:ident, $abs_diff:ident, $dot_norms:ident) => {
+        fn $sq_diff(xs: &[$t], ys: &[$t]) -> $t {
+            let mut acc = <$v>::ZERO;
+            let (xc, yc) = (xs.chunks_exact($lanes), ys.chunks_exact($lanes));
+            let (xr, yr) = (xc.remainder(), yc.remainder());
+            for (a, b) in xc.zip(yc) {
+                let d = <$v>::from(a) - <$v>::from(b);
+                acc = d.mul_add(d, acc);
+            }
+
+            let mut result = acc.reduce_add();
+            for (&a, &b) in xr.iter().zip(yr) {
+                result += (a - b) * (a - b);
+            }
+            result
+        }
+
+        fn $abs_diff(xs: &[$t], ys: &[$t]) -> $t {
+            let mut acc = <$v>::ZERO;
+            let (xc, yc) = (xs.chunks_exact($lanes), ys.chunks_exact($lanes));
+            let (xr, yr) = (xc.remainder(), yc.remainder());
+            for (a, b) in xc.zip(yc) {
+                acc += (<$v>::from(a) - <$v>::from(b)).abs();
+            }
+
+            let mut result = acc.reduce_add();
+            for (&a, &b) in xr.iter().zip(yr) {
+                result += (a - b).abs();
+            }
+            result
+        }
+
+        fn $dot_norms(xs: &[$t], ys: &[$t]) -> ($t, $t, $t) {
+            let (mut dot, mut norm_x, mut norm_y) = (<$v>::ZERO, <$v>::ZERO, <$v>::ZERO);
+            let (xc, yc) = (xs.chunks_exact($lanes), ys.chunks_exact($lanes));
+            let (xr, yr) = (xc.remainder(), yc.remainder());
+            for (a, b) in xc.zip(yc) {
+                let (a, b) = (<$v>::from(a), <$v>::from(b));
+                dot = a.mul_add(b, dot);
+                norm_x = a.mul_add(a, norm_x);
+                norm_y = b.mul_add(b, norm_y);
+            }
+
+            let (mut dot, mut norm_x, mut norm_y) =
+                (dot.reduce_add(), norm_x.reduce_add(), norm_y.reduce_add());
+            for (&a, &b) in xr.iter().zip(yr) {
+                dot += a * b;
+                norm_x += a * a;
+                norm_y += b * b;
+            }
+            (dot, norm_x, norm_y)
+        }
+    };
+}
+
+wide_kernels!(
+    f64,
+    f64x4,
+    4,
+    wide_sq_diff_f64,
+    wide_abs_diff_f64,
+    wide_dot_norms_f64
+);
+wide_kernels!(
+    f32,
+    f32x8,
+    8,
+    wide_sq_diff_f32,
+    wide_abs_diff_f32,
+    wide_dot_norms_f32
+);
+
+/// Calls the `$kernel` of the dispatched [`SimdLevel`], or `$baseline` if there is none.
+macro_rules! dispatch {
+    ($level:expr, $kernel:ident, $baseline:ident, $($arg:expr),*) => {
+        match $level {
+            // SAFETY: the level is only `Avx512` or `Avx2` if the CPU supports those features.
+            #[cfg(target_arch = "x86_64")]
+            SimdLevel::Avx512 => unsafe { avx512::$kernel($($arg),*) },
+            #[cfg(target_arch = "x86_64")]
+            SimdLevel::Avx2 => unsafe { avx2::$kernel($($arg),*) },
+            _ => $baseline($($arg),*),
+        }
+    };
+}
+
+macro_rules! simd_kernels {
+    (
+        $t:ty,
+        $euclidean:ident,
+        $manhattan:ident,
+        $cosine:ident,
+        [$sq_diff:ident, $abs_diff:ident, $dot_norms:ident],
+        [$wide_sq_diff:ident, $wide_abs_diff:ident, $wide_dot_norms:ident]
+    ) => {
+        #[doc = concat!("SIMD [`euclidean`](crate::euclidean) distance for `", stringify!($t), "` vectors.")]
+        ///
+        /// # Panics
+        ///
+        /// This function will panic if the input arrays do not have the same length.
+        pub fn $euclidean(x: &ArrayView1<$t>, y: &ArrayView1<$t>) -> $t {
+            assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+            let (Some(xs), Some(ys)) = (x.as_slice(), y.as_slice()) else {
+                return crate::euclidean(x, y);
+            };
+            dispatch!(simd_level(), $sq_diff, $wide_sq_diff, xs, ys).sqrt()
+        }
+
+        #[doc = concat!("SIMD [`manhattan`](crate::manhattan) distance for `", stringify!($t), "` vectors.")]
+        ///
+        /// # Panics
+        ///
+        /// This function will panic if the input arrays do not have the same length.
+        pub fn $manhattan(x: &ArrayView1<$t>, y: &ArrayView1<$t>) -> $t {
+            assert_eq!(x.len(), y.len(), "Input vectors must have the same length");
+            let (Some(xs), Some(ys)) = (x.as_slice(), y.as_slice()) else {
+                return crate::manhattan(x, y);
+            };
+            dispatch!(simd_level(), $abs_diff, $wide_abs_diff, xs, ys)
+        }
+
+        #[doc = concat!("SIMD [`cosine`](crate::cosine) distance for `", stringify!($t), "` vectors.")]
+        ///
+        /// Zero-norm inputs follow the same conventions as `cosine`.
+        ///
+        /// # Panics
+        ///
+        /// This function will panic if the input arrays do not have the same length.
+        pub fn $cosine(x: &ArrayView1<$t>, y: &ArrayView1<$t>) -> $t {
+            assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+            let (Some(xs), Some(ys)) = (x.as_slice(), y.as_slice()) else {
+                return crate::cosine(x, y);
+            };
+            let (dot, norm_x, norm_y) = dispatch!(simd_level(), $dot_norms, $wide_dot_norms, xs, ys);
+            cosine_from_parts(dot, norm_x, norm_y)
+        }
+    };
+}
+
+simd_kernels!(
+    f64,
+    euclidean_f64,
+    manhattan_f64,
+    cosine_f64,
+    [sq_diff_f64, abs_diff_f64, dot_norms_f64],
+    [wide_sq_diff_f64, wide_abs_diff_f64, wide_dot_norms_f64]
+);
+simd_kernels!(
+    f32,
+    euclidean_f32,
+    manhattan_f32,
+    cosine_f32,
+    [sq_diff_f32, abs_diff_f32, dot_norms_f32],
+    [wide_sq_diff_f32, wide_abs_diff_f32, wide_dot_norms_f32]
+);
+
+fn cosine_from_parts<T: Float>(dot: T, norm_x: T, norm_y: T) -> T {
+    if norm_x.is_zero() && norm_y.is_zero() {
+        T::zero()
+    } else if norm_x.is_zero() || norm_y.is_zero() {
+        T::one()
+    } else {
+        T::one() - dot / (norm_x.sqrt() * norm_y.sqrt())
+    }
+}
+
+/// SIMD [`hamming_bytes`](crate::hamming_bytes) distance, the number of differing bytes.
+///
+/// Compares 32 bytes at a time and counts the equal lanes of each comparison mask with a single
+/// popcount.
+///
+/// # Panics
+///
+/// This function will panic if the input arrays do not have the same length.
+pub fn hamming_u8(x: &[u8], y: &[u8]) -> usize {
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+
+    let mut equal = 0;
+    let (xc, yc) = (x.chunks_exact(32), y.chunks_exact(32));
+    let (xr, yr) = (xc.remainder(), yc.remainder());
+    for (a, b) in xc.zip(yc) {
+        equal += u8x32::from(a)
+            .simd_eq(u8x32::from(b))
+            .to_bitmask()
+            .count_ones() as usize;
+    }
+
+    let differing = x.len() - xr.len() - equal;
+    differing + xr.iter().zip(yr).filter(|(a, b)| a != b).count()
+}
+
+/// SIMD [`manhattan_bytes`](crate::manhattan_bytes) distance for byte vectors.
+///
+/// Absolute differences are computed 16 bytes at a time as `max - min` and summed into 16-bit
+/// lanes, which are flushed into a `u64` before they can overflow.
+///
+/// # Panics
+///
+/// This function will panic if the input arrays do not have the same length.
+pub fn manhattan_u8(x: &[u8], y: &[u8]) -> u64 {
+    assert_eq!(x.len(), y.len(), "Input arrays must have the same length.");
+
+    // A 16-bit lane holds at least 257 differences of at most 255.
+    const FLUSH: usize = 256;
+
+    let mut result = 0;
+    let mut acc = u16x16::ZERO;
+    let (xc, yc) = (x.chunks_exact(16), y.chunks_exact(16));
+    let (xr, yr) = (xc.remainder(), yc.remainder());
+    for (i, (a, b)) in xc.zip(yc).enumerate() {
+        let (a, b) = (u8x16::from(a), u8x16::from(b));
+        acc += u16x16::from(a.max(b) - a.min(b));
+        if (i + 1) % FLUSH == 0 {
+            result += acc.to_array().iter().map(|&v| v as u64).sum::<u64>();
+            acc = u16x16::ZERO;
+        }
+    }
+
+    result += acc.to_array().iter().map(|&v| v as u64).sum::<u64>();
+    result + crate::manhattan_bytes(xr, yr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cosine, euclidean, hamming_bytes, manhattan, manhattan_bytes};
+    use ndarray::{s, Array1};
+
+    fn sample<T: num::Float>(n: usize, offset: f64) -> Array1<T> {
+        (0..n)
+            .map(|i| T::from(((i as f64 + offset) * 0.37).sin() * 3.0).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_f64_kernels_match_scalar() {
+        // Lengths around the lane width exercise both the vector body and the remainder.
+        for n in [0, 1, 3, 4, 5, 8, 17, 100] {
+            let x = sample::<f64>(n, 0.0);
+            let y = sample::<f64>(n, 1.5);
+            let (x, y) = (x.view(), y.view());
+
+            assert!((euclidean_f64(&x, &y) - euclidean(&x, &y)).abs() < 1e-12);
+            assert!((manhattan_f64(&x, &y) - manhattan(&x, &y)).abs() < 1e-12);
+            assert!((cosine_f64(&x, &y) - cosine(&x, &y)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_f32_kernels_match_scalar() {
+        for n in [0, 1, 7, 8, 9, 16, 33, 100] {
+            let x = sample::<f32>(n, 0.0);
+            let y = sample::<f32>(n, 1.5);
+            let (x, y) = (x.view(), y.view());
+
+            assert!((euclidean_f32(&x, &y) - euclidean(&x, &y)).abs() < 1e-4);
+            assert!((manhattan_f32(&x, &y) - manhattan(&x, &y)).abs() < 1e-3);
+            assert!((cosine_f32(&x, &y) - cosine(&x, &y)).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_non_contiguous_fallback() {
+        let x = sample::<f64>(20, 0.0);
+        let y = sample::<f64>(20, 2.0);
+        let (x, y) = (x.slice(s![..;2]), y.slice(s![..;2]));
+
+        assert_eq!(euclidean_f64(&x, &y), euclidean(&x, &y));
+        assert_eq!(manhattan_f64(&x, &y), manhattan(&x, &y));
+        assert_eq!(cosine_f64(&x, &y), cosine(&x, &y));
+    }
+
+    #[test]
+    fn test_cosine_zero_norms() {
+        let zero = Array1::<f32>::zeros(9);
+        let ones = Array1::<f32>::ones(9);
+
+        assert_eq!(cosine_f32(&zero.view(), &zero.view()), 0.0);
+        assert_eq!(cosine_f32(&zero.view(), &ones.view()), 1.0);
+    }
+
+    #[test]
+    fn test_byte_kernels_match_scalar() {
+        // 4200 bytes cover several accumulator flushes as well as the remainder.
+        for n in [0, 1, 15, 16, 31, 32, 33, 100, 4200] {
+            let x: Vec<u8> = (0..n).map(|i| (i * 37 % 256) as u8).collect();
+            let y: Vec<u8> = (0..n).map(|i| (i * 91 % 5 * 60) as u8).collect();
+
+            assert_eq!(hamming_u8(&x, &y), hamming_bytes(&x, &y));
+            assert_eq!(manhattan_u8(&x, &y), manhattan_bytes(&x, &y));
+        }
+
+        let (zero, full) = (vec![0u8; 10_000], vec![255u8; 10_000]);
+        assert_eq!(manhattan_u8(&zero, &full), 2_550_000);
+        assert_eq!(hamming_u8(&zero, &full), 10_000);
+    }
+
+    fn supported_levels() -> Vec<SimdLevel> {
+        let mut levels = vec![SimdLevel::Baseline];
+        #[cfg(target_arch = "x86_64")]
+        {
+            use std::arch::is_x86_feature_detected;
+            if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+                levels.push(SimdLevel::Avx2);
+            }
+            if is_x86_feature_detected!("avx512f") {
+                levels.push(SimdLevel::Avx512);
+            }
+        }
+        levels
+    }
+
+    #[test]
+    fn test_every_supported_level_matches_scalar() {
+        // 75 elements leave a remainder for every lane count up to 32.
+        let (x64, y64) = (sample::<f64>(75, 0.0), sample::<f64>(75, 1.5));
+        let (x32, y32) = (sample::<f32>(75, 0.0), sample::<f32>(75, 1.5));
+        let (xs, ys) = (x64.as_slice().unwrap(), y64.as_slice().unwrap());
+        let (xs32, ys32) = (x32.as_slice().unwrap(), y32.as_slice().unwrap());
+
+        for level in supported_levels() {
+            let sq = dispatch!(level, sq_diff_f64, wide_sq_diff_f64, xs, ys);
+            assert!((sq.sqrt() - euclidean(&x64.view(), &y64.view())).abs() < 1e-12);
+            let l1 = dispatch!(level, abs_diff_f64, wide_abs_diff_f64, xs, ys);
+            assert!((l1 - manhattan(&x64.view(), &y64.view())).abs() < 1e-12);
+            let (dot, nx, ny) = dispatch!(level, dot_norms_f64, wide_dot_norms_f64, xs, ys);
+            let d = cosine_from_parts(dot, nx, ny);
+            assert!((d - cosine(&x64.view(), &y64.view())).abs() < 1e-12);
+
+            let sq = dispatch!(level, sq_diff_f32, wide_sq_diff_f32, xs32, ys32);
+            assert!((sq.sqrt() - euclidean(&x32.view(), &y32.view())).abs() < 1e-4);
+            let l1 = dispatch!(level, abs_diff_f32, wide_abs_diff_f32, xs32, ys32);
+            assert!((l1 - manhattan(&x32.view(), &y32.view())).abs() < 1e-3);
+            let (dot, nx, ny) = dispatch!(level, dot_norms_f32, wide_dot_norms_f32, xs32, ys32);
+            let d = cosine_from_parts(dot, nx, ny);
+            assert!((d - cosine(&x32.view(), &y32.view())).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_simd_level_is_cached_and_supported() {
+        let level = simd_level();
+        assert_eq!(level, simd_level());
+        assert_eq!(level.to_string(), level.name());
+        #[cfg(target_arch = "x86_64")]
+        assert!(supported_levels().contains(&level));
+    }
+}
@@ -0,0 +1,172 @@
+//! SimHash sketches for estimating angles between dense vectors.
+//!
+//! A random hyperplane through the origin separates two vectors with probability `θ / π`,
+//! where `θ` is the angle between them. A [`SimHasher`] records on which side of each of `b`
+//! random hyperplanes a vector lies, one bit per hyperplane, so the fraction of differing bits
+//! between two sketches estimates `θ / π` with a standard error of about
+//! `sqrt(p (1 - p) / b)`. Comparing sketches costs a few `popcnt` instructions, which makes
+//! them a cheap pre-filter before computing the exact [`cosine`](crate::cosine) distance.
+
+use ndarray::{Array2, ArrayView1, Zip};
+use num::Float;
+
+use crate::hamming_bits;
+use crate::rng::SplitMix64;
+
+/// The sketch of one vector: one bit per hyperplane, packed into `u64` words.
+///
+/// Bit `k` is bit `k % 64` of word `k / 64`, as for [`hamming_bits`], and the padding bits of
+/// the last word are zero.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SimHash {
+    words: Vec<u64>,
+    bits: usize,
+}
+
+impl SimHash {
+    /// Returns the packed bits.
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// Returns the number of bits, i.e. of hyperplanes.
+    pub fn num_bits(&self) -> usize {
+        self.bits
+    }
+}
+
+/// A seeded set of random hyperplanes that maps dense vectors to [`SimHash`] sketches.
+///
+/// Sketches are only comparable when they come from the same hasher, i.e. the same dimension,
+/// number of bits and seed.
+///
+/// # Example
+/// ```
+/// use ndarray::Array1;
+/// use fast_distances::*;
+///
+/// let x = Array1::from_shape_fn(100, |i| (i as f64 * 0.1).sin());
+/// let y = Array1::from_shape_fn(100, |i| (i as f64 * 0.1).sin() + 0.3);
+///
+/// let hasher = SimHasher::new(100, 1024, 42);
+/// let estimate = estimated_angular(&hasher.sketch(&x.view()), &hasher.sketch(&y.view()));
+///
+/// let exact = (1.0 - cosine(&x.view(), &y.view())).acos() / std::f64::consts::PI;
+/// assert!((estimate - exact).abs() < 0.05);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimHasher<T> {
+    hyperplanes: Array2<T>,
+}
+
+impl<T> SimHasher<T>
+where
+    T: Float,
+{
+    /// Draws `num_bits` hyperplanes in `dim` dimensions from `seed`, with independent standard
+    /// normal normals.
+    ///
+    /// # Panics
+    /// Panics if `num_bits` is zero.
+    pub fn new(dim: usize, num_bits: usize, seed: u64) -> Self {
+        assert!(num_bits > 0, "Number of bits must be positive.");
+        let mut rng = SplitMix64::new(seed);
+        let hyperplanes =
+            Array2::from_shape_simple_fn((num_bits, dim), || T::from(rng.next_gaussian()).unwrap());
+        Self { hyperplanes }
+    }
+
+    /// Returns the dimension of the vectors being sketched.
+    pub fn dim(&self) -> usize {
+        self.hyperplanes.ncols()
+    }
+
+    /// Returns the number of bits of every sketch.
+    pub fn num_bits(&self) -> usize {
+        self.hyperplanes.nrows()
+    }
+
+    /// Computes the sketch of `x`, whose bit `k` is set when `x` lies on the nonnegative side of
+    /// hyperplane `k`.
+    ///
+    /// The zero vector lies on the nonnegative side of every hyperplane.
+    ///
+    /// # Panics
+    /// Panics if `x` does not have `dim` elements.
+    pub fn sketch(&self, x: &ArrayView1<T>) -> SimHash {
+        assert_eq!(x.len(), self.dim(), "Input must have `dim` elements.");
+        let bits = self.num_bits();
+        let mut words = vec![0u64; bits.div_ceil(64)];
+        for (k, normal) in self.hyperplanes.outer_iter().enumerate() {
+            let side = Zip::from(&normal)
+                .and(x)
+                .fold(T::zero(), |acc, &a, &b| acc + a * b);
+            if side >= T::zero() {
+                words[k / 64] |= 1 << (k % 64);
+            }
+        }
+        SimHash { words, bits }
+    }
+}
+
+/// Estimates the angular distance `θ / π` between two vectors from their [`SimHash`] sketches.
+///
+/// The estimate is the fraction of differing bits, counted with [`hamming_bits`]. It lies in
+/// `[0, 1]`; multiply by `π` for the angle in radians, and `cos(π d)` estimates the cosine
+/// similarity.
+///
+/// # Panics
+/// Panics if the sketches do not have the same number of bits.
+pub fn estimated_angular(sig_a: &SimHash, sig_b: &SimHash) -> f64 {
+    assert_eq!(
+        sig_a.bits, sig_b.bits,
+        "Sketches must have the same number of bits."
+    );
+    hamming_bits(&sig_a.words, &sig_b.words) as f64 / sig_a.bits as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cosine;
+    use ndarray::{arr1, Array1};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_estimate_tracks_angle() {
+        let hasher = SimHasher::new(3, 2000, 5);
+        let x = arr1(&[1.0, 0.0, 0.0]);
+        for angle in [0.0, 0.5, 1.0, 2.0, PI] {
+            let y = arr1(&[angle.cos(), angle.sin(), 0.0]);
+            let estimate = estimated_angular(&hasher.sketch(&x.view()), &hasher.sketch(&y.view()));
+            assert!((estimate - angle / PI).abs() < 0.04, "{angle}: {estimate}");
+        }
+    }
+
+    #[test]
+    fn test_sketch_layout() {
+        let hasher = SimHasher::<f32>::new(4, 70, 1);
+        let sketch = hasher.sketch(&Array1::zeros(4).view());
+        assert_eq!(sketch.num_bits(), 70);
+        assert_eq!(sketch.words(), &[u64::MAX, (1 << 6) - 1]);
+
+        // Sketches do not depend on the scale of the vector.
+        let x = arr1(&[0.3f32, -1.2, 2.0, 0.1]);
+        assert_eq!(hasher.sketch(&x.view()), hasher.sketch(&(&x * 10.0).view()));
+        let exact = cosine(&x.view(), &(-&x).view());
+        assert_eq!(exact, 2.0);
+        assert_eq!(
+            estimated_angular(&hasher.sketch(&x.view()), &hasher.sketch(&(-&x).view())),
+            1.0
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Sketches must have the same number of bits.")]
+    fn test_mismatched_sketches() {
+        let x = arr1(&[1.0, 2.0]);
+        let a = SimHasher::new(2, 8, 0).sketch(&x.view());
+        let b = SimHasher::new(2, 16, 0).sketch(&x.view());
+        estimated_angular(&a, &b);
+    }
+}
@@ -0,0 +1,244 @@
+use ndarray::{Array2, ArrayViewMut2, Axis};
+use num::Zero;
+
+use super::csr::SparseRows;
+use super::sparse_vec::SparseVector;
+#[cfg(feature = "parallel")]
+use crate::Parallelism;
+
+/// A distance between two sparse vectors given as index and value slices.
+///
+/// Every function or closure with the signature of the `sparse_*` metrics implements this
+/// trait, so [`sparse_euclidean`](crate::sparse_euclidean) can be passed directly and metrics
+/// that need the dimension, such as [`sparse_correlation`](crate::sparse_correlation), can
+/// capture it in a closure.
+pub trait SparseDistance<T> {
+    /// Computes the distance between the vectors `(ind1, data1)` and `(ind2, data2)`.
+    fn distance(&self, ind1: &[usize], data1: &[T], ind2: &[usize], data2: &[T]) -> T;
+
+    /// Computes the distance between two [`SparseVector`]s, such as [`SparseVec`](crate::SparseVec) or, with the
+    /// `sprs` feature, `sprs::CsVecView`.
+    ///
+    /// # Example
+    /// ```
+    /// use fast_distances::*;
+    ///
+    /// let x = SparseVec::new(vec![0, 4], vec![3.0, 1.0]);
+    /// let y = SparseVec::new(vec![4, 7], vec![1.0, 4.0]);
+    /// assert_eq!(sparse_euclidean.distance_between(&x, &y), 5.0);
+    /// ```
+    fn distance_between<X, Y>(&self, x: &X, y: &Y) -> T
+    where
+        Self: Sized,
+        X: SparseVector<T> + ?Sized,
+        Y: SparseVector<T> + ?Sized,
+    {
+        self.distance(
+            x.sparse_indices(),
+            x.sparse_values(),
+            y.sparse_indices(),
+            y.sparse_values(),
+        )
+    }
+}
+
+impl<T, F> SparseDistance<T> for F
+where
+    F: Fn(&[usize], &[T], &[usize], &[T]) -> T,
+{
+    fn distance(&self, ind1: &[usize], data1: &[T], ind2: &[usize], data2: &[T]) -> T {
+        self(ind1, data1, ind2, data2)
+    }
+}
+
+/// Computes the distances between every row of the CSR matrix `a` and every row of `b`.
+///
+/// This is the sparse counterpart of [`cdist`](crate::cdist): entry `(i, j)` of the result is
+/// `metric.distance` between row `i` of `a` and row `j` of `b`. Both operands may be any
+/// [`SparseRows`](crate::SparseRows), such as [`CsrView`](crate::CsrView) or, with the `sprs`
+/// feature, an `sprs::CsMat` in CSR order.
+///
+/// # Panics
+/// Panics if `a` and `b` do not have the same number of columns.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// let a = CsrView::new(&[0, 1, 2], &[0, 2], &[4.0, 1.0], 3);
+/// let b = CsrView::new(&[0, 1], &[2], &[-3.0], 3);
+///
+/// let d = cdist_sparse(&a, &b, &sparse_euclidean);
+/// assert_eq!(d, ndarray::arr2(&[[5.0], [4.0]]));
+///
+/// let d = cdist_sparse(&a, &b, &|i1: &[usize], d1: &[f64], i2: &[usize], d2: &[f64]| {
+///     sparse_correlation(i1, d1, i2, d2, 3)
+/// });
+/// assert_eq!(d.dim(), (2, 1));
+/// ```
+pub fn cdist_sparse<T, A, B, M>(a: &A, b: &B, metric: &M) -> Array2<T>
+where
+    T: Clone + Zero,
+    A: SparseRows<T> + ?Sized,
+    B: SparseRows<T> + ?Sized,
+    M: SparseDistance<T> + ?Sized,
+{
+    check_ncols(a, b);
+    let mut out = Array2::zeros((a.nrows(), b.nrows()));
+    fill_rows(a, b, metric, 0, &mut out.view_mut());
+    out
+}
+
+/// Parallel version of [`cdist_sparse`], available with the `parallel` feature.
+///
+/// The output is split into blocks of rows of `a` that are filled independently on the rayon
+/// thread pool, so the result is identical to [`cdist_sparse`].
+///
+/// # Panics
+/// Panics if `a` and `b` do not have the same number of columns.
+#[cfg(feature = "parallel")]
+pub fn par_cdist_sparse<T, A, B, M>(a: &A, b: &B, metric: &M) -> Array2<T>
+where
+    T: Clone + Zero + Send + Sync,
+    A: SparseRows<T> + Sync + ?Sized,
+    B: SparseRows<T> + Sync + ?Sized,
+    M: SparseDistance<T> + Sync + ?Sized,
+{
+    par_cdist_sparse_with(a, b, metric, Parallelism::Global)
+}
+
+/// [`par_cdist_sparse`] on the thread pool selected by `parallelism`.
+///
+/// # Panics
+/// Panics if `a` and `b` do not have the same number of columns.
+#[cfg(feature = "parallel")]
+pub fn par_cdist_sparse_with<T, A, B, M>(
+    a: &A,
+    b: &B,
+    metric: &M,
+    parallelism: Parallelism,
+) -> Array2<T>
+where
+    T: Clone + Zero + Send + Sync,
+    A: SparseRows<T> + Sync + ?Sized,
+    B: SparseRows<T> + Sync + ?Sized,
+    M: SparseDistance<T> + Sync + ?Sized,
+{
+    use ndarray::parallel::prelude::*;
+
+    /// Rows of `a` per task; sparse rows are cheap, so blocks amortize the scheduling cost.
+    const BLOCK_ROWS: usize = 16;
+
+    check_ncols(a, b);
+    let mut out = Array2::zeros((a.nrows(), b.nrows()));
+    parallelism.install(|| {
+        out.axis_chunks_iter_mut(Axis(0), BLOCK_ROWS)
+            .into_par_iter()
+            .enumerate()
+            .for_each(|(block, mut chunk)| fill_rows(a, b, metric, block * BLOCK_ROWS, &mut chunk))
+    });
+    out
+}
+
+fn check_ncols<T, A, B>(a: &A, b: &B)
+where
+    A: SparseRows<T> + ?Sized,
+    B: SparseRows<T> + ?Sized,
+{
+    assert_eq!(
+        a.ncols(),
+        b.ncols(),
+        "Input matrices must have the same number of columns."
+    );
+}
+
+/// Fills `out` with the distances from rows `row_offset..` of `a` to every row of `b`.
+fn fill_rows<T, A, B, M>(a: &A, b: &B, metric: &M, row_offset: usize, out: &mut ArrayViewMut2<T>)
+where
+    A: SparseRows<T> + ?Sized,
+    B: SparseRows<T> + ?Sized,
+    M: SparseDistance<T> + ?Sized,
+{
+    for (i, mut out_row) in out.axis_iter_mut(Axis(0)).enumerate() {
+        let (ind1, data1) = a.row(row_offset + i);
+        for (j, value) in out_row.iter_mut().enumerate() {
+            let (ind2, data2) = b.row(j);
+            *value = metric.distance(ind1, data1, ind2, data2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    /// Converts a dense matrix to CSR buffers.
+    fn to_csr(dense: &Array2<f64>) -> (Vec<usize>, Vec<usize>, Vec<f64>) {
+        let mut indptr = vec![0];
+        let (mut indices, mut data) = (Vec::new(), Vec::new());
+        for row in dense.rows() {
+            let sparse = SparseVec::from_dense(&row);
+            indices.extend_from_slice(sparse.indices());
+            data.extend_from_slice(sparse.values());
+            indptr.push(indices.len());
+        }
+        (indptr, indices, data)
+    }
+
+    fn sample(rows: usize, seed: usize) -> Array2<f64> {
+        Array2::from_shape_fn((rows, 12), |(i, j)| {
+            let h = (i * 31 + j * 17 + seed) % 23;
+            if h < 8 {
+                h as f64 - 3.5
+            } else {
+                0.0
+            }
+        })
+    }
+
+    #[test]
+    fn test_matches_dense_cdist() {
+        let (a, b) = (sample(21, 1), sample(9, 5));
+        let (pa, ia, da) = to_csr(&a);
+        let (pb, ib, db) = to_csr(&b);
+        let (sa, sb) = (
+            CsrView::new(&pa, &ia, &da, 12),
+            CsrView::new(&pb, &ib, &db, 12),
+        );
+
+        let d = cdist_sparse(&sa, &sb, &sparse_manhattan);
+        assert_eq!(d, cdist(&a.view(), &b.view(), &Manhattan));
+
+        let d = cdist_sparse(&sa, &sb, &sparse_cosine);
+        let expected = cdist(&a.view(), &b.view(), &Cosine);
+        assert!(d.iter().zip(&expected).all(|(x, y)| (x - y).abs() < 1e-12));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_par_matches_serial() {
+        let (a, b) = (sample(40, 2), sample(13, 7));
+        let (pa, ia, da) = to_csr(&a);
+        let (pb, ib, db) = to_csr(&b);
+        let (sa, sb) = (
+            CsrView::new(&pa, &ia, &da, 12),
+            CsrView::new(&pb, &ib, &db, 12),
+        );
+
+        let expected = cdist_sparse(&sa, &sb, &sparse_euclidean);
+        assert_eq!(par_cdist_sparse(&sa, &sb, &sparse_euclidean), expected);
+        assert_eq!(
+            par_cdist_sparse_with(&sa, &sb, &sparse_euclidean, Parallelism::Threads(2)),
+            expected
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Input matrices must have the same number of columns.")]
+    fn test_column_mismatch() {
+        let a = CsrView::new(&[0, 0], &[], &[] as &[f64], 3);
+        let b = CsrView::new(&[0, 0], &[], &[] as &[f64], 4);
+        cdist_sparse(&a, &b, &sparse_euclidean);
+    }
+}
@@ -0,0 +1,146 @@
+/// A matrix whose rows can be read as sparse vectors, as accepted by
+/// [`cdist_sparse`](crate::cdist_sparse).
+///
+/// Implemented by [`CsrView`] and, with the `sprs` feature, by CSR matrices of the `sprs`
+/// crate.
+pub trait SparseRows<T> {
+    /// Returns the number of rows.
+    fn nrows(&self) -> usize;
+
+    /// Returns the number of columns.
+    fn ncols(&self) -> usize;
+
+    /// Returns the strictly increasing indices and the values of row `i`.
+    fn row(&self, i: usize) -> (&[usize], &[T]);
+}
+
+/// A borrowed compressed sparse row (CSR) matrix.
+///
+/// Row `i` is stored in `indices[indptr[i]..indptr[i + 1]]` and the matching range of `data`,
+/// the layout used by `scipy.sparse.csr_matrix`, so buffers produced elsewhere can be compared
+/// with [`cdist_sparse`](crate::cdist_sparse) without copying. The indices of every row must be
+/// strictly increasing, which is checked on construction.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// // [[1, 0, 2],
+/// //  [0, 0, 0],
+/// //  [0, 3, 0]]
+/// let m = CsrView::new(&[0, 2, 2, 3], &[0, 2, 1], &[1.0, 2.0, 3.0], 3);
+/// assert_eq!(m.nrows(), 3);
+/// assert_eq!(m.row(0), (&[0, 2][..], &[1.0, 2.0][..]));
+/// assert_eq!(m.row(1).0.len(), 0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CsrView<'a, T> {
+    indptr: &'a [usize],
+    indices: &'a [usize],
+    data: &'a [T],
+    ncols: usize,
+}
+
+impl<'a, T> CsrView<'a, T> {
+    /// Wraps CSR buffers describing a matrix with `indptr.len() - 1` rows and `ncols` columns.
+    ///
+    /// # Panics
+    /// Panics if `indptr` is empty, does not start at zero, is decreasing or does not end at
+    /// `indices.len()`; if `indices` and `data` have different lengths; or if the indices of a
+    /// row are not strictly increasing or not smaller than `ncols`.
+    pub fn new(indptr: &'a [usize], indices: &'a [usize], data: &'a [T], ncols: usize) -> Self {
+        assert_eq!(
+            indices.len(),
+            data.len(),
+            "Indices and values must have the same length."
+        );
+        assert!(
+            indptr.first() == Some(&0)
+                && indptr.last() == Some(&indices.len())
+                && indptr.windows(2).all(|w| w[0] <= w[1]),
+            "Row pointers must increase from zero to the number of stored values."
+        );
+        for row in indptr.windows(2) {
+            let row = &indices[row[0]..row[1]];
+            assert!(
+                row.windows(2).all(|w| w[0] < w[1]),
+                "Indices must be strictly increasing."
+            );
+            assert!(
+                row.last().is_none_or(|&i| i < ncols),
+                "Indices must be smaller than the number of features."
+            );
+        }
+        Self {
+            indptr,
+            indices,
+            data,
+            ncols,
+        }
+    }
+
+    /// Returns the number of rows.
+    pub fn nrows(&self) -> usize {
+        self.indptr.len() - 1
+    }
+
+    /// Returns the number of columns.
+    pub fn ncols(&self) -> usize {
+        self.ncols
+    }
+
+    /// Returns the number of stored values.
+    pub fn nnz(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Returns the indices and values of row `i`.
+    ///
+    /// # Panics
+    /// Panics if `i` is not smaller than the number of rows.
+    pub fn row(&self, i: usize) -> (&'a [usize], &'a [T]) {
+        let (start, end) = (self.indptr[i], self.indptr[i + 1]);
+        (&self.indices[start..end], &self.data[start..end])
+    }
+}
+
+impl<T> SparseRows<T> for CsrView<'_, T> {
+    fn nrows(&self) -> usize {
+        CsrView::nrows(self)
+    }
+
+    fn ncols(&self) -> usize {
+        self.ncols
+    }
+
+    fn row(&self, i: usize) -> (&[usize], &[T]) {
+        CsrView::row(self, i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rows() {
+        let m = CsrView::new(&[0, 1, 1, 3], &[4, 0, 5], &[1.0, 2.0, 3.0], 6);
+        assert_eq!((m.nrows(), m.ncols(), m.nnz()), (3, 6, 3));
+        assert_eq!(m.row(0), (&[4][..], &[1.0][..]));
+        assert_eq!(m.row(2), (&[0, 5][..], &[2.0, 3.0][..]));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Row pointers must increase from zero to the number of stored values."
+    )]
+    fn test_invalid_indptr() {
+        CsrView::new(&[0, 2], &[0], &[1.0], 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Indices must be strictly increasing.")]
+    fn test_unsorted_row() {
+        CsrView::new(&[0, 2], &[2, 1], &[1.0, 1.0], 3);
+    }
+}
@@ -0,0 +1,31 @@
+mod cdist_sparse;
+mod csr;
+mod sparse_binary;
+mod sparse_bray_curtis;
+mod sparse_canberra;
+mod sparse_chebyshev;
+mod sparse_correlation;
+mod sparse_cosine;
+mod sparse_cosine_grad;
+mod sparse_euclidean;
+mod sparse_hellinger;
+mod sparse_manhattan;
+mod sparse_minkowski;
+mod sparse_vec;
+#[cfg(feature = "sprs")]
+mod sprs_interop;
+
+pub use cdist_sparse::*;
+pub use csr::*;
+pub use sparse_binary::*;
+pub use sparse_bray_curtis::*;
+pub use sparse_canberra::*;
+pub use sparse_chebyshev::*;
+pub use sparse_correlation::*;
+pub use sparse_cosine::*;
+pub use sparse_cosine_grad::*;
+pub use sparse_euclidean::*;
+pub use sparse_hellinger::*;
+pub use sparse_manhattan::*;
+pub use sparse_minkowski::*;
+pub use sparse_vec::*;
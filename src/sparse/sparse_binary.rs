@@ -0,0 +1,194 @@
+use super::sparse_vec::check_features;
+
+/// Counts computed from the merged supports of two binary sparse vectors.
+struct SetCounts {
+    /// Indices present in both vectors.
+    num_true_true: usize,
+    /// Indices present in exactly one of the vectors.
+    num_not_equal: usize,
+}
+
+/// Counts the intersection and symmetric difference of two strictly increasing index arrays.
+fn set_counts(ind1: &[usize], ind2: &[usize]) -> SetCounts {
+    let (mut i, mut j, mut num_true_true) = (0, 0, 0);
+    while i < ind1.len() && j < ind2.len() {
+        match ind1[i].cmp(&ind2[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                num_true_true += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    SetCounts {
+        num_true_true,
+        num_not_equal: ind1.len() + ind2.len() - 2 * num_true_true,
+    }
+}
+
+/// Computes the Jaccard distance between two binary sparse vectors given by their nonzero
+/// indices.
+///
+/// Both index arrays must be strictly increasing. Like the dense [`jaccard`](crate::jaccard),
+/// the distance is `0` when both sets are empty.
+///
+/// ..math::
+///    d(x, y) = 1 - \frac{|x \cap y|}{|x \cup y|}
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// assert_eq!(sparse_jaccard(&[0, 2, 5], &[2, 5, 7, 9]), 0.6);
+/// ```
+pub fn sparse_jaccard(ind1: &[usize], ind2: &[usize]) -> f64 {
+    let counts = set_counts(ind1, ind2);
+    let num_non_zero = counts.num_true_true + counts.num_not_equal;
+    if num_non_zero == 0 {
+        0.0
+    } else {
+        counts.num_not_equal as f64 / num_non_zero as f64
+    }
+}
+
+/// Computes the Dice dissimilarity between two binary sparse vectors given by their nonzero
+/// indices.
+///
+/// Both index arrays must be strictly increasing. Matches the dense [`dice`](crate::dice).
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// assert_eq!(sparse_dice(&[0, 2], &[2, 3]), 0.5);
+/// ```
+pub fn sparse_dice(ind1: &[usize], ind2: &[usize]) -> f64 {
+    let counts = set_counts(ind1, ind2);
+    if counts.num_not_equal == 0 {
+        0.0
+    } else {
+        let num_not_equal = counts.num_not_equal as f64;
+        num_not_equal / (2.0 * counts.num_true_true as f64 + num_not_equal)
+    }
+}
+
+/// Computes the matching dissimilarity between two binary sparse vectors over `n_features`
+/// dimensions.
+///
+/// Both index arrays must be strictly increasing. Matches the dense
+/// [`matching`](crate::matching) on vectors of length `n_features`.
+///
+/// # Panics
+/// Panics if an index is not smaller than `n_features`.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// assert_eq!(sparse_matching(&[0, 2], &[2, 3], 8), 0.25);
+/// ```
+pub fn sparse_matching(ind1: &[usize], ind2: &[usize], n_features: usize) -> f64 {
+    check_features(ind1, ind2, n_features);
+    set_counts(ind1, ind2).num_not_equal as f64 / n_features as f64
+}
+
+/// Computes the Kulsinski dissimilarity between two binary sparse vectors over `n_features`
+/// dimensions.
+///
+/// Both index arrays must be strictly increasing. Matches the dense
+/// [`kulsinski`](crate::kulsinski) on vectors of length `n_features`.
+///
+/// # Panics
+/// Panics if an index is not smaller than `n_features`.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// assert_eq!(sparse_kulsinski(&[0, 2], &[2, 3], 4), 5.0 / 6.0);
+/// ```
+pub fn sparse_kulsinski(ind1: &[usize], ind2: &[usize], n_features: usize) -> f64 {
+    check_features(ind1, ind2, n_features);
+    let counts = set_counts(ind1, ind2);
+    if counts.num_not_equal == 0 {
+        0.0
+    } else {
+        let (num_not_equal, n) = (counts.num_not_equal as f64, n_features as f64);
+        (num_not_equal - counts.num_true_true as f64 + n) / (num_not_equal + n)
+    }
+}
+
+/// Computes the Rogers-Tanimoto dissimilarity between two binary sparse vectors over
+/// `n_features` dimensions.
+///
+/// Both index arrays must be strictly increasing. Matches the dense
+/// [`rogers_tanimoto`](crate::rogers_tanimoto) on vectors of length `n_features`.
+///
+/// # Panics
+/// Panics if an index is not smaller than `n_features`.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// assert_eq!(sparse_rogers_tanimoto(&[0, 2], &[2, 3], 6), 0.5);
+/// ```
+pub fn sparse_rogers_tanimoto(ind1: &[usize], ind2: &[usize], n_features: usize) -> f64 {
+    check_features(ind1, ind2, n_features);
+    let num_not_equal = set_counts(ind1, ind2).num_not_equal as f64;
+    (2.0 * num_not_equal) / (n_features as f64 + num_not_equal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{dice, jaccard, kulsinski, matching, rogers_tanimoto};
+    use ndarray::Array1;
+
+    fn dense(indices: &[usize], n: usize) -> Array1<f64> {
+        let mut x = Array1::zeros(n);
+        for &i in indices {
+            x[i] = 1.0;
+        }
+        x
+    }
+
+    #[test]
+    fn test_matches_dense() {
+        let n = 16;
+        let cases: [(&[usize], &[usize]); 4] = [
+            (&[0, 3, 4, 9, 15], &[1, 3, 9, 10]),
+            (&[2, 5], &[2, 5]),
+            (&[], &[7]),
+            (&[1, 2, 3], &[4, 5, 6]),
+        ];
+        for (ind1, ind2) in cases {
+            let (x, y) = (dense(ind1, n), dense(ind2, n));
+            let (x, y) = (x.view(), y.view());
+            assert_eq!(sparse_jaccard(ind1, ind2), jaccard(&x, &y));
+            assert_eq!(sparse_dice(ind1, ind2), dice(&x, &y));
+            assert_eq!(sparse_matching(ind1, ind2, n), matching(&x, &y));
+            assert_eq!(sparse_kulsinski(ind1, ind2, n), kulsinski(&x, &y));
+            assert_eq!(
+                sparse_rogers_tanimoto(ind1, ind2, n),
+                rogers_tanimoto(&x, &y)
+            );
+        }
+    }
+
+    #[test]
+    fn test_empty_sets() {
+        assert_eq!(sparse_jaccard(&[], &[]), 0.0);
+        assert_eq!(sparse_dice(&[], &[]), 0.0);
+        assert_eq!(sparse_kulsinski(&[], &[], 5), 0.0);
+        assert_eq!(sparse_rogers_tanimoto(&[], &[], 5), 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Indices must be smaller than the number of features.")]
+    fn test_index_out_of_range() {
+        sparse_matching(&[1, 4], &[2], 4);
+    }
+}
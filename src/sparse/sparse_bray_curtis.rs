@@ -0,0 +1,75 @@
+use num::Float;
+
+use super::sparse_vec::union;
+
+/// Computes the Bray-Curtis dissimilarity between two sparse vectors.
+///
+/// ..math::
+///    d(x, y) = \frac{\sum_i |x_i - y_i|}{\sum_i |x_i + y_i|}
+///
+/// Coordinates that are zero in both vectors contribute to neither sum, so only the merged
+/// supports are visited. The result equals [`bray_curtis`](crate::bray_curtis) on the densified
+/// vectors and is `0` when the denominator vanishes.
+///
+/// # Arguments
+/// * `ind1` - The indices of the first vector.
+/// * `data1` - The values of the first vector.
+/// * `ind2` - The indices of the second vector.
+/// * `data2` - The values of the second vector.
+///
+/// # Panics
+/// Panics if either vector has a different number of indices and values.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// let d = sparse_bray_curtis(&[0, 2], &[1.0, 3.0], &[2], &[1.0]);
+/// assert_eq!(d, 0.6);
+/// ```
+pub fn sparse_bray_curtis<T>(ind1: &[usize], data1: &[T], ind2: &[usize], data2: &[T]) -> T
+where
+    T: Float,
+{
+    let (numerator, denominator) = union(ind1, data1, ind2, data2)
+        .fold((T::zero(), T::zero()), |(num, den), (_, x, y)| {
+            (num + (x - y).abs(), den + (x + y).abs())
+        });
+
+    if denominator > T::zero() {
+        numerator / denominator
+    } else {
+        T::zero()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bray_curtis, SparseVec};
+    use ndarray::Array1;
+
+    #[test]
+    fn test_matches_dense() {
+        let x: Array1<f64> = (0..20)
+            .map(|i| if i % 4 != 1 { i as f64 } else { 0.0 })
+            .collect();
+        let y: Array1<f64> = (0..20)
+            .map(|i| if i % 3 == 0 { 20.0 - i as f64 } else { 0.0 })
+            .collect();
+        let (sx, sy) = (
+            SparseVec::from_dense(&x.view()),
+            SparseVec::from_dense(&y.view()),
+        );
+
+        let d = sparse_bray_curtis(sx.indices(), sx.values(), sy.indices(), sy.values());
+        assert!((d - bray_curtis(&x.view(), &y.view())).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_zero_denominator() {
+        let empty: [f32; 0] = [];
+        assert_eq!(sparse_bray_curtis(&[], &empty, &[], &empty), 0.0);
+        assert_eq!(sparse_bray_curtis(&[4], &[2.0f32], &[], &empty), 1.0);
+    }
+}
@@ -0,0 +1,86 @@
+use num::Float;
+
+use super::sparse_vec::union;
+
+/// Computes the Canberra distance between two sparse vectors.
+///
+/// ..math::
+///    d(x, y) = \sum_i \frac{|x_i - y_i|}{|x_i| + |y_i|}
+///
+/// Coordinates that are zero in both vectors have a zero denominator and are skipped, as in
+/// the dense [`canberra`](crate::canberra), so only the merged supports are visited. A
+/// coordinate stored in only one of the vectors always contributes `1`.
+///
+/// # Arguments
+/// * `ind1` - The indices of the first vector.
+/// * `data1` - The values of the first vector.
+/// * `ind2` - The indices of the second vector.
+/// * `data2` - The values of the second vector.
+///
+/// # Panics
+/// Panics if either vector has a different number of indices and values.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// let d = sparse_canberra(&[0, 2], &[1.0, 3.0], &[2, 5], &[1.0, 2.0]);
+/// assert_eq!(d, 2.5);
+/// ```
+pub fn sparse_canberra<T>(ind1: &[usize], data1: &[T], ind2: &[usize], data2: &[T]) -> T
+where
+    T: Float,
+{
+    union(ind1, data1, ind2, data2).fold(T::zero(), |acc, (_, x, y)| {
+        let denominator = x.abs() + y.abs();
+        if denominator > T::zero() {
+            acc + (x - y).abs() / denominator
+        } else {
+            acc
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+    use crate::{canberra, SparseVec};
+    use ndarray::Array1;
+
+    /// A random vector of length `n` in which roughly 70% of the coordinates are zero.
+    fn random_sparse(rng: &mut SplitMix64, n: usize) -> Array1<f64> {
+        (0..n)
+            .map(|_| {
+                if rng.next_f64() < 0.3 {
+                    rng.next_gaussian()
+                } else {
+                    0.0
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_matches_dense_on_random_inputs() {
+        let mut rng = SplitMix64::new(7);
+        for _ in 0..200 {
+            let n = 1 + rng.next_below(40);
+            let (x, y) = (random_sparse(&mut rng, n), random_sparse(&mut rng, n));
+            let (sx, sy) = (
+                SparseVec::from_dense(&x.view()),
+                SparseVec::from_dense(&y.view()),
+            );
+
+            let d = sparse_canberra(sx.indices(), sx.values(), sy.indices(), sy.values());
+            assert!((d - canberra(&x.view(), &y.view())).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_explicit_zeros_are_skipped() {
+        assert_eq!(sparse_canberra(&[1, 3], &[0.0, 2.0], &[1], &[0.0]), 1.0);
+        let empty: [f64; 0] = [];
+        assert_eq!(sparse_canberra(&[], &empty, &[], &empty), 0.0);
+    }
+}
@@ -0,0 +1,76 @@
+use num::Float;
+
+use super::sparse_vec::union;
+
+/// Computes the Chebyshev (L∞) distance between two sparse vectors.
+///
+/// The maximum of `|x_i - y_i|` is taken over the merged supports; every other coordinate is
+/// zero in both vectors, so the result equals [`chebyshev`](crate::chebyshev) on the densified
+/// vectors and is `0` when both are empty.
+///
+/// # Arguments
+/// * `ind1` - The indices of the first vector.
+/// * `data1` - The values of the first vector.
+/// * `ind2` - The indices of the second vector.
+/// * `data2` - The values of the second vector.
+///
+/// # Panics
+/// Panics if either vector has a different number of indices and values.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// let d = sparse_chebyshev(&[0, 2], &[1.0, 3.0], &[2, 5], &[1.0, -4.0]);
+/// assert_eq!(d, 4.0);
+/// ```
+pub fn sparse_chebyshev<T>(ind1: &[usize], data1: &[T], ind2: &[usize], data2: &[T]) -> T
+where
+    T: Float,
+{
+    union(ind1, data1, ind2, data2).fold(T::zero(), |acc, (_, x, y)| acc.max((x - y).abs()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+    use crate::{chebyshev, SparseVec};
+    use ndarray::Array1;
+
+    /// A random vector of length `n` in which roughly 70% of the coordinates are zero.
+    fn random_sparse(rng: &mut SplitMix64, n: usize) -> Array1<f64> {
+        (0..n)
+            .map(|_| {
+                if rng.next_f64() < 0.3 {
+                    rng.next_gaussian()
+                } else {
+                    0.0
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_matches_dense_on_random_inputs() {
+        let mut rng = SplitMix64::new(11);
+        for _ in 0..200 {
+            let n = 1 + rng.next_below(40);
+            let (x, y) = (random_sparse(&mut rng, n), random_sparse(&mut rng, n));
+            let (sx, sy) = (
+                SparseVec::from_dense(&x.view()),
+                SparseVec::from_dense(&y.view()),
+            );
+
+            let d = sparse_chebyshev(sx.indices(), sx.values(), sy.indices(), sy.values());
+            assert_eq!(d, chebyshev(&x.view(), &y.view()));
+        }
+    }
+
+    #[test]
+    fn test_one_sided_support() {
+        assert_eq!(sparse_chebyshev(&[4], &[-3.0f32], &[], &[]), 3.0);
+        let empty: [f32; 0] = [];
+        assert_eq!(sparse_chebyshev(&[], &empty, &[], &empty), 0.0);
+    }
+}
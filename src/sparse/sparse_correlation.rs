@@ -0,0 +1,136 @@
+use num::Float;
+
+use super::sparse_vec::{check_features, union};
+
+/// Computes the correlation distance between two sparse vectors over `n_features` dimensions.
+///
+/// Centering makes the implicit zeros nonzero, so the means are taken over all `n_features`
+/// coordinates and each coordinate outside the merged supports contributes `mu_x^2`, `mu_y^2`
+/// and `mu_x * mu_y` to the norms and the dot product in closed form. Only the supports are
+/// visited, and the result agrees with [`correlation`](crate::correlation) on the densified
+/// vectors up to rounding, including its handling of constant vectors.
+///
+/// # Arguments
+/// * `ind1` - The indices of the first vector.
+/// * `data1` - The values of the first vector.
+/// * `ind2` - The indices of the second vector.
+/// * `data2` - The values of the second vector.
+/// * `n_features` - The dimension of both vectors.
+///
+/// # Panics
+/// Panics if either vector has a different number of indices and values, or if an index is
+/// not smaller than `n_features`.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// // [1, 0, 2, 0] and [2, 0, 4, 0] are perfectly correlated.
+/// let d: f64 = sparse_correlation(&[0, 2], &[1.0, 2.0], &[0, 2], &[2.0, 4.0], 4);
+/// assert!(d.abs() < 1e-12);
+/// ```
+pub fn sparse_correlation<T>(
+    ind1: &[usize],
+    data1: &[T],
+    ind2: &[usize],
+    data2: &[T],
+    n_features: usize,
+) -> T
+where
+    T: Float,
+{
+    check_features(ind1, ind2, n_features);
+
+    let n = T::from(n_features).unwrap();
+    let mu_x = data1.iter().fold(T::zero(), |acc, &v| acc + v) / n;
+    let mu_y = data2.iter().fold(T::zero(), |acc, &v| acc + v) / n;
+
+    let mut support = 0;
+    let (mut dot_product, mut norm_x, mut norm_y) = (T::zero(), T::zero(), T::zero());
+    for (_, x, y) in union(ind1, data1, ind2, data2) {
+        let (shifted_x, shifted_y) = (x - mu_x, y - mu_y);
+        norm_x = norm_x + shifted_x * shifted_x;
+        norm_y = norm_y + shifted_y * shifted_y;
+        dot_product = dot_product + shifted_x * shifted_y;
+        support += 1;
+    }
+
+    // Both vectors are zero outside the merged supports, so those coordinates shift to the
+    // negated means.
+    let zeros = T::from(n_features - support).unwrap();
+    norm_x = norm_x + zeros * mu_x * mu_x;
+    norm_y = norm_y + zeros * mu_y * mu_y;
+    dot_product = dot_product + zeros * mu_x * mu_y;
+
+    if norm_x.is_zero() && norm_y.is_zero() {
+        T::zero()
+    } else if dot_product.is_zero() {
+        T::one()
+    } else {
+        T::one() - dot_product / (norm_x.sqrt() * norm_y.sqrt())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{correlation, SparseVec};
+    use ndarray::Array1;
+
+    #[test]
+    fn test_matches_dense() {
+        let n = 40;
+        let x: Array1<f64> = (0..n)
+            .map(|i| {
+                if i % 5 == 0 {
+                    (i as f64).sin() + 2.0
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        let y: Array1<f64> = (0..n)
+            .map(|i| {
+                if i % 3 == 2 {
+                    1.0 - 0.1 * i as f64
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        let (sx, sy) = (
+            SparseVec::from_dense(&x.view()),
+            SparseVec::from_dense(&y.view()),
+        );
+
+        let d = sparse_correlation(sx.indices(), sx.values(), sy.indices(), sy.values(), n);
+        assert!((d - correlation(&x.view(), &y.view())).abs() < 1e-12);
+        let d = sparse_correlation(sx.indices(), sx.values(), sx.indices(), sx.values(), n);
+        assert!(d.abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_dense_support() {
+        // Every coordinate stored, so no implicit zeros remain.
+        let ind = [0, 1, 2, 3];
+        let (x, y) = ([1.0, 2.0, 3.0, 4.0], [4.0, 1.0, 3.0, 2.0]);
+        let expected = correlation(
+            &Array1::from_vec(x.to_vec()).view(),
+            &Array1::from_vec(y.to_vec()).view(),
+        );
+        assert!((sparse_correlation(&ind, &x, &ind, &y, 4) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_constant_vectors() {
+        let empty: [f64; 0] = [];
+        assert_eq!(sparse_correlation(&[], &empty, &[], &empty, 6), 0.0);
+        assert_eq!(sparse_correlation(&[], &empty, &[2], &[1.0], 6), 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Indices must be smaller than the number of features.")]
+    fn test_index_out_of_range() {
+        sparse_correlation(&[5], &[1.0], &[0], &[1.0], 5);
+    }
+}
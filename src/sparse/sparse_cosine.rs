@@ -0,0 +1,94 @@
+use num::Float;
+
+use super::sparse_vec::union;
+
+/// Computes the cosine distance between two sparse vectors.
+///
+/// The dot product and both norms are accumulated in one merge over the supports, so the cost
+/// is `O(nnz(x) + nnz(y))`. As for the dense [`cosine`](crate::cosine), the distance is `0` if
+/// both vectors are zero and `1` if only one of them is.
+///
+/// # Arguments
+/// * `ind1` - The indices of the first vector.
+/// * `data1` - The values of the first vector.
+/// * `ind2` - The indices of the second vector.
+/// * `data2` - The values of the second vector.
+///
+/// # Panics
+/// Panics if either vector has a different number of indices and values.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// assert_eq!(sparse_cosine(&[1, 5], &[2.0, 0.0], &[1], &[3.0]), 0.0);
+/// assert_eq!(sparse_cosine(&[1], &[2.0], &[4], &[3.0]), 1.0);
+/// ```
+pub fn sparse_cosine<T>(ind1: &[usize], data1: &[T], ind2: &[usize], data2: &[T]) -> T
+where
+    T: Float,
+{
+    let (dot, norm_x, norm_y) = dot_and_norms(ind1, data1, ind2, data2);
+
+    if norm_x.is_zero() && norm_y.is_zero() {
+        T::zero()
+    } else if norm_x.is_zero() || norm_y.is_zero() {
+        T::one()
+    } else {
+        T::one() - dot / (norm_x.sqrt() * norm_y.sqrt())
+    }
+}
+
+/// Returns the dot product and the squared norms of two sparse vectors.
+pub(crate) fn dot_and_norms<T>(
+    ind1: &[usize],
+    data1: &[T],
+    ind2: &[usize],
+    data2: &[T],
+) -> (T, T, T)
+where
+    T: Float,
+{
+    union(ind1, data1, ind2, data2).fold(
+        (T::zero(), T::zero(), T::zero()),
+        |(dot, nx, ny), (_, x, y)| (dot + x * y, nx + x * x, ny + y * y),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cosine, SparseVec};
+    use ndarray::Array1;
+
+    #[test]
+    fn test_matches_dense() {
+        let x: Array1<f64> = (0..30)
+            .map(|i| if i % 3 == 0 { (i as f64).sin() } else { 0.0 })
+            .collect();
+        let y: Array1<f64> = (0..30)
+            .map(|i| if i % 2 == 0 { (i as f64).cos() } else { 0.0 })
+            .collect();
+        let (sx, sy) = (
+            SparseVec::from_dense(&x.view()),
+            SparseVec::from_dense(&y.view()),
+        );
+
+        let d = sparse_cosine(sx.indices(), sx.values(), sy.indices(), sy.values());
+        assert!((d - cosine(&x.view(), &y.view())).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_zero_norms() {
+        let empty: [f64; 0] = [];
+        assert_eq!(sparse_cosine(&[], &empty, &[], &empty), 0.0);
+        assert_eq!(sparse_cosine(&[3], &[0.0], &[], &empty), 0.0);
+        assert_eq!(sparse_cosine(&[3], &[1.0], &[], &empty), 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Indices and values must have the same length.")]
+    fn test_mismatched_parts() {
+        sparse_cosine(&[0], &[1.0], &[0, 2], &[1.0]);
+    }
+}
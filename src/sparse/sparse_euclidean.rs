@@ -0,0 +1,78 @@
+use num::Float;
+
+use super::sparse_vec::union;
+
+/// Computes the Euclidean distance between two sparse vectors.
+///
+/// Each vector is given as strictly increasing indices and the values stored at them, like a
+/// row of a CSR matrix or a [`SparseVec`](crate::SparseVec). The supports are merged in a
+/// single pass, so the cost is `O(nnz(x) + nnz(y))` regardless of the dimension, and the result
+/// equals [`euclidean`](crate::euclidean) on the densified vectors.
+///
+/// # Arguments
+/// * `ind1` - The indices of the first vector.
+/// * `data1` - The values of the first vector.
+/// * `ind2` - The indices of the second vector.
+/// * `data2` - The values of the second vector.
+///
+/// # Panics
+/// Panics if either vector has a different number of indices and values.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// let d = sparse_euclidean(&[0, 4], &[3.0, 1.0], &[4, 7], &[1.0, 4.0]);
+/// assert_eq!(d, 5.0);
+/// ```
+pub fn sparse_euclidean<T>(ind1: &[usize], data1: &[T], ind2: &[usize], data2: &[T]) -> T
+where
+    T: Float,
+{
+    union(ind1, data1, ind2, data2)
+        .fold(T::zero(), |acc, (_, x, y)| acc + (x - y) * (x - y))
+        .sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{euclidean, SparseVec};
+    use ndarray::{arr1, Array1};
+
+    #[test]
+    fn test_matches_dense() {
+        let x: Array1<f64> = (0..20)
+            .map(|i| if i % 3 == 0 { i as f64 * 0.5 } else { 0.0 })
+            .collect();
+        let y: Array1<f64> = (0..20)
+            .map(|i| if i % 4 == 1 { 1.0 - i as f64 } else { 0.0 })
+            .collect();
+        let (sx, sy) = (
+            SparseVec::from_dense(&x.view()),
+            SparseVec::from_dense(&y.view()),
+        );
+
+        let d = sparse_euclidean(sx.indices(), sx.values(), sy.indices(), sy.values());
+        assert!((d - euclidean(&x.view(), &y.view())).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_empty_and_identical() {
+        let empty: [f32; 0] = [];
+        assert_eq!(sparse_euclidean(&[], &empty, &[], &empty), 0.0);
+        assert_eq!(sparse_euclidean(&[2], &[3.0f32], &[], &empty), 3.0);
+
+        let x = SparseVec::from_dense(&arr1(&[0.0, 1.0, -2.0]).view());
+        assert_eq!(
+            sparse_euclidean(x.indices(), x.values(), x.indices(), x.values()),
+            0.0
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Indices and values must have the same length.")]
+    fn test_mismatched_parts() {
+        sparse_euclidean(&[0, 1], &[1.0], &[0], &[1.0]);
+    }
+}
@@ -0,0 +1,81 @@
+use num::Float;
+
+use super::sparse_vec::union;
+
+/// Computes the Hellinger distance between two sparse vectors of nonnegative weights.
+///
+/// Only coordinates stored in both vectors contribute to `sum(sqrt(x_i * y_i))`, and the L1
+/// norms are plain sums of the stored values, so the result equals
+/// [`hellinger`](crate::hellinger) on the densified vectors, including its handling of zero
+/// vectors.
+///
+/// # Arguments
+/// * `ind1` - The indices of the first vector.
+/// * `data1` - The values of the first vector.
+/// * `ind2` - The indices of the second vector.
+/// * `data2` - The values of the second vector.
+///
+/// # Panics
+/// Panics if either vector has a different number of indices and values.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// assert_eq!(sparse_hellinger(&[0, 3], &[0.5, 0.5], &[0, 3], &[0.5, 0.5]), 0.0);
+/// assert_eq!(sparse_hellinger(&[0], &[1.0], &[3], &[1.0]), 1.0);
+/// ```
+pub fn sparse_hellinger<T>(ind1: &[usize], data1: &[T], ind2: &[usize], data2: &[T]) -> T
+where
+    T: Float,
+{
+    let (result, l1_norm_x, l1_norm_y) = union(ind1, data1, ind2, data2).fold(
+        (T::zero(), T::zero(), T::zero()),
+        |(result, nx, ny), (_, x, y)| (result + (x * y).sqrt(), nx + x, ny + y),
+    );
+
+    if l1_norm_x.is_zero() && l1_norm_y.is_zero() {
+        T::zero()
+    } else if l1_norm_x.is_zero() || l1_norm_y.is_zero() {
+        T::one()
+    } else {
+        T::one() - result / (l1_norm_x * l1_norm_y).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hellinger, SparseVec};
+    use ndarray::Array1;
+
+    #[test]
+    fn test_matches_dense() {
+        let x: Array1<f64> = (0..20)
+            .map(|i| if i % 3 == 0 { 0.1 * i as f64 } else { 0.0 })
+            .collect();
+        let y: Array1<f64> = (0..20)
+            .map(|i| {
+                if i % 2 == 0 {
+                    1.0 / (1 + i) as f64
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        let (sx, sy) = (
+            SparseVec::from_dense(&x.view()),
+            SparseVec::from_dense(&y.view()),
+        );
+
+        let d = sparse_hellinger(sx.indices(), sx.values(), sy.indices(), sy.values());
+        assert!((d - hellinger(&x.view(), &y.view())).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_zero_vectors() {
+        let empty: [f64; 0] = [];
+        assert_eq!(sparse_hellinger(&[], &empty, &[], &empty), 0.0);
+        assert_eq!(sparse_hellinger(&[], &empty, &[1], &[0.3]), 1.0);
+    }
+}
@@ -0,0 +1,66 @@
+use num::Float;
+
+use super::sparse_vec::union;
+
+/// Computes the Manhattan (L1) distance between two sparse vectors.
+///
+/// The supports are merged with two pointers; a coordinate stored in only one of the vectors
+/// contributes `|x_i - 0|`, so the result equals [`manhattan`](crate::manhattan) on the
+/// densified vectors.
+///
+/// # Arguments
+/// * `ind1` - The indices of the first vector.
+/// * `data1` - The values of the first vector.
+/// * `ind2` - The indices of the second vector.
+/// * `data2` - The values of the second vector.
+///
+/// # Panics
+/// Panics if either vector has a different number of indices and values.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// let d = sparse_manhattan(&[0, 4], &[3.0, 1.0], &[4, 7], &[-1.0, 4.0]);
+/// assert_eq!(d, 9.0);
+/// ```
+pub fn sparse_manhattan<T>(ind1: &[usize], data1: &[T], ind2: &[usize], data2: &[T]) -> T
+where
+    T: Float,
+{
+    union(ind1, data1, ind2, data2).fold(T::zero(), |acc, (_, x, y)| acc + (x - y).abs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{manhattan, SparseVec};
+    use ndarray::Array1;
+
+    #[test]
+    fn test_matches_dense() {
+        let x: Array1<f64> = (0..20)
+            .map(|i| if i % 3 == 0 { i as f64 - 7.0 } else { 0.0 })
+            .collect();
+        let y: Array1<f64> = (0..20)
+            .map(|i| if i % 5 < 2 { 0.25 * i as f64 } else { 0.0 })
+            .collect();
+        let (sx, sy) = (
+            SparseVec::from_dense(&x.view()),
+            SparseVec::from_dense(&y.view()),
+        );
+
+        let d = sparse_manhattan(sx.indices(), sx.values(), sy.indices(), sy.values());
+        assert!((d - manhattan(&x.view(), &y.view())).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_disjoint_supports() {
+        assert_eq!(
+            sparse_manhattan(&[1, 2], &[-2.0f32, 3.0], &[0], &[4.0]),
+            9.0
+        );
+        let empty: [f32; 0] = [];
+        assert_eq!(sparse_manhattan(&[], &empty, &[], &empty), 0.0);
+    }
+}
@@ -0,0 +1,76 @@
+use num::Float;
+
+use super::sparse_vec::union;
+
+/// Computes the Minkowski distance of order `p` between two sparse vectors.
+///
+/// ..math::
+///    d(x, y) = \left(\sum_i |x_i - y_i|^p\right)^{1/p}
+///
+/// Only the union of the supports is visited, since coordinates that are zero in both vectors
+/// contribute nothing; a coordinate stored in only one vector contributes `|x_i|^p`. The result
+/// equals [`minkowski`](crate::minkowski) on the densified vectors.
+///
+/// # Arguments
+/// * `ind1` - The indices of the first vector.
+/// * `data1` - The values of the first vector.
+/// * `ind2` - The indices of the second vector.
+/// * `data2` - The values of the second vector.
+/// * `p` - The order of the distance; `1` is Manhattan and `2` is Euclidean.
+///
+/// # Panics
+/// Panics if either vector has a different number of indices and values.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// let d = sparse_minkowski(&[0], &[3.0], &[2], &[4.0], 2.0);
+/// assert_eq!(d, 5.0);
+/// ```
+pub fn sparse_minkowski<T>(ind1: &[usize], data1: &[T], ind2: &[usize], data2: &[T], p: T) -> T
+where
+    T: Float,
+{
+    union(ind1, data1, ind2, data2)
+        .fold(T::zero(), |acc, (_, x, y)| acc + (x - y).abs().powf(p))
+        .powf(T::one() / p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{minkowski, sparse_euclidean, sparse_manhattan, SparseVec};
+    use ndarray::Array1;
+
+    #[test]
+    fn test_matches_dense() {
+        let x: Array1<f64> = (0..24)
+            .map(|i| if i % 4 == 0 { 1.5 - i as f64 } else { 0.0 })
+            .collect();
+        let y: Array1<f64> = (0..24)
+            .map(|i| if i % 3 == 0 { (i as f64).sqrt() } else { 0.0 })
+            .collect();
+        let (sx, sy) = (
+            SparseVec::from_dense(&x.view()),
+            SparseVec::from_dense(&y.view()),
+        );
+
+        for p in [0.5, 1.0, 3.0, 4.5] {
+            let d = sparse_minkowski(sx.indices(), sx.values(), sy.indices(), sy.values(), p);
+            assert!((d - minkowski(&x.view(), &y.view(), p)).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_special_orders() {
+        let (ind1, data1) = ([0, 3, 6], [1.0, -2.0, 0.5]);
+        let (ind2, data2) = ([3, 5], [2.0, 1.0]);
+        assert_eq!(
+            sparse_minkowski(&ind1, &data1, &ind2, &data2, 1.0),
+            sparse_manhattan(&ind1, &data1, &ind2, &data2)
+        );
+        let d = sparse_minkowski(&ind1, &data1, &ind2, &data2, 2.0);
+        assert!((d - sparse_euclidean(&ind1, &data1, &ind2, &data2)).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,249 @@
+use ndarray::{Array1, ArrayView1};
+use num::Zero;
+
+/// A sparse vector stored as strictly increasing indices and the values at those indices.
+///
+/// This is the layout used by the `sparse_*` metrics, which take the index and value slices
+/// directly (as in UMAP's `sparse.py`) so that rows of a CSR matrix can be passed without
+/// copying. Coordinates that are not listed are zero; explicitly stored zeros are allowed.
+///
+/// # Example
+/// ```
+/// use ndarray::arr1;
+/// use fast_distances::*;
+///
+/// let x = SparseVec::from_dense(&arr1(&[0.0, 2.0, 0.0, -1.0]).view());
+/// assert_eq!(x.indices(), &[1, 3]);
+/// assert_eq!(x.values(), &[2.0, -1.0]);
+/// assert_eq!(x.to_dense(5), arr1(&[0.0, 2.0, 0.0, -1.0, 0.0]));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseVec<T> {
+    indices: Vec<usize>,
+    values: Vec<T>,
+}
+
+impl<T> Default for SparseVec<T> {
+    /// Returns the empty (all-zero) sparse vector.
+    fn default() -> Self {
+        Self {
+            indices: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+}
+
+impl<T> SparseVec<T> {
+    /// Creates a sparse vector from its nonzero indices and values.
+    ///
+    /// # Panics
+    /// Panics if `indices` and `values` have different lengths or if `indices` is not strictly
+    /// increasing.
+    pub fn new(indices: Vec<usize>, values: Vec<T>) -> Self {
+        check_parts(&indices, &values);
+        assert!(
+            indices.windows(2).all(|w| w[0] < w[1]),
+            "Indices must be strictly increasing."
+        );
+        Self { indices, values }
+    }
+
+    /// Creates a sparse vector from parts already known to be well formed.
+    pub(crate) fn from_sorted(indices: Vec<usize>, values: Vec<T>) -> Self {
+        debug_assert!(indices.windows(2).all(|w| w[0] < w[1]));
+        Self { indices, values }
+    }
+
+    /// Returns the stored indices, in increasing order.
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// Returns the stored values, aligned with [`SparseVec::indices`].
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Returns the number of stored entries.
+    pub fn nnz(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Returns the smallest dense length that can hold this vector.
+    pub fn min_dim(&self) -> usize {
+        self.indices.last().map_or(0, |&i| i + 1)
+    }
+}
+
+impl<T> SparseVec<T>
+where
+    T: Copy + Zero,
+{
+    /// Creates a sparse vector holding the nonzero entries of a dense one.
+    pub fn from_dense(x: &ArrayView1<T>) -> Self {
+        let (indices, values) = x
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| !v.is_zero())
+            .map(|(i, &v)| (i, v))
+            .unzip();
+        Self { indices, values }
+    }
+
+    /// Expands the vector into a dense array of length `dim`.
+    ///
+    /// # Panics
+    /// Panics if an index is not smaller than `dim`.
+    pub fn to_dense(&self, dim: usize) -> Array1<T> {
+        assert!(
+            self.min_dim() <= dim,
+            "Indices must be smaller than the dimension."
+        );
+        let mut dense = Array1::zeros(dim);
+        for (&i, &v) in self.indices.iter().zip(&self.values) {
+            dense[i] = v;
+        }
+        dense
+    }
+}
+
+/// A sparse vector that exposes its strictly increasing indices and the matching values, as
+/// accepted by [`SparseDistance::distance_between`](crate::SparseDistance::distance_between).
+///
+/// Implemented by [`SparseVec`] and, with the `sprs` feature, by the vectors of the `sprs`
+/// crate.
+pub trait SparseVector<T> {
+    /// Returns the stored indices, in increasing order.
+    fn sparse_indices(&self) -> &[usize];
+
+    /// Returns the stored values, aligned with the indices.
+    fn sparse_values(&self) -> &[T];
+}
+
+impl<T> SparseVector<T> for SparseVec<T> {
+    fn sparse_indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    fn sparse_values(&self) -> &[T] {
+        &self.values
+    }
+}
+
+pub(crate) fn check_parts<T>(indices: &[usize], values: &[T]) {
+    assert_eq!(
+        indices.len(),
+        values.len(),
+        "Indices and values must have the same length."
+    );
+}
+
+/// Checks that every index of either vector is smaller than `n_features`, which only needs
+/// the last index since both arrays are sorted.
+pub(crate) fn check_features(ind1: &[usize], ind2: &[usize], n_features: usize) {
+    assert!(
+        ind1.last().max(ind2.last()).is_none_or(|&i| i < n_features),
+        "Indices must be smaller than the number of features."
+    );
+}
+
+/// Walks the union of the supports of two sparse vectors in increasing index order, yielding
+/// `(index, x_i, y_i)` with zero for a coordinate stored in only one of them.
+pub(crate) struct Union<'a, T> {
+    ind1: &'a [usize],
+    data1: &'a [T],
+    ind2: &'a [usize],
+    data2: &'a [T],
+    i: usize,
+    j: usize,
+}
+
+/// Checks that both vectors are well formed and returns their merged support.
+///
+/// # Panics
+/// Panics if either vector has a different number of indices and values.
+pub(crate) fn union<'a, T>(
+    ind1: &'a [usize],
+    data1: &'a [T],
+    ind2: &'a [usize],
+    data2: &'a [T],
+) -> Union<'a, T> {
+    check_parts(ind1, data1);
+    check_parts(ind2, data2);
+    Union {
+        ind1,
+        data1,
+        ind2,
+        data2,
+        i: 0,
+        j: 0,
+    }
+}
+
+impl<T> Iterator for Union<'_, T>
+where
+    T: Copy + Zero,
+{
+    type Item = (usize, T, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (i, j) = (self.i, self.j);
+        match (self.ind1.get(i), self.ind2.get(j)) {
+            (Some(&a), Some(&b)) if a == b => {
+                self.i += 1;
+                self.j += 1;
+                Some((a, self.data1[i], self.data2[j]))
+            }
+            (Some(&a), Some(&b)) if a < b => {
+                self.i += 1;
+                Some((a, self.data1[i], T::zero()))
+            }
+            (Some(&a), None) => {
+                self.i += 1;
+                Some((a, self.data1[i], T::zero()))
+            }
+            (_, Some(&b)) => {
+                self.j += 1;
+                Some((b, T::zero(), self.data2[j]))
+            }
+            (None, None) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_dense_roundtrip() {
+        let dense = arr1(&[0.0, 1.5, 0.0, 0.0, -2.0]);
+        let x = SparseVec::from_dense(&dense.view());
+        assert_eq!(x.nnz(), 2);
+        assert_eq!(x.min_dim(), 5);
+        assert_eq!(x.to_dense(5), dense);
+        assert_eq!(SparseVec::<f64>::default().min_dim(), 0);
+    }
+
+    #[test]
+    fn test_union_order() {
+        let merged: Vec<_> = union(&[0, 2, 5], &[1.0, 2.0, 3.0], &[2, 3], &[4.0, 5.0]).collect();
+        assert_eq!(
+            merged,
+            vec![(0, 1.0, 0.0), (2, 2.0, 4.0), (3, 0.0, 5.0), (5, 3.0, 0.0)]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Indices must be strictly increasing.")]
+    fn test_unsorted_indices() {
+        SparseVec::new(vec![3, 1], vec![1.0, 2.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Indices and values must have the same length.")]
+    fn test_mismatched_parts() {
+        SparseVec::new(vec![1, 3], vec![1.0]);
+    }
+}
@@ -0,0 +1,101 @@
+//! Lets the sparse metrics read vectors and matrices of the `sprs` crate without copying.
+
+use std::ops::Deref;
+
+use sprs::{CsMatBase, CsVecBase};
+
+use super::csr::SparseRows;
+use super::sparse_vec::SparseVector;
+
+impl<IStorage, DStorage, T> SparseVector<T> for CsVecBase<IStorage, DStorage, T>
+where
+    IStorage: Deref<Target = [usize]>,
+    DStorage: Deref<Target = [T]>,
+{
+    fn sparse_indices(&self) -> &[usize] {
+        self.indices()
+    }
+
+    fn sparse_values(&self) -> &[T] {
+        self.data()
+    }
+}
+
+/// Rows of an `sprs` matrix in CSR order, including views of a range of rows.
+///
+/// # Panics
+/// [`SparseRows::row`] panics if the matrix is stored in CSC order, whose rows are not
+/// contiguous; convert it with `to_csr` first.
+impl<T, IptrStorage, IndStorage, DataStorage> SparseRows<T>
+    for CsMatBase<T, usize, IptrStorage, IndStorage, DataStorage>
+where
+    IptrStorage: Deref<Target = [usize]>,
+    IndStorage: Deref<Target = [usize]>,
+    DataStorage: Deref<Target = [T]>,
+{
+    fn nrows(&self) -> usize {
+        self.rows()
+    }
+
+    fn ncols(&self) -> usize {
+        self.cols()
+    }
+
+    fn row(&self, i: usize) -> (&[usize], &[T]) {
+        assert!(
+            self.is_csr(),
+            "Sparse matrices must be stored in CSR order."
+        );
+        let range = self.indptr().outer_inds_sz(i);
+        (&self.indices()[range.clone()], &self.data()[range])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use sprs::{CsMat, CsVec};
+
+    fn sample() -> CsMat<f64> {
+        CsMat::new(
+            (4, 5),
+            vec![0, 2, 3, 3, 5],
+            vec![0, 3, 1, 2, 4],
+            vec![1.0, -2.0, 3.0, 0.5, 4.0],
+        )
+    }
+
+    #[test]
+    fn test_vectors() {
+        let x: CsVec<f64> = CsVec::new(6, vec![0, 4], vec![3.0, 1.0]);
+        let y: CsVec<f64> = CsVec::new(6, vec![4, 5], vec![1.0, 4.0]);
+        assert_eq!(sparse_euclidean.distance_between(&x.view(), &y.view()), 5.0);
+        assert_eq!(sparse_manhattan.distance_between(&x, &y), 7.0);
+    }
+
+    #[test]
+    fn test_matrices_match_csr_view() {
+        let m = sample();
+        let (indptr, indices, data) = (
+            vec![0, 2, 3, 3, 5],
+            vec![0, 3, 1, 2, 4],
+            vec![1.0, -2.0, 3.0, 0.5, 4.0],
+        );
+        let view = CsrView::new(&indptr, &indices, &data, 5);
+
+        let expected = cdist_sparse(&view, &view, &sparse_euclidean);
+        assert_eq!(cdist_sparse(&m, &m.view(), &sparse_euclidean), expected);
+
+        // A view of the middle rows has an `indptr` that does not start at zero.
+        let middle = m.slice_outer(1..4);
+        let d = cdist_sparse(&middle, &m, &sparse_euclidean);
+        assert_eq!(d, expected.slice(ndarray::s![1..4, ..]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Sparse matrices must be stored in CSR order.")]
+    fn test_csc_rejected() {
+        let m = sample().to_csc();
+        cdist_sparse(&m, &m, &sparse_euclidean);
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use super::levenshtein::normalize;
+
+/// Computes the Damerau-Levenshtein edit distance between two strings.
+///
+/// In addition to the insertions, deletions and substitutions of
+/// [`levenshtein`](crate::levenshtein), swapping two adjacent characters counts as a single
+/// edit, which matches the most common typing errors. This is the unrestricted distance of
+/// Lowrance and Wagner, so substrings may be edited again after a transposition ("ca" to "abc"
+/// is two edits) and it satisfies the triangle inequality, unlike the restricted optimal
+/// string alignment distance. Strings are compared by `char`s.
+///
+/// The computation keeps the full `(len(a) + 2) * (len(b) + 2)` table, plus the last row at
+/// which each character of `a` was seen.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// assert_eq!(damerau_levenshtein("ca", "ac"), 1);
+/// assert_eq!(levenshtein("ca", "ac"), 2);
+/// assert_eq!(damerau_levenshtein("ca", "abc"), 2);
+/// ```
+pub fn damerau_levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (n, m) = (a.len(), b.len());
+    let max = n + m;
+    let width = m + 2;
+
+    // Row and column 0 hold `max` as a sentinel; cell `(i + 1, j + 1)` is the distance between
+    // the first `i` characters of `a` and the first `j` of `b`.
+    let mut table = vec![0; (n + 2) * width];
+    table[0] = max;
+    for i in 0..=n {
+        table[(i + 1) * width] = max;
+        table[(i + 1) * width + 1] = i;
+    }
+    for j in 0..=m {
+        table[j + 1] = max;
+        table[width + j + 1] = j;
+    }
+
+    let mut last_row: HashMap<char, usize> = HashMap::new();
+    for i in 1..=n {
+        let mut last_col = 0;
+        for j in 1..=m {
+            let k = last_row.get(&b[j - 1]).copied().unwrap_or(0);
+            let l = last_col;
+            let cost = if a[i - 1] == b[j - 1] {
+                last_col = j;
+                0
+            } else {
+                1
+            };
+            let substitution = table[i * width + j] + cost;
+            let insertion = table[(i + 1) * width + j] + 1;
+            let deletion = table[i * width + j + 1] + 1;
+            let transposition = table[k * width + l] + (i - k - 1) + 1 + (j - l - 1);
+            table[(i + 1) * width + j + 1] =
+                substitution.min(insertion).min(deletion).min(transposition);
+        }
+        last_row.insert(a[i - 1], i);
+    }
+
+    table[(n + 1) * width + m + 1]
+}
+
+/// Computes the [`damerau_levenshtein`] distance divided by the length of the longer string,
+/// in `chars`.
+///
+/// # Returns
+/// A distance between `0` and `1`; `0` if both strings are empty.
+pub fn damerau_levenshtein_normalized(a: &str, b: &str) -> f64 {
+    normalize(damerau_levenshtein(a, b), a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levenshtein;
+    use crate::rng::SplitMix64;
+
+    #[test]
+    fn test_damerau_levenshtein_known_values() {
+        assert_eq!(damerau_levenshtein("", ""), 0);
+        assert_eq!(damerau_levenshtein("abc", ""), 3);
+        assert_eq!(damerau_levenshtein("", "abc"), 3);
+        assert_eq!(damerau_levenshtein("abcdef", "abdcef"), 1);
+        assert_eq!(damerau_levenshtein("teh", "the"), 1);
+        assert_eq!(damerau_levenshtein("kitten", "sitting"), 3);
+        assert_eq!(damerau_levenshtein("abcdef", "badcfe"), 3);
+        assert_eq!(damerau_levenshtein_normalized("teh", "the"), 1.0 / 3.0);
+    }
+
+    #[test]
+    fn test_damerau_levenshtein_bounds() {
+        let mut rng = SplitMix64::new(42);
+        let word = |rng: &mut SplitMix64| -> String {
+            let len = rng.next_below(8);
+            (0..len)
+                .map(|_| (b'a' + rng.next_below(3) as u8) as char)
+                .collect()
+        };
+        for _ in 0..200 {
+            let (a, b, c) = (word(&mut rng), word(&mut rng), word(&mut rng));
+            let d = damerau_levenshtein(&a, &b);
+            assert!(d <= levenshtein(&a, &b));
+            assert!(2 * d >= levenshtein(&a, &b));
+            assert_eq!(d, damerau_levenshtein(&b, &a));
+            assert!(damerau_levenshtein(&a, &c) <= d + damerau_levenshtein(&b, &c));
+        }
+    }
+}
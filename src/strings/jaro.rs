@@ -0,0 +1,124 @@
+/// Computes the Jaro similarity between two strings.
+///
+/// Characters match when they are equal and no further apart than half the length of the
+/// longer string, minus one. With `m` matches and `t` matched characters out of order,
+///
+/// ..math::
+///    sim(a, b) = \frac{1}{3} \left( \frac{m}{|a|} + \frac{m}{|b|} + \frac{m - t / 2}{m} \right)
+///
+/// and the similarity is `0` when nothing matches. Strings are compared by `char`s.
+///
+/// # Returns
+/// A similarity between `0` and `1`; `1` if both strings are empty.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// let sim: f64 = jaro("martha", "marhta");
+/// assert!((sim - 17.0 / 18.0).abs() < 1e-12);
+/// ```
+pub fn jaro(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut b_matched = vec![false; b.len()];
+    let mut a_matches = Vec::new();
+    for (i, &c) in a.iter().enumerate() {
+        let start = i.saturating_sub(window);
+        let end = (i + window + 1).min(b.len());
+        if let Some(j) = (start..end).find(|&j| !b_matched[j] && b[j] == c) {
+            b_matched[j] = true;
+            a_matches.push(c);
+        }
+    }
+    if a_matches.is_empty() {
+        return 0.0;
+    }
+
+    let b_matches = b
+        .iter()
+        .zip(&b_matched)
+        .filter(|(_, &m)| m)
+        .map(|(&c, _)| c);
+    let out_of_order = a_matches
+        .iter()
+        .zip(b_matches)
+        .filter(|(&x, y)| x != *y)
+        .count();
+    let m = a_matches.len() as f64;
+    let t = out_of_order as f64 / 2.0;
+    (m / a.len() as f64 + m / b.len() as f64 + (m - t) / m) / 3.0
+}
+
+/// Computes the Jaro-Winkler similarity between two strings.
+///
+/// The [`jaro`] similarity is raised for strings that share a common prefix of `l` characters,
+/// up to four, with Winkler's scaling factor of `0.1`:
+///
+/// ..math::
+///    sim_w(a, b) = sim(a, b) + 0.1 \, l \, (1 - sim(a, b))
+///
+/// This favours the typos and abbreviations that leave the start of a name intact.
+///
+/// # Returns
+/// A similarity between `0` and `1`; `1` if both strings are empty.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// let sim: f64 = jaro_winkler("martha", "marhta");
+/// assert!((sim - 0.9611111111111111).abs() < 1e-12);
+/// assert!(jaro_winkler("dixon", "dicksonx") > jaro("dixon", "dicksonx"));
+/// ```
+pub fn jaro_winkler(a: &str, b: &str) -> f64 {
+    let sim = jaro(a, b);
+    let prefix = a
+        .chars()
+        .zip(b.chars())
+        .take(4)
+        .take_while(|(x, y)| x == y)
+        .count();
+    sim + 0.1 * prefix as f64 * (1.0 - sim)
+}
+
+/// Computes the Jaro-Winkler distance, one minus the [`jaro_winkler`] similarity.
+pub fn jaro_winkler_distance(a: &str, b: &str) -> f64 {
+    1.0 - jaro_winkler(a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jaro_known_values() {
+        assert_eq!(jaro("", ""), 1.0);
+        assert_eq!(jaro("abc", ""), 0.0);
+        assert_eq!(jaro("abc", "abc"), 1.0);
+        assert_eq!(jaro("abc", "xyz"), 0.0);
+        assert!((jaro("dwayne", "duane") - 0.8222222222222223).abs() < 1e-12);
+        assert!((jaro("dixon", "dicksonx") - 0.7666666666666666).abs() < 1e-12);
+        assert_eq!(jaro("crate", "trace"), jaro("trace", "crate"));
+    }
+
+    #[test]
+    fn test_jaro_winkler_known_values() {
+        assert!((jaro_winkler("dwayne", "duane") - 0.84).abs() < 1e-12);
+        assert!((jaro_winkler("dixon", "dicksonx") - 0.8133333333333332).abs() < 1e-12);
+        // The prefix bonus is capped at four characters.
+        let a = "prefixed-a";
+        let b = "prefixed-b";
+        let sim = jaro(a, b);
+        assert!((jaro_winkler(a, b) - (sim + 0.4 * (1.0 - sim))).abs() < 1e-12);
+        assert_eq!(jaro_winkler_distance("same", "same"), 0.0);
+    }
+}
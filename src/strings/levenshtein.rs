@@ -0,0 +1,81 @@
+use crate::distances::align;
+
+/// Computes the Levenshtein edit distance between two strings.
+///
+/// The distance is the minimum number of single-character insertions, deletions and
+/// substitutions that turn `a` into `b`. Strings are compared by Unicode scalar values
+/// (`char`s), not bytes. It is computed with the same two-row alignment recurrence as
+/// [`edr`](crate::edr), in `O(len(a) * len(b))` time and `O(len(b))` memory.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// assert_eq!(levenshtein("kitten", "sitting"), 3);
+/// assert_eq!(levenshtein("", "abc"), 3);
+/// assert_eq!(levenshtein("naïve", "naive"), 1);
+/// ```
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    align(
+        a.len(),
+        b.len(),
+        |i, j| i + j,
+        |i, j, diagonal, up, left| {
+            let substitution = diagonal + usize::from(a[i] != b[j]);
+            substitution.min(up.min(left) + 1)
+        },
+    )
+}
+
+/// Computes the [`levenshtein`] distance divided by the length of the longer string, in
+/// `chars`.
+///
+/// # Returns
+/// A distance between `0` and `1`; `0` if both strings are empty.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// assert_eq!(levenshtein_normalized("flaw", "lawn"), 0.5);
+/// ```
+pub fn levenshtein_normalized(a: &str, b: &str) -> f64 {
+    normalize(levenshtein(a, b), a, b)
+}
+
+/// Divides an edit count by the length of the longer string, with empty strings at distance
+/// zero.
+pub(crate) fn normalize(edits: usize, a: &str, b: &str) -> f64 {
+    let longest = a.chars().count().max(b.chars().count());
+    if longest == 0 {
+        0.0
+    } else {
+        edits as f64 / longest as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levenshtein_known_values() {
+        assert_eq!(levenshtein("", ""), 0);
+        assert_eq!(levenshtein("abc", "abc"), 0);
+        assert_eq!(levenshtein("abc", ""), 3);
+        assert_eq!(levenshtein("flaw", "lawn"), 2);
+        assert_eq!(levenshtein("saturday", "sunday"), 3);
+        assert_eq!(levenshtein("sunday", "saturday"), 3);
+        // A transposition costs two edits.
+        assert_eq!(levenshtein("ab", "ba"), 2);
+    }
+
+    #[test]
+    fn test_levenshtein_normalized() {
+        assert_eq!(levenshtein_normalized("", ""), 0.0);
+        assert_eq!(levenshtein_normalized("abc", ""), 1.0);
+        assert_eq!(levenshtein_normalized("日本語", "日本"), 1.0 / 3.0);
+    }
+}
@@ -0,0 +1,13 @@
+mod damerau_levenshtein;
+mod jaro;
+mod levenshtein;
+mod ngram;
+mod str_distance;
+mod terms;
+
+pub use damerau_levenshtein::*;
+pub use jaro::*;
+pub use levenshtein::*;
+pub use ngram::*;
+pub use str_distance::*;
+pub use terms::*;
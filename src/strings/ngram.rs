@@ -0,0 +1,234 @@
+use std::collections::{BTreeMap, HashMap};
+
+use ndarray::Array1;
+
+use crate::{sparse_cosine, sparse_jaccard, SparseVec};
+
+/// Splits a string into its overlapping character n-grams (shingles), in order.
+///
+/// N-grams are taken over `char`s, so multi-byte characters are never split. A non-empty
+/// string shorter than `n` yields itself as its only n-gram, so short strings are still
+/// comparable; the empty string yields none.
+///
+/// # Panics
+/// Panics if `n` is zero.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// assert_eq!(char_ngrams("night", 2), ["ni", "ig", "gh", "ht"]);
+/// assert_eq!(char_ngrams("ab", 3), ["ab"]);
+/// ```
+pub fn char_ngrams(s: &str, n: usize) -> Vec<&str> {
+    assert!(n > 0, "N-gram length must be positive.");
+    let bounds: Vec<usize> = s.char_indices().map(|(i, _)| i).chain([s.len()]).collect();
+    let chars = bounds.len() - 1;
+    if chars == 0 {
+        Vec::new()
+    } else if chars < n {
+        vec![s]
+    } else {
+        bounds.windows(n + 1).map(|w| &s[w[0]..w[n]]).collect()
+    }
+}
+
+/// A vocabulary that assigns each character n-gram an index, so strings can be turned into
+/// n-gram count vectors for the vector metrics.
+///
+/// Indices are assigned in the order n-grams are first seen by [`fit`](Self::fit). Counting a
+/// string ignores n-grams that are not in the vocabulary, so every vector built from one
+/// vocabulary lives in the same space.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// let mut vocabulary = NgramVocabulary::new(2);
+/// vocabulary.fit("banana");
+/// vocabulary.fit("bandana");
+///
+/// let x = vocabulary.dense_counts("banana");
+/// let y = vocabulary.dense_counts("bandana");
+/// assert_eq!(x.len(), vocabulary.len());
+///
+/// let sx = vocabulary.sparse_counts("banana");
+/// let sy = vocabulary.sparse_counts("bandana");
+/// let d = sparse_cosine(sx.indices(), sx.values(), sy.indices(), sy.values());
+/// assert!((d - cosine(&x.view(), &y.view())).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NgramVocabulary {
+    n: usize,
+    index: HashMap<String, usize>,
+}
+
+impl NgramVocabulary {
+    /// Creates an empty vocabulary of `n`-grams.
+    ///
+    /// # Panics
+    /// Panics if `n` is zero.
+    pub fn new(n: usize) -> Self {
+        assert!(n > 0, "N-gram length must be positive.");
+        Self {
+            n,
+            index: HashMap::new(),
+        }
+    }
+
+    /// Returns the n-gram length.
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// Returns the number of distinct n-grams, which is the dimension of the count vectors.
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Returns `true` if no n-gram has been added.
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Returns the index of `ngram`, if it is in the vocabulary.
+    pub fn index_of(&self, ngram: &str) -> Option<usize> {
+        self.index.get(ngram).copied()
+    }
+
+    /// Adds the n-grams of `s` that are not yet in the vocabulary.
+    pub fn fit(&mut self, s: &str) {
+        for gram in char_ngrams(s, self.n) {
+            if !self.index.contains_key(gram) {
+                let next = self.index.len();
+                self.index.insert(gram.to_owned(), next);
+            }
+        }
+    }
+
+    /// Counts the n-grams of `s` as a sparse vector with sorted indices.
+    pub fn sparse_counts(&self, s: &str) -> SparseVec<f64> {
+        let mut counts = BTreeMap::new();
+        for index in self.indices(s) {
+            *counts.entry(index).or_insert(0.0) += 1.0;
+        }
+        let (indices, values) = counts.into_iter().unzip();
+        SparseVec::from_sorted(indices, values)
+    }
+
+    /// Counts the n-grams of `s` as a dense vector of length [`len`](Self::len).
+    pub fn dense_counts(&self, s: &str) -> Array1<f64> {
+        let mut counts = Array1::zeros(self.len());
+        for index in self.indices(s) {
+            counts[index] += 1.0;
+        }
+        counts
+    }
+
+    fn indices<'a>(&'a self, s: &'a str) -> impl Iterator<Item = usize> + 'a {
+        char_ngrams(s, self.n)
+            .into_iter()
+            .filter_map(|gram| self.index_of(gram))
+    }
+}
+
+/// Fits a vocabulary on both strings and returns their sparse n-gram counts.
+fn pair_counts(a: &str, b: &str, n: usize) -> (SparseVec<f64>, SparseVec<f64>) {
+    let mut vocabulary = NgramVocabulary::new(n);
+    vocabulary.fit(a);
+    vocabulary.fit(b);
+    (vocabulary.sparse_counts(a), vocabulary.sparse_counts(b))
+}
+
+/// Computes the cosine distance between the character `n`-gram count vectors of two strings.
+///
+/// Repeated n-grams are weighted by their counts, and the distance is computed by
+/// [`sparse_cosine`], so it is `0` for two empty strings and `1` if only one is empty.
+///
+/// # Panics
+/// Panics if `n` is zero.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// // Both strings have the bigrams "ab" and "ba", with the counts swapped.
+/// assert!((ngram_cosine("abab", "baba", 2) - 0.2).abs() < 1e-12);
+/// assert_eq!(ngram_cosine("abc", "xyz", 2), 1.0);
+/// ```
+pub fn ngram_cosine(a: &str, b: &str, n: usize) -> f64 {
+    let (x, y) = pair_counts(a, b, n);
+    sparse_cosine(x.indices(), x.values(), y.indices(), y.values())
+}
+
+/// Computes the Jaccard distance between the sets of character `n`-grams of two strings.
+///
+/// Only the presence of each n-gram matters. The distance is computed by [`sparse_jaccard`],
+/// so it is `0` for two empty strings.
+///
+/// # Panics
+/// Panics if `n` is zero.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// // "night" and "nacht" share only "ht" among seven distinct bigrams.
+/// assert_eq!(ngram_jaccard("night", "nacht", 2), 6.0 / 7.0);
+/// ```
+pub fn ngram_jaccard(a: &str, b: &str, n: usize) -> f64 {
+    let (x, y) = pair_counts(a, b, n);
+    sparse_jaccard(x.indices(), y.indices())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cosine, jaccard};
+
+    #[test]
+    fn test_char_ngrams_unicode_and_short() {
+        assert_eq!(char_ngrams("日本語", 2), ["日本", "本語"]);
+        assert_eq!(char_ngrams("abc", 3), ["abc"]);
+        assert_eq!(char_ngrams("abc", 1), ["a", "b", "c"]);
+        assert!(char_ngrams("", 2).is_empty());
+    }
+
+    #[test]
+    fn test_ngram_metrics_match_dense_kernels() {
+        let (a, b) = ("the quick brown fox", "the quick brown dog");
+        let mut vocabulary = NgramVocabulary::new(3);
+        vocabulary.fit(a);
+        vocabulary.fit(b);
+        let (x, y) = (vocabulary.dense_counts(a), vocabulary.dense_counts(b));
+
+        assert!((ngram_cosine(a, b, 3) - cosine(&x.view(), &y.view())).abs() < 1e-12);
+        assert_eq!(ngram_jaccard(a, b, 3), jaccard(&x.view(), &y.view()));
+    }
+
+    #[test]
+    fn test_ngram_edge_cases() {
+        assert_eq!(ngram_cosine("", "", 2), 0.0);
+        assert_eq!(ngram_cosine("", "ab", 2), 1.0);
+        assert_eq!(ngram_jaccard("", "", 2), 0.0);
+        assert_eq!(ngram_jaccard("a", "a", 2), 0.0);
+        assert_eq!(ngram_jaccard("a", "b", 2), 1.0);
+    }
+
+    #[test]
+    fn test_vocabulary_ignores_unknown_ngrams() {
+        let mut vocabulary = NgramVocabulary::new(2);
+        vocabulary.fit("abc");
+        assert_eq!(vocabulary.len(), 2);
+        assert_eq!(vocabulary.index_of("bc"), Some(1));
+        let counts = vocabulary.sparse_counts("abxab");
+        assert_eq!(counts.indices(), &[0]);
+        assert_eq!(counts.values(), &[2.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "N-gram length must be positive.")]
+    fn test_zero_length() {
+        ngram_cosine("a", "b", 0);
+    }
+}
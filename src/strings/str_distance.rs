@@ -0,0 +1,97 @@
+use super::{damerau_levenshtein, jaro_winkler, levenshtein};
+
+/// A distance between two strings.
+///
+/// This is the string counterpart of [`Distance`](crate::Distance). Every function or closure
+/// `Fn(&str, &str) -> f64` implements it, so normalized variants such as
+/// [`levenshtein_normalized`](crate::levenshtein_normalized) can be passed directly, and the
+/// unit structs below wrap the metrics that return counts or similarities.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// fn closest<'a, M: StrDistance>(metric: &M, query: &str, words: &[&'a str]) -> &'a str {
+///     words
+///         .iter()
+///         .copied()
+///         .min_by(|a, b| metric.distance(query, a).total_cmp(&metric.distance(query, b)))
+///         .unwrap()
+/// }
+///
+/// let words = ["kitten", "sitting", "mitten"];
+/// assert_eq!(closest(&Levenshtein, "sittin", &words), "sitting");
+/// assert_eq!(closest(&levenshtein_normalized, "kitte", &words), "kitten");
+/// ```
+pub trait StrDistance {
+    /// Computes the distance between `a` and `b`.
+    fn distance(&self, a: &str, b: &str) -> f64;
+}
+
+impl<F> StrDistance for F
+where
+    F: Fn(&str, &str) -> f64,
+{
+    fn distance(&self, a: &str, b: &str) -> f64 {
+        self(a, b)
+    }
+}
+
+/// The [`levenshtein`] edit distance, as a number of edits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Levenshtein;
+
+impl StrDistance for Levenshtein {
+    fn distance(&self, a: &str, b: &str) -> f64 {
+        levenshtein(a, b) as f64
+    }
+}
+
+/// The [`damerau_levenshtein`] edit distance, as a number of edits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DamerauLevenshtein;
+
+impl StrDistance for DamerauLevenshtein {
+    fn distance(&self, a: &str, b: &str) -> f64 {
+        damerau_levenshtein(a, b) as f64
+    }
+}
+
+/// One minus the [`jaro_winkler`] similarity.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JaroWinkler;
+
+impl StrDistance for JaroWinkler {
+    fn distance(&self, a: &str, b: &str) -> f64 {
+        1.0 - jaro_winkler(a, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{damerau_levenshtein_normalized, jaro_winkler_distance};
+
+    #[test]
+    fn test_builtin_str_metrics() {
+        assert_eq!(Levenshtein.distance("kitten", "sitting"), 3.0);
+        assert_eq!(DamerauLevenshtein.distance("ca", "ac"), 1.0);
+        assert_eq!(
+            JaroWinkler.distance("martha", "marhta"),
+            jaro_winkler_distance("martha", "marhta")
+        );
+    }
+
+    #[test]
+    fn test_functions_as_str_metrics() {
+        let metrics: [&dyn StrDistance; 3] = [
+            &damerau_levenshtein_normalized,
+            &jaro_winkler_distance,
+            &|a: &str, b: &str| (a.len() as f64 - b.len() as f64).abs(),
+        ];
+        for metric in metrics {
+            assert_eq!(metric.distance("same", "same"), 0.0);
+            assert!(metric.distance("abc", "xyzw") > 0.0);
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{sparse_cosine, sparse_jaccard, SparseVec};
+
+/// A vocabulary of whole-word terms with their document frequencies, for turning tokenized
+/// documents into sparse term-count or TF-IDF vectors.
+///
+/// Indices are assigned in the order terms are first seen by [`fit`](Self::fit), and each
+/// fitted document counts once towards the document frequency of every term it contains.
+/// Tokenization is left to the caller, e.g. `text.split_whitespace()`. The vectors come out
+/// as [`SparseVec`]s, ready for [`sparse_cosine`], [`sparse_jaccard`] or any other
+/// [`SparseDistance`](crate::SparseDistance).
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// let docs = ["the cat sat", "the dog sat", "a cat and a dog"];
+/// let vocabulary = TermVocabulary::from_documents(docs.iter().map(|d| d.split_whitespace()));
+///
+/// let x = vocabulary.tf_idf("the cat sat".split_whitespace());
+/// let y = vocabulary.tf_idf("the dog sat".split_whitespace());
+/// let d = sparse_cosine.distance_between(&x, &y);
+/// assert!(d > 0.0 && d < 1.0);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TermVocabulary {
+    index: HashMap<String, usize>,
+    document_frequency: Vec<usize>,
+    documents: usize,
+}
+
+impl TermVocabulary {
+    /// Creates an empty vocabulary.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a vocabulary fitted on every document of `documents`.
+    pub fn from_documents<D, I, S>(documents: D) -> Self
+    where
+        D: IntoIterator<Item = I>,
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut vocabulary = Self::new();
+        for tokens in documents {
+            vocabulary.fit(tokens);
+        }
+        vocabulary
+    }
+
+    /// Returns the number of distinct terms, which is the dimension of the vectors.
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Returns `true` if no term has been added.
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Returns the number of fitted documents.
+    pub fn num_documents(&self) -> usize {
+        self.documents
+    }
+
+    /// Returns the index of `term`, if it is in the vocabulary.
+    pub fn index_of(&self, term: &str) -> Option<usize> {
+        self.index.get(term).copied()
+    }
+
+    /// Returns the number of fitted documents containing `term`.
+    pub fn document_frequency(&self, term: &str) -> usize {
+        self.index_of(term)
+            .map_or(0, |index| self.document_frequency[index])
+    }
+
+    /// Adds the terms of one document and updates the document frequencies.
+    pub fn fit<I, S>(&mut self, tokens: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut seen = Vec::new();
+        for token in tokens {
+            let token = token.as_ref();
+            let index = match self.index.get(token) {
+                Some(&index) => index,
+                None => {
+                    let next = self.index.len();
+                    self.index.insert(token.to_owned(), next);
+                    self.document_frequency.push(0);
+                    next
+                }
+            };
+            seen.push(index);
+        }
+        seen.sort_unstable();
+        seen.dedup();
+        for index in seen {
+            self.document_frequency[index] += 1;
+        }
+        self.documents += 1;
+    }
+
+    /// Counts the terms of a document as a sparse vector with sorted indices.
+    ///
+    /// Terms that are not in the vocabulary are ignored.
+    pub fn counts_from_tokens<I, S>(&self, tokens: I) -> SparseVec<f64>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut counts = BTreeMap::new();
+        for token in tokens {
+            if let Some(index) = self.index_of(token.as_ref()) {
+                *counts.entry(index).or_insert(0.0) += 1.0;
+            }
+        }
+        let (indices, values) = counts.into_iter().unzip();
+        SparseVec::from_sorted(indices, values)
+    }
+
+    /// Weights the term counts of a document by their smoothed inverse document frequencies.
+    ///
+    /// Each count is multiplied by `ln((1 + N) / (1 + df)) + 1`, where `N` is the number of
+    /// fitted documents and `df` the document frequency of the term, as in scikit-learn's
+    /// `TfidfVectorizer` with `smooth_idf=True`. The vectors are not normalized, which does not
+    /// change their cosine distances.
+    pub fn tf_idf<I, S>(&self, tokens: I) -> SparseVec<f64>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let counts = self.counts_from_tokens(tokens);
+        let documents = self.documents as f64;
+        let values = counts
+            .indices()
+            .iter()
+            .zip(counts.values())
+            .map(|(&index, &count)| {
+                let df = self.document_frequency[index] as f64;
+                count * (((1.0 + documents) / (1.0 + df)).ln() + 1.0)
+            })
+            .collect();
+        SparseVec::from_sorted(counts.indices().to_vec(), values)
+    }
+}
+
+/// Fits a vocabulary on both documents and returns their sparse term counts.
+fn pair_counts<S: AsRef<str>>(a: &[S], b: &[S]) -> (SparseVec<f64>, SparseVec<f64>) {
+    let mut vocabulary = TermVocabulary::new();
+    vocabulary.fit(a);
+    vocabulary.fit(b);
+    (
+        vocabulary.counts_from_tokens(a),
+        vocabulary.counts_from_tokens(b),
+    )
+}
+
+/// Computes the cosine distance between the term-count vectors of two tokenized documents.
+///
+/// Repeated terms are weighted by their counts, and the distance is computed by
+/// [`sparse_cosine`], so it is `0` for two empty documents and `1` if only one is empty. Use
+/// [`TermVocabulary::tf_idf`] to weight terms across a corpus instead.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// let a: Vec<&str> = "to be or not to be".split_whitespace().collect();
+/// let b: Vec<&str> = "to be is to do".split_whitespace().collect();
+/// // Counts (to, be, or, not, is, do): a = (2, 2, 1, 1, 0, 0), b = (2, 1, 0, 0, 1, 1).
+/// let expected = 1.0 - 6.0 / (10f64.sqrt() * 7f64.sqrt());
+/// assert!((token_cosine(&a, &b) - expected).abs() < 1e-12);
+/// ```
+pub fn token_cosine<S: AsRef<str>>(a: &[S], b: &[S]) -> f64 {
+    let (x, y) = pair_counts(a, b);
+    sparse_cosine(x.indices(), x.values(), y.indices(), y.values())
+}
+
+/// Computes the Jaccard distance between the sets of terms of two tokenized documents.
+///
+/// Only the presence of each term matters. The distance is computed by [`sparse_jaccard`], so
+/// it is `0` for two empty documents.
+///
+/// # Example
+/// ```
+/// use fast_distances::*;
+///
+/// let a = ["red", "green", "blue"];
+/// let b = ["green", "blue", "blue", "cyan"];
+/// assert_eq!(token_jaccard(&a, &b), 0.5);
+/// ```
+pub fn token_jaccard<S: AsRef<str>>(a: &[S], b: &[S]) -> f64 {
+    let (x, y) = pair_counts(a, b);
+    sparse_jaccard(x.indices(), y.indices())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cosine;
+
+    #[test]
+    fn test_counts_and_document_frequency() {
+        let vocabulary = TermVocabulary::from_documents([vec!["a", "b", "a"], vec!["b", "c"]]);
+        assert_eq!(vocabulary.len(), 3);
+        assert_eq!(vocabulary.num_documents(), 2);
+        assert_eq!(vocabulary.document_frequency("a"), 1);
+        assert_eq!(vocabulary.document_frequency("b"), 2);
+        assert_eq!(vocabulary.document_frequency("z"), 0);
+
+        let counts = vocabulary.counts_from_tokens(["c", "a", "z", "a"]);
+        assert_eq!(counts.indices(), &[0, 2]);
+        assert_eq!(counts.values(), &[2.0, 1.0]);
+    }
+
+    #[test]
+    fn test_tf_idf_matches_sklearn() {
+        // TfidfVectorizer(norm=None).fit(["a b a", "b c"]).transform(["a b a"])
+        let vocabulary =
+            TermVocabulary::from_documents(["a b a", "b c"].map(str::split_whitespace));
+        let x = vocabulary.tf_idf("a b a".split_whitespace());
+        assert_eq!(x.indices(), &[0, 1]);
+        let idf_a = (3.0f64 / 2.0).ln() + 1.0;
+        assert!((x.values()[0] - 2.0 * idf_a).abs() < 1e-12);
+        assert!((x.values()[1] - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_token_metrics_match_dense_cosine() {
+        let a = ["x", "y", "y", "z"];
+        let b = ["y", "w", "x", "x"];
+        let (x, y) = pair_counts(&a, &b);
+        let (x, y) = (x.to_dense(4), y.to_dense(4));
+        assert!((token_cosine(&a, &b) - cosine(&x.view(), &y.view())).abs() < 1e-12);
+        assert_eq!(token_cosine::<&str>(&[], &[]), 0.0);
+        assert_eq!(token_jaccard(&a, &b), 0.5);
+    }
+}
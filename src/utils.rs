@@ -45,6 +45,46 @@ where
     Array2::<T>::from_elem((n, n), T::one()) - identity
 }
 
+/// Compensated (Neumaier) summation.
+///
+/// Keeps a running compensation term for the low-order bits lost by each addition, so the
+/// error of the final sum does not grow with the number of terms. Used by the `*_accurate`
+/// distance variants, which matter for very high-dimensional `f32` inputs.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NeumaierSum<T> {
+    sum: T,
+    compensation: T,
+}
+
+impl<T> NeumaierSum<T>
+where
+    T: Float,
+{
+    /// Creates an empty sum.
+    pub fn new() -> Self {
+        NeumaierSum {
+            sum: T::zero(),
+            compensation: T::zero(),
+        }
+    }
+
+    /// Adds `value` to the sum.
+    pub fn add(&mut self, value: T) {
+        let t = self.sum + value;
+        if self.sum.abs() >= value.abs() {
+            self.compensation = self.compensation + ((self.sum - t) + value);
+        } else {
+            self.compensation = self.compensation + ((value - t) + self.sum);
+        }
+        self.sum = t;
+    }
+
+    /// Returns the compensated sum.
+    pub fn sum(&self) -> T {
+        self.sum + self.compensation
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -227,4 +267,25 @@ mod tests {
         // Assert that it is a 1x1 matrix with 1.0
         assert_eq!(identity[(0, 0)], 1.0);
     }
+
+    // Test that compensated summation recovers most of the digits lost by naive summation
+    #[test]
+    fn test_neumaier_sum() {
+        let mut acc = NeumaierSum::<f32>::new();
+        let mut naive = 0.0f32;
+        for _ in 0..1_000_000 {
+            acc.add(0.1);
+            naive += 0.1;
+        }
+        let exact = 1_000_000.0 * f64::from(0.1f32);
+        let compensated_error = (f64::from(acc.sum()) - exact).abs();
+        let naive_error = (f64::from(naive) - exact).abs();
+        assert!(compensated_error * 100.0 < naive_error);
+
+        let mut acc = NeumaierSum::new();
+        for v in [1.0, 1e100, 1.0, -1e100] {
+            acc.add(v);
+        }
+        assert_eq!(acc.sum(), 2.0);
+    }
 }
@@ -0,0 +1,148 @@
+//! Input checks used by [`Distance::try_distance`](crate::Distance::try_distance).
+//!
+//! Only compiled with the `validate` feature, so the plain distance functions stay free of
+//! these branches when it is disabled.
+
+use ndarray::ArrayView1;
+use num::Float;
+
+use crate::DistanceError;
+
+/// Checks that `x` and `y` have the same length.
+pub fn check_same_length<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> Result<(), DistanceError> {
+    if x.len() != y.len() {
+        return Err(DistanceError::DimensionMismatch {
+            expected: x.len(),
+            found: y.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Checks that `v` has exactly `expected` elements.
+pub fn check_length<T>(v: &ArrayView1<T>, expected: usize) -> Result<(), DistanceError> {
+    if v.len() != expected {
+        return Err(DistanceError::DimensionMismatch {
+            expected,
+            found: v.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Checks that every element of `v` is finite (neither NaN nor infinite).
+pub fn check_finite<T>(v: &ArrayView1<T>, argument: &'static str) -> Result<(), DistanceError>
+where
+    T: Float,
+{
+    match v.iter().position(|value| !value.is_finite()) {
+        Some(index) => Err(DistanceError::NonFinite { argument, index }),
+        None => Ok(()),
+    }
+}
+
+/// Checks that every element of `v` is non-negative.
+pub fn check_non_negative<T>(v: &ArrayView1<T>, argument: &'static str) -> Result<(), DistanceError>
+where
+    T: Float,
+{
+    match v.iter().position(|&value| value < T::zero()) {
+        Some(index) => Err(DistanceError::Negative { argument, index }),
+        None => Ok(()),
+    }
+}
+
+/// Checks that `v` can be interpreted as an (unnormalized) probability vector: finite and
+/// non-negative.
+pub fn check_probability<T>(v: &ArrayView1<T>, argument: &'static str) -> Result<(), DistanceError>
+where
+    T: Float,
+{
+    check_finite(v, argument)?;
+    check_non_negative(v, argument)
+}
+
+/// The default check for a pair of inputs: equal lengths and finite values.
+pub fn check_pair<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> Result<(), DistanceError>
+where
+    T: Float,
+{
+    check_same_length(x, y)?;
+    check_finite(x, "x")?;
+    check_finite(y, "y")
+}
+
+/// The check for a pair of sequences that may differ in length: finite values only.
+pub fn check_finite_pair<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> Result<(), DistanceError>
+where
+    T: Float,
+{
+    check_finite(x, "x")?;
+    check_finite(y, "y")
+}
+
+/// The check for a pair of sequences compared by warping, which may differ in length: both
+/// must be non-empty, since a warping path needs at least one element on each side, and finite.
+pub fn check_sequence_pair<T>(x: &ArrayView1<T>, y: &ArrayView1<T>) -> Result<(), DistanceError>
+where
+    T: Float,
+{
+    if x.is_empty() || y.is_empty() {
+        return Err(DistanceError::InvalidInput(
+            "sequences must not be empty".to_string(),
+        ));
+    }
+    check_finite_pair(x, y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::arr1;
+
+    #[test]
+    fn test_check_pair() {
+        let x = arr1(&[1.0, 2.0]);
+        let y = arr1(&[1.0, 2.0, 3.0]);
+        assert_eq!(
+            check_pair(&x.view(), &y.view()),
+            Err(DistanceError::DimensionMismatch {
+                expected: 2,
+                found: 3
+            })
+        );
+
+        let y = arr1(&[1.0, f64::NAN]);
+        assert_eq!(
+            check_pair(&x.view(), &y.view()),
+            Err(DistanceError::NonFinite {
+                argument: "y",
+                index: 1
+            })
+        );
+
+        assert!(check_pair(&x.view(), &x.view()).is_ok());
+    }
+
+    #[test]
+    fn test_check_probability() {
+        let p = arr1(&[0.5, -0.1, 0.6]);
+        assert_eq!(
+            check_probability(&p.view(), "x"),
+            Err(DistanceError::Negative {
+                argument: "x",
+                index: 1
+            })
+        );
+
+        let p = arr1(&[0.5, f64::INFINITY]);
+        assert!(check_probability(&p.view(), "x").is_err());
+    }
+
+    #[test]
+    fn test_check_length() {
+        let v = arr1(&[0.1, 0.2, 0.3]);
+        assert!(check_length(&v.view(), 3).is_ok());
+        assert!(check_length(&v.view(), 2).is_err());
+    }
+}